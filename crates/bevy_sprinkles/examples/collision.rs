@@ -0,0 +1,97 @@
+//! Particles bouncing off colliders placed in the scene.
+//!
+//! ```sh
+//! cargo run -p bevy_sprinkles --example collision
+//! ```
+
+use bevy::{light::light_consts::lux, prelude::*};
+use bevy_sprinkles::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SprinklesPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut particles: ResMut<Assets<ParticlesAsset>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 10.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            illuminance: lux::OVERCAST_DAY,
+            ..default()
+        },
+        Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.4, 0.0)),
+    ));
+
+    // colliders are regular scene entities, so they can share a transform with a mesh
+    let floor_size = Vec3::new(12.0, 0.2, 12.0);
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::from_size(floor_size))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.35))),
+        Transform::from_xyz(0.0, -0.1, 0.0),
+        ParticlesCollider3D {
+            shape: ParticlesColliderShape3D::Box { size: floor_size },
+            ..default()
+        },
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.4, 0.3))),
+        Transform::from_xyz(0.0, 1.0, 0.0),
+        ParticlesCollider3D {
+            shape: ParticlesColliderShape3D::Sphere { radius: 1.0 },
+            ..default()
+        },
+    ));
+
+    let handle = particles.add(ParticlesAsset::new(
+        "Collision".into(),
+        ParticlesDimension::D3,
+        Default::default(),
+        vec![EmitterData {
+            name: "Drops".into(),
+            time: EmitterTime {
+                lifetime: 4.0,
+                ..default()
+            },
+            draw_pass: EmitterDrawPass {
+                mesh: ParticleMesh::default_sphere(),
+                ..default()
+            },
+            emission: EmitterEmission {
+                particles_amount: 400,
+                shape: EmissionShape::Sphere { radius: 0.5 },
+                ..default()
+            },
+            scale: EmitterScale {
+                range: ParticleRange::new(0.05, 0.1),
+                ..default()
+            },
+            collision: EmitterCollision {
+                mode: Some(EmitterCollisionMode::Rigid {
+                    friction: 0.2,
+                    bounce: 0.6,
+                }),
+                // the sphere mesh has a radius of 1, so its diameter scales with the particle
+                use_scale: true,
+                base_size: 2.0,
+                ..default()
+            },
+            ..default()
+        }],
+        vec![],
+        false,
+        Default::default(),
+    ));
+
+    commands.spawn((Particles3d(handle), Transform::from_xyz(0.0, 5.0, 0.0)));
+}
@@ -0,0 +1,102 @@
+//! A fully customized particle material, tinted per instance at runtime.
+//!
+//! The material is described by a [`StandardParticleMaterial`] in the asset, while
+//! [`ParticleMaterialOverrides`] animates each instance without touching the shared
+//! material asset.
+//!
+//! ```sh
+//! cargo run -p bevy_sprinkles --example custom_material
+//! ```
+
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SprinklesPlugin))
+        .insert_resource(ClearColor(Color::srgb(0.02, 0.02, 0.05)))
+        .add_systems(Startup, setup)
+        .add_systems(Update, animate_overrides)
+        .run();
+}
+
+#[derive(Component)]
+struct HueOffset(f32);
+
+fn setup(mut commands: Commands, mut particles: ResMut<Assets<ParticlesAsset>>) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 10.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+    ));
+
+    let handle = particles.add(ParticlesAsset::new(
+        "Embers".into(),
+        ParticlesDimension::D3,
+        Default::default(),
+        vec![EmitterData {
+            name: "Embers".into(),
+            time: EmitterTime {
+                lifetime: 2.5,
+                ..default()
+            },
+            draw_pass: EmitterDrawPass {
+                material: DrawPassMaterial::Standard(StandardParticleMaterial {
+                    base_color: [1.0, 1.0, 1.0, 1.0],
+                    base_color_texture: Some(TextureRef::Preset(PresetTexture::Spark1)),
+                    emissive: [1.0, 0.5, 0.2, 1.0],
+                    alpha_mode: SerializableAlphaMode::Blend,
+                    blend_mode: Some(ParticleBlendMode::AdditiveWithAlpha),
+                    unlit: true,
+                    double_sided: true,
+                    ..default()
+                }),
+                transform_align: Some(TransformAlign::BillboardYToVelocity),
+                ..default()
+            },
+            emission: EmitterEmission {
+                particles_amount: 200,
+                shape: EmissionShape::Sphere { radius: 0.4 },
+                ..default()
+            },
+            scale: EmitterScale {
+                range: ParticleRange::new(0.2, 0.35),
+                ..default()
+            },
+            velocities: EmitterVelocities {
+                initial_direction: Vec3::Y,
+                spread: 30.0,
+                initial_velocity: ParticleRange::new(1.0, 2.5),
+                ..default()
+            },
+            accelerations: EmitterAccelerations {
+                gravity: Vec3::new(0.0, 0.5, 0.0),
+                ..default()
+            },
+            ..default()
+        }],
+        vec![],
+        false,
+        Default::default(),
+    ));
+
+    // the same asset spawned three times, each instance with its own overrides
+    for (index, x) in [-3.0, 0.0, 3.0].into_iter().enumerate() {
+        commands.spawn((
+            Particles3d(handle.clone()),
+            ParticleMaterialOverrides::default(),
+            HueOffset(index as f32 * 120.0),
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+}
+
+fn animate_overrides(
+    time: Res<Time>,
+    mut systems: Query<(&mut ParticleMaterialOverrides, &HueOffset)>,
+) {
+    let elapsed = time.elapsed_secs();
+    for (mut overrides, hue_offset) in &mut systems {
+        overrides.tint = Color::hsl((hue_offset.0 + elapsed * 45.0) % 360.0, 0.8, 0.7);
+        overrides.emissive_multiplier = 1.0 + (elapsed * 4.0 + hue_offset.0).sin().max(0.0);
+    }
+}
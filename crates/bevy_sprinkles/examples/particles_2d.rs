@@ -0,0 +1,90 @@
+//! A flat effect confined to the XY plane and viewed through an orthographic camera.
+//!
+//! [`Particles2d`] is not implemented yet, so 2D effects are spawned with
+//! [`Particles3d`] and keep their particles on a plane with [`ParticleFlags::DISABLE_Z`].
+//!
+//! ```sh
+//! cargo run -p bevy_sprinkles --example particles_2d
+//! ```
+
+use bevy::{camera::ScalingMode, prelude::*};
+use bevy_sprinkles::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SprinklesPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut particles: ResMut<Assets<ParticlesAsset>>) {
+    commands.spawn((
+        Camera3d::default(),
+        Projection::from(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: 12.0,
+            },
+            ..OrthographicProjection::default_3d()
+        }),
+        Transform::from_xyz(0.0, 3.0, 10.0).looking_at(Vec3::new(0.0, 3.0, 0.0), Vec3::Y),
+    ));
+
+    let handle = particles.add(ParticlesAsset::new(
+        "Fountain 2D".into(),
+        ParticlesDimension::D2,
+        Default::default(),
+        vec![EmitterData {
+            name: "Fountain".into(),
+            time: EmitterTime {
+                lifetime: 2.0,
+                ..default()
+            },
+            draw_pass: EmitterDrawPass {
+                material: DrawPassMaterial::Standard(StandardParticleMaterial {
+                    unlit: true,
+                    ..default()
+                }),
+                ..default()
+            },
+            emission: EmitterEmission {
+                particles_amount: 300,
+                ..default()
+            },
+            scale: EmitterScale {
+                range: ParticleRange::new(0.1, 0.15),
+                ..default()
+            },
+            colors: EmitterColors {
+                initial_color: SolidOrGradientColor::Gradient {
+                    gradient: ParticleGradient {
+                        stops: vec![
+                            GradientStop {
+                                color: [0.3, 0.7, 1.0, 1.0],
+                                position: 0.0,
+                            },
+                            GradientStop {
+                                color: [0.9, 0.95, 1.0, 1.0],
+                                position: 1.0,
+                            },
+                        ],
+                        ..default()
+                    },
+                },
+                ..default()
+            },
+            velocities: EmitterVelocities {
+                initial_direction: Vec3::Y,
+                spread: 20.0,
+                initial_velocity: ParticleRange::new(8.0, 10.0),
+                ..default()
+            },
+            particle_flags: ParticleFlags::DISABLE_Z,
+            ..default()
+        }],
+        vec![],
+        false,
+        Default::default(),
+    ));
+
+    commands.spawn((Particles3d(handle), Transform::default()));
+}
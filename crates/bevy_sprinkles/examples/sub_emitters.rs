@@ -0,0 +1,147 @@
+//! Fireworks built from a rocket emitter that bursts into sparks through a sub-emitter.
+//!
+//! ```sh
+//! cargo run -p bevy_sprinkles --example sub_emitters
+//! ```
+
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SprinklesPlugin))
+        .insert_resource(ClearColor(Color::srgb(0.02, 0.02, 0.05)))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn glow_material(color: [f32; 4]) -> DrawPassMaterial {
+    DrawPassMaterial::Standard(StandardParticleMaterial {
+        base_color: color,
+        base_color_texture: Some(TextureRef::Preset(PresetTexture::Circle5)),
+        alpha_mode: SerializableAlphaMode::Blend,
+        blend_mode: Some(ParticleBlendMode::AdditiveWithAlpha),
+        unlit: true,
+        ..default()
+    })
+}
+
+fn setup(mut commands: Commands, mut particles: ResMut<Assets<ParticlesAsset>>) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 6.0, 16.0).looking_at(Vec3::new(0.0, 6.0, 0.0), Vec3::Y),
+    ));
+
+    let rockets = EmitterData {
+        name: "Rockets".into(),
+        time: EmitterTime {
+            lifetime: 1.2,
+            lifetime_randomness: 0.2,
+            ..default()
+        },
+        draw_pass: EmitterDrawPass {
+            material: glow_material([1.0, 0.7, 0.3, 1.0]),
+            ..default()
+        },
+        emission: EmitterEmission {
+            particles_amount: 4,
+            ..default()
+        },
+        scale: EmitterScale {
+            range: ParticleRange::new(0.15, 0.2),
+            ..default()
+        },
+        velocities: EmitterVelocities {
+            initial_direction: Vec3::Y,
+            spread: 15.0,
+            initial_velocity: ParticleRange::new(9.0, 11.0),
+            ..default()
+        },
+        accelerations: EmitterAccelerations {
+            gravity: Vec3::new(0.0, -4.0, 0.0),
+            ..default()
+        },
+        // every rocket bursts into sparks when it dies
+        sub_emitter: Some(SubEmitterConfig {
+            mode: SubEmitterMode::AtEnd,
+            target_emitter: 1,
+            amount: 64,
+            ..default()
+        }),
+        ..default()
+    };
+
+    let sparks = EmitterData {
+        name: "Sparks".into(),
+        time: EmitterTime {
+            lifetime: 1.5,
+            lifetime_randomness: 0.3,
+            ..default()
+        },
+        draw_pass: EmitterDrawPass {
+            material: glow_material([1.0, 1.0, 1.0, 1.0]),
+            ..default()
+        },
+        emission: EmitterEmission {
+            particles_amount: 512,
+            ..default()
+        },
+        scale: EmitterScale {
+            range: ParticleRange::new(0.08, 0.12),
+            ..default()
+        },
+        colors: EmitterColors {
+            initial_color: SolidOrGradientColor::Gradient {
+                gradient: ParticleGradient {
+                    stops: vec![
+                        GradientStop {
+                            color: [1.0, 0.3, 0.4, 1.0],
+                            position: 0.0,
+                        },
+                        GradientStop {
+                            color: [0.4, 0.6, 1.0, 1.0],
+                            position: 1.0,
+                        },
+                    ],
+                    ..default()
+                },
+            },
+            color_over_lifetime: ParticleGradient {
+                stops: vec![
+                    GradientStop {
+                        color: [1.0, 1.0, 1.0, 1.0],
+                        position: 0.0,
+                    },
+                    GradientStop {
+                        color: [1.0, 1.0, 1.0, 0.0],
+                        position: 1.0,
+                    },
+                ],
+                ..default()
+            },
+            ..default()
+        },
+        velocities: EmitterVelocities {
+            spread: 180.0,
+            initial_velocity: ParticleRange::new(2.0, 4.0),
+            ..default()
+        },
+        accelerations: EmitterAccelerations {
+            gravity: Vec3::new(0.0, -2.0, 0.0),
+            ..default()
+        },
+        ..default()
+    };
+
+    let handle = particles.add(ParticlesAsset::new(
+        "Fireworks".into(),
+        ParticlesDimension::D3,
+        Default::default(),
+        vec![rockets, sparks],
+        vec![],
+        false,
+        Default::default(),
+    ));
+
+    commands.spawn((Particles3d(handle), Transform::default()));
+}
@@ -0,0 +1,108 @@
+//! Turbulence applied everywhere versus limited to a volume in the scene.
+//!
+//! Both columns rise from the ground. The left one is turbulent along its whole path,
+//! while the right one only starts swirling once it enters the [`TurbulenceVolume`].
+//!
+//! ```sh
+//! cargo run -p bevy_sprinkles --example turbulence
+//! ```
+
+use bevy::{light::light_consts::lux, prelude::*};
+use bevy_sprinkles::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SprinklesPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn column(name: &str, use_volumes: bool) -> ParticlesAsset {
+    ParticlesAsset::new(
+        name.into(),
+        ParticlesDimension::D3,
+        Default::default(),
+        vec![EmitterData {
+            name: name.into(),
+            time: EmitterTime {
+                lifetime: 4.0,
+                ..default()
+            },
+            emission: EmitterEmission {
+                particles_amount: 600,
+                shape: EmissionShape::Sphere { radius: 0.3 },
+                ..default()
+            },
+            scale: EmitterScale {
+                range: ParticleRange::new(0.05, 0.08),
+                ..default()
+            },
+            velocities: EmitterVelocities {
+                initial_direction: Vec3::Y,
+                spread: 5.0,
+                initial_velocity: ParticleRange::new(1.5, 2.0),
+                ..default()
+            },
+            accelerations: EmitterAccelerations {
+                gravity: Vec3::ZERO,
+                ..default()
+            },
+            turbulence: EmitterTurbulence {
+                enabled: true,
+                noise_strength: 2.0,
+                noise_scale: 1.5,
+                noise_speed: Vec3::new(0.0, 0.5, 0.0),
+                influence: ParticleRange::new(0.2, 0.4),
+                use_volumes,
+                ..default()
+            },
+            ..default()
+        }],
+        vec![],
+        false,
+        Default::default(),
+    )
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut particles: ResMut<Assets<ParticlesAsset>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 12.0).looking_at(Vec3::new(0.0, 3.0, 0.0), Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            illuminance: lux::OVERCAST_DAY,
+            ..default()
+        },
+        Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.4, 0.0)),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(16.0, 16.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.35))),
+    ));
+
+    commands.spawn((
+        Particles3d(particles.add(column("Everywhere", false))),
+        Transform::from_xyz(-2.5, 0.0, 0.0),
+    ));
+    commands.spawn((
+        Particles3d(particles.add(column("Volume only", true))),
+        Transform::from_xyz(2.5, 0.0, 0.0),
+    ));
+
+    // volumes are positioned in world space and affect every emitter that opts in
+    commands.spawn((
+        TurbulenceVolume {
+            shape: ParticlesColliderShape3D::Box {
+                size: Vec3::new(3.0, 3.0, 3.0),
+            },
+            ..default()
+        },
+        Transform::from_xyz(2.5, 5.0, 0.0),
+    ));
+}
@@ -21,6 +21,10 @@ pub enum CurveMode {
     Stairs,
     /// Staircase interpolation with smooth transitions between steps.
     SmoothStairs,
+    /// Cubic Bezier interpolation shaped by explicit tangents: the left point's
+    /// [`out_tangent`](CurvePoint::out_tangent) and this point's
+    /// [`in_tangent`](CurvePoint::in_tangent). Tension and easing are ignored.
+    Bezier,
 }
 
 impl FromStr for CurveMode {
@@ -32,6 +36,7 @@ impl FromStr for CurveMode {
             "Hold" => Ok(Self::Hold),
             "Stairs" => Ok(Self::Stairs),
             "SmoothStairs" => Ok(Self::SmoothStairs),
+            "Bezier" => Ok(Self::Bezier),
             _ => Err(()),
         }
     }
@@ -64,10 +69,68 @@ impl FromStr for CurveEasing {
     }
 }
 
+/// How a [`CurveTexture`] is sampled at positions outside `[0.0, 1.0]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, Reflect)]
+pub enum CurveWrap {
+    /// Holds the value at the nearest end of the curve.
+    #[default]
+    Clamp,
+    /// Starts over from the beginning of the curve.
+    Loop,
+    /// Plays the curve backwards and forwards in turn.
+    PingPong,
+}
+
+impl FromStr for CurveWrap {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Clamp" => Ok(Self::Clamp),
+            "Loop" => Ok(Self::Loop),
+            "PingPong" => Ok(Self::PingPong),
+            _ => Err(()),
+        }
+    }
+}
+
+impl CurveWrap {
+    /// Maps position `t` into `[0.0, 1.0]`. Positions already inside that range are
+    /// returned unchanged.
+    pub fn apply(self, t: f32) -> f32 {
+        if (0.0..=1.0).contains(&t) {
+            return t;
+        }
+        match self {
+            Self::Clamp => t.clamp(0.0, 1.0),
+            Self::Loop => t.rem_euclid(1.0),
+            Self::PingPong => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 { 2.0 - t } else { t }
+            }
+        }
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 fn default_tension() -> f64 {
     0.0
 }
 
+fn default_repeat() -> f32 {
+    1.0
+}
+
+fn is_default_repeat(repeat: &f32) -> bool {
+    *repeat == default_repeat()
+}
+
+fn is_zero_tangent(tangent: &Vec2) -> bool {
+    *tangent == Vec2::ZERO
+}
+
 /// A single control point in a [`CurveTexture`].
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Reflect)]
 pub struct CurvePoint {
@@ -85,6 +148,16 @@ pub struct CurvePoint {
     /// Easing function applied within this segment.
     #[serde(default)]
     pub easing: CurveEasing,
+    /// Bezier handle of the segment leading to this point, as an offset from the point
+    /// in `(position, value)` units. Only used when [`mode`](Self::mode) is
+    /// [`CurveMode::Bezier`].
+    #[serde(default, skip_serializing_if = "is_zero_tangent")]
+    pub in_tangent: Vec2,
+    /// Bezier handle of the segment leaving this point, as an offset from the point in
+    /// `(position, value)` units. Only used when the next point's mode is
+    /// [`CurveMode::Bezier`].
+    #[serde(default, skip_serializing_if = "is_zero_tangent")]
+    pub out_tangent: Vec2,
 }
 
 impl CurvePoint {
@@ -96,6 +169,8 @@ impl CurvePoint {
             mode: CurveMode::default(),
             tension: 0.0,
             easing: CurveEasing::default(),
+            in_tangent: Vec2::ZERO,
+            out_tangent: Vec2::ZERO,
         }
     }
 
@@ -116,6 +191,13 @@ impl CurvePoint {
         self.easing = easing;
         self
     }
+
+    /// Sets the Bezier handles on both sides of this point.
+    pub fn with_tangents(mut self, in_tangent: Vec2, out_tangent: Vec2) -> Self {
+        self.in_tangent = in_tangent;
+        self.out_tangent = out_tangent;
+        self
+    }
 }
 
 fn is_empty_string(s: &Option<String>) -> bool {
@@ -167,6 +249,11 @@ impl Curve {
 /// When `y` or `z` is `None`, those channels fall back to the X channel. This allows a
 /// single `CurveTexture` to represent both scalar curves and per-axis curves without a
 /// separate type.
+///
+/// A curve can play more than once over a particle's lifetime with
+/// [`repeat`](Self::repeat), in which case [`wrap`](Self::wrap) decides how it continues
+/// past its end. The baked texture always holds a single pass over `[0.0, 1.0]`, and the
+/// shader wraps its sampling position the same way [`sample`](Self::sample) does.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Reflect)]
 pub struct CurveTexture {
     /// Optional display name for this curve (e.g., "Constant", "Fade Out").
@@ -180,6 +267,16 @@ pub struct CurveTexture {
     /// Optional Z channel. Falls back to `x` when `None`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub z: Option<Curve>,
+    /// How the curve is sampled past its end. Defaults to [`CurveWrap::Clamp`].
+    #[serde(default, skip_serializing_if = "CurveWrap::is_default")]
+    pub wrap: CurveWrap,
+    /// How many times the curve plays over the span it animates, such as a particle's
+    /// lifetime. Values above `1.0` sample past the end of the curve, following
+    /// [`wrap`](Self::wrap).
+    ///
+    /// Defaults to `1.0`.
+    #[serde(default = "default_repeat", skip_serializing_if = "is_default_repeat")]
+    pub repeat: f32,
 }
 
 impl Default for CurveTexture {
@@ -189,6 +286,8 @@ impl Default for CurveTexture {
             x: Curve::default(),
             y: None,
             z: None,
+            wrap: CurveWrap::default(),
+            repeat: default_repeat(),
         }
     }
 }
@@ -201,6 +300,8 @@ impl CurveTexture {
             x: Curve::new(points),
             y: None,
             z: None,
+            wrap: CurveWrap::default(),
+            repeat: default_repeat(),
         }
     }
 
@@ -215,6 +316,8 @@ impl CurveTexture {
             x: Curve::new(points_x),
             y: Some(Curve::new(points_y)),
             z: Some(Curve::new(points_z)),
+            wrap: CurveWrap::default(),
+            repeat: default_repeat(),
         }
     }
 
@@ -230,6 +333,18 @@ impl CurveTexture {
         self
     }
 
+    /// Sets how the curve is sampled past its end.
+    pub fn with_wrap(mut self, wrap: CurveWrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets how many times the curve plays over a particle's lifetime.
+    pub fn with_repeat(mut self, repeat: f32) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
     /// Returns the effective range for the Y channel, falling back to the X range when unset.
     pub fn effective_range_y(&self) -> &Range {
         self.y.as_ref().map(|c| &c.range).unwrap_or(&self.x.range)
@@ -301,13 +416,15 @@ impl CurveTexture {
         true
     }
 
-    /// Samples the X (primary) channel at position `t` (clamped to `[0.0, 1.0]`).
+    /// Samples the X (primary) channel at position `t`, wrapped into `[0.0, 1.0]` by
+    /// [`wrap`](Self::wrap).
     pub fn sample(&self, t: f32) -> f32 {
-        sample_points(&self.x.points, t)
+        sample_points(&self.x.points, self.wrap.apply(t))
     }
 
-    /// Samples a specific channel at position `t`. Channel 0 is X, 1 is Y, 2 is Z.
-    /// Y and Z fall back to the X channel when unset.
+    /// Samples a specific channel at position `t`, wrapped into `[0.0, 1.0]` by
+    /// [`wrap`](Self::wrap). Channel 0 is X, 1 is Y, 2 is Z. Y and Z fall back to the X
+    /// channel when unset.
     pub fn sample_channel(&self, channel: usize, t: f32) -> f32 {
         let points = match channel {
             1 => self
@@ -322,7 +439,7 @@ impl CurveTexture {
                 .unwrap_or(&self.x.points),
             _ => &self.x.points,
         };
-        sample_points(points, t)
+        sample_points(points, self.wrap.apply(t))
     }
 }
 
@@ -332,6 +449,10 @@ fn hash_points(points: &[CurvePoint], hasher: &mut impl Hasher) {
         (point.value as f32).to_bits().hash(hasher);
         std::mem::discriminant(&point.mode).hash(hasher);
         (point.tension as f32).to_bits().hash(hasher);
+        point.in_tangent.x.to_bits().hash(hasher);
+        point.in_tangent.y.to_bits().hash(hasher);
+        point.out_tangent.x.to_bits().hash(hasher);
+        point.out_tangent.y.to_bits().hash(hasher);
     }
 }
 
@@ -382,6 +503,10 @@ fn sample_points(points: &[CurvePoint], t: f32) -> f32 {
         return left.value as f32;
     }
 
+    if right.mode == CurveMode::Bezier {
+        return sample_bezier(left, right, t);
+    }
+
     let local_t = (t - left.position) / segment_range;
 
     let slope_sign = (right.value - left.value).signum() as f32;
@@ -391,6 +516,36 @@ fn sample_points(points: &[CurvePoint], t: f32) -> f32 {
     (left.value + (right.value - left.value) * curved_t as f64) as f32
 }
 
+const BEZIER_ITERATIONS: u32 = 24;
+
+// solves the segment's x(s) = t by bisection, which is safe because the handles are
+// clamped inside the segment and keep x(s) monotonic
+fn sample_bezier(left: &CurvePoint, right: &CurvePoint, t: f32) -> f32 {
+    let (x0, x3) = (left.position, right.position);
+    let x1 = (x0 + left.out_tangent.x).clamp(x0, x3);
+    let x2 = (x3 + right.in_tangent.x).clamp(x0, x3);
+    let y0 = left.value as f32;
+    let y3 = right.value as f32;
+    let y1 = y0 + left.out_tangent.y;
+    let y2 = y3 + right.in_tangent.y;
+
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    for _ in 0..BEZIER_ITERATIONS {
+        let mid = (lo + hi) * 0.5;
+        if cubic_bezier(x0, x1, x2, x3, mid) < t {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    cubic_bezier(y0, y1, y2, y3, (lo + hi) * 0.5)
+}
+
+fn cubic_bezier(p0: f32, p1: f32, p2: f32, p3: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    u * u * u * p0 + 3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s * p3
+}
+
 fn apply_curve(t: f32, mode: CurveMode, easing: CurveEasing, tension: f32) -> f32 {
     match mode {
         CurveMode::SingleCurve => apply_easing(t, easing, tension),
@@ -404,6 +559,8 @@ fn apply_curve(t: f32, mode: CurveMode, easing: CurveEasing, tension: f32) -> f3
             }
         }
         CurveMode::Hold => 0.0,
+        // sampled from the points' tangents in `sample_bezier`
+        CurveMode::Bezier => t,
         CurveMode::Stairs => {
             let steps = tension_to_steps(tension);
             (t * steps as f32).floor() / (steps - 1).max(1) as f32
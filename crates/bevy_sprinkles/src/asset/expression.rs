@@ -0,0 +1,490 @@
+use std::f32::consts::{PI, TAU};
+use std::fmt;
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when parsing an [`Expression`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ExpressionError {
+    /// The source contains a character that is not part of the language.
+    #[error("unexpected character '{0}' at position {1}")]
+    UnexpectedCharacter(char, usize),
+    /// The source ended in the middle of an expression.
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    /// A token appeared where it is not allowed.
+    #[error("unexpected '{0}' at position {1}")]
+    UnexpectedToken(String, usize),
+    /// The source references a variable that does not exist.
+    #[error("unknown variable '{0}'")]
+    UnknownVariable(String),
+    /// The source calls a function that does not exist.
+    #[error("unknown function '{0}'")]
+    UnknownFunction(String),
+    /// A function was called with the wrong number of arguments.
+    #[error("'{function}' takes {expected} argument(s), got {found}")]
+    WrongArgumentCount {
+        /// Name of the function.
+        function: String,
+        /// Number of arguments the function takes.
+        expected: usize,
+        /// Number of arguments it was called with.
+        found: usize,
+    },
+}
+
+/// Values an [`Expression`] can read while it is evaluated.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExpressionContext {
+    /// Seconds since the emitter started, available as `TIME`.
+    pub time: f32,
+    /// The emitter's phase within the current cycle, available as `PHASE`.
+    pub phase: f32,
+    /// The current emission cycle index, available as `CYCLE`.
+    pub cycle: u32,
+}
+
+/// A small math expression evaluated on the CPU every simulation step.
+///
+/// Expressions use a Godot-like syntax, for example `sin(TIME * 2.0) * 0.5`, and are
+/// stored in assets as plain strings. They support:
+///
+/// - number literals, parentheses, unary `-`, and the `+`, `-`, `*`, `/`, `%` operators
+/// - the variables `TIME`, `PHASE`, `CYCLE`, `PI`, and `TAU`
+/// - the functions `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `atan2`, `abs`, `sign`,
+///   `floor`, `ceil`, `round`, `fract`, `sqrt`, `exp`, `log`, `pow`, `min`, `max`,
+///   `clamp`, `mix`, `step`, and `smoothstep`
+///
+/// See [`ExpressionContext`] for what each variable holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(try_from = "String", into = "String")]
+pub struct Expression {
+    source: String,
+}
+
+impl Expression {
+    /// Parses `source`, returning an error if it is not a valid expression.
+    pub fn new(source: impl Into<String>) -> Result<Self, ExpressionError> {
+        let source = source.into();
+        parse(&source)?;
+        Ok(Self { source })
+    }
+
+    /// The source text of this expression.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Parses this expression into a form that can be evaluated repeatedly.
+    ///
+    /// This only fails if the source was changed through reflection after the
+    /// expression was created.
+    pub fn compile(&self) -> Result<CompiledExpression, ExpressionError> {
+        parse(&self.source).map(|root| CompiledExpression { root })
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Expression {
+    type Error = ExpressionError;
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::new(source)
+    }
+}
+
+impl From<Expression> for String {
+    fn from(expression: Expression) -> Self {
+        expression.source
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A parsed [`Expression`], ready to be evaluated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledExpression {
+    root: Node,
+}
+
+impl CompiledExpression {
+    /// Evaluates the expression with the given variables.
+    pub fn evaluate(&self, context: &ExpressionContext) -> f32 {
+        self.root.evaluate(context)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    Time,
+    Phase,
+    Cycle,
+    Pi,
+    Tau,
+}
+
+impl Variable {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "TIME" => Some(Self::Time),
+            "PHASE" => Some(Self::Phase),
+            "CYCLE" => Some(Self::Cycle),
+            "PI" => Some(Self::Pi),
+            "TAU" => Some(Self::Tau),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Atan2,
+    Abs,
+    Sign,
+    Floor,
+    Ceil,
+    Round,
+    Fract,
+    Sqrt,
+    Exp,
+    Log,
+    Pow,
+    Min,
+    Max,
+    Clamp,
+    Mix,
+    Step,
+    Smoothstep,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sin" => Some(Self::Sin),
+            "cos" => Some(Self::Cos),
+            "tan" => Some(Self::Tan),
+            "asin" => Some(Self::Asin),
+            "acos" => Some(Self::Acos),
+            "atan" => Some(Self::Atan),
+            "atan2" => Some(Self::Atan2),
+            "abs" => Some(Self::Abs),
+            "sign" => Some(Self::Sign),
+            "floor" => Some(Self::Floor),
+            "ceil" => Some(Self::Ceil),
+            "round" => Some(Self::Round),
+            "fract" => Some(Self::Fract),
+            "sqrt" => Some(Self::Sqrt),
+            "exp" => Some(Self::Exp),
+            "log" => Some(Self::Log),
+            "pow" => Some(Self::Pow),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "clamp" => Some(Self::Clamp),
+            "mix" => Some(Self::Mix),
+            "step" => Some(Self::Step),
+            "smoothstep" => Some(Self::Smoothstep),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Atan2 | Self::Pow | Self::Min | Self::Max | Self::Step => 2,
+            Self::Clamp | Self::Mix | Self::Smoothstep => 3,
+            _ => 1,
+        }
+    }
+
+    fn apply(self, args: &[f32]) -> f32 {
+        match self {
+            Self::Sin => args[0].sin(),
+            Self::Cos => args[0].cos(),
+            Self::Tan => args[0].tan(),
+            Self::Asin => args[0].asin(),
+            Self::Acos => args[0].acos(),
+            Self::Atan => args[0].atan(),
+            Self::Atan2 => args[0].atan2(args[1]),
+            Self::Abs => args[0].abs(),
+            Self::Sign => {
+                if args[0] == 0.0 {
+                    0.0
+                } else {
+                    args[0].signum()
+                }
+            }
+            Self::Floor => args[0].floor(),
+            Self::Ceil => args[0].ceil(),
+            Self::Round => args[0].round(),
+            Self::Fract => args[0] - args[0].floor(),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Exp => args[0].exp(),
+            Self::Log => args[0].ln(),
+            Self::Pow => args[0].powf(args[1]),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            // min/max instead of f32::clamp, which panics when min > max
+            Self::Clamp => args[0].max(args[1]).min(args[2]),
+            Self::Mix => args[0] + (args[1] - args[0]) * args[2],
+            // matches WGSL argument order: step(edge, x)
+            Self::Step => {
+                if args[1] < args[0] {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Self::Smoothstep => {
+                let t = ((args[2] - args[0]) / (args[1] - args[0])).clamp(0.0, 1.0);
+                t * t * (3.0 - 2.0 * t)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f32),
+    Variable(Variable),
+    Negate(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+impl Node {
+    fn evaluate(&self, context: &ExpressionContext) -> f32 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(variable) => match variable {
+                Variable::Time => context.time,
+                Variable::Phase => context.phase,
+                Variable::Cycle => context.cycle as f32,
+                Variable::Pi => PI,
+                Variable::Tau => TAU,
+            },
+            Self::Negate(node) => -node.evaluate(context),
+            Self::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(context), rhs.evaluate(context));
+                match op {
+                    BinaryOp::Add => lhs + rhs,
+                    BinaryOp::Sub => lhs - rhs,
+                    BinaryOp::Mul => lhs * rhs,
+                    BinaryOp::Div => lhs / rhs,
+                    BinaryOp::Rem => lhs % rhs,
+                }
+            }
+            Self::Call(function, args) => {
+                let mut values = [0.0; 3];
+                for (value, arg) in values.iter_mut().zip(args) {
+                    *value = arg.evaluate(context);
+                }
+                function.apply(&values)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Ident(String),
+    Symbol(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Ident(name) => f.write_str(name),
+            Self::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                let is_exponent_sign =
+                    matches!(c, '+' | '-') && matches!(source[..i].chars().last(), Some('e' | 'E'));
+                if !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E') || is_exponent_sign) {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let value = source[start..end]
+                .parse()
+                .map_err(|_| ExpressionError::UnexpectedToken(source[start..end].into(), start))?;
+            tokens.push((Token::Number(value), start));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + 1;
+                chars.next();
+            }
+            tokens.push((Token::Ident(source[start..end].to_string()), start));
+        } else if matches!(c, '+' | '-' | '*' | '/' | '%' | '(' | ')' | ',') {
+            tokens.push((Token::Symbol(c), start));
+            chars.next();
+        } else {
+            return Err(ExpressionError::UnexpectedCharacter(c, start));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse(source: &str) -> Result<Node, ExpressionError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+    let root = parser.expression()?;
+    match parser.tokens.get(parser.position) {
+        Some((token, at)) => Err(ExpressionError::UnexpectedToken(token.to_string(), *at)),
+        None => Ok(root),
+    }
+}
+
+// recursive descent over `expression = term (('+' | '-') term)*`,
+// `term = unary (('*' | '/' | '%') unary)*` and `unary = '-' unary | primary`
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Result<(Token, usize), ExpressionError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(ExpressionError::UnexpectedEnd)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        let matches =
+            matches!(self.tokens.get(self.position), Some((Token::Symbol(c), _)) if *c == symbol);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, symbol: char) -> Result<(), ExpressionError> {
+        match self.next()? {
+            (Token::Symbol(c), _) if c == symbol => Ok(()),
+            (token, at) => Err(ExpressionError::UnexpectedToken(token.to_string(), at)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.term()?;
+        loop {
+            let op = if self.eat('+') {
+                BinaryOp::Add
+            } else if self.eat('-') {
+                BinaryOp::Sub
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.unary()?;
+        loop {
+            let op = if self.eat('*') {
+                BinaryOp::Mul
+            } else if self.eat('/') {
+                BinaryOp::Div
+            } else if self.eat('%') {
+                BinaryOp::Rem
+            } else {
+                return Ok(node);
+            };
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.eat('-') {
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        match self.next()? {
+            (Token::Number(value), _) => Ok(Node::Number(value)),
+            (Token::Symbol('('), _) => {
+                let node = self.expression()?;
+                self.expect(')')?;
+                Ok(node)
+            }
+            (Token::Ident(name), _) if self.eat('(') => {
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| ExpressionError::UnknownFunction(name.clone()))?;
+                let mut args = Vec::new();
+                if !self.eat(')') {
+                    loop {
+                        args.push(self.expression()?);
+                        if self.eat(')') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                if args.len() != function.arity() {
+                    return Err(ExpressionError::WrongArgumentCount {
+                        function: name,
+                        expected: function.arity(),
+                        found: args.len(),
+                    });
+                }
+                Ok(Node::Call(function, args))
+            }
+            (Token::Ident(name), _) => Variable::from_name(&name)
+                .map(Node::Variable)
+                .ok_or(ExpressionError::UnknownVariable(name)),
+            (token, at) => Err(ExpressionError::UnexpectedToken(token.to_string(), at)),
+        }
+    }
+}
@@ -2,6 +2,10 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
+use super::CurveTexture;
+
+const CURVE_GRADIENT_STOPS: usize = 64;
+
 /// Interpolation mode for sampling between gradient stops.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash, Reflect)]
 pub enum GradientInterpolation {
@@ -79,6 +83,35 @@ impl Gradient {
         }
     }
 
+    /// Creates a gradient from per-channel curves, where the X, Y and Z channels of the
+    /// curve give the red, green and blue values. Alpha is always `1.0`.
+    ///
+    /// The curves are sampled into evenly spaced stops with linear interpolation, following
+    /// their [`repeat`](CurveTexture::repeat) and [`wrap`](CurveTexture::wrap), so the
+    /// result bakes into the same texture as any other gradient.
+    pub fn from_curves(curves: &CurveTexture) -> Self {
+        let last = (CURVE_GRADIENT_STOPS - 1) as f32;
+        let stops = (0..CURVE_GRADIENT_STOPS)
+            .map(|i| {
+                let position = i as f32 / last;
+                let t = position * curves.repeat;
+                GradientStop {
+                    color: [
+                        curves.sample_channel(0, t),
+                        curves.sample_channel(1, t),
+                        curves.sample_channel(2, t),
+                        1.0,
+                    ],
+                    position,
+                }
+            })
+            .collect();
+        Self {
+            stops,
+            interpolation: GradientInterpolation::Linear,
+        }
+    }
+
     /// Computes a hash key for texture caching, based on all stops and the interpolation mode.
     pub fn cache_key(&self) -> u64 {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -1,14 +1,17 @@
 mod curve;
+mod expression;
 mod gradient;
 mod particle_material;
 pub(crate) mod serde_helpers;
 /// Asset format versioning, validation, and migration.
 pub mod versions;
 
-pub use curve::{Curve, CurveEasing, CurveMode, CurvePoint, CurveTexture};
+pub use curve::{Curve, CurveEasing, CurveMode, CurvePoint, CurveTexture, CurveWrap};
+pub use expression::{CompiledExpression, Expression, ExpressionContext, ExpressionError};
 pub use gradient::{Gradient, GradientInterpolation, GradientStop, SolidOrGradientColor};
 pub use particle_material::{
-    DrawPassMaterial, SerializableAlphaMode, SerializableFace, StandardParticleMaterial,
+    AlphaFadeMode, DrawPassMaterial, ParticleBlendMode, SerializableAlphaMode, SerializableFace,
+    StandardParticleMaterial,
 };
 
 use bevy::{
@@ -18,8 +21,10 @@ use bevy::{
 };
 use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use thiserror::Error;
 
+use crate::textures::preset::TextureRef;
 use serde_helpers::*;
 use versions::current_format_version;
 
@@ -76,14 +81,21 @@ bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
     #[serde(transparent)]
     pub struct ParticleFlags: u32 {
+        /// If set, particles align their Y axis to the direction of their velocity, as with
+        /// [`TransformAlign::YToVelocity`]. Ignored when the draw pass sets its own
+        /// [`transform_align`](EmitterDrawPass::transform_align).
+        const ALIGN_Y_TO_VELOCITY = 1 << 0;
         /// If set, particles rotate around the Y axis by the configured angle.
         const ROTATE_Y = 1 << 1;
         /// If set, particles will not move on the Z axis, confining them to a 2D plane.
         const DISABLE_Z = 1 << 2;
         /// If set, angle_over_lifetime uses per-axis (X/Y/Z) rotation instead of single-axis.
         const ANGLE_PER_AXIS = 1 << 3;
-
-        // TODO: requires implementing damping
+        /// If set, [`damping`](EmitterAccelerations::damping) grows with the square of the
+        /// particle's speed, like air drag, instead of slowing it down at a constant rate.
+        const DAMPING_AS_FRICTION = 1 << 4;
+        /// If set, particles never rotate, ignoring their angle and angular velocity.
+        const DISABLE_ROTATION = 1 << 5;
     }
 }
 
@@ -100,8 +112,9 @@ pub enum ParticlesDimension {
 /// Controls the order in which particles are drawn.
 ///
 /// Draw order can affect visual quality depending on the blending mode used.
-/// [`DrawOrder::Index`] is the only option that supports motion vectors for
-/// effects like TAA, making it the best choice for opaque particles.
+/// Every draw order outputs per-particle motion vectors when the draw pass opts into
+/// the [depth prepass](EmitterDrawPass::depth_prepass), so TAA and motion blur work
+/// regardless of the order chosen.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
 pub enum DrawOrder {
     /// Particles are drawn in the order they were emitted.
@@ -111,8 +124,16 @@ pub enum DrawOrder {
     Lifetime,
     /// Particles are drawn by remaining lifetime, lowest first.
     ReverseLifetime,
-    /// Particles are drawn by depth relative to the camera.
+    /// Particles are drawn by depth along the camera's view axis, farthest first.
+    ///
+    /// Particles on the same plane facing the camera keep a stable order, which suits
+    /// planar effects.
     ViewDepth,
+    /// Particles are drawn by straight-line distance from the camera, farthest first.
+    ///
+    /// Unlike [`ViewDepth`](Self::ViewDepth), the order doesn't change when the camera
+    /// only rotates, which avoids popping near the edges of wide field of view cameras.
+    CameraDistance,
 }
 
 impl DrawOrder {
@@ -121,6 +142,33 @@ impl DrawOrder {
     }
 }
 
+/// What happens to an emitter once its one-shot emission has finished and all of its
+/// particles have died.
+///
+/// Stop actions are skipped for particle systems running in
+/// [`EditorMode`](crate::runtime::EditorMode), except for [`Callback`](Self::Callback).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum EmitterStopAction {
+    /// Nothing happens. The emitter stays idle until it is played again.
+    #[default]
+    None,
+    /// The emitter entity is despawned.
+    Despawn,
+    /// The emitter entity is hidden by setting its [`Visibility`] to
+    /// [`Hidden`](Visibility::Hidden). Its previous visibility comes back once it emits
+    /// again, for example after [`restart`](crate::EmitterRuntime::restart).
+    Disable,
+    /// An [`EmitterStopped`](crate::runtime::EmitterStopped) event is triggered on the
+    /// emitter entity.
+    Callback,
+}
+
+impl EmitterStopAction {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Timing and lifecycle configuration for an emitter.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -148,6 +196,12 @@ pub struct EmitterTime {
     /// Defaults to `false`.
     #[serde(skip_serializing_if = "is_false")]
     pub one_shot: bool,
+    /// What happens once a [`one_shot`](Self::one_shot) emitter has finished and all of
+    /// its particles have died.
+    ///
+    /// Defaults to [`EmitterStopAction::None`].
+    #[serde(skip_serializing_if = "EmitterStopAction::is_default")]
+    pub stop_action: EmitterStopAction,
     /// Time ratio between each emission, from `0.0` to `1.0`.
     ///
     /// If `0.0`, particles are emitted continuously over the lifetime. If `1.0`, all
@@ -167,6 +221,15 @@ pub struct EmitterTime {
     /// how often it is evaluated. Defaults to `0` (updates every frame).
     #[serde(skip_serializing_if = "is_zero_u32")]
     pub fixed_fps: u32,
+    /// If `true`, long frames are split into several simulation substeps of at most 1/60
+    /// of a second, so the emitter spawns and moves particles the same way at any frame
+    /// rate.
+    ///
+    /// Substeps are capped per frame to bound their cost, so frames slower than the cap
+    /// still use longer steps. Has no effect when [`fixed_fps`](Self::fixed_fps) is set,
+    /// since fixed steps are already frame rate independent. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub frame_rate_independent: bool,
     /// Optional fixed random seed for deterministic particle behavior.
     ///
     /// When set, the particle system will produce the same visual result across
@@ -182,9 +245,11 @@ impl Default for EmitterTime {
             lifetime_randomness: 0.0,
             delay: 0.0,
             one_shot: false,
+            stop_action: EmitterStopAction::None,
             explosiveness: 0.0,
             spawn_time_randomness: 0.0,
             fixed_fps: 0,
+            frame_rate_independent: false,
             fixed_seed: None,
         }
     }
@@ -197,6 +262,50 @@ impl EmitterTime {
     }
 }
 
+/// Variation re-rolled at the start of every emission cycle.
+///
+/// Particles already get fresh random values each cycle, but emitter-level settings
+/// stay the same, so looping effects can look identical every loop. These settings
+/// jitter them per cycle, salted with the cycle index, so they still repeat exactly
+/// with a [`fixed_seed`](EmitterTime::fixed_seed).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct EmitterCycleRandomness {
+    /// Maximum angle, in degrees, the
+    /// [`initial_direction`](EmitterVelocities::initial_direction) is tilted by each
+    /// cycle. Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub direction: f32,
+    /// How strongly each cycle's picks from an initial color gradient are pulled
+    /// toward one random gradient position shared by the whole cycle.
+    ///
+    /// At `0.0` every particle picks its own color. At `1.0` each cycle is a single
+    /// color from the gradient. Has no effect with a solid initial color.
+    /// Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub color: f32,
+    /// Maximum amount added to or removed from
+    /// [`explosiveness`](EmitterTime::explosiveness) each cycle. Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub explosiveness: f32,
+}
+
+impl Default for EmitterCycleRandomness {
+    fn default() -> Self {
+        Self {
+            direction: 0.0,
+            color: 0.0,
+            explosiveness: 0.0,
+        }
+    }
+}
+
+impl EmitterCycleRandomness {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// The initial transform applied when spawning a particle system, emitter, or collider.
 ///
 /// Used only during spawning if no [`Transform`] component is already present on the entity. To change the transform at runtime, modify the entity's [`Transform`] directly.
@@ -270,6 +379,10 @@ pub struct EmitterData {
     /// Timing and lifecycle settings (lifetime, delay, one-shot, etc.).
     pub time: EmitterTime,
 
+    /// Per-cycle variation of the emission direction, color pick, and explosiveness.
+    #[serde(skip_serializing_if = "EmitterCycleRandomness::is_default")]
+    pub cycle_randomness: EmitterCycleRandomness,
+
     /// Draw pass configuration (mesh, material, draw order).
     pub draw_pass: EmitterDrawPass,
 
@@ -286,6 +399,10 @@ pub struct EmitterData {
     /// Color and alpha settings, including gradients and curves over lifetime.
     pub colors: EmitterColors,
 
+    /// Texture sheet settings for picking a different atlas cell per particle.
+    #[serde(skip_serializing_if = "EmitterTextureSheet::should_skip")]
+    pub texture_sheet: EmitterTextureSheet,
+
     /// Velocity settings (direction, spread, radial/angular velocity, etc.).
     pub velocities: EmitterVelocities,
 
@@ -296,6 +413,14 @@ pub struct EmitterData {
     #[serde(skip_serializing_if = "EmitterTurbulence::should_skip")]
     pub turbulence: EmitterTurbulence,
 
+    /// Sinusoidal drifting motion settings.
+    #[serde(skip_serializing_if = "EmitterWave::should_skip")]
+    pub wave: EmitterWave,
+
+    /// Target shape particles ease toward over their lifetime.
+    #[serde(skip_serializing_if = "EmitterTarget::should_skip")]
+    pub target: EmitterTarget,
+
     /// Collision behavior settings.
     pub collision: EmitterCollision,
 
@@ -307,6 +432,10 @@ pub struct EmitterData {
     #[serde(skip_serializing_if = "EmitterTrail::should_skip")]
     pub trail: EmitterTrail,
 
+    /// Expressions that animate select emitter fields over time.
+    #[serde(skip_serializing_if = "EmitterExpressions::is_empty")]
+    pub expressions: EmitterExpressions,
+
     /// Bitflags controlling per-particle behavior (Y rotation, Z-axis disable, etc.).
     #[reflect(ignore)]
     pub particle_flags: ParticleFlags,
@@ -319,23 +448,38 @@ impl Default for EmitterData {
             enabled: true,
             initial_transform: InitialTransform::default(),
             time: EmitterTime::default(),
+            cycle_randomness: EmitterCycleRandomness::default(),
             draw_pass: EmitterDrawPass::default(),
             emission: EmitterEmission::default(),
             scale: EmitterScale::default(),
             angle: EmitterAngle::default(),
             colors: EmitterColors::default(),
+            texture_sheet: EmitterTextureSheet::default(),
             velocities: EmitterVelocities::default(),
             accelerations: EmitterAccelerations::default(),
             turbulence: EmitterTurbulence::default(),
+            wave: EmitterWave::default(),
+            target: EmitterTarget::default(),
             collision: EmitterCollision::default(),
             sub_emitter: None,
             trail: EmitterTrail::default(),
+            expressions: EmitterExpressions::default(),
             particle_flags: ParticleFlags::empty(),
         }
     }
 }
 
 impl EmitterData {
+    /// Returns the transform alignment particles are drawn with, falling back to
+    /// [`TransformAlign::YToVelocity`] when [`ParticleFlags::ALIGN_Y_TO_VELOCITY`] is set.
+    pub fn transform_align(&self) -> Option<TransformAlign> {
+        self.draw_pass.transform_align.or_else(|| {
+            self.particle_flags
+                .contains(ParticleFlags::ALIGN_Y_TO_VELOCITY)
+                .then_some(TransformAlign::YToVelocity)
+        })
+    }
+
     pub(crate) fn trail_size(&self) -> u32 {
         if !self.trail.enabled {
             return 1;
@@ -345,7 +489,7 @@ impl EmitterData {
 }
 
 /// Controls how each particle's transform is aligned relative to the camera or its velocity.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, Reflect)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default, Reflect)]
 pub enum TransformAlign {
     /// Particles always face the camera (Z-billboard).
     #[default]
@@ -356,6 +500,35 @@ pub enum TransformAlign {
     BillboardYToVelocity,
     /// Particles face the camera with a fixed world-space Y axis.
     BillboardFixedY,
+    /// Particles point their Z axis along a fixed world-space direction, regardless of
+    /// the camera. Their Y axis leans toward world up.
+    FixedAxis(Vec3),
+    /// Particles point their Y axis along their velocity and rotate around it to face
+    /// the camera, as axial billboards. Suited to rain, sparks and tracers.
+    ///
+    /// The value stretches particles along their velocity by this factor per unit of
+    /// speed, where `0.0` keeps their original length.
+    VelocityStretchedBillboard(f32),
+}
+
+/// Controls which render passes a draw pass contributes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum DrawPassVisibility {
+    /// Particles are drawn normally and cast shadows if
+    /// [`shadow_caster`](EmitterDrawPass::shadow_caster) is enabled.
+    #[default]
+    Normal,
+    /// Particles are only drawn into shadow maps, which lets a cheap proxy cast
+    /// shadows for an effect rendered by another emitter.
+    ShadowOnly,
+    /// Particles are drawn normally but never cast shadows.
+    NoShadow,
+}
+
+impl DrawPassVisibility {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Configuration for how particles are rendered in a single draw pass.
@@ -372,10 +545,51 @@ pub struct EmitterDrawPass {
     /// Whether particles cast shadows. Defaults to `true`.
     #[serde(skip_serializing_if = "is_true")]
     pub shadow_caster: bool,
+    /// Which passes the particles are drawn into. Defaults to
+    /// [`DrawPassVisibility::Normal`].
+    #[serde(skip_serializing_if = "DrawPassVisibility::is_default")]
+    pub visibility: DrawPassVisibility,
+    /// Whether opaque and alpha-masked particles are drawn into the depth, normal and
+    /// motion vector prepasses, so SSAO, TAA and other depth-based effects treat them
+    /// like regular geometry.
+    ///
+    /// Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub depth_prepass: bool,
+    /// Offset, in world units, added to this emitter's distance from the camera when
+    /// transparent particles are sorted.
+    ///
+    /// Positive values draw the emitter as if it were closer to the camera, on top of
+    /// other emitters, and negative values draw it behind them. Use this to keep one
+    /// emitter of a system (e.g. a glow) consistently behind another (e.g. the core).
+    ///
+    /// Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub sort_bias: f32,
+    /// Number of frames between sorts of this emitter's particles.
+    ///
+    /// Sorting huge emitters every frame can be expensive. With an interval of `n`, the
+    /// emitter is sorted once every `n` frames, staggered with other emitters, and drawn
+    /// in the last sorted order in between, so the order is at most `n - 1` frames stale.
+    /// Particles spawned in between still show up, just not in order. Values below `1`
+    /// behave as `1`.
+    ///
+    /// Defaults to `1`, which sorts every frame.
+    #[serde(skip_serializing_if = "is_one_u32")]
+    pub sort_interval: u32,
     /// Optional transform alignment mode for particles. When `None`, no special
     /// alignment is applied.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transform_align: Option<TransformAlign>,
+    /// Whether particles keep their up axis pointing toward world up.
+    ///
+    /// With [`TransformAlign::Billboard`], particles still face the camera but ignore
+    /// its roll. Without a billboard alignment, particles ignore the emitter's rotation
+    /// and keep a constant world orientation.
+    ///
+    /// Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub billboard_keep_world_up: bool,
     /// Whether particles use local coordinates and follow the emitter's transform.
     ///
     /// When `false` (default), particles are emitted into world space and remain
@@ -385,6 +599,37 @@ pub struct EmitterDrawPass {
     /// Defaults to `false`.
     #[serde(skip_serializing_if = "is_false")]
     pub use_local_coords: bool,
+    /// Enables culling individual particles outside the camera's view, with the given
+    /// margin in world units.
+    ///
+    /// Culled particles are skipped by sorting and drawn as empty, which helps huge
+    /// world-space systems such as rain over a whole city sector. Each particle is
+    /// tested as a sphere of its scale plus this margin, so raise it for large meshes or
+    /// long trails. Culling follows the first 3D camera, so particles culled from its
+    /// view are also missing from other cameras and shadow maps.
+    ///
+    /// Defaults to `None`, which draws every particle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frustum_cull_margin: Option<f32>,
+    /// Draws particles as a blended liquid surface instead of individual meshes, for
+    /// blood pools, slime and water splashes.
+    ///
+    /// Requires the `metaballs` feature, and only renders on cameras with a
+    /// `MetaballSettings` component. The [`mesh`](Self::mesh) and
+    /// [`material`](Self::material) are unused, and the surface takes its color from the
+    /// particles. Without the feature, particles are drawn normally. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub metaball: bool,
+    /// Value written to the [`ParticleMaskTexture`](crate::ParticleMaskTexture) of cameras
+    /// with a [`ParticleMaskTarget`](crate::ParticleMaskTarget), wherever this emitter's
+    /// particles are drawn.
+    ///
+    /// Lets post-processing such as outlines, selective bloom or damage flashes tell
+    /// particle effects apart from the rest of the scene, and from each other. Use values
+    /// above `0`, which marks pixels without particles. Defaults to `None`, which leaves
+    /// the emitter out of the mask.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process_mask: Option<u32>,
     /// The axis-aligned bounding box used for the emitter's visibility.
     #[serde(skip_serializing_if = "VisibilityAabb::is_default")]
     pub visibility_aabb: VisibilityAabb,
@@ -397,13 +642,37 @@ impl Default for EmitterDrawPass {
             mesh: ParticleMesh::default(),
             material: DrawPassMaterial::default(),
             shadow_caster: true,
+            visibility: DrawPassVisibility::default(),
+            depth_prepass: false,
+            sort_bias: 0.0,
+            sort_interval: 1,
             transform_align: None,
+            billboard_keep_world_up: false,
             use_local_coords: false,
+            frustum_cull_margin: None,
+            metaball: false,
+            post_process_mask: None,
             visibility_aabb: VisibilityAabb::default(),
         }
     }
 }
 
+impl EmitterDrawPass {
+    /// Returns whether particles are drawn by the metaball pass instead of as meshes.
+    pub fn draws_metaballs(&self) -> bool {
+        cfg!(feature = "metaballs") && self.metaball
+    }
+
+    /// Returns whether particles from this draw pass should cast shadows.
+    pub fn casts_shadows(&self) -> bool {
+        match self.visibility {
+            DrawPassVisibility::Normal => self.shadow_caster,
+            DrawPassVisibility::ShadowOnly => true,
+            DrawPassVisibility::NoShadow => false,
+        }
+    }
+}
+
 /// An axis-aligned bounding box, copied from Bevy's [`Aabb`](bevy::camera::primitives::Aabb).
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -775,10 +1044,54 @@ impl Range {
     fn zero() -> Self {
         Self { min: 0.0, max: 0.0 }
     }
+
+    fn is_one(&self) -> bool {
+        self.min == 1.0 && self.max == 1.0
+    }
+
+    fn one() -> Self {
+        Self { min: 1.0, max: 1.0 }
+    }
+}
+
+/// The order in which particles are placed around an [`EmissionShape::Ring`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum RingEmissionOrder {
+    /// Particles are placed at random angles.
+    #[default]
+    Random,
+    /// Particles are placed at evenly spaced angles in emission order, sweeping clockwise
+    /// when looking down the ring axis.
+    Clockwise,
+    /// Particles are placed at evenly spaced angles in emission order, sweeping
+    /// counter-clockwise when looking down the ring axis.
+    CounterClockwise,
+}
+
+impl RingEmissionOrder {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The image channel used to weight spawn positions of an [`EmissionShape::Image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum EmissionMaskChannel {
+    /// Spawn density follows the image's alpha channel.
+    #[default]
+    Alpha,
+    /// Spawn density follows the image's brightness, multiplied by its alpha.
+    Luminance,
+}
+
+impl EmissionMaskChannel {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// The region in which particles are spawned.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Reflect)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Reflect)]
 pub enum EmissionShape {
     /// All particles are emitted from a single point.
     #[default]
@@ -810,9 +1123,48 @@ pub enum EmissionShape {
         radius: f32,
         /// The inner radius of the ring. A value of `0.0` fills the entire disc.
         inner_radius: f32,
+        /// The angle of the arc particles are emitted along, in degrees. Defaults to `360.0`.
+        #[serde(default = "default_ring_arc", skip_serializing_if = "is_full_ring_arc")]
+        arc: f32,
+        /// How particles are distributed along the arc.
+        ///
+        /// With an ordered option and full explosiveness every particle lands on an evenly
+        /// spaced angle at once, while lower explosiveness sweeps around the ring over the
+        /// lifetime. Defaults to [`RingEmissionOrder::Random`].
+        #[serde(default, skip_serializing_if = "RingEmissionOrder::is_default")]
+        order: RingEmissionOrder,
+    },
+    /// Particles are emitted from the visible areas of an image, such as a logo or text.
+    ///
+    /// Spawn positions are importance-sampled from the image, so brighter or more opaque
+    /// pixels receive more particles. The image lies flat on the emitter's XY plane,
+    /// centered on its origin. Until an image is assigned and finishes loading, particles
+    /// spawn at the origin.
+    Image {
+        /// The image spawn positions are sampled from.
+        texture: Option<TextureRef>,
+        /// The size of the image in world units.
+        size: Vec2,
+        /// Which channel of the image drives spawn density. Defaults to
+        /// [`EmissionMaskChannel::Alpha`].
+        #[serde(default, skip_serializing_if = "EmissionMaskChannel::is_default")]
+        channel: EmissionMaskChannel,
+        /// When enabled, each particle's initial color is tinted by the image pixel it
+        /// spawned from, so a logo or picture dissolving into particles keeps its
+        /// colors. Defaults to `false`.
+        #[serde(default, skip_serializing_if = "is_false")]
+        inherit_color: bool,
     },
 }
 
+fn default_ring_arc() -> f32 {
+    360.0
+}
+
+fn is_full_ring_arc(arc: &f32) -> bool {
+    *arc == default_ring_arc()
+}
+
 impl EmissionShape {
     fn is_default(&self) -> bool {
         *self == Self::default()
@@ -840,10 +1192,43 @@ impl EmissionShape {
             height: 0.0,
             radius: 1.0,
             inner_radius: 0.0,
+            arc: default_ring_arc(),
+            order: RingEmissionOrder::default(),
+        }
+    }
+
+    /// Returns a default [`Image`](Self::Image) shape with no texture assigned.
+    pub fn default_image() -> Self {
+        Self::Image {
+            texture: None,
+            size: Vec2::splat(2.0),
+            channel: EmissionMaskChannel::default(),
+            inherit_color: false,
         }
     }
 }
 
+/// Which axes an emitter's local space is aligned to when spawning particles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum EmissionOrientation {
+    /// The emission shape, initial direction and spread follow the emitter's full
+    /// [`GlobalTransform`], so effects attached to a moving entity, like a rocket exhaust,
+    /// turn with it.
+    #[default]
+    Emitter,
+    /// The emission shape, initial direction and spread keep the world axes, and only
+    /// follow the emitter's position and scale, including the shear of non-uniformly
+    /// scaled parents. Useful for effects that should keep rising or falling straight no
+    /// matter how their parent is rotated, like smoke or rain.
+    World,
+}
+
+impl EmissionOrientation {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Emission configuration: shape, offset, scale, and particle count.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -857,10 +1242,67 @@ pub struct EmitterEmission {
     /// The shape of the emission region. Defaults to [`EmissionShape::Point`].
     #[serde(skip_serializing_if = "EmissionShape::is_default")]
     pub shape: EmissionShape,
+    /// Which axes the emission shape and initial velocities are aligned to, along with
+    /// everything else placed in the emitter's local space. Defaults to
+    /// [`EmissionOrientation::Emitter`].
+    #[serde(skip_serializing_if = "EmissionOrientation::is_default")]
+    pub orientation: EmissionOrientation,
+    /// Maximum random offset added to each spawn position along each local axis.
+    ///
+    /// Each particle is displaced by a random amount in `-position_randomness..position_randomness`.
+    /// Defaults to [`Vec3::ZERO`].
+    #[serde(skip_serializing_if = "is_zero_vec3")]
+    pub position_randomness: Vec3,
+    /// Thickness of the band particles spawn in for surface-type shapes.
+    ///
+    /// Applies to [`EmissionShape::SphereSurface`] and [`EmissionShape::Ring`], spreading
+    /// spawns evenly inside and outside the surface radius. Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub shell_thickness: f32,
+    /// Lifetime multiplier for particles spawned at the edge of the emission shape.
+    ///
+    /// Particles at the center keep their regular lifetime, and the multiplier blends in
+    /// linearly with the normalized distance from the center. Defaults to `1.0`.
+    #[serde(skip_serializing_if = "is_one_f32")]
+    pub edge_lifetime_scale: f32,
+    /// Initial speed multiplier for particles spawned at the edge of the emission shape.
+    ///
+    /// Blends in the same way as [`edge_lifetime_scale`](Self::edge_lifetime_scale), so
+    /// explosion shells can launch faster particles from the rim. Defaults to `1.0`.
+    #[serde(skip_serializing_if = "is_one_f32")]
+    pub edge_speed_scale: f32,
+    /// How much the random initial velocity, scale and angle follow a spatial noise of the
+    /// spawn position instead of pure randomness.
+    ///
+    /// At `0.0` every particle rolls independently. At `1.0` particles born near each other
+    /// get similar values, which keeps large emitters from looking like white noise.
+    /// Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub spatial_randomness: f32,
+    /// Size of the features in the [`spatial_randomness`](Self::spatial_randomness) noise,
+    /// in local units. Defaults to `1.0`.
+    #[serde(skip_serializing_if = "is_one_f32")]
+    pub spatial_randomness_scale: f32,
     /// The number of particles to emit in one emission cycle.
     ///
     /// Higher values will increase GPU load. Defaults to `8`.
     pub particles_amount: u32,
+    /// Number of particle slots allocated for this emitter, if larger than
+    /// [`particles_amount`](Self::particles_amount).
+    ///
+    /// Regular emission only ever uses `particles_amount` slots. The extra capacity is
+    /// for particles spawned on demand, such as an emitter targeted by a
+    /// [sub-emitter](SubEmitterConfig), so bursts from many parents don't get dropped.
+    /// See [`capacity`](Self::capacity). Defaults to `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_particles: Option<u32>,
+    /// Optional curve that scales the emission rate over each cycle.
+    ///
+    /// The curve is sampled at the emitter's phase, and each particle that is due to
+    /// spawn does so with a probability equal to the curve value, so effects can ramp up
+    /// and trail off within a loop. Values are expected in `0.0..=1.0`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_over_time: Option<CurveTexture>,
 }
 
 impl Default for EmitterEmission {
@@ -869,11 +1311,33 @@ impl Default for EmitterEmission {
             offset: Vec3::ZERO,
             scale: Vec3::ONE,
             shape: EmissionShape::default(),
+            orientation: EmissionOrientation::default(),
+            position_randomness: Vec3::ZERO,
+            shell_thickness: 0.0,
+            edge_lifetime_scale: 1.0,
+            edge_speed_scale: 1.0,
+            spatial_randomness: 0.0,
+            spatial_randomness_scale: 1.0,
             particles_amount: 8,
+            max_particles: None,
+            rate_over_time: None,
         }
     }
 }
 
+impl EmitterEmission {
+    /// Returns the number of particle slots allocated for this emitter: the larger of
+    /// [`particles_amount`](Self::particles_amount) and
+    /// [`max_particles`](Self::max_particles).
+    ///
+    /// Each slot also holds the particle's trail segments, so buffers hold
+    /// `capacity * trail_size` particles in total.
+    pub fn capacity(&self) -> u32 {
+        self.max_particles
+            .map_or(self.particles_amount, |max| max.max(self.particles_amount))
+    }
+}
+
 /// Particle scale configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -899,6 +1363,27 @@ impl Default for EmitterScale {
     }
 }
 
+/// How each particle picks its position in an initial color gradient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum InitialColorSampling {
+    /// Each particle samples a random position along the gradient.
+    #[default]
+    Random,
+    /// Particles sweep through the gradient in spawn order, starting over every emission
+    /// cycle. Useful for rainbow fountains and ordered color sequences.
+    SpawnOrder,
+    /// Particles sample the gradient by how far from the center of the [`EmissionShape`]
+    /// they spawned, from the start of the gradient at the center to its end at the edge.
+    /// Every particle of an [`EmissionShape::Point`] uses the start of the gradient.
+    EmissionPosition,
+}
+
+impl InitialColorSampling {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Color and alpha configuration for particles.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -906,11 +1391,22 @@ pub struct EmitterColors {
     /// Each particle's initial color. Can be a solid color or a gradient from which a random
     /// color is sampled at spawn time. Defaults to opaque white.
     pub initial_color: SolidOrGradientColor,
+    /// How particles pick their color when [`initial_color`](Self::initial_color) is a
+    /// gradient. Defaults to [`InitialColorSampling::Random`].
+    #[serde(skip_serializing_if = "InitialColorSampling::is_default")]
+    pub initial_color_sampling: InitialColorSampling,
     /// Gradient that modulates each particle's color over its lifetime.
     ///
     /// The particle's initial color is multiplied by the gradient value at the
     /// corresponding lifetime position. Defaults to a constant white gradient.
     pub color_over_lifetime: Gradient,
+    /// Optional per-channel curves used instead of
+    /// [`color_over_lifetime`](Self::color_over_lifetime).
+    ///
+    /// The X, Y and Z channels of the curve drive the red, green and blue multipliers. When
+    /// set, the gradient is ignored and these curves are baked in its place.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_over_lifetime_curves: Option<CurveTexture>,
     /// Optional curve that modulates each particle's alpha over its lifetime.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alpha_over_lifetime: Option<CurveTexture>,
@@ -923,13 +1419,26 @@ impl Default for EmitterColors {
     fn default() -> Self {
         Self {
             initial_color: SolidOrGradientColor::default(),
+            initial_color_sampling: InitialColorSampling::default(),
             color_over_lifetime: Gradient::white(),
+            color_over_lifetime_curves: None,
             alpha_over_lifetime: None,
             emission_over_lifetime: None,
         }
     }
 }
 
+impl EmitterColors {
+    /// Returns the gradient that is baked for color over lifetime, built from
+    /// [`color_over_lifetime_curves`](Self::color_over_lifetime_curves) when they are set.
+    pub fn color_over_lifetime_gradient(&self) -> Cow<'_, Gradient> {
+        match &self.color_over_lifetime_curves {
+            Some(curves) => Cow::Owned(Gradient::from_curves(curves)),
+            None => Cow::Borrowed(&self.color_over_lifetime),
+        }
+    }
+}
+
 /// A velocity value with an optional curve for animation over a particle's lifetime.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
@@ -954,8 +1463,11 @@ impl Default for AnimatedVelocity {
 
 /// Initial rotation angle and animated rotation for particles.
 ///
-/// Only applied when [`ParticleFlags::DISABLE_Z`] or [`ParticleFlags::ROTATE_Y`] are set,
-/// or when using billboard rendering.
+/// Particles rotate around their own Z axis, or their Y axis with
+/// [`ParticleFlags::ROTATE_Y`], before any [`TransformAlign`] is applied. This works for
+/// billboards, world-oriented quads and meshes alike: a billboard spins in the view
+/// plane, and a particle aligned with [`TransformAlign::YToVelocity`] spins around its
+/// velocity when rotating around Y.
 #[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
 #[serde(default)]
 pub struct EmitterAngle {
@@ -1008,8 +1520,7 @@ pub struct EmitterVelocities {
     pub radial_velocity: AnimatedVelocity,
     /// Angular (rotation) velocity applied to each particle, in degrees per second.
     ///
-    /// Only applied when [`ParticleFlags::DISABLE_Z`] or [`ParticleFlags::ROTATE_Y`] are set,
-    /// or when using billboard rendering.
+    /// Spins particles around the same axis as their [angle](EmitterAngle).
     pub angular_velocity: AnimatedVelocity,
     /// Orbital velocity that makes particles orbit around the [`pivot`](Self::pivot)
     /// point, in revolutions per second.
@@ -1057,16 +1568,65 @@ pub struct EmitterAccelerations {
     ///
     /// Defaults to `(0.0, -9.8, 0.0)`.
     pub gravity: Vec3,
+    /// Per-particle multiplier applied to [`gravity`](Self::gravity).
+    ///
+    /// A random value between `min` and `max` is selected at spawn time. Negative
+    /// values make particles buoyant, rising against gravity. Defaults to `1.0..1.0`.
+    #[serde(skip_serializing_if = "Range::is_one")]
+    pub gravity_scale: Range,
+    /// Acceleration away from the [`pivot`](EmitterVelocities::pivot) point, in units per
+    /// second squared.
+    ///
+    /// Negative values pull particles toward the pivot. The optional curve modulates the
+    /// acceleration over each particle's lifetime.
+    pub radial_acceleration: AnimatedVelocity,
+    /// Acceleration toward a fixed point in the emitter's local space.
+    #[serde(skip_serializing_if = "PointGravity::is_default")]
+    pub point_gravity: PointGravity,
+    /// How quickly particles slow down, in units per second squared.
+    ///
+    /// A random value between `min` and `max` is selected for each particle. Damping
+    /// only ever slows particles down to a stop, and becomes proportional to the square
+    /// of their speed with [`ParticleFlags::DAMPING_AS_FRICTION`]. Defaults to `0.0..0.0`.
+    #[serde(skip_serializing_if = "Range::is_zero")]
+    pub damping: Range,
 }
 
 impl Default for EmitterAccelerations {
     fn default() -> Self {
         Self {
             gravity: Vec3::new(0.0, -9.8, 0.0),
+            gravity_scale: Range::one(),
+            radial_acceleration: AnimatedVelocity::default(),
+            point_gravity: PointGravity::default(),
+            damping: Range::zero(),
         }
     }
 }
 
+/// Gravity pulling particles toward a point, such as for implosion or charge-up effects.
+///
+/// The acceleration has a constant magnitude regardless of distance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, Reflect)]
+#[serde(default)]
+pub struct PointGravity {
+    /// The point particles are attracted to, in the emitter's local space.
+    /// Defaults to [`Vec3::ZERO`].
+    #[serde(skip_serializing_if = "is_zero_vec3")]
+    pub position: Vec3,
+    /// Acceleration toward [`position`](Self::position), in units per second squared.
+    ///
+    /// Negative values push particles away. A value of `0.0` disables point gravity.
+    /// Defaults to `0.0`.
+    pub strength: f32,
+}
+
+impl PointGravity {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Turbulence noise settings for varying particle movement based on position.
 ///
 /// Turbulence uses a 3D noise pattern to displace particles, creating organic,
@@ -1105,6 +1665,11 @@ pub struct EmitterTurbulence {
     /// Optional curve that modulates turbulence influence over each particle's lifetime.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub influence_over_lifetime: Option<CurveTexture>,
+    /// If `true`, turbulence only affects particles inside a
+    /// [`TurbulenceVolume`](crate::TurbulenceVolume) in the scene, instead of every
+    /// particle of the emitter. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub use_volumes: bool,
 }
 
 impl Default for EmitterTurbulence {
@@ -1117,6 +1682,7 @@ impl Default for EmitterTurbulence {
             noise_speed_random: 0.0,
             influence: Range { min: 0.0, max: 0.1 },
             influence_over_lifetime: None,
+            use_volumes: false,
         }
     }
 }
@@ -1134,6 +1700,195 @@ impl EmitterTurbulence {
             && self.influence.min == d.influence.min
             && self.influence.max == d.influence.max
             && self.influence_over_lifetime.is_none()
+            && self.use_volumes == d.use_volumes
+    }
+}
+
+/// Sinusoidal displacement that makes particles sway back and forth along an axis.
+///
+/// A cheap alternative to [`EmitterTurbulence`] for floaty dust, fireflies and drifting
+/// motes.
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct EmitterWave {
+    /// Whether the wave motion is enabled. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    /// The maximum distance particles sway from their path, in units. Defaults to `0.25`.
+    pub amplitude: f32,
+    /// How many full oscillations happen per second. Defaults to `0.5`.
+    pub frequency: f32,
+    /// How much each particle's starting phase is randomized, from `0.0` (all particles
+    /// sway in sync) to `1.0` (fully random). Defaults to `1.0`.
+    pub phase_randomness: f32,
+    /// The local axis particles sway along. Defaults to [`Vec3::Y`].
+    pub axis: Vec3,
+}
+
+impl Default for EmitterWave {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            amplitude: 0.25,
+            frequency: 0.5,
+            phase_randomness: 1.0,
+            axis: Vec3::Y,
+        }
+    }
+}
+
+impl EmitterWave {
+    fn should_skip(&self) -> bool {
+        if self.enabled {
+            return false;
+        }
+        let d = Self::default();
+        self.amplitude == d.amplitude
+            && self.frequency == d.frequency
+            && self.phase_randomness == d.phase_randomness
+            && self.axis == d.axis
+    }
+}
+
+/// The set of positions particles are pulled toward by an [`EmitterTarget`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Reflect)]
+pub enum TargetShape {
+    /// An explicit list of positions in emitter space.
+    ///
+    /// Particles are assigned to positions by index, wrapping around when there are more
+    /// particles than positions.
+    Points {
+        /// The target positions.
+        positions: Vec<Vec3>,
+    },
+    /// The visible areas of an image, laid out like [`EmissionShape::Image`].
+    ///
+    /// Particles are spread evenly across the image's spawn distribution by index, so the
+    /// shape fills in as more particles arrive.
+    Image {
+        /// The image target positions are sampled from.
+        texture: Option<TextureRef>,
+        /// The size of the image in world units.
+        size: Vec2,
+        /// Which channel of the image drives target density. Defaults to
+        /// [`EmissionMaskChannel::Alpha`].
+        #[serde(default, skip_serializing_if = "EmissionMaskChannel::is_default")]
+        channel: EmissionMaskChannel,
+    },
+}
+
+impl Default for TargetShape {
+    fn default() -> Self {
+        Self::Points {
+            positions: Vec::new(),
+        }
+    }
+}
+
+impl TargetShape {
+    /// Returns a default [`Image`](Self::Image) target with no texture assigned.
+    pub fn default_image() -> Self {
+        Self::Image {
+            texture: None,
+            size: Vec2::splat(2.0),
+            channel: EmissionMaskChannel::default(),
+        }
+    }
+}
+
+/// Shape morphing settings that pull particles toward a target shape over their lifetime.
+///
+/// Particles move freely at first, then blend toward their assigned position in the
+/// [`shape`](Self::shape) as the blend rises, fully assembling into it at `1.0`. This lets
+/// an asset describe effects such as particles gathering into a logo or object.
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct EmitterTarget {
+    /// Whether particles are pulled toward the target. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    /// The positions particles ease toward.
+    pub shape: TargetShape,
+    /// Optional curve that controls how far particles have blended toward the target
+    /// over their lifetime, from `0.0` (free) to `1.0` (on target).
+    ///
+    /// When `None`, the blend rises linearly over the lifetime.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend_over_lifetime: Option<CurveTexture>,
+}
+
+impl Default for EmitterTarget {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shape: TargetShape::default(),
+            blend_over_lifetime: None,
+        }
+    }
+}
+
+impl EmitterTarget {
+    fn should_skip(&self) -> bool {
+        !self.enabled && self.shape == TargetShape::default() && self.blend_over_lifetime.is_none()
+    }
+}
+
+/// Which cells of a texture sheet particles pick from at random.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum TextureSheetRandomMode {
+    /// Each particle picks a random cell from the whole sheet.
+    #[default]
+    Cell,
+    /// Each particle picks a random row and uses its first column.
+    Row,
+    /// Each particle picks a random column and uses its first row.
+    Column,
+}
+
+impl TextureSheetRandomMode {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Texture sheet settings that split the material's textures into a grid of cells.
+///
+/// Each particle samples a single cell, picked at random when it spawns, so one
+/// texture can hold several variants (e.g. four debris chunks) and each particle
+/// shows a different one.
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct EmitterTextureSheet {
+    /// Whether the texture sheet is enabled. Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    /// Number of columns in the sheet. Defaults to `1`.
+    pub columns: u32,
+    /// Number of rows in the sheet. Defaults to `1`.
+    pub rows: u32,
+    /// Which cells particles pick from. Defaults to [`TextureSheetRandomMode::Cell`].
+    #[serde(skip_serializing_if = "TextureSheetRandomMode::is_default")]
+    pub random_mode: TextureSheetRandomMode,
+}
+
+impl Default for EmitterTextureSheet {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            columns: 1,
+            rows: 1,
+            random_mode: TextureSheetRandomMode::default(),
+        }
+    }
+}
+
+impl EmitterTextureSheet {
+    fn should_skip(&self) -> bool {
+        if self.enabled {
+            return false;
+        }
+        let d = Self::default();
+        self.columns == d.columns && self.rows == d.rows && self.random_mode == d.random_mode
     }
 }
 
@@ -1181,6 +1936,33 @@ pub struct EmitterCollision {
     /// appear to float above surfaces, decrease it. Particles always use a spherical
     /// collision shape. Defaults to `0.01`.
     pub base_size: f32,
+    /// Maximum number of points tested along each step's travel.
+    ///
+    /// Fast particles can move further than a thin collider is deep in a single
+    /// step and pass straight through it. Values above `1` test points spaced at
+    /// most one particle radius apart along the path, up to this many per step,
+    /// and stop the particle at the first one that collides. Defaults to `1`
+    /// (only the end of each step is tested).
+    #[serde(skip_serializing_if = "is_one_u32")]
+    pub max_substeps: u32,
+    /// Height of an infinite horizontal ground plane particles collide with, using the
+    /// friction and bounce of [`mode`](Self::mode).
+    ///
+    /// A shortcut for bouncing off the floor without spawning a collider. The height is
+    /// in world space, or in the emitter's local space when
+    /// [`use_local_coords`](EmitterDrawPass::use_local_coords) is set. Has no effect
+    /// when `mode` is `None`. Defaults to `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ground_plane: Option<f32>,
+    /// Number of hits kept in the emitter's
+    /// [`ParticleAccumulationBuffer`](crate::ParticleAccumulationBuffer).
+    ///
+    /// When set, particles removed by [`HideOnContact`](EmitterCollisionMode::HideOnContact)
+    /// record where they landed, so a terrain or decal shader can build up snow cover or
+    /// puddles from the particles themselves. Once the buffer is full, the oldest hits
+    /// are overwritten. Defaults to `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accumulation_capacity: Option<u32>,
 }
 
 impl Default for EmitterCollision {
@@ -1189,6 +1971,9 @@ impl Default for EmitterCollision {
             mode: None,
             base_size: 0.01,
             use_scale: false,
+            max_substeps: 1,
+            ground_plane: None,
+            accumulation_capacity: None,
         }
     }
 }
@@ -1204,6 +1989,14 @@ pub enum SubEmitterMode {
     AtCollision,
     /// Emit once when the parent particle is first spawned.
     AtStart,
+    /// Continuously emit from each parent particle at a fixed frequency, like
+    /// [`Constant`](Self::Constant), but keep the emitted particles attached to the parent
+    /// so they move along with it.
+    ///
+    /// Produces per-particle trails such as firework streaks without the
+    /// [ribbon trail](EmitterTrail) renderer. Attached particles are released once their
+    /// parent dies.
+    Trail,
 }
 
 /// Configuration for a sub-emitter that spawns secondary particles from parent particles.
@@ -1219,15 +2012,27 @@ pub struct SubEmitterConfig {
     pub target_emitter: usize,
     /// How often particles are emitted from the sub-emitter, in seconds.
     ///
-    /// Only used when [`mode`](Self::mode) is [`SubEmitterMode::Constant`]. Defaults to `4.0`.
+    /// Only used when [`mode`](Self::mode) is [`SubEmitterMode::Constant`] or
+    /// [`SubEmitterMode::Trail`]. Defaults to `4.0`.
     pub frequency: f32,
     /// The number of particles to spawn per trigger event. Defaults to `1`.
     pub amount: u32,
-    /// If `true`, the sub-emitted particles inherit the parent particle's velocity.
+    /// Scales [`amount`](Self::amount) per parent particle, so big or long-lived parents
+    /// burst into more particles than small or short-lived ones.
     ///
-    /// Defaults to `false`.
-    #[serde(skip_serializing_if = "is_false")]
-    pub keep_velocity: bool,
+    /// Only used when [`mode`](Self::mode) is [`SubEmitterMode::AtEnd`]. Defaults to
+    /// [`SubEmitterAmountScale::None`].
+    #[serde(skip_serializing_if = "SubEmitterAmountScale::is_default")]
+    pub amount_scale: SubEmitterAmountScale,
+    /// How much of the parent particle's velocity the sub-emitted particles inherit.
+    ///
+    /// Also accepts a plain `true` or `false`, as written by older files, meaning full or
+    /// no inheritance.
+    #[serde(
+        skip_serializing_if = "SubEmitterKeepVelocity::is_default",
+        deserialize_with = "SubEmitterKeepVelocity::deserialize_compat"
+    )]
+    pub keep_velocity: SubEmitterKeepVelocity,
 }
 
 impl Default for SubEmitterConfig {
@@ -1237,8 +2042,112 @@ impl Default for SubEmitterConfig {
             target_emitter: 0,
             frequency: 4.0,
             amount: 1,
-            keep_velocity: false,
+            amount_scale: SubEmitterAmountScale::default(),
+            keep_velocity: SubEmitterKeepVelocity::default(),
+        }
+    }
+}
+
+/// What scales the number of particles a [`SubEmitterMode::AtEnd`] sub-emitter spawns.
+///
+/// The parent's value is divided by the largest value its emitter can produce, and the
+/// [`amount`](SubEmitterConfig::amount) is multiplied by that ratio, spawning at least
+/// one particle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum SubEmitterAmountScale {
+    /// Every parent spawns the full amount.
+    #[default]
+    None,
+    /// Scales by the parent's initial scale relative to the
+    /// [`EmitterScale::range`] maximum.
+    Scale,
+    /// Scales by the parent's lifetime relative to the emitter's
+    /// [`lifetime`](EmitterTime::lifetime).
+    Lifetime,
+}
+
+impl SubEmitterAmountScale {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How sub-emitted particles are launched relative to their parent particle.
+///
+/// Each sub-emitted particle starts with its own emission velocity blended towards the
+/// inherited velocity by [`inherit_ratio`](Self::inherit_ratio), then scaled by a
+/// random [`speed_multiplier`](Self::speed_multiplier).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct SubEmitterKeepVelocity {
+    /// How far the initial velocity is blended from the particle's own emission velocity
+    /// (`0.0`) towards the inherited velocity (`1.0`). Values in between launch particles
+    /// partly along the parent's travel direction while keeping some of their own spread.
+    ///
+    /// Defaults to `0.0`.
+    #[serde(skip_serializing_if = "is_zero_f32")]
+    pub inherit_ratio: f32,
+    /// If set, the inherited velocity points along this world space direction instead of
+    /// the parent's travel direction, keeping the parent's speed.
+    ///
+    /// Defaults to `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_direction: Option<Vec3>,
+    /// Random multiplier applied to the final initial velocity of each sub-emitted
+    /// particle. Defaults to `1.0` to `1.0`.
+    #[serde(skip_serializing_if = "SubEmitterKeepVelocity::is_unit_range")]
+    pub speed_multiplier: Range,
+}
+
+impl Default for SubEmitterKeepVelocity {
+    fn default() -> Self {
+        Self {
+            inherit_ratio: 0.0,
+            extra_direction: None,
+            speed_multiplier: Range::new(1.0, 1.0),
+        }
+    }
+}
+
+impl SubEmitterKeepVelocity {
+    /// Fully inherits the parent particle's velocity, replacing the particle's own.
+    pub fn full() -> Self {
+        Self {
+            inherit_ratio: 1.0,
+            ..default()
+        }
+    }
+
+    /// Returns `true` if sub-emitted particles are launched any differently than
+    /// with their own emission velocity.
+    pub fn is_enabled(&self) -> bool {
+        !self.is_default()
+    }
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn is_unit_range(range: &Range) -> bool {
+        range.min == 1.0 && range.max == 1.0
+    }
+
+    fn deserialize_compat<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Compat {
+            Bool(bool),
+            Config(SubEmitterKeepVelocity),
         }
+
+        Ok(match Compat::deserialize(deserializer)? {
+            Compat::Bool(true) => Self::full(),
+            Compat::Bool(false) => Self::default(),
+            Compat::Config(config) => config,
+        })
     }
 }
 
@@ -1286,12 +2195,30 @@ pub struct ColliderData {
     pub enabled: bool,
     /// The collision shape.
     pub shape: ParticlesColliderShape3D,
+    /// If `true`, the collider keeps particles inside its shape instead of outside,
+    /// acting as a container (bubble volumes, snow globes). Defaults to `false`.
+    #[serde(skip_serializing_if = "is_false")]
+    pub invert: bool,
     /// Initial transform applied when spawning this collider.
     ///
     /// Only used during spawning if no [`Transform`] is already present.
     /// To change the transform at runtime, modify the collider entity's [`Transform`] directly.
     #[serde(skip_serializing_if = "InitialTransform::should_skip")]
     pub initial_transform: InitialTransform,
+    /// RGBA color used to draw this collider's gizmo in the editor.
+    ///
+    /// Defaults to [`ColliderData::DEFAULT_GIZMO_COLOR`].
+    #[serde(skip_serializing_if = "ColliderData::is_default_gizmo_color")]
+    pub gizmo_color: [f32; 4],
+}
+
+impl ColliderData {
+    /// The gizmo color used when none has been picked.
+    pub const DEFAULT_GIZMO_COLOR: [f32; 4] = [0.231, 0.51, 0.965, 1.0];
+
+    fn is_default_gizmo_color(color: &[f32; 4]) -> bool {
+        *color == Self::DEFAULT_GIZMO_COLOR
+    }
 }
 
 impl Default for ColliderData {
@@ -1300,7 +2227,9 @@ impl Default for ColliderData {
             name: "Collider".to_string(),
             enabled: true,
             shape: ParticlesColliderShape3D::default(),
+            invert: false,
             initial_transform: InitialTransform::default(),
+            gizmo_color: Self::DEFAULT_GIZMO_COLOR,
         }
     }
 }
@@ -1343,6 +2272,61 @@ impl EmitterTrail {
     }
 }
 
+/// Per-axis [`Expression`]s for a vector field. Axes without an expression keep the
+/// field's regular value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct ExpressionVec3 {
+    /// Expression for the X axis.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<Expression>,
+    /// Expression for the Y axis.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<Expression>,
+    /// Expression for the Z axis.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub z: Option<Expression>,
+}
+
+impl ExpressionVec3 {
+    /// Returns `true` if no axis has an expression.
+    pub fn is_empty(&self) -> bool {
+        self.x.is_none() && self.y.is_none() && self.z.is_none()
+    }
+}
+
+/// Expressions that animate emitter fields without code.
+///
+/// Expressions are evaluated on the CPU every simulation step, so each spawned particle
+/// picks up the value from the step it was born in:
+///
+/// ```ron
+/// expressions: (
+///     offset: (y: "sin(TIME * 2.0) * 0.5"),
+///     scale: "1.0 + PHASE",
+/// ),
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct EmitterExpressions {
+    /// Replaces axes of the emission [`offset`](EmitterEmission::offset).
+    #[serde(skip_serializing_if = "ExpressionVec3::is_empty")]
+    pub offset: ExpressionVec3,
+    /// Replaces axes of the [`initial_direction`](EmitterVelocities::initial_direction).
+    #[serde(skip_serializing_if = "ExpressionVec3::is_empty")]
+    pub direction: ExpressionVec3,
+    /// Multiplies the particle scale [`range`](EmitterScale::range).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Expression>,
+}
+
+impl EmitterExpressions {
+    /// Returns `true` if no field has an expression.
+    pub fn is_empty(&self) -> bool {
+        self.offset.is_empty() && self.direction.is_empty() && self.scale.is_none()
+    }
+}
+
 /// The shape of a ribbon trail mesh cross-section.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash, Reflect)]
 pub enum RibbonTrailShape {
@@ -1363,12 +2347,15 @@ pub struct SprinklesEditorData {
     /// exists on disk is used.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub assets_folder: Vec<String>,
+    /// Saved palette of RGBA colors shared by every emitter in the project.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub palette: Vec<[f32; 4]>,
 }
 
 impl SprinklesEditorData {
     /// Returns `true` if no editor metadata has been recorded.
     pub fn is_empty(&self) -> bool {
-        self.assets_folder.is_empty()
+        self.assets_folder.is_empty() && self.palette.is_empty()
     }
 }
 
@@ -1421,6 +2408,18 @@ pub struct ParticlesAsset {
     /// Attribution information.
     #[serde(default, skip_serializing_if = "ParticlesAuthors::is_empty")]
     pub authors: ParticlesAuthors,
+    /// Short description of the effect, for tools that browse effect libraries.
+    #[serde(default, skip_serializing_if = "is_empty_string")]
+    pub description: String,
+    /// Free-form tags, such as `"fire"` or `"ui"`, for filtering effect libraries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Frame that best shows off the effect, used when rendering previews.
+    ///
+    /// Counted in frames at 60 FPS from the moment the effect starts. When `None`, tools
+    /// pick their own frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview_frame: Option<u32>,
     /// Editor-specific metadata.
     #[serde(default, skip_serializing_if = "SprinklesEditorData::is_empty")]
     pub sprinkles_editor: SprinklesEditorData,
@@ -1446,6 +2445,9 @@ impl ParticlesAsset {
             colliders,
             despawn_on_finish,
             authors,
+            description: String::new(),
+            tags: Vec::new(),
+            preview_frame: None,
             sprinkles_editor: SprinklesEditorData::default(),
         }
     }
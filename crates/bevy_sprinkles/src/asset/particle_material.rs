@@ -2,7 +2,7 @@ use bevy::{material::AlphaMode, prelude::*, render::render_resource::Face};
 use serde::{Deserialize, Serialize};
 use std::hash::{Hash, Hasher};
 
-use super::serde_helpers::{is_false, is_true, is_zero_f32};
+use super::serde_helpers::{is_false, is_true, is_zero_f32, is_zero_vec2};
 use crate::textures::preset::TextureRef;
 
 /// Sets how a material's base color alpha channel is used for transparency, copied from Bevy's [`AlphaMode`](bevy::material::AlphaMode).
@@ -86,6 +86,59 @@ impl From<AlphaMode> for SerializableAlphaMode {
     }
 }
 
+/// An explicit blend operation that overrides the blending implied by
+/// [`SerializableAlphaMode`].
+///
+/// Alpha values modulate the result of every mode.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Reflect)]
+pub enum ParticleBlendMode {
+    /// Adds the fragment color, weighted by its alpha, to the color behind it while
+    /// leaving the destination alpha untouched.
+    ///
+    /// Useful for sparks, glows and other light-emitting effects.
+    AdditiveWithAlpha,
+    /// Brightens the color behind the fragment without ever exceeding white, like
+    /// projecting two images onto the same screen.
+    ///
+    /// Useful for soft glows that shouldn't blow out to white.
+    Screen,
+    /// Subtracts the fragment color from the color behind it, producing darker results.
+    ///
+    /// Useful for dark smoke and other light-absorbing effects.
+    Subtract,
+    /// Keeps the darker of the fragment color and the color behind it, per channel.
+    Min,
+    /// Keeps the brighter of the fragment color and the color behind it, per channel.
+    Max,
+}
+
+/// How a particle's alpha, including [`alpha_over_lifetime`](crate::EmitterColors::alpha_over_lifetime),
+/// fades its material.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Reflect)]
+pub enum AlphaFadeMode {
+    /// The particle's alpha multiplies the material alpha uniformly.
+    #[default]
+    Uniform,
+    /// The particle's alpha is used as a threshold against the base color texture's
+    /// alpha, so low-alpha regions dissolve first and the particle erodes from its
+    /// edges as it fades out.
+    Erosion {
+        /// Width of the ramp between eroded and visible texels, within `[0.0, 1.0]`.
+        softness: f32,
+    },
+}
+
+impl AlphaFadeMode {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns an erosion fade with a default softness of `0.1`.
+    pub fn erosion() -> Self {
+        Self::Erosion { softness: 0.1 }
+    }
+}
+
 /// Serializable face culling mode, copied from wgpu's [`Face`].
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Reflect)]
 pub enum SerializableFace {
@@ -144,8 +197,10 @@ macro_rules! serde_default {
 
 serde_default!(emissive, [f32; 4], [0.0, 0.0, 0.0, 1.0]);
 serde_default!(ior, f32, 1.5);
+serde_default!(fog_density_multiplier, f32, 1.0);
 serde_default!(attenuation_distance, f32, f32::INFINITY);
 serde_default!(white_color, [f32; 4], default_base_color());
+serde_default!(texture_region_size, Vec2, Vec2::ONE);
 serde_default!(
     cull_mode,
     Option<SerializableFace>,
@@ -223,6 +278,20 @@ pub struct StandardParticleMaterial {
     #[serde(default = "default_alpha_mode")]
     pub alpha_mode: SerializableAlphaMode,
 
+    /// An explicit blend operation that replaces the blending implied by
+    /// [`alpha_mode`](Self::alpha_mode).
+    ///
+    /// When set, the material is always rendered as transparent. See
+    /// [`ParticleBlendMode`] for details. Defaults to `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<ParticleBlendMode>,
+
+    /// How the particle's alpha fades the material.
+    ///
+    /// See [`AlphaFadeMode`] for details. Defaults to [`AlphaFadeMode::Uniform`].
+    #[serde(default, skip_serializing_if = "AlphaFadeMode::is_default")]
+    pub alpha_fade: AlphaFadeMode,
+
     /// Linear perceptual roughness, clamped to `[0.089, 1.0]` in the shader.
     ///
     /// Defaults to `0.5`. Low values result in a "glossy" material with specular
@@ -432,6 +501,29 @@ pub struct StandardParticleMaterial {
     #[serde(default = "default_fog_enabled", skip_serializing_if = "is_true")]
     pub fog_enabled: bool,
 
+    /// Scales how dense distance fog and its atmospheric scattering appear on this
+    /// material, relative to the camera's [`DistanceFog`](bevy::pbr::DistanceFog).
+    ///
+    /// Values below `1.0` let effects such as glows cut through fog, while values
+    /// above `1.0` make them fade out sooner. This value has no effect if
+    /// [`fog_enabled`](Self::fog_enabled) is `false`.
+    ///
+    /// Defaults to `1.0`.
+    #[serde(
+        default = "default_fog_density_multiplier",
+        skip_serializing_if = "is_default_fog_density_multiplier"
+    )]
+    pub fog_density_multiplier: f32,
+
+    /// Distance from the camera, in world units, over which particles fade out as the
+    /// camera gets closer to them.
+    ///
+    /// Prevents full-screen flashes when the camera flies through dense effects such
+    /// as smoke clouds. Has no effect on opaque materials. Defaults to `0.0`, which
+    /// disables the fade.
+    #[serde(default, skip_serializing_if = "is_zero_f32")]
+    pub near_fade_distance: f32,
+
     /// Adjust rendered depth.
     ///
     /// A material with a positive depth bias will render closer to the camera
@@ -441,6 +533,47 @@ pub struct StandardParticleMaterial {
     /// Defaults to `0.0`.
     #[serde(default, skip_serializing_if = "is_zero_f32")]
     pub depth_bias: f32,
+
+    /// A scrolling noise texture that distorts the UVs used to sample the material's
+    /// textures.
+    ///
+    /// The red and green channels are remapped to `[-1.0, 1.0]` and offset the UVs by
+    /// [`distortion_strength`](Self::distortion_strength). Useful to make static
+    /// sprites such as smoke feel alive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distortion_texture: Option<TextureRef>,
+
+    /// How far the distortion texture offsets UVs, in UV units.
+    ///
+    /// This value has no effect without a
+    /// [`distortion_texture`](Self::distortion_texture). Defaults to `0.0`.
+    #[serde(default, skip_serializing_if = "is_zero_f32")]
+    pub distortion_strength: f32,
+
+    /// How fast the distortion texture scrolls, in UV units per second.
+    ///
+    /// Defaults to `[0.0, 0.0]`.
+    #[serde(default, skip_serializing_if = "is_zero_vec2")]
+    pub distortion_speed: Vec2,
+
+    /// Top-left corner of the region of the material's textures that particles sample,
+    /// in UV units.
+    ///
+    /// Together with [`texture_region_size`](Self::texture_region_size) this selects a
+    /// sub-rectangle of a packed texture page, so many effects can share one atlas. The
+    /// region applies to every texture of the material and is applied after the texture
+    /// sheet picks its cell. Defaults to `[0.0, 0.0]`.
+    #[serde(default, skip_serializing_if = "is_zero_vec2")]
+    pub texture_region_offset: Vec2,
+
+    /// Size of the region of the material's textures that particles sample, in UV units.
+    ///
+    /// Defaults to `[1.0, 1.0]`, which samples the whole texture.
+    #[serde(
+        default = "default_texture_region_size",
+        skip_serializing_if = "is_default_texture_region_size"
+    )]
+    pub texture_region_size: Vec2,
 }
 
 impl Default for StandardParticleMaterial {
@@ -452,6 +585,8 @@ impl Default for StandardParticleMaterial {
             emissive_texture: None,
             emissive_exposure_weight: 0.0,
             alpha_mode: default_alpha_mode(),
+            blend_mode: None,
+            alpha_fade: AlphaFadeMode::default(),
             perceptual_roughness: default_perceptual_roughness(),
             metallic: 0.0,
             reflectance: default_reflectance(),
@@ -474,12 +609,38 @@ impl Default for StandardParticleMaterial {
             cull_mode: default_cull_mode(),
             unlit: false,
             fog_enabled: true,
+            fog_density_multiplier: default_fog_density_multiplier(),
+            near_fade_distance: 0.0,
             depth_bias: 0.0,
+            distortion_texture: None,
+            distortion_strength: 0.0,
+            distortion_speed: Vec2::ZERO,
+            texture_region_offset: Vec2::ZERO,
+            texture_region_size: default_texture_region_size(),
         }
     }
 }
 
 impl StandardParticleMaterial {
+    /// Limits the material's textures to `region`, given in UV units.
+    pub fn set_texture_region(&mut self, region: Rect) {
+        self.texture_region_offset = region.min;
+        self.texture_region_size = region.size();
+    }
+
+    /// Limits the material's textures to the `index`-th region of a
+    /// [`TextureAtlasLayout`], leaving the region unchanged if the index is out of range.
+    pub fn set_atlas_region(&mut self, layout: &TextureAtlasLayout, index: usize) {
+        let Some(rect) = layout.textures.get(index) else {
+            return;
+        };
+        let page_size = layout.size.as_vec2();
+        self.set_texture_region(Rect {
+            min: rect.min.as_vec2() / page_size,
+            max: rect.max.as_vec2() / page_size,
+        });
+    }
+
     /// Converts this serializable material into a Bevy [`StandardMaterial`],
     /// loading any referenced textures via the provided [`AssetServer`].
     pub fn to_standard_material(
@@ -496,7 +657,12 @@ impl StandardParticleMaterial {
             emissive: color_from_array(self.emissive).into(),
             emissive_texture: load_tex(&self.emissive_texture),
             emissive_exposure_weight: self.emissive_exposure_weight,
-            alpha_mode: self.alpha_mode.into(),
+            // blend overrides are applied on top of premultiplied output
+            alpha_mode: if self.blend_mode.is_some() {
+                AlphaMode::Premultiplied
+            } else {
+                self.alpha_mode.into()
+            },
             perceptual_roughness: self.perceptual_roughness,
             metallic: self.metallic,
             reflectance: self.reflectance,
@@ -520,6 +686,11 @@ impl StandardParticleMaterial {
             unlit: self.unlit,
             fog_enabled: self.fog_enabled,
             depth_bias: self.depth_bias,
+            uv_transform: Affine2::from_scale_angle_translation(
+                self.texture_region_size,
+                0.0,
+                self.texture_region_offset,
+            ),
             ..default()
         }
     }
@@ -535,6 +706,8 @@ impl StandardParticleMaterial {
             emissive_texture: None,
             emissive_exposure_weight: material.emissive_exposure_weight,
             alpha_mode: material.alpha_mode.into(),
+            blend_mode: None,
+            alpha_fade: AlphaFadeMode::default(),
             perceptual_roughness: material.perceptual_roughness,
             metallic: material.metallic,
             reflectance: material.reflectance,
@@ -557,7 +730,17 @@ impl StandardParticleMaterial {
             cull_mode: material.cull_mode.map(|f| f.into()),
             unlit: material.unlit,
             fog_enabled: material.fog_enabled,
+            fog_density_multiplier: default_fog_density_multiplier(),
+            near_fade_distance: 0.0,
             depth_bias: material.depth_bias,
+            distortion_texture: None,
+            distortion_strength: 0.0,
+            distortion_speed: Vec2::ZERO,
+            texture_region_offset: material.uv_transform.translation,
+            texture_region_size: Vec2::new(
+                material.uv_transform.matrix2.x_axis.x,
+                material.uv_transform.matrix2.y_axis.y,
+            ),
         }
     }
 
@@ -582,6 +765,11 @@ impl StandardParticleMaterial {
         if let SerializableAlphaMode::Mask { cutoff } = self.alpha_mode {
             cutoff.to_bits().hash(&mut hasher);
         }
+        self.blend_mode.hash(&mut hasher);
+        std::mem::discriminant(&self.alpha_fade).hash(&mut hasher);
+        if let AlphaFadeMode::Erosion { softness } = self.alpha_fade {
+            softness.to_bits().hash(&mut hasher);
+        }
         hash_f32(&mut hasher, self.perceptual_roughness);
         hash_f32(&mut hasher, self.metallic);
         hash_f32(&mut hasher, self.reflectance);
@@ -604,7 +792,17 @@ impl StandardParticleMaterial {
         self.cull_mode.hash(&mut hasher);
         self.unlit.hash(&mut hasher);
         self.fog_enabled.hash(&mut hasher);
+        hash_f32(&mut hasher, self.fog_density_multiplier);
+        hash_f32(&mut hasher, self.near_fade_distance);
         hash_f32(&mut hasher, self.depth_bias);
+        self.distortion_texture.hash(&mut hasher);
+        hash_f32(&mut hasher, self.distortion_strength);
+        hash_f32(&mut hasher, self.distortion_speed.x);
+        hash_f32(&mut hasher, self.distortion_speed.y);
+        hash_f32(&mut hasher, self.texture_region_offset.x);
+        hash_f32(&mut hasher, self.texture_region_offset.y);
+        hash_f32(&mut hasher, self.texture_region_size.x);
+        hash_f32(&mut hasher, self.texture_region_size.y);
         hasher.finish()
     }
 }
@@ -12,10 +12,18 @@ pub(crate) fn is_zero_f32(v: &f32) -> bool {
     *v == 0.0
 }
 
+pub(crate) fn is_one_f32(v: &f32) -> bool {
+    *v == 1.0
+}
+
 pub(crate) fn is_zero_u32(v: &u32) -> bool {
     *v == 0
 }
 
+pub(crate) fn is_one_u32(v: &u32) -> bool {
+    *v == 1
+}
+
 pub(crate) fn is_zero_vec2(v: &Vec2) -> bool {
     *v == Vec2::ZERO
 }
@@ -50,7 +50,9 @@ pub fn migrate(bytes: &[u8]) -> Result<MigrationResult, MigrationError> {
             })
         }
         "0.2" => {
-            let asset: ParticlesAsset = ron::de::from_bytes(bytes)?;
+            // the layout is unchanged, only the version needs bumping
+            let mut asset: ParticlesAsset = ron::de::from_bytes(bytes)?;
+            asset.sprinkles_version = current.to_string();
             Ok(MigrationResult {
                 asset,
                 was_migrated: true,
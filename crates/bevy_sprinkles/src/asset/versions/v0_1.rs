@@ -173,7 +173,9 @@ impl From<EmitterColors> for CurrentEmitterColors {
     fn from(old: EmitterColors) -> Self {
         Self {
             initial_color: old.initial_color,
+            initial_color_sampling: Default::default(),
             color_over_lifetime: old.color_over_lifetime,
+            color_over_lifetime_curves: None,
             alpha_over_lifetime: migrate_curve(old.alpha_over_lifetime),
             emission_over_lifetime: migrate_curve(old.emission_over_lifetime),
         }
@@ -443,6 +445,7 @@ impl From<EmitterData> for CurrentEmitterData {
             sub_emitter: old.sub_emitter,
             trail: old.trail.into(),
             particle_flags: old.particle_flags,
+            ..Default::default()
         }
     }
 }
@@ -454,6 +457,7 @@ impl From<ColliderData> for CurrentColliderData {
             enabled: old.enabled,
             shape: old.shape,
             initial_transform: migrate_position(old.position),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::runtime::{EmitterEntity, EmitterRuntime};
+
+/// Frequency band amplitudes that [`AudioReactive`] emitters follow.
+///
+/// Sprinkles doesn't analyze audio itself. Run an FFT over the playing audio in your
+/// own system and write the result here every frame, one value per band, usually
+/// normalized to `0.0..=1.0`:
+///
+/// ```ignore
+/// fn analyze_music(mut bands: ResMut<AudioBands>, analyzer: Res<MyFftAnalyzer>) {
+///     bands.set(analyzer.band_magnitudes());
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct AudioBands {
+    amplitudes: Vec<f32>,
+}
+
+impl AudioBands {
+    /// Replaces every band amplitude.
+    pub fn set(&mut self, amplitudes: impl IntoIterator<Item = f32>) {
+        self.amplitudes.clear();
+        self.amplitudes.extend(amplitudes);
+    }
+
+    /// Returns the amplitude of `band`, or `0.0` if no value was written for it.
+    pub fn get(&self, band: usize) -> f32 {
+        self.amplitudes.get(band).copied().unwrap_or(0.0)
+    }
+
+    /// Returns every band amplitude.
+    pub fn amplitudes(&self) -> &[f32] {
+        &self.amplitudes
+    }
+}
+
+/// Modulates an emitter's emission rate and initial velocity by one [`AudioBands`] band.
+///
+/// Add it to an emitter entity, or to a [`Particles3d`](crate::Particles3d) entity to
+/// affect all of its emitters. An emitter's own component takes precedence.
+///
+/// Each strength blends between the emitter's regular value at `0.0` and a value that
+/// follows the band amplitude directly at `1.0`:
+///
+/// ```ignore
+/// commands.spawn((
+///     Particles3d(asset_server.load("sparks.ron")),
+///     AudioReactive::new(0).with_emission_rate(1.0),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct AudioReactive {
+    /// Index of the band in [`AudioBands`] to follow.
+    pub band: usize,
+    /// How strongly the band drives [`EmitterRuntime::amount_ratio`]. Defaults to `0.0`.
+    pub emission_rate: f32,
+    /// How strongly the band drives [`EmitterRuntime::speed_scale`]. Defaults to `0.0`.
+    pub initial_velocity: f32,
+}
+
+impl AudioReactive {
+    /// Creates a component that follows `band`, with both strengths at `0.0`.
+    pub fn new(band: usize) -> Self {
+        Self {
+            band,
+            emission_rate: 0.0,
+            initial_velocity: 0.0,
+        }
+    }
+
+    /// Sets how strongly the band drives the emission rate.
+    pub fn with_emission_rate(mut self, strength: f32) -> Self {
+        self.emission_rate = strength;
+        self
+    }
+
+    /// Sets how strongly the band drives the initial velocity.
+    pub fn with_initial_velocity(mut self, strength: f32) -> Self {
+        self.initial_velocity = strength;
+        self
+    }
+}
+
+pub(crate) fn apply_audio_reactivity(
+    bands: Res<AudioBands>,
+    mut emitters: Query<(&EmitterEntity, &mut EmitterRuntime, Option<&AudioReactive>)>,
+    systems: Query<&AudioReactive>,
+) {
+    for (emitter_entity, mut runtime, reactive) in &mut emitters {
+        let Some(reactive) = reactive.or_else(|| systems.get(emitter_entity.parent_system).ok())
+        else {
+            continue;
+        };
+        let amplitude = bands.get(reactive.band);
+        runtime.amount_ratio = (1.0 + (amplitude - 1.0) * reactive.emission_rate).clamp(0.0, 1.0);
+        runtime.speed_scale = (1.0 + (amplitude - 1.0) * reactive.initial_velocity).max(0.0);
+    }
+}
@@ -1,105 +1,325 @@
 use bevy::{
-    core_pipeline::schedule::camera_driver,
+    ecs::entity::{EntityHashMap, EntityHashSet},
     prelude::*,
     render::{
         Render, RenderApp, RenderStartup, RenderSystems,
         render_asset::RenderAssets,
         render_resource::{
-            BindGroup, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries, Buffer,
-            BufferUsages, CachedComputePipelineId, CachedPipelineState, ComputePassDescriptor,
-            ComputePipelineDescriptor, PipelineCache, SamplerBindingType, SamplerDescriptor,
-            ShaderStages, TextureSampleType,
+            BindGroup, BindGroupEntries, BindGroupEntry, BindGroupLayoutDescriptor,
+            BindGroupLayoutEntries, Buffer, BufferId, BufferUsages, CachedComputePipelineId,
+            CachedPipelineState, ComputePassDescriptor, ComputePipelineDescriptor,
+            DynamicUniformBuffer, PipelineCache, SamplerBindingType, SamplerDescriptor,
+            ShaderStages, SpecializedComputePipeline, SpecializedComputePipelines,
+            TextureSampleType, TextureViewId,
             binding_types::{
-                sampler, storage_buffer, storage_buffer_read_only, storage_buffer_sized,
-                texture_2d, uniform_buffer,
+                sampler, storage_buffer, storage_buffer_sized, texture_2d, uniform_buffer,
             },
         },
-        renderer::{RenderContext, RenderDevice, RenderGraph, RenderGraphSystems, RenderQueue},
+        renderer::{RenderContext, RenderDevice, RenderGraph, RenderQueue},
         storage::GpuShaderBuffer,
         texture::GpuImage,
     },
 };
+use bitflags::bitflags;
 use std::borrow::Cow;
 
 use bevy::render::render_resource::ShaderType;
-use bevy::shader::ShaderCacheError;
+use bevy::shader::{ShaderCacheError, ShaderDefVal};
 
+use crate::SprinklesRenderSystems;
+#[cfg(feature = "sub-emitters")]
+use crate::extract::SUB_EMITTER_MODE_DISABLED;
+#[cfg(feature = "collision")]
 use crate::extract::{
-    ColliderUniform, EmitterUniforms, ExtractedColliders, ExtractedEmitterData,
-    ExtractedParticleSystem, MAX_COLLIDERS,
+    COLLISION_MODE_DISABLED, COLLISION_MODE_HIDE_ON_CONTACT, ColliderUniform, ExtractedColliders,
+    MAX_COLLIDERS,
 };
+use crate::extract::{EmitterUniforms, ExtractedEmitterData, ExtractedParticleSystem};
+#[cfg(feature = "turbulence")]
+use crate::extract::{ExtractedTurbulenceVolumes, MAX_TURBULENCE_VOLUMES, TurbulenceVolumeUniform};
 use crate::runtime::ParticleData;
 use crate::textures::{FallbackCurveTexture, FallbackGradientTexture};
+#[cfg(feature = "collision")]
+use bevy::render::render_resource::binding_types::storage_buffer_read_only;
 
+#[cfg(feature = "collision")]
 #[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable, ShaderType)]
 #[repr(C)]
 pub struct ColliderArray {
     pub colliders: [ColliderUniform; MAX_COLLIDERS],
 }
 
+#[cfg(feature = "turbulence")]
+#[derive(Clone, Copy, Default, bytemuck::Pod, bytemuck::Zeroable, ShaderType)]
+#[repr(C)]
+pub struct TurbulenceVolumeArray {
+    pub volumes: [TurbulenceVolumeUniform; MAX_TURBULENCE_VOLUMES],
+}
+
 const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_simulate.wgsl";
 const WORKGROUP_SIZE: u32 = 64;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
-pub struct ParticleComputeLabel;
+/// Storage buffers bound by the simulate shader: particles, trail history, colliders and
+/// both sub emitter buffers, the last three only when their features are compiled in.
+/// Emitters that record collision hits bind one more.
+const SIMULATE_STORAGE_BUFFERS: u32 =
+    2 + cfg!(feature = "collision") as u32 + 2 * cfg!(feature = "sub-emitters") as u32;
+
+/// Binding of the collision accumulation buffer, only present in
+/// [`ParticleComputePipeline::accumulation_bind_group_layout`].
+const ACCUMULATION_BINDING: u32 = 35;
 
 #[derive(Resource)]
 pub struct ParticleComputePipeline {
     pub bind_group_layout: BindGroupLayoutDescriptor,
-    pub simulate_pipeline: CachedComputePipelineId,
+    /// Layout for emitters with [`SimulateFeatures::ACCUMULATION`], which adds the
+    /// accumulation buffer. `None` when the device has no storage buffer slot left for it,
+    /// in which case collision hits are not recorded.
+    pub accumulation_bind_group_layout: Option<BindGroupLayoutDescriptor>,
+    pub shader: Handle<Shader>,
+    /// Set on devices that allow fewer storage buffers per shader stage than the simulate
+    /// shader binds by default (DX11-level hardware, some mobile GPUs). Colliders are then
+    /// bound as a uniform buffer, freeing one storage buffer slot.
+    #[cfg(feature = "collision")]
+    pub colliders_as_uniform: bool,
+}
+
+bitflags! {
+    /// Optional simulation features an emitter uses. Each one maps to a shader define,
+    /// so emitters that don't use a feature run a permutation without its code.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct SimulateFeatures: u32 {
+        const TURBULENCE = 1 << 0;
+        const COLLISION = 1 << 1;
+        const SUB_EMITTER = 1 << 2;
+        const CURVES = 1 << 3;
+        const ACCUMULATION = 1 << 4;
+        /// Spawns particles from another emitter's sub emitter buffer.
+        const SUB_EMITTER_TARGET = 1 << 5;
+    }
+}
+
+impl SimulateFeatures {
+    pub fn from_uniforms(uniforms: &EmitterUniforms) -> Self {
+        let mut features = Self::empty();
+        // disabled cargo features never compile their shader code
+        #[cfg(feature = "turbulence")]
+        features.set(Self::TURBULENCE, uniforms.turbulence_enabled != 0);
+        #[cfg(feature = "collision")]
+        {
+            features.set(
+                Self::COLLISION,
+                uniforms.collision_mode != COLLISION_MODE_DISABLED,
+            );
+            features.set(
+                Self::ACCUMULATION,
+                uniforms.collision_mode == COLLISION_MODE_HIDE_ON_CONTACT
+                    && uniforms.accumulation_capacity != 0,
+            );
+        }
+        #[cfg(feature = "sub-emitters")]
+        {
+            features.set(
+                Self::SUB_EMITTER,
+                uniforms.sub_emitter_mode != SUB_EMITTER_MODE_DISABLED,
+            );
+            features.set(
+                Self::SUB_EMITTER_TARGET,
+                uniforms.is_sub_emitter_target != 0,
+            );
+        }
+        let curves = [
+            &uniforms.scale_over_lifetime,
+            &uniforms.alpha_over_lifetime,
+            &uniforms.emission_over_lifetime,
+            &uniforms.turbulence_influence_over_lifetime,
+            &uniforms.angle_over_lifetime,
+            &uniforms.target_blend_over_lifetime,
+            &uniforms.radial_velocity.curve,
+            &uniforms.radial_acceleration.curve,
+            &uniforms.angular_velocity.curve,
+            &uniforms.orbit_velocity.curve,
+            &uniforms.directional_velocity.curve,
+        ];
+        features.set(Self::CURVES, curves.iter().any(|curve| curve.enabled != 0));
+        features
+    }
+}
+
+impl SpecializedComputePipeline for ParticleComputePipeline {
+    type Key = SimulateFeatures;
+
+    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
+        let mut shader_defs: Vec<ShaderDefVal> = Vec::new();
+        if key.contains(SimulateFeatures::TURBULENCE) {
+            shader_defs.push("HAS_TURBULENCE".into());
+        }
+        if key.contains(SimulateFeatures::COLLISION) {
+            shader_defs.push("HAS_COLLISION".into());
+        }
+        if key.contains(SimulateFeatures::SUB_EMITTER) {
+            shader_defs.push("HAS_SUBEMITTER".into());
+        }
+        if key.contains(SimulateFeatures::SUB_EMITTER_TARGET) {
+            shader_defs.push("HAS_SUBEMITTER_TARGET".into());
+        }
+        if key.contains(SimulateFeatures::CURVES) {
+            shader_defs.push("HAS_CURVES".into());
+        }
+        #[cfg(feature = "collision")]
+        if self.colliders_as_uniform {
+            shader_defs.push("COLLIDERS_UNIFORM".into());
+        }
+        let layout = match &self.accumulation_bind_group_layout {
+            Some(layout) if key.contains(SimulateFeatures::ACCUMULATION) => {
+                shader_defs.push("HAS_ACCUMULATION".into());
+                layout.clone()
+            }
+            _ => self.bind_group_layout.clone(),
+        };
+
+        ComputePipelineDescriptor {
+            label: Some("particle_simulate_pipeline".into()),
+            layout: vec![layout],
+            shader: self.shader.clone(),
+            shader_defs,
+            entry_point: Some(Cow::from("main")),
+            ..default()
+        }
+    }
 }
 
 pub fn init_particle_compute_pipeline(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    pipeline_cache: Res<PipelineCache>,
     render_device: Res<RenderDevice>,
 ) {
-    let bind_group_layout = BindGroupLayoutDescriptor::new(
-        "ParticleComputeBindGroup",
-        &BindGroupLayoutEntries::sequential(
-            ShaderStages::COMPUTE,
+    let max_storage_buffers = render_device.limits().max_storage_buffers_per_shader_stage;
+    // colliders can fall back to a uniform buffer, so they never need a storage slot
+    let min_storage_buffers = SIMULATE_STORAGE_BUFFERS - cfg!(feature = "collision") as u32;
+    if max_storage_buffers < min_storage_buffers {
+        error!(
+            "Particle simulation needs at least {min_storage_buffers} storage buffers per \
+             shader stage, but this device only supports {max_storage_buffers}. Particles \
+             will not be simulated."
+        );
+        return;
+    }
+    #[cfg(feature = "collision")]
+    let colliders_as_uniform = max_storage_buffers < SIMULATE_STORAGE_BUFFERS;
+
+    let base_entries = BindGroupLayoutEntries::sequential(
+        ShaderStages::COMPUTE,
+        (
+            uniform_buffer::<EmitterUniforms>(true),
+            storage_buffer::<ParticleData>(false),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+            texture_2d(TextureSampleType::Float { filterable: true }),
+            sampler(SamplerBindingType::Filtering),
+        ),
+    );
+    // `sequential` tops out at 32 entries and the bindings of optional features are only
+    // present when they are compiled in, so later bindings use explicit indices
+    let shape_entries = BindGroupLayoutEntries::with_indices(
+        ShaderStages::COMPUTE,
+        (
+            (27, storage_buffer_sized(false, None)),
             (
-                uniform_buffer::<EmitterUniforms>(false),
-                storage_buffer::<ParticleData>(false),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
+                28,
                 texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
+            ),
+            (29, sampler(SamplerBindingType::Filtering)),
+            (
+                30,
+                texture_2d(TextureSampleType::Float { filterable: false }),
+            ),
+            (
+                31,
+                texture_2d(TextureSampleType::Float { filterable: false }),
+            ),
+            (
+                32,
                 texture_2d(TextureSampleType::Float { filterable: true }),
-                sampler(SamplerBindingType::Filtering),
-                storage_buffer_read_only::<ColliderArray>(false),
-                storage_buffer_sized(false, None),
-                storage_buffer_sized(false, None),
-                storage_buffer_sized(false, None),
             ),
+            (33, sampler(SamplerBindingType::Filtering)),
         ),
     );
+    let entries = [&base_entries[..], &shape_entries[..]].concat();
+    #[cfg(feature = "collision")]
+    let entries = {
+        let colliders_entry = if colliders_as_uniform {
+            uniform_buffer::<ColliderArray>(false)
+        } else {
+            storage_buffer_read_only::<ColliderArray>(false)
+        };
+        let collision_entries =
+            BindGroupLayoutEntries::with_indices(ShaderStages::COMPUTE, ((24, colliders_entry),));
+        [&entries[..], &collision_entries[..]].concat()
+    };
+    #[cfg(feature = "sub-emitters")]
+    let entries = {
+        let sub_emitter_entries = BindGroupLayoutEntries::with_indices(
+            ShaderStages::COMPUTE,
+            (
+                (25, storage_buffer_sized(false, None)),
+                (26, storage_buffer_sized(false, None)),
+            ),
+        );
+        [&entries[..], &sub_emitter_entries[..]].concat()
+    };
+    #[cfg(feature = "turbulence")]
+    let entries = {
+        let turbulence_entries = BindGroupLayoutEntries::with_indices(
+            ShaderStages::COMPUTE,
+            ((34, uniform_buffer::<TurbulenceVolumeArray>(false)),),
+        );
+        [&entries[..], &turbulence_entries[..]].concat()
+    };
+    let bind_group_layout = BindGroupLayoutDescriptor::new("ParticleComputeBindGroup", &entries);
+
+    #[cfg(feature = "collision")]
+    let accumulation_bind_group_layout = {
+        let storage_buffers = SIMULATE_STORAGE_BUFFERS - colliders_as_uniform as u32;
+        if max_storage_buffers > storage_buffers {
+            let accumulation_entries = BindGroupLayoutEntries::with_indices(
+                ShaderStages::COMPUTE,
+                ((ACCUMULATION_BINDING, storage_buffer_sized(false, None)),),
+            );
+            Some(BindGroupLayoutDescriptor::new(
+                "ParticleComputeAccumulationBindGroup",
+                &[&entries[..], &accumulation_entries[..]].concat(),
+            ))
+        } else {
+            warn!(
+                "This device supports {max_storage_buffers} storage buffers per shader stage, \
+                 so particle collision hits will not be recorded in accumulation buffers."
+            );
+            None
+        }
+    };
+    #[cfg(not(feature = "collision"))]
+    let accumulation_bind_group_layout = None;
 
     let shader = asset_server.load(SHADER_ASSET_PATH);
-    let simulate_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
-        label: Some("particle_simulate_pipeline".into()),
-        layout: vec![bind_group_layout.clone()],
-        shader,
-        entry_point: Some(Cow::from("main")),
-        ..default()
-    });
 
     let linear_clamp_sampler = SamplerDescriptor {
         address_mode_u: bevy::render::render_resource::AddressMode::ClampToEdge,
@@ -122,6 +342,7 @@ pub fn init_particle_compute_pipeline(
     // dst and src need distinct buffers even when unused: WebGPU rejects two
     // writable storage bindings aliasing the same buffer range, even if neither
     // is read or written by the shader for this dispatch.
+    #[cfg(feature = "sub-emitters")]
     let fallback_emission_dst_buffer = render_device.create_buffer_with_data(
         &bevy::render::render_resource::BufferInitDescriptor {
             label: Some("fallback_emission_dst_buffer"),
@@ -130,6 +351,7 @@ pub fn init_particle_compute_pipeline(
         },
     );
 
+    #[cfg(feature = "sub-emitters")]
     let fallback_emission_src_buffer = render_device.create_buffer_with_data(
         &bevy::render::render_resource::BufferInitDescriptor {
             label: Some("fallback_emission_src_buffer"),
@@ -138,6 +360,24 @@ pub fn init_particle_compute_pipeline(
         },
     );
 
+    #[cfg(feature = "collision")]
+    let colliders_buffer = render_device.create_buffer_with_data(
+        &bevy::render::render_resource::BufferInitDescriptor {
+            label: Some("colliders_buffer"),
+            contents: bytemuck::bytes_of(&ColliderArray::default()),
+            usage: BufferUsages::STORAGE | BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        },
+    );
+
+    #[cfg(feature = "turbulence")]
+    let turbulence_volumes_buffer = render_device.create_buffer_with_data(
+        &bevy::render::render_resource::BufferInitDescriptor {
+            label: Some("turbulence_volumes_buffer"),
+            contents: bytemuck::bytes_of(&TurbulenceVolumeArray::default()),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        },
+    );
+
     let fallback_trail_history_buffer = render_device.create_buffer_with_data(
         &bevy::render::render_resource::BufferInitDescriptor {
             label: Some("fallback_trail_history_buffer"),
@@ -148,15 +388,23 @@ pub fn init_particle_compute_pipeline(
 
     commands.insert_resource(ParticleComputePipeline {
         bind_group_layout,
-        simulate_pipeline,
+        accumulation_bind_group_layout,
+        shader,
+        #[cfg(feature = "collision")]
+        colliders_as_uniform,
     });
     commands.insert_resource(GradientSampler(gradient_sampler));
     commands.insert_resource(CurveSampler(curve_sampler));
+    #[cfg(feature = "sub-emitters")]
     commands.insert_resource(FallbackEmissionBuffers {
         dst: fallback_emission_dst_buffer,
         src: fallback_emission_src_buffer,
     });
     commands.insert_resource(FallbackTrailHistoryBuffer(fallback_trail_history_buffer));
+    #[cfg(feature = "collision")]
+    commands.insert_resource(CollidersBuffer(colliders_buffer));
+    #[cfg(feature = "turbulence")]
+    commands.insert_resource(TurbulenceVolumesBuffer(turbulence_volumes_buffer));
 }
 
 #[derive(Resource)]
@@ -165,6 +413,7 @@ pub struct GradientSampler(pub bevy::render::render_resource::Sampler);
 #[derive(Resource)]
 pub struct CurveSampler(pub bevy::render::render_resource::Sampler);
 
+#[cfg(feature = "sub-emitters")]
 #[derive(Resource)]
 pub struct FallbackEmissionBuffers {
     pub dst: Buffer,
@@ -174,34 +423,82 @@ pub struct FallbackEmissionBuffers {
 #[derive(Resource)]
 pub(crate) struct FallbackTrailHistoryBuffer(pub(crate) Buffer);
 
+/// Collider data shared by all emitters, rewritten every frame in place so bind groups
+/// that reference it stay valid.
+#[cfg(feature = "collision")]
+#[derive(Resource)]
+pub(crate) struct CollidersBuffer(pub(crate) Buffer);
+
+/// Turbulence volume data shared by all emitters, rewritten every frame in place like
+/// the collider buffer.
+#[cfg(feature = "turbulence")]
+#[derive(Resource)]
+pub(crate) struct TurbulenceVolumesBuffer(pub(crate) Buffer);
+
+#[cfg(feature = "sub-emitters")]
 #[derive(Resource, Default)]
 pub struct EmissionBufferClearList {
     pub buffers: Vec<Buffer>,
 }
 
+pub struct EmitterComputeBindGroup {
+    pub entity: Entity,
+    /// The simulate pipeline specialized for this emitter's [`SimulateFeatures`].
+    pub pipeline: CachedComputePipelineId,
+    pub bind_group: BindGroup,
+    /// Offsets into the shared uniform buffer, one per simulation step.
+    pub step_offsets: Vec<u32>,
+}
+
 #[derive(Resource, Default)]
 pub struct ParticleComputeBindGroups {
-    pub bind_groups: Vec<(Entity, Vec<BindGroup>)>,
+    pub emitters: Vec<EmitterComputeBindGroup>,
+}
+
+/// Every resource bound by an emitter's compute bind group, so the bind group is only
+/// rebuilt when one of them changes.
+#[derive(PartialEq, Eq)]
+struct ComputeBindGroupKey {
+    buffers: [BufferId; 3],
+    textures: [TextureViewId; 15],
+    /// Buffers of optional features and the bindings they are bound at. Includes the
+    /// accumulation buffer when the bind group uses the accumulation layout.
+    feature_buffers: Vec<(u32, BufferId)>,
+}
+
+/// Compute bind groups kept across frames, keyed by emitter entity. The uniform
+/// buffer they bind is reused too, so it is only reallocated when it needs to grow.
+#[derive(Resource, Default)]
+pub struct ParticleComputeBindGroupCache {
+    uniforms: DynamicUniformBuffer<EmitterUniforms>,
+    entries: EntityHashMap<(ComputeBindGroupKey, BindGroup)>,
 }
 
 pub fn prepare_particle_compute_bind_groups(
     mut commands: Commands,
     pipeline: Res<ParticleComputePipeline>,
     pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedComputePipelines<ParticleComputePipeline>>,
     render_device: Res<RenderDevice>,
-    _render_queue: Res<RenderQueue>,
+    render_queue: Res<RenderQueue>,
     extracted_systems: Res<ExtractedParticleSystem>,
-    extracted_colliders: Option<Res<ExtractedColliders>>,
+    #[cfg(feature = "collision")] extracted_colliders: Option<Res<ExtractedColliders>>,
+    #[cfg(feature = "turbulence")] extracted_turbulence_volumes: Option<
+        Res<ExtractedTurbulenceVolumes>,
+    >,
     gpu_storage_buffers: Res<RenderAssets<GpuShaderBuffer>>,
     gpu_images: Res<RenderAssets<GpuImage>>,
     fallback_gradient_texture: Option<Res<FallbackGradientTexture>>,
     fallback_curve_texture: Option<Res<FallbackCurveTexture>>,
-    fallback_emission_buffers: Res<FallbackEmissionBuffers>,
+    #[cfg(feature = "sub-emitters")] fallback_emission_buffers: Res<FallbackEmissionBuffers>,
     fallback_trail_history_buffer: Res<FallbackTrailHistoryBuffer>,
+    #[cfg(feature = "collision")] colliders_buffer: Res<CollidersBuffer>,
+    #[cfg(feature = "turbulence")] turbulence_volumes_buffer: Res<TurbulenceVolumesBuffer>,
     gradient_sampler: Res<GradientSampler>,
     curve_sampler: Res<CurveSampler>,
+    mut bind_group_cache: ResMut<ParticleComputeBindGroupCache>,
 ) {
-    let mut bind_groups = Vec::new();
+    let mut emitters = Vec::new();
 
     let fallback_gradient_gpu_image = fallback_gradient_texture
         .as_ref()
@@ -211,34 +508,117 @@ pub fn prepare_particle_compute_bind_groups(
         .as_ref()
         .and_then(|ft| gpu_images.get(&ft.handle));
 
-    let mut collider_array = ColliderArray::default();
-    let collider_count = if let Some(ref colliders) = extracted_colliders {
-        for (i, collider) in colliders.colliders.iter().enumerate() {
-            if i >= MAX_COLLIDERS {
-                break;
+    #[cfg(feature = "collision")]
+    let collider_count = {
+        let mut collider_array = ColliderArray::default();
+        let collider_count = if let Some(ref colliders) = extracted_colliders {
+            for (i, collider) in colliders.colliders.iter().enumerate() {
+                if i >= MAX_COLLIDERS {
+                    break;
+                }
+                collider_array.colliders[i] = *collider;
             }
-            collider_array.colliders[i] = *collider;
-        }
-        colliders.colliders.len().min(MAX_COLLIDERS) as u32
-    } else {
-        0
+            colliders.colliders.len().min(MAX_COLLIDERS) as u32
+        } else {
+            0
+        };
+
+        render_queue.write_buffer(&colliders_buffer.0, 0, bytemuck::bytes_of(&collider_array));
+        collider_count
     };
+    #[cfg(not(feature = "collision"))]
+    let collider_count = 0;
 
-    let colliders_buffer = render_device.create_buffer_with_data(
-        &bevy::render::render_resource::BufferInitDescriptor {
-            label: Some("colliders_buffer"),
-            contents: bytemuck::bytes_of(&collider_array),
-            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
-        },
-    );
+    #[cfg(feature = "turbulence")]
+    let turbulence_volume_count = {
+        let mut volume_array = TurbulenceVolumeArray::default();
+        let turbulence_volume_count = if let Some(ref volumes) = extracted_turbulence_volumes {
+            for (slot, volume) in volume_array.volumes.iter_mut().zip(&volumes.volumes) {
+                *slot = *volume;
+            }
+            volumes.volumes.len().min(MAX_TURBULENCE_VOLUMES) as u32
+        } else {
+            0
+        };
+
+        render_queue.write_buffer(
+            &turbulence_volumes_buffer.0,
+            0,
+            bytemuck::bytes_of(&volume_array),
+        );
+        turbulence_volume_count
+    };
+    #[cfg(not(feature = "turbulence"))]
+    let turbulence_volume_count = 0;
+
+    // the uniforms of every emitter and step share one buffer, so each emitter needs a
+    // single bind group and steps only change the dynamic offset
+    let ParticleComputeBindGroupCache {
+        uniforms: dynamic_uniform,
+        entries: cached_bind_groups,
+    } = &mut *bind_group_cache;
+    dynamic_uniform.clear();
+    let step_offsets: Vec<Vec<u32>> = extracted_systems
+        .emitters
+        .iter()
+        .map(|(_, emitter_data)| {
+            emitter_data
+                .uniform_steps
+                .iter()
+                .map(|step_uniforms| {
+                    let mut uniforms = *step_uniforms;
+                    uniforms.collider_count = collider_count;
+                    uniforms.turbulence_volume_count = turbulence_volume_count;
+                    dynamic_uniform.push(&uniforms)
+                })
+                .collect()
+        })
+        .collect();
+    dynamic_uniform.write_buffer(&render_device, &render_queue);
 
+    let (Some(uniform_buffer), Some(uniform_binding)) =
+        (dynamic_uniform.buffer(), dynamic_uniform.binding())
+    else {
+        cached_bind_groups.clear();
+        commands.insert_resource(ParticleComputeBindGroups::default());
+        #[cfg(feature = "sub-emitters")]
+        commands.insert_resource(EmissionBufferClearList::default());
+        return;
+    };
+
+    #[cfg(feature = "sub-emitters")]
     let mut emission_clear_list = Vec::new();
 
-    for (entity, emitter_data) in &extracted_systems.emitters {
+    for ((entity, emitter_data), step_offsets) in
+        extracted_systems.emitters.iter().zip(step_offsets)
+    {
         let Some(gpu_buffer) = gpu_storage_buffers.get(&emitter_data.particle_buffer_handle) else {
             continue;
         };
 
+        // every step comes from the same emitter config, so they share one feature set
+        let Some(first_step) = emitter_data.uniform_steps.first() else {
+            continue;
+        };
+        let mut features = SimulateFeatures::from_uniforms(first_step);
+
+        // without a buffer to write into, the emitter simulates without recording hits
+        let accumulation_buffer = emitter_data
+            .accumulation_buffer_handle
+            .as_ref()
+            .and_then(|h| gpu_storage_buffers.get(h))
+            .map(|b| &b.buffer)
+            .filter(|_| {
+                features.contains(SimulateFeatures::ACCUMULATION)
+                    && pipeline.accumulation_bind_group_layout.is_some()
+            });
+        features.set(
+            SimulateFeatures::ACCUMULATION,
+            accumulation_buffer.is_some(),
+        );
+
+        let simulate_pipeline = pipelines.specialize(&pipeline_cache, &pipeline, features);
+
         fn resolve_texture<'a>(
             handle: &Option<Handle<Image>>,
             gpu_images: &'a RenderAssets<GpuImage>,
@@ -248,99 +628,122 @@ pub fn prepare_particle_compute_bind_groups(
         }
 
         let Some(gradient_image) = resolve_texture(
-            &emitter_data.gradient_texture_handle,
+            &emitter_data.textures.gradient_texture_handle,
             &gpu_images,
             fallback_gradient_gpu_image,
         ) else {
             continue;
         };
         let Some(color_over_lifetime_image) = resolve_texture(
-            &emitter_data.color_over_lifetime_texture_handle,
+            &emitter_data.textures.color_over_lifetime_texture_handle,
             &gpu_images,
             fallback_gradient_gpu_image,
         ) else {
             continue;
         };
         let Some(scale_over_lifetime_image) = resolve_texture(
-            &emitter_data.scale_over_lifetime_texture_handle,
+            &emitter_data.textures.scale_over_lifetime_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(alpha_over_lifetime_image) = resolve_texture(
-            &emitter_data.alpha_over_lifetime_texture_handle,
+            &emitter_data.textures.alpha_over_lifetime_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(emission_over_lifetime_image) = resolve_texture(
-            &emitter_data.emission_over_lifetime_texture_handle,
+            &emitter_data.textures.emission_over_lifetime_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(turbulence_influence_over_lifetime_image) = resolve_texture(
-            &emitter_data.turbulence_influence_over_lifetime_texture_handle,
+            &emitter_data
+                .textures
+                .turbulence_influence_over_lifetime_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(radial_velocity_curve_image) = resolve_texture(
-            &emitter_data.radial_velocity_curve_texture_handle,
+            &emitter_data.textures.radial_velocity_curve_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(angle_over_lifetime_image) = resolve_texture(
-            &emitter_data.angle_over_lifetime_texture_handle,
+            &emitter_data.textures.angle_over_lifetime_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(angular_velocity_curve_image) = resolve_texture(
-            &emitter_data.angular_velocity_curve_texture_handle,
+            &emitter_data.textures.angular_velocity_curve_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(orbit_velocity_curve_image) = resolve_texture(
-            &emitter_data.orbit_velocity_curve_texture_handle,
+            &emitter_data.textures.orbit_velocity_curve_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
         let Some(directional_velocity_curve_image) = resolve_texture(
-            &emitter_data.directional_velocity_curve_texture_handle,
+            &emitter_data
+                .textures
+                .directional_velocity_curve_texture_handle,
+            &gpu_images,
+            fallback_curve_gpu_image,
+        ) else {
+            continue;
+        };
+        let Some(radial_acceleration_curve_image) = resolve_texture(
+            &emitter_data
+                .textures
+                .radial_acceleration_curve_texture_handle,
+            &gpu_images,
+            fallback_curve_gpu_image,
+        ) else {
+            continue;
+        };
+        let Some(emission_mask_image) = resolve_texture(
+            &emitter_data.textures.emission_mask_texture_handle,
+            &gpu_images,
+            fallback_curve_gpu_image,
+        ) else {
+            continue;
+        };
+        let Some(target_image) = resolve_texture(
+            &emitter_data.textures.target_texture_handle,
+            &gpu_images,
+            fallback_curve_gpu_image,
+        ) else {
+            continue;
+        };
+        let Some(target_blend_curve_image) = resolve_texture(
+            &emitter_data.textures.target_blend_curve_texture_handle,
             &gpu_images,
             fallback_curve_gpu_image,
         ) else {
             continue;
         };
 
-        let bind_group_layout = pipeline_cache.get_bind_group_layout(&pipeline.bind_group_layout);
-
-        let dst_buffer = emitter_data
-            .emission_buffer_handle
-            .as_ref()
-            .and_then(|h| gpu_storage_buffers.get(h))
-            .map(|b| &b.buffer);
-
-        let src_buffer = emitter_data
-            .source_buffer_handle
-            .as_ref()
-            .and_then(|h| gpu_storage_buffers.get(h))
-            .map(|b| &b.buffer);
-
-        let dst_binding = dst_buffer.unwrap_or(&fallback_emission_buffers.dst);
-        let src_binding = src_buffer.unwrap_or(&fallback_emission_buffers.src);
+        let layout_descriptor = match &pipeline.accumulation_bind_group_layout {
+            Some(layout) if accumulation_buffer.is_some() => layout,
+            _ => &pipeline.bind_group_layout,
+        };
+        let bind_group_layout = pipeline_cache.get_bind_group_layout(layout_descriptor);
 
         let trail_history_buffer = emitter_data
             .trail_history_buffer_handle
@@ -350,106 +753,181 @@ pub fn prepare_particle_compute_bind_groups(
         let trail_history_binding =
             trail_history_buffer.unwrap_or(&fallback_trail_history_buffer.0);
 
-        if let Some(buf) = dst_buffer {
-            emission_clear_list.push(buf.clone());
+        let mut feature_buffers: Vec<(u32, &Buffer)> = Vec::new();
+        #[cfg(feature = "collision")]
+        feature_buffers.push((24, &colliders_buffer.0));
+        #[cfg(feature = "sub-emitters")]
+        {
+            let dst_buffer = emitter_data
+                .emission_buffer_handle
+                .as_ref()
+                .and_then(|h| gpu_storage_buffers.get(h))
+                .map(|b| &b.buffer);
+
+            let src_buffer = emitter_data
+                .source_buffer_handle
+                .as_ref()
+                .and_then(|h| gpu_storage_buffers.get(h))
+                .map(|b| &b.buffer);
+
+            if let Some(buf) = dst_buffer {
+                emission_clear_list.push(buf.clone());
+            }
+
+            feature_buffers.push((25, dst_buffer.unwrap_or(&fallback_emission_buffers.dst)));
+            feature_buffers.push((26, src_buffer.unwrap_or(&fallback_emission_buffers.src)));
+        }
+        #[cfg(feature = "turbulence")]
+        feature_buffers.push((34, &turbulence_volumes_buffer.0));
+        if let Some(buffer) = accumulation_buffer {
+            feature_buffers.push((ACCUMULATION_BINDING, buffer));
+        }
+
+        let key = ComputeBindGroupKey {
+            buffers: [
+                uniform_buffer.id(),
+                gpu_buffer.buffer.id(),
+                trail_history_binding.id(),
+            ],
+            textures: [
+                gradient_image.texture_view.id(),
+                scale_over_lifetime_image.texture_view.id(),
+                alpha_over_lifetime_image.texture_view.id(),
+                emission_over_lifetime_image.texture_view.id(),
+                turbulence_influence_over_lifetime_image.texture_view.id(),
+                radial_velocity_curve_image.texture_view.id(),
+                angle_over_lifetime_image.texture_view.id(),
+                angular_velocity_curve_image.texture_view.id(),
+                color_over_lifetime_image.texture_view.id(),
+                orbit_velocity_curve_image.texture_view.id(),
+                directional_velocity_curve_image.texture_view.id(),
+                radial_acceleration_curve_image.texture_view.id(),
+                emission_mask_image.texture_view.id(),
+                target_image.texture_view.id(),
+                target_blend_curve_image.texture_view.id(),
+            ],
+            feature_buffers: feature_buffers
+                .iter()
+                .map(|(binding, buffer)| (*binding, buffer.id()))
+                .collect(),
+        };
+        if let Some((cached_key, bind_group)) = cached_bind_groups.get(entity)
+            && *cached_key == key
+        {
+            emitters.push(EmitterComputeBindGroup {
+                entity: *entity,
+                pipeline: simulate_pipeline,
+                bind_group: bind_group.clone(),
+                step_offsets,
+            });
+            continue;
         }
 
-        let step_bind_groups: Vec<BindGroup> = emitter_data
-            .uniform_steps
-            .iter()
-            .map(|step_uniforms| {
-                let mut uniforms = *step_uniforms;
-                uniforms.collider_count = collider_count;
-
-                let uniform_buffer = render_device.create_buffer_with_data(
-                    &bevy::render::render_resource::BufferInitDescriptor {
-                        label: Some("emitter_uniform_buffer"),
-                        contents: bytemuck::bytes_of(&uniforms),
-                        usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
-                    },
-                );
-
-                render_device.create_bind_group(
-                    Some("particle_compute_bind_group"),
-                    &bind_group_layout,
-                    &BindGroupEntries::sequential((
-                        uniform_buffer.as_entire_binding(),
-                        gpu_buffer.buffer.as_entire_binding(),
-                        &gradient_image.texture_view,
-                        &gradient_sampler.0,
-                        &scale_over_lifetime_image.texture_view,
-                        &curve_sampler.0,
-                        &alpha_over_lifetime_image.texture_view,
-                        &curve_sampler.0,
-                        &emission_over_lifetime_image.texture_view,
-                        &curve_sampler.0,
-                        &turbulence_influence_over_lifetime_image.texture_view,
-                        &curve_sampler.0,
-                        &radial_velocity_curve_image.texture_view,
-                        &curve_sampler.0,
-                        &angle_over_lifetime_image.texture_view,
-                        &curve_sampler.0,
-                        &angular_velocity_curve_image.texture_view,
-                        &curve_sampler.0,
-                        &color_over_lifetime_image.texture_view,
-                        &gradient_sampler.0,
-                        &orbit_velocity_curve_image.texture_view,
-                        &curve_sampler.0,
-                        &directional_velocity_curve_image.texture_view,
-                        &curve_sampler.0,
-                        colliders_buffer.as_entire_binding(),
-                        dst_binding.as_entire_binding(),
-                        src_binding.as_entire_binding(),
-                        trail_history_binding.as_entire_binding(),
-                    )),
-                )
-            })
-            .collect();
-
-        bind_groups.push((*entity, step_bind_groups));
+        let base_entries = BindGroupEntries::sequential((
+            uniform_binding.clone(),
+            gpu_buffer.buffer.as_entire_binding(),
+            &gradient_image.texture_view,
+            &gradient_sampler.0,
+            &scale_over_lifetime_image.texture_view,
+            &curve_sampler.0,
+            &alpha_over_lifetime_image.texture_view,
+            &curve_sampler.0,
+            &emission_over_lifetime_image.texture_view,
+            &curve_sampler.0,
+            &turbulence_influence_over_lifetime_image.texture_view,
+            &curve_sampler.0,
+            &radial_velocity_curve_image.texture_view,
+            &curve_sampler.0,
+            &angle_over_lifetime_image.texture_view,
+            &curve_sampler.0,
+            &angular_velocity_curve_image.texture_view,
+            &curve_sampler.0,
+            &color_over_lifetime_image.texture_view,
+            &gradient_sampler.0,
+            &orbit_velocity_curve_image.texture_view,
+            &curve_sampler.0,
+            &directional_velocity_curve_image.texture_view,
+            &curve_sampler.0,
+        ));
+        let shape_entries = BindGroupEntries::with_indices((
+            (27, trail_history_binding.as_entire_binding()),
+            (28, &radial_acceleration_curve_image.texture_view),
+            (29, &curve_sampler.0),
+            (30, &emission_mask_image.texture_view),
+            (31, &target_image.texture_view),
+            (32, &target_blend_curve_image.texture_view),
+            (33, &curve_sampler.0),
+        ));
+        let mut entries = [&base_entries[..], &shape_entries[..]].concat();
+        entries.extend(
+            feature_buffers
+                .iter()
+                .map(|(binding, buffer)| BindGroupEntry {
+                    binding: *binding,
+                    resource: buffer.as_entire_binding(),
+                }),
+        );
+
+        let bind_group = render_device.create_bind_group(
+            Some("particle_compute_bind_group"),
+            &bind_group_layout,
+            &entries,
+        );
+
+        cached_bind_groups.insert(*entity, (key, bind_group.clone()));
+        emitters.push(EmitterComputeBindGroup {
+            entity: *entity,
+            pipeline: simulate_pipeline,
+            bind_group,
+            step_offsets,
+        });
     }
 
-    let mut unique_buffers: Vec<Buffer> = Vec::new();
-    for buf in emission_clear_list {
-        if !unique_buffers.iter().any(|b| b.id() == buf.id()) {
-            unique_buffers.push(buf);
+    let live_emitters: EntityHashSet = emitters.iter().map(|emitter| emitter.entity).collect();
+    cached_bind_groups.retain(|entity, _| live_emitters.contains(entity));
+
+    commands.insert_resource(ParticleComputeBindGroups { emitters });
+
+    #[cfg(feature = "sub-emitters")]
+    {
+        let mut unique_buffers: Vec<Buffer> = Vec::new();
+        for buf in emission_clear_list {
+            if !unique_buffers.iter().any(|b| b.id() == buf.id()) {
+                unique_buffers.push(buf);
+            }
         }
+        commands.insert_resource(EmissionBufferClearList {
+            buffers: unique_buffers,
+        });
     }
-
-    commands.insert_resource(ParticleComputeBindGroups { bind_groups });
-    commands.insert_resource(EmissionBufferClearList {
-        buffers: unique_buffers,
-    });
 }
 
 pub fn run_particle_compute_node(
-    pipeline: Res<ParticleComputePipeline>,
     pipeline_cache: Res<PipelineCache>,
     bind_groups: Res<ParticleComputeBindGroups>,
     extracted: Res<ExtractedParticleSystem>,
-    emission_clear_list: Res<EmissionBufferClearList>,
+    #[cfg(feature = "sub-emitters")] emission_clear_list: Res<EmissionBufferClearList>,
     mut ctx: RenderContext,
 ) {
-    match pipeline_cache.get_compute_pipeline_state(pipeline.simulate_pipeline) {
-        CachedPipelineState::Ok(_) => {}
-        CachedPipelineState::Queued
-        | CachedPipelineState::Creating(_)
-        | CachedPipelineState::Err(ShaderCacheError::ShaderNotLoaded(_))
-        | CachedPipelineState::Err(ShaderCacheError::ShaderImportNotYetAvailable) => return,
-        CachedPipelineState::Err(err) => {
-            panic!("Failed to compile particle compute shader: {err}")
+    // sub emitter sources and targets simulate in lockstep, so nothing runs until every
+    // permutation in use is ready
+    for emitter in &bind_groups.emitters {
+        match pipeline_cache.get_compute_pipeline_state(emitter.pipeline) {
+            CachedPipelineState::Ok(_) => {}
+            CachedPipelineState::Queued
+            | CachedPipelineState::Creating(_)
+            | CachedPipelineState::Err(ShaderCacheError::ShaderNotLoaded(_))
+            | CachedPipelineState::Err(ShaderCacheError::ShaderImportNotYetAvailable) => return,
+            CachedPipelineState::Err(err) => {
+                panic!("Failed to compile particle compute shader: {err}")
+            }
         }
     }
 
-    let Some(compute_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.simulate_pipeline)
-    else {
-        return;
-    };
-
     let max_steps = bind_groups
-        .bind_groups
+        .emitters
         .iter()
-        .map(|(_, steps)| steps.len())
+        .map(|emitter| emitter.step_offsets.len())
         .max()
         .unwrap_or(0);
 
@@ -467,6 +945,7 @@ pub fn run_particle_compute_node(
     };
 
     for step_index in 0..max_steps {
+        #[cfg(feature = "sub-emitters")]
         for buf in &emission_clear_list.buffers {
             ctx.command_encoder().clear_buffer(buf, 0, Some(4));
         }
@@ -481,14 +960,16 @@ pub fn run_particle_compute_node(
                     ..default()
                 });
 
-            pass.set_pipeline(compute_pipeline);
+            let mut bound_pipeline = None;
 
-            for (entity, step_bind_groups) in &bind_groups.bind_groups {
-                let Some(bind_group) = step_bind_groups.get(step_index) else {
+            // one dispatch per emitter: each emitter has its own particle buffer and baked
+            // curve/gradient textures, so they can't share a dispatch over one particle range
+            for emitter in &bind_groups.emitters {
+                let Some(&offset) = emitter.step_offsets.get(step_index) else {
                     continue;
                 };
 
-                let Some(emitter_data) = emitter_map.get(entity) else {
+                let Some(emitter_data) = emitter_map.get(&emitter.entity) else {
                     continue;
                 };
 
@@ -506,7 +987,16 @@ pub fn run_particle_compute_node(
                     emitter_data.amount
                 };
                 let workgroups = (thread_count + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
-                pass.set_bind_group(0, bind_group, &[]);
+                if bound_pipeline != Some(emitter.pipeline) {
+                    let Some(compute_pipeline) =
+                        pipeline_cache.get_compute_pipeline(emitter.pipeline)
+                    else {
+                        continue;
+                    };
+                    pass.set_pipeline(compute_pipeline);
+                    bound_pipeline = Some(emitter.pipeline);
+                }
+                pass.set_bind_group(0, &emitter.bind_group, &[offset]);
                 pass.dispatch_workgroups(workgroups, 1, 1);
             }
         }
@@ -521,20 +1011,22 @@ impl Plugin for ParticleComputePlugin {
             return;
         };
 
+        #[cfg(feature = "sub-emitters")]
+        render_app.init_resource::<EmissionBufferClearList>();
         render_app
             .init_resource::<ParticleComputeBindGroups>()
-            .init_resource::<EmissionBufferClearList>()
+            .init_resource::<ParticleComputeBindGroupCache>()
+            .init_resource::<SpecializedComputePipelines<ParticleComputePipeline>>()
             .add_systems(RenderStartup, init_particle_compute_pipeline)
             .add_systems(
                 Render,
-                prepare_particle_compute_bind_groups.in_set(RenderSystems::PrepareBindGroups),
+                prepare_particle_compute_bind_groups
+                    .in_set(RenderSystems::PrepareBindGroups)
+                    .run_if(resource_exists::<ParticleComputePipeline>),
             )
             .add_systems(
                 RenderGraph,
-                run_particle_compute_node
-                    .in_set(ParticleComputeLabel)
-                    .in_set(RenderGraphSystems::Render)
-                    .before(camera_driver),
+                run_particle_compute_node.in_set(SprinklesRenderSystems::Simulate),
             );
     }
 }
@@ -1,20 +1,42 @@
+use std::f32::consts::TAU;
+
 use bevy::{
+    camera::primitives::Frustum,
+    ecs::entity::{EntityHashMap, EntityHashSet},
     prelude::*,
     render::{Extract, render_resource::ShaderType, storage::ShaderBuffer},
 };
 use bytemuck::{Pod, Zeroable};
 
+#[cfg(any(feature = "collision", feature = "turbulence"))]
+use crate::asset::ParticlesColliderShape3D;
+#[cfg(feature = "turbulence")]
+use crate::runtime::TurbulenceVolume;
+#[cfg(feature = "collision")]
+use crate::runtime::{ColliderEntity, ParticlesCollider3D};
+#[cfg(feature = "collision")]
+use crate::transform::ColliderTransforms;
 use crate::{
+    SprinklesSettings,
     asset::{
-        AnimatedVelocity, CurveTexture, DrawOrder, EmissionShape, EmitterCollisionMode,
-        EmitterData, ParticleFlags, ParticlesAsset, ParticlesColliderShape3D, SolidOrGradientColor,
-        SubEmitterMode,
+        AnimatedVelocity, CompiledExpression, CurveTexture, CurveWrap, DrawOrder, DrawPassMaterial,
+        EmissionShape, EmitterCollisionMode, EmitterCycleRandomness, EmitterData, EmitterDrawPass,
+        EmitterExpressions, EmitterTarget, Expression, ExpressionContext, ExpressionVec3,
+        InitialColorSampling, ParticleFlags, ParticlesAsset, RingEmissionOrder,
+        SerializableAlphaMode, SolidOrGradientColor, SubEmitterAmountScale, SubEmitterKeepVelocity,
+        SubEmitterMode, TargetShape,
     },
     runtime::{
-        EmitterEntity, EmitterRuntime, ParticleBufferHandle, ParticleSystemRuntime, Particles3d,
-        ParticlesCollider3D, SubEmitterBufferHandle, compute_phase, is_past_delay,
+        EmitterEntity, EmitterRuntime, ParticleAccumulationBuffer, ParticleAreaEmitter,
+        ParticleBufferHandle, ParticleSpawnHook, ParticleSystemRuntime, Particles3d,
+        ParticlesCollisionLayers, SpawnParams, SubEmitterBufferHandle,
+    },
+    textures::{
+        CurveTextureCache, EMISSION_MASK_SAMPLE_COUNT, EmissionMaskCache, GradientTextureCache,
+        TargetPointsCache,
     },
-    textures::{CurveTextureCache, GradientTextureCache},
+    timing::{compute_phase, hash_to_float, is_past_delay},
+    transform::{emitter_transforms, interpolate_transform},
 };
 
 pub const EMISSION_SHAPE_POINT: u32 = 0;
@@ -22,13 +44,38 @@ pub const EMISSION_SHAPE_SPHERE: u32 = 1;
 pub const EMISSION_SHAPE_SPHERE_SURFACE: u32 = 2;
 pub const EMISSION_SHAPE_BOX: u32 = 3;
 pub const EMISSION_SHAPE_RING: u32 = 4;
+pub const EMISSION_SHAPE_IMAGE: u32 = 5;
+
+pub const RING_ORDER_RANDOM: u32 = 0;
+pub const RING_ORDER_CLOCKWISE: u32 = 1;
+pub const RING_ORDER_COUNTER_CLOCKWISE: u32 = 2;
+
+pub const INITIAL_COLOR_SAMPLING_RANDOM: u32 = 0;
+pub const INITIAL_COLOR_SAMPLING_SPAWN_ORDER: u32 = 1;
+pub const INITIAL_COLOR_SAMPLING_EMISSION_POSITION: u32 = 2;
 
+pub const CURVE_WRAP_CLAMP: u32 = 0;
+pub const CURVE_WRAP_LOOP: u32 = 1;
+pub const CURVE_WRAP_PING_PONG: u32 = 2;
+
+pub const TARGET_MODE_DISABLED: u32 = 0;
+pub const TARGET_MODE_POINTS: u32 = 1;
+pub const TARGET_MODE_IMAGE: u32 = 2;
+
+#[cfg(any(feature = "collision", feature = "turbulence"))]
 pub const COLLIDER_TYPE_SPHERE: u32 = 0;
+#[cfg(any(feature = "collision", feature = "turbulence"))]
 pub const COLLIDER_TYPE_BOX: u32 = 1;
+#[cfg(feature = "collision")]
 pub const MAX_COLLIDERS: usize = 32;
+#[cfg(feature = "turbulence")]
+pub const MAX_TURBULENCE_VOLUMES: usize = 16;
 
 const DEFAULT_FPS: f32 = 60.0;
 
+/// Upper bound for turbulence influence in [`SprinklesSettings::mobile_mode`].
+const MOBILE_MAX_TURBULENCE_INFLUENCE: f32 = 0.5;
+
 pub const COLLISION_MODE_DISABLED: u32 = 0;
 pub const COLLISION_MODE_RIGID: u32 = 1;
 pub const COLLISION_MODE_HIDE_ON_CONTACT: u32 = 2;
@@ -38,6 +85,11 @@ pub const SUB_EMITTER_MODE_CONSTANT: u32 = 1;
 pub const SUB_EMITTER_MODE_AT_END: u32 = 2;
 pub const SUB_EMITTER_MODE_AT_COLLISION: u32 = 3;
 pub const SUB_EMITTER_MODE_AT_START: u32 = 4;
+pub const SUB_EMITTER_MODE_TRAIL: u32 = 5;
+
+pub const SUB_EMITTER_AMOUNT_SCALE_NONE: u32 = 0;
+pub const SUB_EMITTER_AMOUNT_SCALE_SCALE: u32 = 1;
+pub const SUB_EMITTER_AMOUNT_SCALE_LIFETIME: u32 = 2;
 
 #[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
 #[repr(C)]
@@ -49,7 +101,11 @@ pub struct CurveUniform {
     pub max_y: f32,
     pub min_z: f32,
     pub max_z: f32,
-    pub _pad: u32,
+    pub wrap: u32,
+    pub repeat: f32,
+    pub _pad0: u32,
+    pub _pad1: u32,
+    pub _pad2: u32,
 }
 
 impl CurveUniform {
@@ -62,7 +118,11 @@ impl CurveUniform {
             max_y: 1.0,
             min_z: 0.0,
             max_z: 1.0,
-            _pad: 0,
+            wrap: CURVE_WRAP_CLAMP,
+            repeat: 1.0,
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
         }
     }
 
@@ -77,7 +137,15 @@ impl CurveUniform {
             max_y: range_y.max,
             min_z: range_z.min,
             max_z: range_z.max,
-            _pad: 0,
+            wrap: match curve.wrap {
+                CurveWrap::Clamp => CURVE_WRAP_CLAMP,
+                CurveWrap::Loop => CURVE_WRAP_LOOP,
+                CurveWrap::PingPong => CURVE_WRAP_PING_PONG,
+            },
+            repeat: curve.repeat,
+            _pad0: 0,
+            _pad1: 0,
+            _pad2: 0,
         }
     }
 }
@@ -92,6 +160,7 @@ pub struct AnimatedVelocityUniform {
     pub curve: CurveUniform,
 }
 
+#[cfg(feature = "collision")]
 #[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
 #[repr(C)]
 pub struct ColliderUniform {
@@ -99,6 +168,15 @@ pub struct ColliderUniform {
     pub inverse_transform: [[f32; 4]; 4],
     pub extents: [f32; 3],
     pub collider_type: u32,
+    /// Maps a world-space point on the collider to that point's velocity over the last
+    /// frame, so rotating colliders impart the right velocity at each contact.
+    pub velocity_transform: [[f32; 4]; 4],
+    pub inverted: u32,
+    /// Bit mask of the layer this collider is tagged with, or `0` if it affects every
+    /// system.
+    pub layer_mask: u32,
+    pub _pad1: u32,
+    pub _pad2: u32,
 }
 
 #[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
@@ -133,7 +211,7 @@ pub struct EmitterUniforms {
     pub spawn_time_randomness: f32,
 
     pub emission_offset: [f32; 3],
-    pub _pad1: f32,
+    pub emission_rate_scale: f32,
 
     pub emission_scale: [f32; 3],
     pub _pad2: f32,
@@ -144,6 +222,46 @@ pub struct EmitterUniforms {
     pub emission_ring_axis: [f32; 3],
     pub _pad4: f32,
 
+    pub emission_position_randomness: [f32; 3],
+    pub emission_shell_thickness: f32,
+
+    pub emission_ring_arc: f32,
+    pub emission_ring_order: u32,
+    pub emission_edge_lifetime_scale: f32,
+    pub emission_edge_speed_scale: f32,
+
+    pub emission_image_size: [f32; 2],
+    pub emission_image_sample_count: u32,
+    pub emission_image_inherit_color: u32,
+
+    pub gravity_scale_min: f32,
+    pub gravity_scale_max: f32,
+    pub damping_min: f32,
+    pub damping_max: f32,
+
+    pub point_gravity_position: [f32; 3],
+    pub point_gravity_strength: f32,
+
+    pub radial_acceleration: AnimatedVelocityUniform,
+
+    pub wave_axis: [f32; 3],
+    pub wave_amplitude: f32,
+    pub wave_frequency: f32,
+    pub wave_phase_randomness: f32,
+    pub _wave_pad0: f32,
+    pub _wave_pad1: f32,
+
+    pub spatial_randomness: f32,
+    pub spatial_randomness_scale: f32,
+    pub _spatial_pad0: f32,
+    pub _spatial_pad1: f32,
+
+    pub target_mode: u32,
+    pub target_count: u32,
+    pub target_image_size: [f32; 2],
+
+    pub target_blend_over_lifetime: CurveUniform,
+
     pub direction: [f32; 3],
     pub _pad5: f32,
 
@@ -160,7 +278,7 @@ pub struct EmitterUniforms {
     pub use_initial_color_gradient: u32,
     pub turbulence_enabled: u32,
     pub particle_flags: u32,
-    pub _pad7: u32,
+    pub amount_ratio: f32,
 
     pub initial_color: [f32; 4],
 
@@ -177,6 +295,11 @@ pub struct EmitterUniforms {
 
     pub turbulence_influence_over_lifetime: CurveUniform,
 
+    pub turbulence_use_volumes: u32,
+    pub turbulence_volume_count: u32,
+    pub _turbulence_pad0: u32,
+    pub _turbulence_pad1: u32,
+
     pub radial_velocity: AnimatedVelocityUniform,
 
     pub collision_mode: u32,
@@ -186,8 +309,13 @@ pub struct EmitterUniforms {
 
     pub collision_bounce: f32,
     pub collider_count: u32,
-    pub _collision_pad0: f32,
-    pub _collision_pad1: f32,
+    pub collision_max_substeps: u32,
+    pub collision_layers: u32,
+
+    pub ground_plane_enabled: u32,
+    pub ground_plane_height: f32,
+    pub _ground_plane_pad0: f32,
+    pub _ground_plane_pad1: f32,
 
     pub angle_min: f32,
     pub angle_max: f32,
@@ -208,11 +336,20 @@ pub struct EmitterUniforms {
     pub sub_emitter_keep_velocity: u32,
 
     pub is_sub_emitter_target: u32,
+    pub sub_emitter_inherit_ratio: f32,
+    pub sub_emitter_speed_min: f32,
+    pub sub_emitter_speed_max: f32,
+
+    pub sub_emitter_direction: [f32; 3],
+    pub sub_emitter_direction_enabled: u32,
+
+    pub sub_emitter_amount_scale: u32,
     pub _sub_emitter_pad0: u32,
     pub _sub_emitter_pad1: u32,
     pub _sub_emitter_pad2: u32,
 
     pub emitter_transform: [[f32; 4]; 4],
+    pub prev_emitter_transform: [[f32; 4]; 4],
 
     pub trail_size: u32,
     pub trail_pass: u32,
@@ -223,8 +360,43 @@ pub struct EmitterUniforms {
     pub trail_effective_fps: f32,
     pub _trail_pad0: u32,
     pub _trail_pad1: u32,
+
+    pub cycle_color_pick: f32,
+    pub cycle_color_randomness: f32,
+    pub initial_color_sampling: u32,
+    pub _cycle_pad1: f32,
+
+    pub capacity: u32,
+    pub _capacity_pad0: u32,
+    pub _capacity_pad1: u32,
+    pub _capacity_pad2: u32,
+
+    pub area_center: [f32; 3],
+    pub area_wrap_enabled: u32,
+    pub area_half_extents: [f32; 3],
+    pub accumulation_capacity: u32,
 }
 
+#[cfg(feature = "turbulence")]
+#[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
+#[repr(C)]
+pub struct TurbulenceVolumeUniform {
+    pub inverse_transform: [[f32; 4]; 4],
+    pub extents: [f32; 3],
+    pub shape_type: u32,
+    pub falloff: f32,
+    pub strength: f32,
+    pub _pad0: f32,
+    pub _pad1: f32,
+}
+
+#[cfg(feature = "turbulence")]
+#[derive(Resource, Default)]
+pub struct ExtractedTurbulenceVolumes {
+    pub volumes: Vec<TurbulenceVolumeUniform>,
+}
+
+#[cfg(feature = "collision")]
 #[derive(Resource, Default)]
 pub struct ExtractedColliders {
     pub colliders: Vec<ColliderUniform>,
@@ -235,16 +407,9 @@ pub struct ExtractedParticleSystem {
     pub emitters: Vec<(Entity, ExtractedEmitterData)>,
 }
 
-pub struct ExtractedEmitterData {
-    pub uniform_steps: Vec<EmitterUniforms>,
-    pub particle_buffer_handle: Handle<ShaderBuffer>,
-    pub indices_buffer_handle: Handle<ShaderBuffer>,
-    pub sorted_particles_buffer_handle: Handle<ShaderBuffer>,
-    pub amount: u32,
-    pub draw_order: u32,
-    pub camera_position: [f32; 3],
-    pub camera_forward: [f32; 3],
-    pub emitter_transform: Mat4,
+/// Baked textures sampled by an emitter's simulation.
+#[derive(Clone, Default)]
+pub struct EmitterTextureHandles {
     pub gradient_texture_handle: Option<Handle<Image>>,
     pub color_over_lifetime_texture_handle: Option<Handle<Image>>,
     pub scale_over_lifetime_texture_handle: Option<Handle<Image>>,
@@ -256,11 +421,35 @@ pub struct ExtractedEmitterData {
     pub angular_velocity_curve_texture_handle: Option<Handle<Image>>,
     pub orbit_velocity_curve_texture_handle: Option<Handle<Image>>,
     pub directional_velocity_curve_texture_handle: Option<Handle<Image>>,
+    pub radial_acceleration_curve_texture_handle: Option<Handle<Image>>,
+    pub emission_mask_texture_handle: Option<Handle<Image>>,
+    pub target_texture_handle: Option<Handle<Image>>,
+    pub target_blend_curve_texture_handle: Option<Handle<Image>>,
+}
+
+pub struct ExtractedEmitterData {
+    pub uniform_steps: Vec<EmitterUniforms>,
+    pub particle_buffer_handle: Handle<ShaderBuffer>,
+    pub indices_buffer_handle: Handle<ShaderBuffer>,
+    pub sorted_particles_buffer_handle: Handle<ShaderBuffer>,
+    pub amount: u32,
+    pub draw_order: u32,
+    pub sort_interval: u32,
+    pub camera_position: [f32; 3],
+    pub camera_forward: [f32; 3],
+    pub frustum_cull: Option<([Vec4; 6], f32)>,
+    pub emitter_transform: Mat4,
+    pub textures: EmitterTextureHandles,
     pub is_sub_emitter_target: bool,
+    #[cfg(feature = "sub-emitters")]
     pub emission_buffer_handle: Option<Handle<ShaderBuffer>>,
+    #[cfg(feature = "sub-emitters")]
     pub source_buffer_handle: Option<Handle<ShaderBuffer>>,
     pub trail_size: u32,
     pub trail_history_buffer_handle: Option<Handle<ShaderBuffer>>,
+    pub accumulation_buffer_handle: Option<Handle<ShaderBuffer>>,
+    /// Whether the parent system is paused, so its buffers stay untouched this frame.
+    pub frozen: bool,
 }
 
 fn curve_uniform_from(curve: &Option<CurveTexture>) -> CurveUniform {
@@ -324,9 +513,17 @@ struct EmissionShapeUniforms {
     ring_height: f32,
     ring_radius: f32,
     ring_inner_radius: f32,
+    ring_arc: f32,
+    ring_order: u32,
+    image_size: Vec2,
+    image_sample_count: u32,
+    image_inherit_color: bool,
 }
 
-fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms {
+fn emission_shape_uniforms_from(
+    shape: &EmissionShape,
+    mask_texture: Option<&Handle<Image>>,
+) -> EmissionShapeUniforms {
     match *shape {
         EmissionShape::Point => EmissionShapeUniforms {
             shape: EMISSION_SHAPE_POINT,
@@ -336,6 +533,11 @@ fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms
             ring_height: 0.0,
             ring_radius: 0.0,
             ring_inner_radius: 0.0,
+            ring_arc: TAU,
+            ring_order: RING_ORDER_RANDOM,
+            image_size: Vec2::ZERO,
+            image_sample_count: 0,
+            image_inherit_color: false,
         },
         EmissionShape::Sphere { radius } => EmissionShapeUniforms {
             shape: EMISSION_SHAPE_SPHERE,
@@ -345,6 +547,11 @@ fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms
             ring_height: 0.0,
             ring_radius: 0.0,
             ring_inner_radius: 0.0,
+            ring_arc: TAU,
+            ring_order: RING_ORDER_RANDOM,
+            image_size: Vec2::ZERO,
+            image_sample_count: 0,
+            image_inherit_color: false,
         },
         EmissionShape::SphereSurface { radius } => EmissionShapeUniforms {
             shape: EMISSION_SHAPE_SPHERE_SURFACE,
@@ -354,6 +561,11 @@ fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms
             ring_height: 0.0,
             ring_radius: 0.0,
             ring_inner_radius: 0.0,
+            ring_arc: TAU,
+            ring_order: RING_ORDER_RANDOM,
+            image_size: Vec2::ZERO,
+            image_sample_count: 0,
+            image_inherit_color: false,
         },
         EmissionShape::Box { extents } => EmissionShapeUniforms {
             shape: EMISSION_SHAPE_BOX,
@@ -363,12 +575,19 @@ fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms
             ring_height: 0.0,
             ring_radius: 0.0,
             ring_inner_radius: 0.0,
+            ring_arc: TAU,
+            ring_order: RING_ORDER_RANDOM,
+            image_size: Vec2::ZERO,
+            image_sample_count: 0,
+            image_inherit_color: false,
         },
         EmissionShape::Ring {
             axis,
             height,
             radius,
             inner_radius,
+            arc,
+            order,
         } => EmissionShapeUniforms {
             shape: EMISSION_SHAPE_RING,
             sphere_radius: 0.0,
@@ -377,7 +596,97 @@ fn emission_shape_uniforms_from(shape: &EmissionShape) -> EmissionShapeUniforms
             ring_height: height,
             ring_radius: radius,
             ring_inner_radius: inner_radius,
+            ring_arc: arc.clamp(0.0, 360.0).to_radians(),
+            ring_order: match order {
+                RingEmissionOrder::Random => RING_ORDER_RANDOM,
+                RingEmissionOrder::Clockwise => RING_ORDER_CLOCKWISE,
+                RingEmissionOrder::CounterClockwise => RING_ORDER_COUNTER_CLOCKWISE,
+            },
+            image_size: Vec2::ZERO,
+            image_sample_count: 0,
+            image_inherit_color: false,
         },
+        EmissionShape::Image {
+            size,
+            inherit_color,
+            ..
+        } => EmissionShapeUniforms {
+            shape: EMISSION_SHAPE_IMAGE,
+            sphere_radius: 0.0,
+            box_extents: Vec3::ZERO,
+            ring_axis: Vec3::Z,
+            ring_height: 0.0,
+            ring_radius: 0.0,
+            ring_inner_radius: 0.0,
+            ring_arc: TAU,
+            ring_order: RING_ORDER_RANDOM,
+            image_size: size,
+            // with no baked mask the shader falls back to spawning at the origin
+            image_sample_count: if mask_texture.is_some() {
+                EMISSION_MASK_SAMPLE_COUNT
+            } else {
+                0
+            },
+            image_inherit_color: inherit_color,
+        },
+    }
+}
+
+fn resolve_emission_mask_texture(
+    shape: &EmissionShape,
+    cache: &EmissionMaskCache,
+) -> Option<Handle<Image>> {
+    match shape {
+        EmissionShape::Image {
+            texture: Some(texture),
+            channel,
+            ..
+        } => cache.get(texture, *channel),
+        _ => None,
+    }
+}
+
+struct TargetUniforms {
+    mode: u32,
+    count: u32,
+    texture_handle: Option<Handle<Image>>,
+}
+
+fn resolve_target(
+    target: &EmitterTarget,
+    points_cache: &TargetPointsCache,
+    mask_cache: &EmissionMaskCache,
+) -> TargetUniforms {
+    let disabled = TargetUniforms {
+        mode: TARGET_MODE_DISABLED,
+        count: 0,
+        texture_handle: None,
+    };
+    if !target.enabled {
+        return disabled;
+    }
+    match &target.shape {
+        TargetShape::Points { positions } => match points_cache.get(positions) {
+            Some(handle) if !positions.is_empty() => TargetUniforms {
+                mode: TARGET_MODE_POINTS,
+                count: positions.len() as u32,
+                texture_handle: Some(handle),
+            },
+            _ => disabled,
+        },
+        TargetShape::Image {
+            texture: Some(texture),
+            channel,
+            ..
+        } => match mask_cache.get(texture, *channel) {
+            Some(handle) => TargetUniforms {
+                mode: TARGET_MODE_IMAGE,
+                count: EMISSION_MASK_SAMPLE_COUNT,
+                texture_handle: Some(handle),
+            },
+            None => disabled,
+        },
+        TargetShape::Image { texture: None, .. } => disabled,
     }
 }
 
@@ -391,16 +700,224 @@ fn resolve_curve_texture(
         .and_then(|c| cache.get(c))
 }
 
+/// Everything the emitter config part of extraction depends on besides the asset
+/// contents and the baked texture caches.
+#[derive(PartialEq)]
+struct EmitterConfigKey {
+    asset: AssetId<ParticlesAsset>,
+    emitter_index: usize,
+    random_seed: u32,
+    spawn_scale: Vec3,
+}
+
+/// Uniforms and textures derived from an emitter's config, reused across frames until
+/// the asset, the baked texture caches or the [key](EmitterConfigKey) change.
+struct CachedEmitterConfig {
+    key: EmitterConfigKey,
+    base_uniforms: EmitterUniforms,
+    textures: EmitterTextureHandles,
+    expressions: CompiledEmitterExpressions,
+}
+
+/// An emitter's [`EmitterExpressions`], parsed once per config rebuild.
+struct CompiledEmitterExpressions {
+    offset: [Option<CompiledExpression>; 3],
+    direction: [Option<CompiledExpression>; 3],
+    scale: Option<CompiledExpression>,
+}
+
+impl CompiledEmitterExpressions {
+    fn new(expressions: &EmitterExpressions) -> Self {
+        let compile_vec3 = |vec: &ExpressionVec3| [&vec.x, &vec.y, &vec.z].map(compile_expression);
+        Self {
+            offset: compile_vec3(&expressions.offset),
+            direction: compile_vec3(&expressions.direction),
+            scale: compile_expression(&expressions.scale),
+        }
+    }
+
+    fn apply(&self, uniforms: &mut EmitterUniforms, context: &ExpressionContext) {
+        for (value, expression) in uniforms.emission_offset.iter_mut().zip(&self.offset) {
+            if let Some(expression) = expression {
+                *value = expression.evaluate(context);
+            }
+        }
+        for (value, expression) in uniforms.direction.iter_mut().zip(&self.direction) {
+            if let Some(expression) = expression {
+                *value = expression.evaluate(context);
+            }
+        }
+        if let Some(scale) = &self.scale {
+            let multiplier = scale.evaluate(context);
+            uniforms.scale_min *= multiplier;
+            uniforms.scale_max *= multiplier;
+        }
+    }
+}
+
+fn compile_expression(expression: &Option<Expression>) -> Option<CompiledExpression> {
+    let expression = expression.as_ref()?;
+    expression
+        .compile()
+        .inspect_err(|err| warn!("ignoring invalid expression \"{expression}\": {err}"))
+        .ok()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_emitter_config(
+    key: EmitterConfigKey,
+    emitter: &EmitterData,
+    runtime: &EmitterRuntime,
+    draw_order: u32,
+    spawn_transform: Mat4,
+    gradient_cache: &GradientTextureCache,
+    curve_cache: &CurveTextureCache,
+    mask_cache: &EmissionMaskCache,
+    points_cache: &TargetPointsCache,
+    mobile_mode: bool,
+) -> CachedEmitterConfig {
+    let emission_mask_texture_handle =
+        resolve_emission_mask_texture(&emitter.emission.shape, mask_cache);
+    let es = emission_shape_uniforms_from(
+        &emitter.emission.shape,
+        emission_mask_texture_handle.as_ref(),
+    );
+    let collision = collision_uniforms_from(&emitter.collision.mode);
+    let target = resolve_target(&emitter.target, points_cache, mask_cache);
+
+    let sub_emitter_uniforms = match &emitter.sub_emitter {
+        Some(config) => {
+            let mode = match config.mode {
+                SubEmitterMode::Constant => SUB_EMITTER_MODE_CONSTANT,
+                SubEmitterMode::AtEnd => SUB_EMITTER_MODE_AT_END,
+                SubEmitterMode::AtCollision => SUB_EMITTER_MODE_AT_COLLISION,
+                SubEmitterMode::AtStart => SUB_EMITTER_MODE_AT_START,
+                SubEmitterMode::Trail => SUB_EMITTER_MODE_TRAIL,
+            };
+            let freq = if config.frequency > 0.0 {
+                1.0 / config.frequency
+            } else {
+                1.0
+            };
+            (mode, freq, config.amount, config.keep_velocity.clone())
+        }
+        None => (
+            SUB_EMITTER_MODE_DISABLED,
+            1.0,
+            1,
+            SubEmitterKeepVelocity::default(),
+        ),
+    };
+
+    let mut base_uniforms = build_base_uniforms(
+        emitter,
+        runtime,
+        draw_order,
+        &es,
+        &collision,
+        sub_emitter_uniforms,
+        spawn_transform,
+    );
+    base_uniforms.trail_size = emitter.trail_size();
+    base_uniforms.trail_stretch_time = emitter.trail.stretch_time;
+    base_uniforms.target_mode = target.mode;
+    base_uniforms.target_count = target.count;
+    if mobile_mode {
+        base_uniforms.turbulence_influence_min = base_uniforms
+            .turbulence_influence_min
+            .min(MOBILE_MAX_TURBULENCE_INFLUENCE);
+        base_uniforms.turbulence_influence_max = base_uniforms
+            .turbulence_influence_max
+            .min(MOBILE_MAX_TURBULENCE_INFLUENCE);
+    }
+
+    let gradient_texture_handle = match &emitter.colors.initial_color {
+        SolidOrGradientColor::Gradient { gradient } => gradient_cache.get(gradient),
+        SolidOrGradientColor::Solid { .. } => None,
+    };
+
+    let textures = EmitterTextureHandles {
+        gradient_texture_handle,
+        color_over_lifetime_texture_handle: gradient_cache
+            .get(&emitter.colors.color_over_lifetime_gradient()),
+        scale_over_lifetime_texture_handle: resolve_curve_texture(
+            &emitter.scale.scale_over_lifetime,
+            curve_cache,
+        ),
+        alpha_over_lifetime_texture_handle: resolve_curve_texture(
+            &emitter.colors.alpha_over_lifetime,
+            curve_cache,
+        ),
+        emission_over_lifetime_texture_handle: resolve_curve_texture(
+            &emitter.colors.emission_over_lifetime,
+            curve_cache,
+        ),
+        turbulence_influence_over_lifetime_texture_handle: resolve_curve_texture(
+            &emitter.turbulence.influence_over_lifetime,
+            curve_cache,
+        ),
+        radial_velocity_curve_texture_handle: resolve_curve_texture(
+            &emitter.velocities.radial_velocity.velocity_over_lifetime,
+            curve_cache,
+        ),
+        angle_over_lifetime_texture_handle: resolve_curve_texture(
+            &emitter.angle.angle_over_lifetime,
+            curve_cache,
+        ),
+        angular_velocity_curve_texture_handle: resolve_curve_texture(
+            &emitter.velocities.angular_velocity.velocity_over_lifetime,
+            curve_cache,
+        ),
+        orbit_velocity_curve_texture_handle: resolve_curve_texture(
+            &emitter.velocities.orbit_velocity.velocity_over_lifetime,
+            curve_cache,
+        ),
+        directional_velocity_curve_texture_handle: resolve_curve_texture(
+            &emitter
+                .velocities
+                .directional_velocity
+                .velocity_over_lifetime,
+            curve_cache,
+        ),
+        radial_acceleration_curve_texture_handle: resolve_curve_texture(
+            &emitter
+                .accelerations
+                .radial_acceleration
+                .velocity_over_lifetime,
+            curve_cache,
+        ),
+        emission_mask_texture_handle,
+        target_texture_handle: target.texture_handle,
+        target_blend_curve_texture_handle: resolve_curve_texture(
+            &emitter.target.blend_over_lifetime,
+            curve_cache,
+        ),
+    };
+
+    CachedEmitterConfig {
+        key,
+        base_uniforms,
+        textures,
+        expressions: CompiledEmitterExpressions::new(&emitter.expressions),
+    }
+}
+
 fn build_base_uniforms(
     emitter: &EmitterData,
     runtime: &EmitterRuntime,
     draw_order: u32,
     es: &EmissionShapeUniforms,
     collision: &CollisionUniforms,
-    sub_emitter_uniforms: (u32, f32, u32, u32),
+    sub_emitter_uniforms: (u32, f32, u32, SubEmitterKeepVelocity),
     spawn_transform: Mat4,
 ) -> EmitterUniforms {
     let turbulence = &emitter.turbulence;
+    let keep_velocity = &sub_emitter_uniforms.3;
+    let amount_scale = match emitter.sub_emitter.as_ref().map(|c| c.amount_scale) {
+        Some(SubEmitterAmountScale::Scale) => SUB_EMITTER_AMOUNT_SCALE_SCALE,
+        Some(SubEmitterAmountScale::Lifetime) => SUB_EMITTER_AMOUNT_SCALE_LIFETIME,
+        Some(SubEmitterAmountScale::None) | None => SUB_EMITTER_AMOUNT_SCALE_NONE,
+    };
 
     // uniform scale factor from the spawn transform so physics quantities
     // (gravity, radial velocity, etc.) stay proportional to scaled distances.
@@ -442,7 +959,7 @@ fn build_base_uniforms(
         spawn_time_randomness: emitter.time.spawn_time_randomness,
 
         emission_offset: emitter.emission.offset.into(),
-        _pad1: 0.0,
+        emission_rate_scale: 1.0,
 
         emission_scale: emitter.emission.scale.into(),
         _pad2: 0.0,
@@ -453,6 +970,57 @@ fn build_base_uniforms(
         emission_ring_axis: es.ring_axis.into(),
         _pad4: 0.0,
 
+        emission_position_randomness: emitter.emission.position_randomness.into(),
+        emission_shell_thickness: emitter.emission.shell_thickness.max(0.0),
+
+        emission_ring_arc: es.ring_arc,
+        emission_ring_order: es.ring_order,
+        emission_edge_lifetime_scale: emitter.emission.edge_lifetime_scale.max(0.0),
+        emission_edge_speed_scale: emitter.emission.edge_speed_scale.max(0.0),
+
+        emission_image_size: es.image_size.into(),
+        emission_image_sample_count: es.image_sample_count,
+        emission_image_inherit_color: es.image_inherit_color as u32,
+
+        gravity_scale_min: emitter.accelerations.gravity_scale.min,
+        gravity_scale_max: emitter.accelerations.gravity_scale.max,
+        damping_min: emitter.accelerations.damping.min.max(0.0) * transform_scale,
+        damping_max: emitter.accelerations.damping.max.max(0.0) * transform_scale,
+
+        point_gravity_position: emitter.accelerations.point_gravity.position.into(),
+        point_gravity_strength: emitter.accelerations.point_gravity.strength * transform_scale,
+
+        radial_acceleration: scaled_animated_velocity_uniform_from(
+            &emitter.accelerations.radial_acceleration,
+            transform_scale,
+        ),
+
+        wave_axis: emitter.wave.axis.normalize_or(Vec3::Y).into(),
+        wave_amplitude: if emitter.wave.enabled {
+            emitter.wave.amplitude * transform_scale
+        } else {
+            0.0
+        },
+        wave_frequency: emitter.wave.frequency,
+        wave_phase_randomness: emitter.wave.phase_randomness.clamp(0.0, 1.0),
+        _wave_pad0: 0.0,
+        _wave_pad1: 0.0,
+
+        spatial_randomness: emitter.emission.spatial_randomness.clamp(0.0, 1.0),
+        spatial_randomness_scale: emitter.emission.spatial_randomness_scale,
+        _spatial_pad0: 0.0,
+        _spatial_pad1: 0.0,
+
+        // mode and count depend on baked textures and are filled in during extraction
+        target_mode: TARGET_MODE_DISABLED,
+        target_count: 0,
+        target_image_size: match &emitter.target.shape {
+            TargetShape::Image { size, .. } => (*size).into(),
+            TargetShape::Points { .. } => [0.0; 2],
+        },
+
+        target_blend_over_lifetime: curve_uniform_from(&emitter.target.blend_over_lifetime),
+
         direction: emitter.velocities.initial_direction.into(),
         _pad5: 0.0,
 
@@ -480,7 +1048,7 @@ fn build_base_uniforms(
             }
             flags.bits()
         },
-        _pad7: 0,
+        amount_ratio: 1.0,
 
         initial_color: match &emitter.colors.initial_color {
             SolidOrGradientColor::Solid { color } => *color,
@@ -500,6 +1068,11 @@ fn build_base_uniforms(
 
         turbulence_influence_over_lifetime: curve_uniform_from(&turbulence.influence_over_lifetime),
 
+        turbulence_use_volumes: turbulence.use_volumes as u32,
+        turbulence_volume_count: 0,
+        _turbulence_pad0: 0,
+        _turbulence_pad1: 0,
+
         radial_velocity: scaled_animated_velocity_uniform_from(
             &emitter.velocities.radial_velocity,
             transform_scale,
@@ -511,8 +1084,13 @@ fn build_base_uniforms(
         collision_friction: collision.friction,
         collision_bounce: collision.bounce,
         collider_count: 0,
-        _collision_pad0: 0.0,
-        _collision_pad1: 0.0,
+        collision_max_substeps: emitter.collision.max_substeps.max(1),
+        collision_layers: 0,
+
+        ground_plane_enabled: emitter.collision.ground_plane.is_some() as u32,
+        ground_plane_height: emitter.collision.ground_plane.unwrap_or(0.0),
+        _ground_plane_pad0: 0.0,
+        _ground_plane_pad1: 0.0,
 
         angle_min: emitter.angle.range.min,
         angle_max: emitter.angle.range.max,
@@ -532,13 +1110,25 @@ fn build_base_uniforms(
         sub_emitter_mode: sub_emitter_uniforms.0,
         sub_emitter_frequency: sub_emitter_uniforms.1,
         sub_emitter_amount: sub_emitter_uniforms.2,
-        sub_emitter_keep_velocity: sub_emitter_uniforms.3,
+        sub_emitter_keep_velocity: keep_velocity.is_enabled() as u32,
         is_sub_emitter_target: 0,
+        sub_emitter_inherit_ratio: keep_velocity.inherit_ratio.clamp(0.0, 1.0),
+        sub_emitter_speed_min: keep_velocity.speed_multiplier.min,
+        sub_emitter_speed_max: keep_velocity.speed_multiplier.max,
+
+        sub_emitter_direction: keep_velocity
+            .extra_direction
+            .map_or(Vec3::ZERO, Vec3::normalize_or_zero)
+            .to_array(),
+        sub_emitter_direction_enabled: keep_velocity.extra_direction.is_some() as u32,
+
+        sub_emitter_amount_scale: amount_scale,
         _sub_emitter_pad0: 0,
         _sub_emitter_pad1: 0,
         _sub_emitter_pad2: 0,
 
         emitter_transform: spawn_transform.to_cols_array_2d(),
+        prev_emitter_transform: spawn_transform.to_cols_array_2d(),
 
         trail_size: 1,
         trail_pass: 0,
@@ -549,7 +1139,78 @@ fn build_base_uniforms(
         trail_effective_fps: 60.0,
         _trail_pad0: 0,
         _trail_pad1: 0,
+
+        cycle_color_pick: 0.0,
+        cycle_color_randomness: 0.0,
+        initial_color_sampling: match emitter.colors.initial_color_sampling {
+            InitialColorSampling::Random => INITIAL_COLOR_SAMPLING_RANDOM,
+            InitialColorSampling::SpawnOrder => INITIAL_COLOR_SAMPLING_SPAWN_ORDER,
+            InitialColorSampling::EmissionPosition => INITIAL_COLOR_SAMPLING_EMISSION_POSITION,
+        },
+        _cycle_pad1: 0.0,
+
+        capacity: emitter.emission.capacity(),
+        _capacity_pad0: 0,
+        _capacity_pad1: 0,
+        _capacity_pad2: 0,
+
+        area_center: [0.0; 3],
+        area_wrap_enabled: 0,
+        area_half_extents: [0.0; 3],
+        accumulation_capacity: 0,
+    }
+}
+
+fn apply_cycle_randomness(
+    randomness: &EmitterCycleRandomness,
+    uniforms: &mut EmitterUniforms,
+    random_seed: u32,
+    cycle: u32,
+) {
+    // the same values for every step of a cycle, different for every cycle
+    let cycle_seed = random_seed ^ cycle.wrapping_mul(0x9e37_79b9);
+    let random = |salt: u32| hash_to_float(cycle_seed.wrapping_add(salt));
+
+    let direction = Vec3::from(uniforms.direction);
+    if randomness.direction > 0.0 && direction.length_squared() > 0.0 {
+        let tilt_axis = Quat::from_axis_angle(direction.normalize(), random(0) * TAU)
+            * direction.any_orthonormal_vector();
+        let tilt = Quat::from_axis_angle(tilt_axis, random(1) * randomness.direction.to_radians());
+        uniforms.direction = (tilt * direction).into();
     }
+
+    uniforms.explosiveness = (uniforms.explosiveness
+        + (random(2) * 2.0 - 1.0) * randomness.explosiveness)
+        .clamp(0.0, 1.0);
+
+    uniforms.cycle_color_pick = random(3);
+    uniforms.cycle_color_randomness = randomness.color.clamp(0.0, 1.0);
+}
+
+fn sample_rate_over_time(curve: &Option<CurveTexture>, phase: f32) -> f32 {
+    match curve {
+        Some(curve) => {
+            let range = &curve.x.range;
+            range.min + (range.max - range.min) * curve.sample(phase * curve.repeat)
+        }
+        None => 1.0,
+    }
+}
+
+/// Whether a draw pass looks the same regardless of the order its particles are drawn in.
+fn is_order_independent(draw_pass: &EmitterDrawPass) -> bool {
+    let DrawPassMaterial::Standard(material) = &draw_pass.material else {
+        return false;
+    };
+    // every particle blend mode is commutative
+    material.blend_mode.is_some()
+        || matches!(
+            material.alpha_mode,
+            SerializableAlphaMode::Opaque
+                | SerializableAlphaMode::Mask { .. }
+                | SerializableAlphaMode::Add
+                | SerializableAlphaMode::Multiply
+        )
 }
 
 pub fn extract_particle_systems(
@@ -562,31 +1223,66 @@ pub fn extract_particle_systems(
             &ParticleBufferHandle,
             &GlobalTransform,
             Option<&SubEmitterBufferHandle>,
+            Option<&ParticleSpawnHook>,
+            Option<&ParticleAccumulationBuffer>,
+            &InheritedVisibility,
         )>,
     >,
-    system_query: Extract<Query<(&Particles3d, &ParticleSystemRuntime)>>,
-    camera_query: Extract<Query<&GlobalTransform, With<Camera3d>>>,
+    system_query: Extract<
+        Query<(
+            &Particles3d,
+            &ParticleSystemRuntime,
+            Option<&ParticlesCollisionLayers>,
+            Option<&ParticleSpawnHook>,
+            Option<(&ParticleAreaEmitter, &GlobalTransform)>,
+        )>,
+    >,
+    camera_query: Extract<Query<(&GlobalTransform, Option<&Frustum>), With<Camera3d>>>,
     assets: Extract<Res<Assets<ParticlesAsset>>>,
     gradient_cache: Extract<Res<GradientTextureCache>>,
     curve_cache: Extract<Res<CurveTextureCache>>,
+    mask_cache: Extract<Res<EmissionMaskCache>>,
+    points_cache: Extract<Res<TargetPointsCache>>,
+    settings: Extract<Res<SprinklesSettings>>,
+    mut previous_transforms: Local<EntityHashMap<Mat4>>,
+    mut emitter_configs: Local<EntityHashMap<CachedEmitterConfig>>,
 ) {
+    // the texture caches bake in the same frame an asset changes, except for emission
+    // masks, which wait for their source image to load
+    if assets.is_changed() || mask_cache.is_changed() || settings.is_changed() {
+        emitter_configs.clear();
+    }
+
     let mut extracted = ExtractedParticleSystem::default();
+    let mut seen_emitters = EntityHashSet::default();
 
-    let (camera_position, camera_forward) = camera_query
-        .iter()
-        .next()
-        .map(|t| (t.translation(), t.forward().as_vec3()))
+    let camera = camera_query.iter().next();
+    let (camera_position, camera_forward) = camera
+        .map(|(t, _)| (t.translation(), t.forward().as_vec3()))
         .unwrap_or((Vec3::ZERO, Vec3::NEG_Z));
+    let camera_frustum_planes = camera
+        .and_then(|(_, frustum)| frustum)
+        .map(|frustum| frustum.half_spaces.map(|half_space| half_space.normal_d()));
 
     let mut emission_buffer_map: std::collections::HashMap<(Entity, usize), Handle<ShaderBuffer>> =
         std::collections::HashMap::new();
-    for (_entity, emitter_entity, runtime, _buffer_handle, _global_transform, sub_emitter_buf) in
-        emitter_query.iter()
+    for (
+        _entity,
+        emitter_entity,
+        runtime,
+        _buffer_handle,
+        _global_transform,
+        sub_emitter_buf,
+        _,
+        _,
+        _,
+    ) in emitter_query.iter()
     {
         let Some(sub_buf) = sub_emitter_buf else {
             continue;
         };
-        let Ok((particle_system, _)) = system_query.get(emitter_entity.parent_system) else {
+        let Ok((particle_system, _, _, _, _)) = system_query.get(emitter_entity.parent_system)
+        else {
             continue;
         };
         let Some(asset) = assets.get(particle_system) else {
@@ -604,10 +1300,20 @@ pub fn extract_particle_systems(
         );
     }
 
-    for (entity, emitter_entity, runtime, buffer_handle, global_transform, sub_emitter_buf) in
-        emitter_query.iter()
+    for (
+        entity,
+        emitter_entity,
+        runtime,
+        buffer_handle,
+        global_transform,
+        sub_emitter_buf,
+        emitter_hook,
+        accumulation,
+        visibility,
+    ) in emitter_query.iter()
     {
-        let Ok((particle_system, _system_runtime)) = system_query.get(emitter_entity.parent_system)
+        let Ok((particle_system, system_runtime, collision_layers, system_hook, area)) =
+            system_query.get(emitter_entity.parent_system)
         else {
             continue;
         };
@@ -623,48 +1329,25 @@ pub fn extract_particle_systems(
         if !emitter.enabled || runtime.inactive {
             continue;
         }
+        seen_emitters.insert(entity);
 
         let draw_order = match emitter.draw_pass.draw_order {
+            _ if settings.mobile_mode && is_order_independent(&emitter.draw_pass) => 0,
             DrawOrder::Index => 0,
             DrawOrder::Lifetime => 1,
             DrawOrder::ReverseLifetime => 2,
             DrawOrder::ViewDepth => 3,
-        };
-
-        let es = emission_shape_uniforms_from(&emitter.emission.shape);
-        let collision = collision_uniforms_from(&emitter.collision.mode);
-
-        let sub_emitter_uniforms = match &emitter.sub_emitter {
-            Some(config) => {
-                let mode = match config.mode {
-                    SubEmitterMode::Constant => SUB_EMITTER_MODE_CONSTANT,
-                    SubEmitterMode::AtEnd => SUB_EMITTER_MODE_AT_END,
-                    SubEmitterMode::AtCollision => SUB_EMITTER_MODE_AT_COLLISION,
-                    SubEmitterMode::AtStart => SUB_EMITTER_MODE_AT_START,
-                };
-                let freq = if config.frequency > 0.0 {
-                    1.0 / config.frequency
-                } else {
-                    1.0
-                };
-                (mode, freq, config.amount, config.keep_velocity as u32)
-            }
-            None => (SUB_EMITTER_MODE_DISABLED, 1.0, 1, 0),
+            DrawOrder::CameraDistance => 4,
         };
 
         let use_local_coords = emitter.draw_pass.use_local_coords;
-        let world_matrix = global_transform.to_matrix();
-
-        // local mode: spawn in local space (identity), render via mesh transform (world)
-        // global mode: spawn in world space (world), render without transform (identity)
-        let (spawn_transform, render_transform) = if use_local_coords {
-            (Mat4::IDENTITY, world_matrix)
-        } else {
-            (world_matrix, Mat4::IDENTITY)
-        };
+        let (spawn_transform, render_transform) = emitter_transforms(
+            global_transform,
+            emitter.emission.orientation,
+            use_local_coords,
+        );
 
         let trail_size = emitter.trail_size();
-        let trail_stretch_time = emitter.trail.stretch_time;
 
         let effective_fps = if emitter.time.fixed_fps > 0 {
             emitter.time.fixed_fps as f32
@@ -678,44 +1361,145 @@ pub fn extract_particle_systems(
         };
         let trail_history_frames = buffer_handle.trail_history_frames;
 
-        let mut base_uniforms = build_base_uniforms(
-            emitter,
-            runtime,
-            draw_order,
-            &es,
-            &collision,
-            sub_emitter_uniforms,
-            spawn_transform,
-        );
-        base_uniforms.trail_size = trail_size;
-        base_uniforms.trail_stretch_time = trail_stretch_time;
-        base_uniforms.trail_history_size = trail_history_frames;
-        base_uniforms.trail_effective_fps = effective_fps;
+        let key = EmitterConfigKey {
+            asset: particle_system.id(),
+            emitter_index: runtime.emitter_index,
+            random_seed: runtime.random_seed,
+            spawn_scale: Vec3::new(
+                spawn_transform.x_axis.truncate().length(),
+                spawn_transform.y_axis.truncate().length(),
+                spawn_transform.z_axis.truncate().length(),
+            ),
+        };
+        if emitter_configs
+            .get(&entity)
+            .is_none_or(|config| config.key != key)
+        {
+            let config = build_emitter_config(
+                key,
+                emitter,
+                runtime,
+                draw_order,
+                spawn_transform,
+                &gradient_cache,
+                &curve_cache,
+                &mask_cache,
+                &points_cache,
+                settings.mobile_mode,
+            );
+            emitter_configs.insert(entity, config);
+        }
+        let config = &emitter_configs[&entity];
+
+        // area emitters wrap world-space particles around the camera-following center
+        let (area_center, area_half_extents, area_wrap_enabled) = match area {
+            Some((area, system_transform)) if !use_local_coords => (
+                system_transform.translation().to_array(),
+                area.half_extents.max(Vec3::ZERO).to_array(),
+                1,
+            ),
+            _ => ([0.0; 3], [0.0; 3], 0),
+        };
+
+        let base_uniforms = EmitterUniforms {
+            trail_history_size: trail_history_frames,
+            area_center,
+            area_half_extents,
+            area_wrap_enabled,
+            accumulation_capacity: accumulation.map_or(0, |buffer| buffer.capacity),
+            trail_effective_fps: effective_fps,
+            collision_layers: collision_layers.map_or(0, |layers| layers.0),
+            amount_ratio: runtime.amount_ratio.clamp(0.0, 1.0),
+            initial_velocity_min: config.base_uniforms.initial_velocity_min * runtime.speed_scale,
+            initial_velocity_max: config.base_uniforms.initial_velocity_max * runtime.speed_scale,
+            ..config.base_uniforms
+        };
 
         let is_sub_emitter_target = emission_buffer_map
             .contains_key(&(emitter_entity.parent_system, runtime.emitter_index));
 
+        // spread this frame's movement across its steps so each step spawns along
+        // the segment it covers instead of clumping at the current transform
+        let previous_transform = previous_transforms
+            .get(&entity)
+            .copied()
+            .unwrap_or(spawn_transform);
+        let total_delta: f32 = runtime.simulation_steps.iter().map(|s| s.delta_time).sum();
+        let mut elapsed_delta = 0.0;
+        if !runtime.simulation_steps.is_empty() {
+            previous_transforms.insert(entity, spawn_transform);
+        }
+
         let uniform_steps: Vec<EmitterUniforms> = runtime
             .simulation_steps
             .iter()
             .flat_map(|step| {
+                let from = elapsed_delta / total_delta.max(f32::EPSILON);
+                elapsed_delta += step.delta_time;
+                let to = elapsed_delta / total_delta.max(f32::EPSILON);
+                let (step_from, step_to) = if step.clear_requested || total_delta <= 0.0 {
+                    (spawn_transform, spawn_transform)
+                } else {
+                    (
+                        interpolate_transform(previous_transform, spawn_transform, from),
+                        interpolate_transform(previous_transform, spawn_transform, to),
+                    )
+                };
                 let should_emit = if is_sub_emitter_target {
                     false
                 } else {
                     runtime.emitting && is_past_delay(step.system_time, &emitter.time)
                 };
-                let head_uniforms = EmitterUniforms {
+                let system_phase = compute_phase(step.system_time, &emitter.time);
+                let mut head_uniforms = EmitterUniforms {
                     delta_time: step.delta_time,
-                    system_phase: compute_phase(step.system_time, &emitter.time),
+                    system_phase,
                     prev_system_phase: compute_phase(step.prev_system_time, &emitter.time),
+                    emission_rate_scale: sample_rate_over_time(
+                        &emitter.emission.rate_over_time,
+                        system_phase,
+                    ),
                     cycle: step.cycle,
                     emitting: if should_emit { 1 } else { 0 },
                     clear_particles: if step.clear_requested { 1 } else { 0 },
                     is_sub_emitter_target: if is_sub_emitter_target { 1 } else { 0 },
                     trail_pass: 0,
                     trail_history_write_index: step.trail_history_write_index,
+                    emitter_transform: step_to.to_cols_array_2d(),
+                    prev_emitter_transform: step_from.to_cols_array_2d(),
                     ..base_uniforms
                 };
+                if !emitter.cycle_randomness.is_default() {
+                    apply_cycle_randomness(
+                        &emitter.cycle_randomness,
+                        &mut head_uniforms,
+                        runtime.random_seed,
+                        step.cycle,
+                    );
+                }
+                config.expressions.apply(
+                    &mut head_uniforms,
+                    &ExpressionContext {
+                        time: step.cycle as f32 * emitter.time.total_duration() + step.system_time,
+                        phase: system_phase,
+                        cycle: step.cycle,
+                    },
+                );
+                if system_hook.is_some() || emitter_hook.is_some() {
+                    let mut params = SpawnParams::new(
+                        runtime.emitter_index,
+                        step.cycle,
+                        system_phase,
+                        *global_transform,
+                        Vec3::from(head_uniforms.emission_offset),
+                        head_uniforms.initial_color,
+                    );
+                    for hook in system_hook.into_iter().chain(emitter_hook) {
+                        hook.run(&mut params);
+                    }
+                    head_uniforms.emission_offset = params.emission_offset.into();
+                    head_uniforms.initial_color = params.initial_color;
+                }
                 let trail_uniforms = (trail_size > 1).then(|| EmitterUniforms {
                     trail_pass: 1,
                     ..head_uniforms
@@ -724,45 +1508,11 @@ pub fn extract_particle_systems(
             })
             .collect();
 
-        let gradient_texture_handle = match &emitter.colors.initial_color {
-            SolidOrGradientColor::Gradient { gradient } => gradient_cache.get(gradient),
-            SolidOrGradientColor::Solid { .. } => None,
-        };
-
-        let color_over_lifetime_texture_handle =
-            gradient_cache.get(&emitter.colors.color_over_lifetime);
-
-        let scale_over_lifetime_texture_handle =
-            resolve_curve_texture(&emitter.scale.scale_over_lifetime, &curve_cache);
-        let alpha_over_lifetime_texture_handle =
-            resolve_curve_texture(&emitter.colors.alpha_over_lifetime, &curve_cache);
-        let emission_over_lifetime_texture_handle =
-            resolve_curve_texture(&emitter.colors.emission_over_lifetime, &curve_cache);
-        let turbulence_influence_over_lifetime_texture_handle =
-            resolve_curve_texture(&emitter.turbulence.influence_over_lifetime, &curve_cache);
-        let radial_velocity_curve_texture_handle = resolve_curve_texture(
-            &emitter.velocities.radial_velocity.velocity_over_lifetime,
-            &curve_cache,
-        );
-        let angle_over_lifetime_texture_handle =
-            resolve_curve_texture(&emitter.angle.angle_over_lifetime, &curve_cache);
-        let angular_velocity_curve_texture_handle = resolve_curve_texture(
-            &emitter.velocities.angular_velocity.velocity_over_lifetime,
-            &curve_cache,
-        );
-        let orbit_velocity_curve_texture_handle = resolve_curve_texture(
-            &emitter.velocities.orbit_velocity.velocity_over_lifetime,
-            &curve_cache,
-        );
-        let directional_velocity_curve_texture_handle = resolve_curve_texture(
-            &emitter
-                .velocities
-                .directional_velocity
-                .velocity_over_lifetime,
-            &curve_cache,
-        );
-
+        #[cfg(not(feature = "sub-emitters"))]
+        let _ = sub_emitter_buf;
+        #[cfg(feature = "sub-emitters")]
         let emission_buffer_handle = sub_emitter_buf.map(|b| b.buffer.clone());
+        #[cfg(feature = "sub-emitters")]
         let source_buffer_handle = if is_sub_emitter_target {
             emission_buffer_map
                 .get(&(emitter_entity.parent_system, runtime.emitter_index))
@@ -778,47 +1528,76 @@ pub fn extract_particle_systems(
                 particle_buffer_handle: buffer_handle.particle_buffer.clone(),
                 indices_buffer_handle: buffer_handle.indices_buffer.clone(),
                 sorted_particles_buffer_handle: buffer_handle.sorted_particles_buffer.clone(),
-                amount: emitter.emission.particles_amount,
+                amount: emitter.emission.capacity(),
                 draw_order,
+                sort_interval: emitter.draw_pass.sort_interval.max(1),
                 camera_position: camera_position.into(),
                 camera_forward: camera_forward.into(),
+                frustum_cull: emitter
+                    .draw_pass
+                    .frustum_cull_margin
+                    .zip(camera_frustum_planes)
+                    .map(|(margin, planes)| (planes, margin)),
                 emitter_transform: render_transform,
-                gradient_texture_handle,
-                color_over_lifetime_texture_handle,
-                scale_over_lifetime_texture_handle,
-                alpha_over_lifetime_texture_handle,
-                emission_over_lifetime_texture_handle,
-                turbulence_influence_over_lifetime_texture_handle,
-                radial_velocity_curve_texture_handle,
-                angle_over_lifetime_texture_handle,
-                angular_velocity_curve_texture_handle,
-                orbit_velocity_curve_texture_handle,
-                directional_velocity_curve_texture_handle,
+                textures: config.textures.clone(),
                 is_sub_emitter_target,
+                #[cfg(feature = "sub-emitters")]
                 emission_buffer_handle,
+                #[cfg(feature = "sub-emitters")]
                 source_buffer_handle,
                 trail_size,
                 trail_history_buffer_handle: buffer_handle.trail_history_buffer.clone(),
+                accumulation_buffer_handle: accumulation.map(|buffer| buffer.buffer.clone()),
+                frozen: system_runtime.is_paused_for(visibility)
+                    && runtime.simulation_steps.is_empty(),
             },
         ));
     }
 
+    previous_transforms.retain(|entity, _| seen_emitters.contains(entity));
+    emitter_configs.retain(|entity, _| seen_emitters.contains(entity));
     commands.insert_resource(extracted);
 }
 
+#[cfg(feature = "collision")]
 pub fn extract_colliders(
     mut commands: Commands,
-    colliders_query: Extract<Query<(&GlobalTransform, &ParticlesCollider3D)>>,
+    colliders_query: Extract<
+        Query<(
+            Entity,
+            &GlobalTransform,
+            &ParticlesCollider3D,
+            Has<ColliderEntity>,
+        )>,
+    >,
+    time: Extract<Res<Time>>,
+    mut previous_transforms: Local<EntityHashMap<Mat4>>,
 ) {
     let mut colliders = Vec::new();
+    let mut seen_colliders = EntityHashSet::default();
+    let delta = time.delta_secs();
+
+    // colliders spawned from assets come first so scene colliders are the ones dropped
+    // when there are too many
+    let from_assets = colliders_query
+        .iter()
+        .filter(|(.., from_asset)| *from_asset);
+    let from_scene = colliders_query
+        .iter()
+        .filter(|(.., from_asset)| !*from_asset);
 
-    for (global_transform, collider) in colliders_query.iter() {
+    for (entity, global_transform, collider, _) in from_assets.chain(from_scene) {
         if !collider.enabled {
             continue;
         }
+        seen_colliders.insert(entity);
 
-        let transform = global_transform.to_matrix();
-        let inverse = transform.inverse();
+        let transforms = ColliderTransforms::new(
+            global_transform,
+            previous_transforms.get(&entity).copied(),
+            delta,
+        );
+        previous_transforms.insert(entity, transforms.transform);
 
         let (extents, collider_type) = match &collider.shape {
             ParticlesColliderShape3D::Sphere { radius } => {
@@ -828,10 +1607,15 @@ pub fn extract_colliders(
         };
 
         colliders.push(ColliderUniform {
-            transform: transform.to_cols_array_2d(),
-            inverse_transform: inverse.to_cols_array_2d(),
+            transform: transforms.transform.to_cols_array_2d(),
+            inverse_transform: transforms.inverse_transform.to_cols_array_2d(),
             extents,
             collider_type,
+            velocity_transform: transforms.velocity_transform.to_cols_array_2d(),
+            inverted: collider.invert as u32,
+            layer_mask: collider.affects.layer_mask(),
+            _pad1: 0,
+            _pad2: 0,
         });
 
         if colliders.len() >= MAX_COLLIDERS {
@@ -839,5 +1623,38 @@ pub fn extract_colliders(
         }
     }
 
+    previous_transforms.retain(|entity, _| seen_colliders.contains(entity));
     commands.insert_resource(ExtractedColliders { colliders });
 }
+
+#[cfg(feature = "turbulence")]
+pub fn extract_turbulence_volumes(
+    mut commands: Commands,
+    volumes_query: Extract<Query<(&GlobalTransform, &TurbulenceVolume)>>,
+) {
+    let volumes = volumes_query
+        .iter()
+        .filter(|(_, volume)| volume.enabled)
+        .take(MAX_TURBULENCE_VOLUMES)
+        .map(|(global_transform, volume)| {
+            let (extents, shape_type) = match &volume.shape {
+                ParticlesColliderShape3D::Sphere { radius } => {
+                    ([*radius, 0.0, 0.0], COLLIDER_TYPE_SPHERE)
+                }
+                ParticlesColliderShape3D::Box { size } => {
+                    ((*size * 0.5).to_array(), COLLIDER_TYPE_BOX)
+                }
+            };
+            TurbulenceVolumeUniform {
+                inverse_transform: global_transform.to_matrix().inverse().to_cols_array_2d(),
+                extents,
+                shape_type,
+                falloff: volume.falloff.max(0.0),
+                strength: volume.strength,
+                _pad0: 0.0,
+                _pad1: 0.0,
+            }
+        })
+        .collect();
+    commands.insert_resource(ExtractedTurbulenceVolumes { volumes });
+}
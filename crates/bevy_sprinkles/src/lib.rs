@@ -91,6 +91,26 @@
 //!
 //! - `preset-textures` - Bundles a library of built-in particle
 //!   textures, see [`PresetTexture`] (enabled by default)
+//! - `collision` - Simulates [collision](EmitterCollision) with
+//!   [colliders](ParticlesCollider3D) (enabled by default)
+//! - `sub-emitters` - Runs [sub-emitters](asset::SubEmitterConfig) (enabled by default)
+//! - `turbulence` - Simulates [turbulence](EmitterTurbulence) and
+//!   [turbulence volumes](TurbulenceVolume) (enabled by default)
+//! - `sorting` - Sorts particles by their [draw order](DrawOrder), otherwise they are
+//!   always drawn in emission order (enabled by default)
+//! - `metaballs` - Adds a screen-space pass that draws
+//!   [metaball](EmitterDrawPass::metaball) emitters as a liquid surface, enabled per
+//!   camera with `MetaballSettings`
+//! - `thumbnails` - Adds `render_thumbnail`, which renders a preview image of an asset
+//!   in a headless app for editors, launchers and asset browsers
+//! - `shader-hot-reload` - Reads the built-in WGSL shaders from this crate's source
+//!   directory and reloads them when they change, for iterating on shaders without
+//!   recompiling. Only useful when working on Sprinkles itself
+//!
+//! Disabling `collision`, `sub-emitters`, `turbulence` or `sorting` compiles their systems,
+//! GPU bindings and shader code out. Assets that use them still load, and the settings
+//! are ignored. None of these features pull in extra dependencies, so turning them off
+//! shrinks Sprinkles' own code and shaders, not the dependency tree.
 //!
 //! # Table of contents
 //!
@@ -101,6 +121,10 @@
 //! - [Spawning a system](Particles3d) with a handle to a [`ParticlesAsset`]
 //! - [Playback control](ParticleSystemRuntime) (pause, resume, restart)
 //! - [Per-emitter runtime state](EmitterRuntime)
+//! - [Looking up emitter and collider entities](ParticleSystemChildren)
+//! - [Animating material tint and emissive per instance](ParticleMaterialOverrides)
+//! - [Following the camera for ambient weather](ParticleAreaEmitter)
+//! - [Replaying an instance deterministically](ParticleSeed)
 //!
 //! ## Emitters
 //!
@@ -124,7 +148,10 @@
 //! - [Acceleration](EmitterAccelerations): constant forces applied to particles
 //! - [Scale](EmitterScale): particle size over lifetime
 //! - [Color](EmitterColors): particle color over lifetime
-//! - [Turbulence](EmitterTurbulence): noise-based displacement
+//! - [Turbulence](EmitterTurbulence): noise-based displacement, optionally limited to
+//!   [turbulence volumes](TurbulenceVolume) in the scene
+//! - [Expressions](EmitterExpressions): [math expressions](asset::Expression) that
+//!   animate the emission offset, direction and scale over time
 //!
 //! See the [`asset`] module for more details about particle properties.
 //!
@@ -136,6 +163,18 @@
 //! - [Collision mode](EmitterCollisionMode): how particles react to colliders
 //! - [Collider shapes](ParticlesColliderShape3D): the collision surface geometry
 //! - [Collider data](ColliderData): per-collider configuration
+//! - [Collider layers](ParticlesCollisionLayers): which systems scene colliders affect
+//! - [Accumulation](ParticleAccumulationBuffer): hit positions for snow cover and
+//!   other buildup
+//!
+//! ## Spawn hooks
+//!
+//! A [`ParticleSpawnHook`] lets gameplay code adjust [spawn values](SpawnParams) such as
+//! the emission offset or initial color on the CPU each simulation step.
+//!
+//! With the `bevy_audio` feature, an `AudioReactive` component drives an emitter's
+//! emission rate and initial velocity from an `AudioBands` resource you fill with FFT
+//! band amplitudes, for music visualizers and beat-reactive effects.
 //!
 //! ## Sub-emitters
 //!
@@ -144,6 +183,7 @@
 //!
 //! - [Trigger modes](asset::SubEmitterMode): when sub-emitters activate
 //! - [Configuration](asset::SubEmitterConfig): which emitter to spawn and how
+//! - [Velocity inheritance](asset::SubEmitterKeepVelocity): how children are launched
 //!
 //! ## Textures
 //!
@@ -154,14 +194,41 @@
 //! - [Preset textures](PresetTexture): built-in particle textures bundled with the crate
 //!
 //! See the [`textures::baked`] module for more details about texture baking and caching.
+//!
+//! ## Custom render features
+//!
+//! The particle buffer layouts are published as the `sprinkles::particles` WGSL module,
+//! so custom post effects, debug views and other render features can read particle data
+//! directly:
+//!
+//! ```wgsl
+//! #import sprinkles::particles::{Particle, ParticleEmitterUniforms, particle_is_active}
+//! ```
+//!
+//! Bind [`ParticleBufferHandle::sorted_particles_buffer`] as an `array<Particle>` and
+//! [`ParticleBufferHandle::emitter_uniforms_buffer`] as a
+//! [`ParticleEmitterUniforms`](material::ParticleEmitterUniforms). Changes to these
+//! layouts, other than appending new uniform fields, are treated as breaking changes.
+//!
+//! To target particles in post-processing without reading particle buffers, add a
+//! [`ParticleMaskTarget`] to a camera and read its [`ParticleMaskTexture`], which holds
+//! each emitter's [mask value](EmitterDrawPass::post_process_mask) per pixel.
 
 /// Particle system asset definitions, emitter data, and serialization types.
 pub mod asset;
+/// Emitters that react to audio band amplitudes.
+#[cfg(feature = "bevy_audio")]
+pub mod audio;
 mod compute;
 mod extract;
 /// Particle material extension for GPU-driven particle rendering.
 pub mod material;
 mod mesh;
+/// Screen-space liquid rendering for metaball emitters.
+#[cfg(feature = "metaballs")]
+pub mod metaball;
+/// Particle mask textures for targeting particles in post-processing.
+pub mod post_process_mask;
 /// Convenience re-exports for common particle system types.
 pub mod prelude;
 /// Runtime components and state for active particle systems.
@@ -170,32 +237,117 @@ mod sort;
 mod spawning;
 /// Texture baking and caching for gradients and curves.
 pub mod textures;
+/// Headless rendering of asset preview images.
+#[cfg(feature = "thumbnails")]
+pub mod thumbnail;
+/// Phase, delay, and cycle math shared by the CPU scheduler and the simulation shader.
+pub mod timing;
+/// Transform math for emitters and colliders nested under scaled and rotated parents.
+pub mod transform;
 
+#[cfg(not(feature = "shader-hot-reload"))]
+use bevy::asset::{load_internal_asset, uuid_handle};
 use bevy::{
-    asset::{embedded_asset, load_internal_asset, uuid_handle},
+    asset::embedded_asset,
+    core_pipeline::schedule::camera_driver,
     pbr::MaterialPlugin,
     prelude::*,
-    render::{ExtractSchedule, RenderApp, extract_resource::ExtractResourcePlugin},
+    render::{
+        ExtractSchedule, RenderApp,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        renderer::{RenderGraph, RenderGraphSystems},
+    },
+    transform::TransformSystems,
 };
 
+#[cfg(not(feature = "shader-hot-reload"))]
 const SHADER_COMMON: Handle<Shader> = uuid_handle!("10b6a301-2396-4ce0-906a-b3e38aaddddf");
+#[cfg(not(feature = "shader-hot-reload"))]
+const SHADER_PARTICLES: Handle<Shader> = uuid_handle!("5f0c2d8e-7a41-4b9e-9c3d-2e6b81f4a7c5");
+
+/// Keeps the imported shader modules loaded, since nothing else holds a handle to them.
+#[cfg(feature = "shader-hot-reload")]
+#[derive(Resource)]
+struct ShaderLibraries(#[allow(dead_code)] Vec<Handle<Shader>>);
 
 use asset::{ParticlesAsset, ParticlesAssetLoader};
 use compute::ParticleComputePlugin;
-use extract::{extract_colliders, extract_particle_systems};
+#[cfg(feature = "collision")]
+use extract::extract_colliders;
+use extract::extract_particle_systems;
+#[cfg(feature = "turbulence")]
+use extract::extract_turbulence_volumes;
 use mesh::ParticleMeshCache;
-use runtime::check_particle_system_finished;
+use post_process_mask::ParticleMaskPlugin;
+use runtime::{
+    check_particle_system_finished, follow_area_emitter_cameras, restore_disabled_emitters,
+    run_emitter_stop_actions,
+};
 use sort::ParticleSortPlugin;
+#[cfg(feature = "collision")]
+use spawning::sync_collider_data;
 use spawning::{
-    cleanup_particle_entities, setup_particle_systems, sync_collider_data, sync_particle_buffers,
+    cleanup_particle_entities, setup_particle_systems, sync_particle_buffers,
     sync_particle_material, sync_particle_mesh, update_particle_time, write_emitter_uniforms,
 };
 use textures::{
-    CurveTextureCache, FallbackCurveTexture, FallbackGradientTexture, GradientTextureCache,
-    create_fallback_curve_texture, create_fallback_gradient_texture, prepare_curve_textures,
-    prepare_gradient_textures,
+    CurveTextureCache, EmissionMaskCache, FallbackCurveTexture, FallbackGradientTexture,
+    GradientTextureCache, TargetPointsCache, create_fallback_curve_texture,
+    create_fallback_gradient_texture, prepare_curve_textures, prepare_emission_masks,
+    prepare_gradient_textures, prepare_target_points,
 };
 
+/// System sets for the particle compute passes, in the render app's [`RenderGraph`]
+/// schedule.
+///
+/// Simulation runs before sorting, and both run before the camera driver. Order custom
+/// compute passes against these sets to read or write particle buffers at a
+/// deterministic point in the frame, for example:
+///
+/// ```ignore
+/// render_app.add_systems(
+///     RenderGraph,
+///     run_fluid_coupling_pass
+///         .after(SprinklesRenderSystems::Simulate)
+///         .before(SprinklesRenderSystems::Sort),
+/// );
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum SprinklesRenderSystems {
+    /// Simulates particles for every emitter.
+    Simulate,
+    /// Compacts alive particles to the front, sorts them into draw order and copies
+    /// them to the buffers read by the particle material.
+    Sort,
+}
+
+/// Global settings for every particle system in the app.
+///
+/// Insert this resource before or after adding [`SprinklesPlugin`] to override the
+/// defaults:
+///
+/// ```ignore
+/// app.insert_resource(SprinklesSettings {
+///     mobile_mode: true,
+/// });
+/// ```
+#[derive(Resource, Debug, Clone, Default, Reflect, ExtractResource)]
+pub struct SprinklesSettings {
+    /// Trades accuracy for GPU time on mobile and other low-power devices.
+    ///
+    /// When enabled:
+    /// - sorted emitters are re-sorted every other frame, drawing with the previous
+    ///   frame's order in between
+    /// - turbulence influence is clamped, since strongly turbulent particles reorder
+    ///   the fastest
+    /// - emitters whose blending doesn't depend on draw order (additive, subtract,
+    ///   screen, min and max blending, additive or multiply alpha modes, and opaque or
+    ///   alpha-mask materials) are never sorted
+    ///
+    /// Defaults to `false`.
+    pub mobile_mode: bool,
+}
+
 /// Plugin that adds GPU particle system support to a Bevy app.
 ///
 /// Registers asset loaders, compute pipelines, material plugins, texture caches,
@@ -204,17 +356,51 @@ pub struct SprinklesPlugin;
 
 impl Plugin for SprinklesPlugin {
     fn build(&self, app: &mut App) {
-        load_internal_asset!(app, SHADER_COMMON, "shaders/common.wgsl", Shader::from_wgsl);
+        #[cfg(not(feature = "shader-hot-reload"))]
+        {
+            load_internal_asset!(
+                app,
+                SHADER_PARTICLES,
+                "shaders/particles.wgsl",
+                Shader::from_wgsl
+            );
+            load_internal_asset!(app, SHADER_COMMON, "shaders/common.wgsl", Shader::from_wgsl);
+        }
+        // embedded assets are read from the source directory and watched for changes when
+        // bevy's `embedded_watcher` feature is on, which internal assets don't support
+        #[cfg(feature = "shader-hot-reload")]
+        {
+            embedded_asset!(app, "shaders/particles.wgsl");
+            embedded_asset!(app, "shaders/common.wgsl");
+            let asset_server = app.world().resource::<AssetServer>();
+            let libraries = ShaderLibraries(vec![
+                asset_server.load("embedded://bevy_sprinkles/shaders/particles.wgsl"),
+                asset_server.load("embedded://bevy_sprinkles/shaders/common.wgsl"),
+            ]);
+            app.insert_resource(libraries);
+        }
         embedded_asset!(app, "shaders/particle_simulate.wgsl");
         embedded_asset!(app, "shaders/particle_material.wgsl");
         embedded_asset!(app, "shaders/particle_sort.wgsl");
+        embedded_asset!(app, "shaders/particle_mask.wgsl");
+        #[cfg(feature = "metaballs")]
+        embedded_asset!(app, "shaders/particle_metaball.wgsl");
 
         #[cfg(feature = "preset-textures")]
         textures::preset::register_preset_textures(app);
 
+        #[cfg(feature = "metaballs")]
+        app.add_plugins(metaball::MetaballPlugin);
+
+        #[cfg(feature = "bevy_audio")]
+        app.init_resource::<audio::AudioBands>()
+            .add_systems(Update, audio::apply_audio_reactivity);
+
         app.init_asset::<ParticlesAsset>()
             .init_asset_loader::<ParticlesAssetLoader>();
 
+        app.init_resource::<SprinklesSettings>();
+
         app.init_resource::<GradientTextureCache>()
             .add_systems(Startup, create_fallback_gradient_texture)
             .add_systems(PostUpdate, prepare_gradient_textures);
@@ -223,6 +409,12 @@ impl Plugin for SprinklesPlugin {
             .add_systems(Startup, create_fallback_curve_texture)
             .add_systems(PostUpdate, prepare_curve_textures);
 
+        app.init_resource::<EmissionMaskCache>()
+            .add_systems(PostUpdate, prepare_emission_masks);
+
+        app.init_resource::<TargetPointsCache>()
+            .add_systems(PostUpdate, prepare_target_points);
+
         app.init_resource::<ParticleMeshCache>();
 
         app.add_plugins(MaterialPlugin::<runtime::ParticleMaterial>::default());
@@ -234,44 +426,80 @@ impl Plugin for SprinklesPlugin {
                 sync_particle_buffers.after(setup_particle_systems),
                 sync_particle_mesh.after(sync_particle_buffers),
                 sync_particle_material,
-                sync_collider_data,
+                restore_disabled_emitters.before(update_particle_time),
                 update_particle_time,
                 check_particle_system_finished.after(update_particle_time),
+                run_emitter_stop_actions.after(check_particle_system_finished),
                 cleanup_particle_entities,
             ),
         );
 
-        app.add_systems(PostUpdate, write_emitter_uniforms);
+        app.add_systems(
+            PostUpdate,
+            (
+                write_emitter_uniforms,
+                follow_area_emitter_cameras.before(TransformSystems::Propagate),
+            ),
+        );
+
+        #[cfg(feature = "collision")]
+        app.add_systems(Update, sync_collider_data);
 
         app.add_plugins((
             ParticleComputePlugin,
             ParticleSortPlugin,
+            ParticleMaskPlugin,
             ExtractResourcePlugin::<FallbackGradientTexture>::default(),
             ExtractResourcePlugin::<FallbackCurveTexture>::default(),
+            ExtractResourcePlugin::<SprinklesSettings>::default(),
         ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.add_systems(
-                ExtractSchedule,
-                (extract_particle_systems, extract_colliders),
-            );
+            render_app
+                .configure_sets(
+                    RenderGraph,
+                    (
+                        SprinklesRenderSystems::Simulate,
+                        SprinklesRenderSystems::Sort,
+                    )
+                        .chain()
+                        .in_set(RenderGraphSystems::Render)
+                        .before(camera_driver),
+                )
+                .add_systems(ExtractSchedule, extract_particle_systems);
+            #[cfg(feature = "collision")]
+            render_app.add_systems(ExtractSchedule, extract_colliders);
+            #[cfg(feature = "turbulence")]
+            render_app.add_systems(ExtractSchedule, extract_turbulence_volumes);
         }
     }
 }
 
 pub use asset::{
-    ColliderData, DrawOrder, DrawPassMaterial, EmitterAccelerations, EmitterCollision,
-    EmitterCollisionMode, EmitterColors, EmitterData, EmitterDrawPass, EmitterEmission,
-    EmitterScale, EmitterTime, EmitterTrail, EmitterTurbulence, EmitterVelocities, ParticleFlags,
-    ParticleMesh, ParticlesColliderShape3D, ParticlesDimension, QuadOrientation, RibbonTrailShape,
+    AlphaFadeMode, ColliderData, DrawOrder, DrawPassMaterial, DrawPassVisibility,
+    EmitterAccelerations, EmitterCollision, EmitterCollisionMode, EmitterColors,
+    EmitterCycleRandomness, EmitterData, EmitterDrawPass, EmitterEmission, EmitterExpressions,
+    EmitterScale, EmitterTarget, EmitterTextureSheet, EmitterTime, EmitterTrail, EmitterTurbulence,
+    EmitterVelocities, EmitterWave, ParticleBlendMode, ParticleFlags, ParticleMesh,
+    ParticlesColliderShape3D, ParticlesDimension, QuadOrientation, RibbonTrailShape,
     SerializableAlphaMode, StandardParticleMaterial, TransformAlign,
 };
+#[cfg(feature = "bevy_audio")]
+pub use audio::{AudioBands, AudioReactive};
 pub use material::ParticleMaterialExtension;
+#[cfg(feature = "metaballs")]
+pub use metaball::MetaballSettings;
+pub use post_process_mask::{PARTICLE_MASK_FORMAT, ParticleMaskTarget, ParticleMaskTexture};
 pub use runtime::{
-    ColliderEntity, EmitterEntity, EmitterRuntime, Finished, ParticleBufferHandle, ParticleData,
-    ParticleMaterial, ParticleMaterialHandle, ParticleSystemRuntime, Particles2d, Particles3d,
-    ParticlesCollider3D,
+    ColliderAffects, ColliderEntity, DisabledEmitter, EmitterEntity, EmitterRuntime,
+    EmitterStopped, Finished, ParticleAccumulationBuffer, ParticleAreaEmitter,
+    ParticleBufferHandle, ParticleData, ParticleMaterial, ParticleMaterialHandle,
+    ParticleMaterialOverrides, ParticleSeed, ParticleSpawnHook, ParticleSystemChildren,
+    ParticleSystemRuntime, Particles2d, Particles3d, ParticlesCollider3D, ParticlesCollisionLayers,
+    SpawnParams, TurbulenceVolume,
 };
 #[cfg(feature = "preset-textures")]
 pub use textures::preset::PresetTexture;
 pub use textures::preset::TextureRef;
+#[cfg(feature = "thumbnails")]
+pub use thumbnail::{ThumbnailError, render_thumbnail, render_thumbnail_sequence};
@@ -4,13 +4,16 @@ use bevy::{
     prelude::*,
     render::{
         render_resource::{
-            AsBindGroup, CompareFunction, RenderPipelineDescriptor, ShaderType,
-            SpecializedMeshPipelineError,
+            AsBindGroup, BlendComponent, BlendFactor, BlendOperation, BlendState, CompareFunction,
+            RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
         },
         storage::ShaderBuffer,
     },
     shader::ShaderRef,
 };
+use bytemuck::{Pod, Zeroable};
+
+use crate::asset::ParticleBlendMode;
 
 const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_material.wgsl";
 
@@ -18,6 +21,8 @@ const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_mate
 pub const TRAIL_THICKNESS_CURVE_SAMPLES: usize = 16;
 
 /// GPU-side per-emitter uniforms passed to the particle material shader.
+///
+/// Mirrors `ParticleEmitterUniforms` in the `sprinkles::particles` WGSL module.
 #[derive(Clone, Copy, ShaderType)]
 pub struct ParticleEmitterUniforms {
     /// World-space transform matrix for the emitter.
@@ -37,9 +42,53 @@ pub struct ParticleEmitterUniforms {
     /// - `2`: Y to velocity
     /// - `3`: Billboard Y to velocity
     /// - `4`: Billboard fixed Y
+    /// - `5`: Fixed axis
+    /// - `6`: Velocity stretched billboard
     pub transform_align: u32,
     /// Baked trail thickness curve samples.
     pub trail_thickness_curve: [f32; TRAIL_THICKNESS_CURVE_SAMPLES],
+    /// World-space direction particles face when `transform_align` is fixed axis.
+    pub transform_align_axis: Vec3,
+    /// Whether particles keep their up axis pointing toward world up.
+    pub keep_world_up: u32,
+    /// Whether particles are only drawn into shadow maps.
+    pub shadow_only: u32,
+    /// Frame delta time in seconds, used to extrapolate motion vectors.
+    pub delta_time: f32,
+    /// Whether the particle alpha erodes the base color texture instead of fading it.
+    pub alpha_erosion: u32,
+    /// Width of the erosion ramp.
+    pub alpha_erosion_softness: f32,
+    /// Current scroll offset of the UV distortion texture.
+    pub distortion_offset: Vec2,
+    /// How far the UV distortion texture offsets UVs.
+    pub distortion_strength: f32,
+    /// Number of texture sheet columns, or `0` when the texture sheet is disabled.
+    pub texture_sheet_columns: u32,
+    /// Number of texture sheet rows.
+    pub texture_sheet_rows: u32,
+    /// Which texture sheet cells particles pick from.
+    ///
+    /// - `0`: Any cell
+    /// - `1`: Random row
+    /// - `2`: Random column
+    pub texture_sheet_mode: u32,
+    /// Scale applied to the density of the camera's distance fog.
+    pub fog_density_multiplier: f32,
+    /// Distance from the camera over which particles fade out, or `0.0` to disable.
+    pub near_fade_distance: f32,
+    /// Stretch along velocity per unit of speed when `transform_align` is velocity
+    /// stretched billboard.
+    pub velocity_stretch: f32,
+    /// Linear color multiplied into the base color, from
+    /// [`ParticleMaterialOverrides::tint`](crate::ParticleMaterialOverrides::tint).
+    pub tint: Vec4,
+    /// Multiplier applied to the emissive color.
+    pub emissive_multiplier: f32,
+    /// Offset added to every particle's erosion threshold.
+    pub erosion_threshold: f32,
+    /// Number of particles spawned per emission cycle.
+    pub amount: u32,
 }
 
 impl Default for ParticleEmitterUniforms {
@@ -52,6 +101,24 @@ impl Default for ParticleEmitterUniforms {
             trail_size: 1,
             transform_align: 0,
             trail_thickness_curve: [1.0; TRAIL_THICKNESS_CURVE_SAMPLES],
+            transform_align_axis: Vec3::Z,
+            keep_world_up: 0,
+            shadow_only: 0,
+            delta_time: 0.0,
+            alpha_erosion: 0,
+            alpha_erosion_softness: 0.0,
+            distortion_offset: Vec2::ZERO,
+            distortion_strength: 0.0,
+            texture_sheet_columns: 0,
+            texture_sheet_rows: 1,
+            texture_sheet_mode: 0,
+            fog_density_multiplier: 1.0,
+            near_fade_distance: 0.0,
+            velocity_stretch: 0.0,
+            tint: Vec4::ONE,
+            emissive_multiplier: 1.0,
+            erosion_threshold: 0.0,
+            amount: 0,
         }
     }
 }
@@ -62,6 +129,7 @@ impl Default for ParticleEmitterUniforms {
 /// to the vertex shader so it can read per-particle state (position, color,
 /// scale, etc.) and transform each instanced mesh accordingly.
 #[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[bind_group_data(ParticleMaterialKey)]
 pub struct ParticleMaterialExtension {
     /// Handle to the sorted particle data buffer, read by the vertex shader.
     #[storage(100, read_only)]
@@ -69,6 +137,72 @@ pub struct ParticleMaterialExtension {
     /// Handle to the per-emitter uniforms buffer (transform, flags, etc.).
     #[storage(101, read_only)]
     pub emitter_uniforms: Handle<ShaderBuffer>,
+    /// Whether particles are drawn into depth-only prepasses.
+    pub depth_prepass: bool,
+    /// Blend operation that overrides the base material's blend state.
+    pub blend_mode: Option<ParticleBlendMode>,
+    /// Noise texture that distorts the UVs of the base material's textures.
+    #[texture(102)]
+    #[sampler(103)]
+    pub distortion_texture: Option<Handle<Image>>,
+    /// Offset added to the base material's depth bias to shift the transparent sort
+    /// distance, see [`EmitterDrawPass::sort_bias`](crate::EmitterDrawPass::sort_bias).
+    pub sort_bias: f32,
+    /// The base material's depth bias before [`sort_bias`](Self::sort_bias) was added.
+    pub depth_bias: f32,
+}
+
+/// Pipeline key for [`ParticleMaterialExtension`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Pod, Zeroable)]
+pub struct ParticleMaterialKey {
+    depth_prepass: u32,
+    blend_mode: u32,
+    depth_bias: i32,
+}
+
+impl From<&ParticleMaterialExtension> for ParticleMaterialKey {
+    fn from(extension: &ParticleMaterialExtension) -> Self {
+        let blend_mode = match extension.blend_mode {
+            None => 0,
+            Some(ParticleBlendMode::AdditiveWithAlpha) => 1,
+            Some(ParticleBlendMode::Screen) => 2,
+            Some(ParticleBlendMode::Subtract) => 3,
+            Some(ParticleBlendMode::Min) => 4,
+            Some(ParticleBlendMode::Max) => 5,
+        };
+        Self {
+            depth_prepass: extension.depth_prepass as u32,
+            blend_mode,
+            depth_bias: extension.depth_bias as i32,
+        }
+    }
+}
+
+// blend states for the premultiplied color written by the fragment shader
+fn blend_state_from_key(blend_mode: u32) -> Option<BlendState> {
+    let keep_alpha = BlendComponent {
+        src_factor: BlendFactor::Zero,
+        dst_factor: BlendFactor::One,
+        operation: BlendOperation::Add,
+    };
+    let color = |dst_factor, operation| BlendComponent {
+        src_factor: BlendFactor::One,
+        dst_factor,
+        operation,
+    };
+    let color = match blend_mode {
+        1 => color(BlendFactor::One, BlendOperation::Add),
+        2 => color(BlendFactor::OneMinusSrc, BlendOperation::Add),
+        3 => color(BlendFactor::One, BlendOperation::ReverseSubtract),
+        4 => color(BlendFactor::One, BlendOperation::Min),
+        5 => color(BlendFactor::One, BlendOperation::Max),
+        _ => return None,
+    };
+    Some(BlendState {
+        color,
+        alpha: keep_alpha,
+    })
 }
 
 impl MaterialExtension for ParticleMaterialExtension {
@@ -106,11 +240,35 @@ impl MaterialExtension for ParticleMaterialExtension {
         if let Some(depth_stencil) = &mut descriptor.depth_stencil {
             depth_stencil.depth_write_enabled = Some(!is_transparent);
             depth_stencil.depth_compare = Some(CompareFunction::GreaterEqual);
+            // the sort bias only shifts draw order, never the depth test
+            depth_stencil.bias.constant = key.bind_group_data.depth_bias;
         }
 
         // disable backface culling so trail tubes render both sides
         descriptor.primitive.cull_mode = None;
 
+        // depth-only prepasses skip particles unless the material may discard, so
+        // opt-in passes take the same path to get the full particle vertex transform
+        let is_prepass = descriptor
+            .vertex
+            .shader_defs
+            .contains(&"PREPASS_PIPELINE".into());
+        if key.bind_group_data.depth_prepass != 0 && is_prepass {
+            descriptor.vertex.shader_defs.push("MAY_DISCARD".into());
+        }
+
+        if let Some(blend) = blend_state_from_key(key.bind_group_data.blend_mode)
+            && !is_prepass
+            && let Some(fragment) = &mut descriptor.fragment
+        {
+            for target in fragment.targets.iter_mut().flatten() {
+                target.blend = Some(blend);
+            }
+            if key.bind_group_data.blend_mode == 4 {
+                fragment.shader_defs.push("PARTICLE_BLEND_MIN".into());
+            }
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,546 @@
+use bevy::{
+    core_pipeline::{
+        FullscreenShader,
+        core_3d::CORE_3D_DEPTH_FORMAT,
+        schedule::{Core3d, Core3dSystems},
+    },
+    prelude::*,
+    render::{
+        Extract, ExtractSchedule, Render, RenderApp, RenderStartup, RenderSystems,
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_resource::{
+            BindGroup, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
+            BlendComponent, BlendFactor, BlendOperation, BlendState, CachedRenderPipelineId,
+            ColorTargetState, ColorWrites, CompareFunction, DepthStencilState, Extent3d,
+            FragmentState, LoadOp, MultisampleState, Operations, PipelineCache,
+            RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor,
+            ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
+            StoreOp, TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType,
+            TextureUsages, VertexState,
+            binding_types::{storage_buffer_read_only_sized, texture_2d, uniform_buffer},
+        },
+        renderer::{RenderContext, RenderDevice, ViewQuery},
+        storage::{GpuShaderBuffer, ShaderBuffer},
+        texture::{CachedTexture, TextureCache},
+        view::{
+            ExtractedView, Msaa, ViewDepthTexture, ViewTarget, ViewUniform, ViewUniformOffset,
+            ViewUniforms,
+        },
+    },
+};
+
+use crate::asset::ParticlesAsset;
+use crate::runtime::{EmitterEntity, EmitterRuntime, ParticleBufferHandle, Particles3d};
+
+const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_metaball.wgsl";
+
+/// Accumulated particle color in rgb and density in alpha.
+const DENSITY_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// Renders [`metaball`](crate::EmitterDrawPass::metaball) emitters as a liquid surface
+/// for the camera it's added to.
+///
+/// Particles are splatted into an offscreen density buffer, which is then thresholded
+/// into a surface with normals reconstructed from the density, and blended over the
+/// scene after the main pass. Splats are depth tested against opaque geometry, so
+/// liquid is hidden behind walls but doesn't write depth itself.
+///
+/// ```ignore
+/// commands.spawn((
+///     Camera3d::default(),
+///     MetaballSettings {
+///         threshold: 0.8,
+///         ..default()
+///     },
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect, ExtractComponent, ShaderType)]
+pub struct MetaballSettings {
+    /// Radius of each particle's splat, as a multiple of the particle's scale. Defaults
+    /// to `1.0`.
+    pub radius: f32,
+    /// Density at which the surface starts. Higher values make thinner, more separated
+    /// blobs. Defaults to `0.5`.
+    pub threshold: f32,
+    /// Width of the fade at the surface's edge, as a fraction of
+    /// [`threshold`](Self::threshold). Defaults to `0.2`.
+    pub softness: f32,
+    /// Strength of the specular highlight. Defaults to `0.6`.
+    pub specular: f32,
+}
+
+impl Default for MetaballSettings {
+    fn default() -> Self {
+        Self {
+            radius: 1.0,
+            threshold: 0.5,
+            softness: 0.2,
+            specular: 0.6,
+        }
+    }
+}
+
+pub(crate) struct MetaballPlugin;
+
+impl Plugin for MetaballPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<MetaballSettings>::default(),
+            UniformComponentPlugin::<MetaballSettings>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<ExtractedMetaballEmitters>()
+            .init_resource::<MetaballEmitterBindGroups>()
+            .init_resource::<SpecializedRenderPipelines<MetaballPipeline>>()
+            .add_systems(RenderStartup, init_metaball_pipeline)
+            .add_systems(ExtractSchedule, extract_metaball_emitters)
+            .add_systems(
+                Render,
+                (
+                    prepare_metaball_pipelines.in_set(RenderSystems::Prepare),
+                    prepare_metaball_textures.in_set(RenderSystems::PrepareResources),
+                    prepare_metaball_bind_groups.in_set(RenderSystems::PrepareBindGroups),
+                )
+                    .run_if(resource_exists::<MetaballPipeline>),
+            )
+            .add_systems(
+                Core3d,
+                run_metaball_pass
+                    .after(Core3dSystems::MainPass)
+                    .before(Core3dSystems::PostProcess),
+            );
+    }
+}
+
+struct ExtractedMetaballEmitter {
+    sorted_particles: Handle<ShaderBuffer>,
+    emitter_uniforms: Handle<ShaderBuffer>,
+    amount: u32,
+}
+
+#[derive(Resource, Default)]
+struct ExtractedMetaballEmitters(Vec<ExtractedMetaballEmitter>);
+
+fn extract_metaball_emitters(
+    mut extracted: ResMut<ExtractedMetaballEmitters>,
+    emitter_query: Extract<
+        Query<(
+            &EmitterEntity,
+            &EmitterRuntime,
+            &ParticleBufferHandle,
+            &InheritedVisibility,
+        )>,
+    >,
+    system_query: Extract<Query<&Particles3d>>,
+    assets: Extract<Res<Assets<ParticlesAsset>>>,
+) {
+    extracted.0.clear();
+    for (emitter_entity, runtime, buffer_handle, visibility) in emitter_query.iter() {
+        if !visibility.get() {
+            continue;
+        }
+        let Some(emitter) = system_query
+            .get(emitter_entity.parent_system)
+            .ok()
+            .and_then(|particle_system| assets.get(particle_system))
+            .and_then(|asset| asset.emitters.get(runtime.emitter_index))
+        else {
+            continue;
+        };
+        if !emitter.enabled || runtime.inactive || !emitter.draw_pass.draws_metaballs() {
+            continue;
+        }
+        extracted.0.push(ExtractedMetaballEmitter {
+            sorted_particles: buffer_handle.sorted_particles_buffer.clone(),
+            emitter_uniforms: buffer_handle.emitter_uniforms_buffer.clone(),
+            amount: buffer_handle.amount,
+        });
+    }
+}
+
+#[derive(Resource)]
+struct MetaballPipeline {
+    view_layout: BindGroupLayoutDescriptor,
+    emitter_layout: BindGroupLayoutDescriptor,
+    composite_layout: BindGroupLayoutDescriptor,
+    shader: Handle<Shader>,
+    fullscreen_shader: FullscreenShader,
+}
+
+fn init_metaball_pipeline(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    fullscreen_shader: Res<FullscreenShader>,
+) {
+    let view_layout = BindGroupLayoutDescriptor::new(
+        "ParticleMetaballViewBindGroup",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::VERTEX_FRAGMENT,
+            (
+                uniform_buffer::<ViewUniform>(true),
+                uniform_buffer::<MetaballSettings>(true),
+            ),
+        ),
+    );
+    let emitter_layout = BindGroupLayoutDescriptor::new(
+        "ParticleMetaballEmitterBindGroup",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::VERTEX,
+            (
+                storage_buffer_read_only_sized(false, None),
+                storage_buffer_read_only_sized(false, None),
+            ),
+        ),
+    );
+    // shares the settings binding with the splat pass, so both entry points can live in
+    // one shader module
+    let composite_layout = BindGroupLayoutDescriptor::new(
+        "ParticleMetaballCompositeBindGroup",
+        &BindGroupLayoutEntries::with_indices(
+            ShaderStages::FRAGMENT,
+            (
+                (1, uniform_buffer::<MetaballSettings>(true)),
+                (
+                    2,
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),
+            ),
+        ),
+    );
+
+    commands.insert_resource(MetaballPipeline {
+        view_layout,
+        emitter_layout,
+        composite_layout,
+        shader: asset_server.load(SHADER_ASSET_PATH),
+        fullscreen_shader: fullscreen_shader.clone(),
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MetaballPipelineKey {
+    Splat { samples: u32 },
+    Composite { hdr: bool },
+}
+
+impl SpecializedRenderPipeline for MetaballPipeline {
+    type Key = MetaballPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        match key {
+            MetaballPipelineKey::Splat { samples } => {
+                let additive = BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                };
+                RenderPipelineDescriptor {
+                    label: Some("particle_metaball_splat_pipeline".into()),
+                    layout: vec![self.view_layout.clone(), self.emitter_layout.clone()],
+                    vertex: VertexState {
+                        shader: self.shader.clone(),
+                        entry_point: Some("splat_vertex".into()),
+                        ..default()
+                    },
+                    fragment: Some(FragmentState {
+                        shader: self.shader.clone(),
+                        entry_point: Some("splat_fragment".into()),
+                        targets: vec![Some(ColorTargetState {
+                            format: DENSITY_FORMAT,
+                            blend: Some(BlendState {
+                                color: additive,
+                                alpha: additive,
+                            }),
+                            write_mask: ColorWrites::ALL,
+                        })],
+                        ..default()
+                    }),
+                    // test against the scene's depth without writing it (reverse z)
+                    depth_stencil: Some(DepthStencilState {
+                        format: CORE_3D_DEPTH_FORMAT,
+                        depth_write_enabled: false,
+                        depth_compare: CompareFunction::GreaterEqual,
+                        stencil: default(),
+                        bias: default(),
+                    }),
+                    multisample: MultisampleState {
+                        count: samples,
+                        ..default()
+                    },
+                    ..default()
+                }
+            }
+            MetaballPipelineKey::Composite { hdr } => RenderPipelineDescriptor {
+                label: Some("particle_metaball_composite_pipeline".into()),
+                layout: vec![self.composite_layout.clone()],
+                vertex: self.fullscreen_shader.to_vertex_state(),
+                fragment: Some(FragmentState {
+                    shader: self.shader.clone(),
+                    entry_point: Some("composite_fragment".into()),
+                    targets: vec![Some(ColorTargetState {
+                        format: if hdr {
+                            ViewTarget::TEXTURE_FORMAT_HDR
+                        } else {
+                            TextureFormat::bevy_default()
+                        },
+                        blend: Some(BlendState::ALPHA_BLENDING),
+                        write_mask: ColorWrites::ALL,
+                    })],
+                    ..default()
+                }),
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+struct ViewMetaballPipelines {
+    splat: CachedRenderPipelineId,
+    composite: CachedRenderPipelineId,
+}
+
+fn prepare_metaball_pipelines(
+    mut commands: Commands,
+    pipeline: Res<MetaballPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<MetaballPipeline>>,
+    views: Query<(Entity, &ExtractedView, &Msaa), With<MetaballSettings>>,
+) {
+    for (entity, view, msaa) in &views {
+        let splat = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            MetaballPipelineKey::Splat {
+                samples: msaa.samples(),
+            },
+        );
+        let composite = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            MetaballPipelineKey::Composite { hdr: view.hdr },
+        );
+        commands
+            .entity(entity)
+            .insert(ViewMetaballPipelines { splat, composite });
+    }
+}
+
+/// The density buffer splats are drawn into, matching the view's MSAA sample count, and
+/// the single-sampled texture it resolves to for the composite pass.
+#[derive(Component)]
+struct ViewMetaballTextures {
+    density: CachedTexture,
+    resolved: Option<CachedTexture>,
+}
+
+impl ViewMetaballTextures {
+    fn resolved(&self) -> &CachedTexture {
+        self.resolved.as_ref().unwrap_or(&self.density)
+    }
+}
+
+fn prepare_metaball_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(Entity, &ExtractedCamera, &Msaa), With<MetaballSettings>>,
+) {
+    for (entity, camera, msaa) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        let mut descriptor = TextureDescriptor {
+            label: Some("particle_metaball_density_texture"),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: msaa.samples(),
+            dimension: TextureDimension::D2,
+            format: DENSITY_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        let density = texture_cache.get(&render_device, descriptor.clone());
+        let resolved = (msaa.samples() > 1).then(|| {
+            descriptor.label = Some("particle_metaball_resolved_density_texture");
+            descriptor.sample_count = 1;
+            texture_cache.get(&render_device, descriptor)
+        });
+        commands
+            .entity(entity)
+            .insert(ViewMetaballTextures { density, resolved });
+    }
+}
+
+#[derive(Component)]
+struct ViewMetaballBindGroups {
+    view: BindGroup,
+    composite: BindGroup,
+}
+
+struct MetaballEmitterDraw {
+    bind_group: BindGroup,
+    vertex_count: u32,
+}
+
+#[derive(Resource, Default)]
+struct MetaballEmitterBindGroups(Vec<MetaballEmitterDraw>);
+
+fn prepare_metaball_bind_groups(
+    mut commands: Commands,
+    pipeline: Res<MetaballPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    view_uniforms: Res<ViewUniforms>,
+    settings_uniforms: Res<ComponentUniforms<MetaballSettings>>,
+    gpu_storage_buffers: Res<RenderAssets<GpuShaderBuffer>>,
+    extracted: Res<ExtractedMetaballEmitters>,
+    mut emitter_bind_groups: ResMut<MetaballEmitterBindGroups>,
+    views: Query<(Entity, &ViewMetaballTextures)>,
+) {
+    emitter_bind_groups.0.clear();
+
+    let (Some(view_binding), Some(settings_binding)) = (
+        view_uniforms.uniforms.binding(),
+        settings_uniforms.uniforms().binding(),
+    ) else {
+        return;
+    };
+
+    let emitter_layout = pipeline_cache.get_bind_group_layout(&pipeline.emitter_layout);
+    for emitter in &extracted.0 {
+        let (Some(particles), Some(uniforms)) = (
+            gpu_storage_buffers.get(&emitter.sorted_particles),
+            gpu_storage_buffers.get(&emitter.emitter_uniforms),
+        ) else {
+            continue;
+        };
+        emitter_bind_groups.0.push(MetaballEmitterDraw {
+            bind_group: render_device.create_bind_group(
+                Some("particle_metaball_emitter_bind_group"),
+                &emitter_layout,
+                &BindGroupEntries::sequential((
+                    particles.buffer.as_entire_binding(),
+                    uniforms.buffer.as_entire_binding(),
+                )),
+            ),
+            // one quad per particle, trail segments are skipped by the shader
+            vertex_count: emitter.amount * 6,
+        });
+    }
+
+    let view_layout = pipeline_cache.get_bind_group_layout(&pipeline.view_layout);
+    let composite_layout = pipeline_cache.get_bind_group_layout(&pipeline.composite_layout);
+    for (entity, textures) in &views {
+        let view = render_device.create_bind_group(
+            Some("particle_metaball_view_bind_group"),
+            &view_layout,
+            &BindGroupEntries::sequential((view_binding.clone(), settings_binding.clone())),
+        );
+        let composite = render_device.create_bind_group(
+            Some("particle_metaball_composite_bind_group"),
+            &composite_layout,
+            &BindGroupEntries::with_indices((
+                (1, settings_binding.clone()),
+                (2, &textures.resolved().default_view),
+            )),
+        );
+        commands
+            .entity(entity)
+            .insert(ViewMetaballBindGroups { view, composite });
+    }
+}
+
+fn run_metaball_pass(
+    view: ViewQuery<(
+        &ViewTarget,
+        &ViewDepthTexture,
+        &ViewUniformOffset,
+        &DynamicUniformIndex<MetaballSettings>,
+        &ViewMetaballPipelines,
+        &ViewMetaballTextures,
+        &ViewMetaballBindGroups,
+    )>,
+    pipeline_cache: Res<PipelineCache>,
+    emitters: Res<MetaballEmitterBindGroups>,
+    mut ctx: RenderContext,
+) {
+    let (target, depth, view_offset, settings_index, pipelines, textures, bind_groups) =
+        view.into_inner();
+
+    if emitters.0.is_empty() {
+        return;
+    }
+
+    let (Some(splat_pipeline), Some(composite_pipeline)) = (
+        pipeline_cache.get_render_pipeline(pipelines.splat),
+        pipeline_cache.get_render_pipeline(pipelines.composite),
+    ) else {
+        return;
+    };
+
+    {
+        let mut pass = ctx
+            .command_encoder()
+            .begin_render_pass(&RenderPassDescriptor {
+                label: Some("particle_metaball_splat_pass"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &textures.density.default_view,
+                    depth_slice: None,
+                    resolve_target: textures
+                        .resolved
+                        .as_ref()
+                        .map(|resolved| &*resolved.default_view),
+                    ops: Operations {
+                        load: LoadOp::Clear(default()),
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+                ..default()
+            });
+        pass.set_pipeline(splat_pipeline);
+        pass.set_bind_group(
+            0,
+            &bind_groups.view,
+            &[view_offset.offset, settings_index.index()],
+        );
+        for emitter in &emitters.0 {
+            pass.set_bind_group(1, &emitter.bind_group, &[]);
+            pass.draw(0..emitter.vertex_count, 0..1);
+        }
+    }
+
+    let mut pass = ctx
+        .command_encoder()
+        .begin_render_pass(&RenderPassDescriptor {
+            label: Some("particle_metaball_composite_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target.main_texture_view(),
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            ..default()
+        });
+    pass.set_pipeline(composite_pipeline);
+    pass.set_bind_group(0, &bind_groups.composite, &[settings_index.index()]);
+    pass.draw(0..3, 0..1);
+}
@@ -0,0 +1,447 @@
+use bevy::{
+    core_pipeline::{
+        core_3d::CORE_3D_DEPTH_FORMAT,
+        schedule::{Core3d, Core3dSystems},
+    },
+    prelude::*,
+    render::{
+        Extract, ExtractSchedule, Render, RenderApp, RenderStartup, RenderSystems,
+        camera::ExtractedCamera,
+        extract_component::{
+            ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+            UniformComponentPlugin,
+        },
+        render_asset::RenderAssets,
+        render_resource::{
+            BindGroup, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries,
+            CachedRenderPipelineId, ColorTargetState, ColorWrites, CompareFunction,
+            DepthStencilState, DynamicUniformBuffer, Extent3d, FragmentState, LoadOp, Operations,
+            PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+            RenderPipelineDescriptor, ShaderStages, ShaderType, SpecializedRenderPipeline,
+            SpecializedRenderPipelines, StoreOp, TextureDescriptor, TextureDimension,
+            TextureFormat, TextureUsages, VertexState,
+            binding_types::{storage_buffer_read_only_sized, uniform_buffer},
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue, ViewQuery},
+        storage::{GpuShaderBuffer, ShaderBuffer},
+        texture::{CachedTexture, TextureCache},
+        view::{Msaa, ViewDepthTexture, ViewUniform, ViewUniformOffset, ViewUniforms},
+    },
+};
+
+use crate::asset::ParticlesAsset;
+use crate::runtime::{EmitterEntity, EmitterRuntime, ParticleBufferHandle, Particles3d};
+
+const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_mask.wgsl";
+
+/// Format of [`ParticleMaskTexture`]: one unsigned mask value per pixel.
+pub const PARTICLE_MASK_FORMAT: TextureFormat = TextureFormat::R32Uint;
+
+/// Renders a [`ParticleMaskTexture`] for the camera it's added to, so post-processing
+/// effects like outlines, selective bloom or damage flashes can target particles.
+///
+/// Emitters with a [`post_process_mask`](crate::EmitterDrawPass::post_process_mask) write
+/// their mask value wherever one of their particles covers the screen, after the main
+/// pass and before post-processing. Each particle covers a camera-facing disc sized by
+/// its scale, rather than the exact shape of its mesh and texture.
+///
+/// ```ignore
+/// commands.spawn((Camera3d::default(), Msaa::Off, ParticleMaskTarget::default()));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect, ExtractComponent, ShaderType)]
+pub struct ParticleMaskTarget {
+    /// Radius of each particle's disc, as a multiple of the particle's scale. Defaults
+    /// to `0.5`, which matches a unit quad.
+    pub radius: f32,
+}
+
+impl Default for ParticleMaskTarget {
+    fn default() -> Self {
+        Self { radius: 0.5 }
+    }
+}
+
+/// The particle mask rendered for a view with a [`ParticleMaskTarget`].
+///
+/// This component lives on the view entity in the render world. Read it from a render
+/// system ordered after [`Core3dSystems::MainPass`] to bind the mask in a custom
+/// post-process pass. The texture is single-sampled, matches the view's physical size
+/// and uses [`PARTICLE_MASK_FORMAT`], so bind it as `texture_2d<u32>` and read it with
+/// `textureLoad`:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var particle_mask: texture_2d<u32>;
+///
+/// let mask = textureLoad(particle_mask, vec2<i32>(position.xy), 0).r;
+/// ```
+///
+/// Pixels without particles are `0`. Where particles overlap, the last one drawn wins.
+/// Particles are occluded by the scene's depth, except on cameras with MSAA enabled,
+/// since the mask can't share a multisampled depth buffer.
+#[derive(Component)]
+pub struct ParticleMaskTexture {
+    /// The mask texture.
+    pub texture: CachedTexture,
+}
+
+pub(crate) struct ParticleMaskPlugin;
+
+impl Plugin for ParticleMaskPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<ParticleMaskTarget>::default(),
+            UniformComponentPlugin::<ParticleMaskTarget>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<ExtractedMaskEmitters>()
+            .init_resource::<MaskEmitterBindGroups>()
+            .init_resource::<SpecializedRenderPipelines<ParticleMaskPipeline>>()
+            .add_systems(RenderStartup, init_particle_mask_pipeline)
+            .add_systems(ExtractSchedule, extract_mask_emitters)
+            .add_systems(
+                Render,
+                (
+                    prepare_particle_mask_pipelines.in_set(RenderSystems::Prepare),
+                    prepare_particle_mask_textures.in_set(RenderSystems::PrepareResources),
+                    prepare_particle_mask_bind_groups.in_set(RenderSystems::PrepareBindGroups),
+                )
+                    .run_if(resource_exists::<ParticleMaskPipeline>),
+            )
+            .add_systems(
+                Core3d,
+                run_particle_mask_pass
+                    .after(Core3dSystems::MainPass)
+                    .before(Core3dSystems::PostProcess),
+            );
+    }
+}
+
+struct ExtractedMaskEmitter {
+    sorted_particles: Handle<ShaderBuffer>,
+    emitter_uniforms: Handle<ShaderBuffer>,
+    amount: u32,
+    value: u32,
+}
+
+#[derive(Resource, Default)]
+struct ExtractedMaskEmitters(Vec<ExtractedMaskEmitter>);
+
+fn extract_mask_emitters(
+    mut extracted: ResMut<ExtractedMaskEmitters>,
+    emitter_query: Extract<
+        Query<(
+            &EmitterEntity,
+            &EmitterRuntime,
+            &ParticleBufferHandle,
+            &InheritedVisibility,
+        )>,
+    >,
+    system_query: Extract<Query<&Particles3d>>,
+    assets: Extract<Res<Assets<ParticlesAsset>>>,
+) {
+    extracted.0.clear();
+    for (emitter_entity, runtime, buffer_handle, visibility) in emitter_query.iter() {
+        if !visibility.get() || runtime.inactive {
+            continue;
+        }
+        let Some(emitter) = system_query
+            .get(emitter_entity.parent_system)
+            .ok()
+            .and_then(|particle_system| assets.get(particle_system))
+            .and_then(|asset| asset.emitters.get(runtime.emitter_index))
+        else {
+            continue;
+        };
+        if !emitter.enabled {
+            continue;
+        }
+        let Some(value) = emitter.draw_pass.post_process_mask else {
+            continue;
+        };
+        extracted.0.push(ExtractedMaskEmitter {
+            sorted_particles: buffer_handle.sorted_particles_buffer.clone(),
+            emitter_uniforms: buffer_handle.emitter_uniforms_buffer.clone(),
+            amount: buffer_handle.amount,
+            value,
+        });
+    }
+}
+
+#[derive(Clone, Copy, Default, ShaderType)]
+struct MaskEmitterUniform {
+    value: u32,
+}
+
+#[derive(Resource)]
+struct ParticleMaskPipeline {
+    view_layout: BindGroupLayoutDescriptor,
+    emitter_layout: BindGroupLayoutDescriptor,
+    shader: Handle<Shader>,
+}
+
+fn init_particle_mask_pipeline(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let view_layout = BindGroupLayoutDescriptor::new(
+        "ParticleMaskViewBindGroup",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::VERTEX,
+            (
+                uniform_buffer::<ViewUniform>(true),
+                uniform_buffer::<ParticleMaskTarget>(true),
+            ),
+        ),
+    );
+    let emitter_layout = BindGroupLayoutDescriptor::new(
+        "ParticleMaskEmitterBindGroup",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::VERTEX_FRAGMENT,
+            (
+                storage_buffer_read_only_sized(false, None),
+                storage_buffer_read_only_sized(false, None),
+                uniform_buffer::<MaskEmitterUniform>(true),
+            ),
+        ),
+    );
+
+    commands.insert_resource(ParticleMaskPipeline {
+        view_layout,
+        emitter_layout,
+        shader: asset_server.load(SHADER_ASSET_PATH),
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ParticleMaskPipelineKey {
+    depth_test: bool,
+}
+
+impl SpecializedRenderPipeline for ParticleMaskPipeline {
+    type Key = ParticleMaskPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("particle_mask_pipeline".into()),
+            layout: vec![self.view_layout.clone(), self.emitter_layout.clone()],
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                entry_point: Some("mask_vertex".into()),
+                ..default()
+            },
+            fragment: Some(FragmentState {
+                shader: self.shader.clone(),
+                entry_point: Some("mask_fragment".into()),
+                targets: vec![Some(ColorTargetState {
+                    format: PARTICLE_MASK_FORMAT,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+                ..default()
+            }),
+            // test against the scene's depth without writing it (reverse z)
+            depth_stencil: key.depth_test.then(|| DepthStencilState {
+                format: CORE_3D_DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: default(),
+                bias: default(),
+            }),
+            ..default()
+        }
+    }
+}
+
+#[derive(Component)]
+struct ViewParticleMaskPipeline(CachedRenderPipelineId);
+
+fn prepare_particle_mask_pipelines(
+    mut commands: Commands,
+    pipeline: Res<ParticleMaskPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<ParticleMaskPipeline>>,
+    views: Query<(Entity, &Msaa), With<ParticleMaskTarget>>,
+) {
+    for (entity, msaa) in &views {
+        let id = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            ParticleMaskPipelineKey {
+                depth_test: msaa.samples() == 1,
+            },
+        );
+        commands.entity(entity).insert(ViewParticleMaskPipeline(id));
+    }
+}
+
+fn prepare_particle_mask_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(Entity, &ExtractedCamera), With<ParticleMaskTarget>>,
+) {
+    for (entity, camera) in &views {
+        let Some(size) = camera.physical_target_size else {
+            continue;
+        };
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("particle_mask_texture"),
+                size: Extent3d {
+                    width: size.x,
+                    height: size.y,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: PARTICLE_MASK_FORMAT,
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        commands
+            .entity(entity)
+            .insert(ParticleMaskTexture { texture });
+    }
+}
+
+#[derive(Component)]
+struct ViewParticleMaskBindGroup(BindGroup);
+
+struct MaskEmitterDraw {
+    bind_group: BindGroup,
+    dynamic_offset: u32,
+    vertex_count: u32,
+}
+
+#[derive(Resource, Default)]
+struct MaskEmitterBindGroups {
+    uniforms: DynamicUniformBuffer<MaskEmitterUniform>,
+    draws: Vec<MaskEmitterDraw>,
+}
+
+fn prepare_particle_mask_bind_groups(
+    mut commands: Commands,
+    pipeline: Res<ParticleMaskPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    view_uniforms: Res<ViewUniforms>,
+    target_uniforms: Res<ComponentUniforms<ParticleMaskTarget>>,
+    gpu_storage_buffers: Res<RenderAssets<GpuShaderBuffer>>,
+    extracted: Res<ExtractedMaskEmitters>,
+    mut emitter_bind_groups: ResMut<MaskEmitterBindGroups>,
+    views: Query<Entity, With<ParticleMaskTexture>>,
+) {
+    let MaskEmitterBindGroups { uniforms, draws } = &mut *emitter_bind_groups;
+    uniforms.clear();
+    draws.clear();
+
+    let mut emitter_buffers = Vec::new();
+    for emitter in &extracted.0 {
+        let (Some(particles), Some(emitter_uniforms)) = (
+            gpu_storage_buffers.get(&emitter.sorted_particles),
+            gpu_storage_buffers.get(&emitter.emitter_uniforms),
+        ) else {
+            continue;
+        };
+        let dynamic_offset = uniforms.push(&MaskEmitterUniform {
+            value: emitter.value,
+        });
+        emitter_buffers.push((particles, emitter_uniforms, dynamic_offset, emitter.amount));
+    }
+    uniforms.write_buffer(&render_device, &render_queue);
+
+    let Some(uniform_binding) = uniforms.binding() else {
+        return;
+    };
+    let emitter_layout = pipeline_cache.get_bind_group_layout(&pipeline.emitter_layout);
+    for (particles, emitter_uniforms, dynamic_offset, amount) in emitter_buffers {
+        draws.push(MaskEmitterDraw {
+            bind_group: render_device.create_bind_group(
+                Some("particle_mask_emitter_bind_group"),
+                &emitter_layout,
+                &BindGroupEntries::sequential((
+                    particles.buffer.as_entire_binding(),
+                    emitter_uniforms.buffer.as_entire_binding(),
+                    uniform_binding.clone(),
+                )),
+            ),
+            dynamic_offset,
+            // one quad per particle, trail segments are skipped by the shader
+            vertex_count: amount * 6,
+        });
+    }
+
+    let (Some(view_binding), Some(target_binding)) = (
+        view_uniforms.uniforms.binding(),
+        target_uniforms.uniforms().binding(),
+    ) else {
+        return;
+    };
+    let view_layout = pipeline_cache.get_bind_group_layout(&pipeline.view_layout);
+    for entity in &views {
+        let bind_group = render_device.create_bind_group(
+            Some("particle_mask_view_bind_group"),
+            &view_layout,
+            &BindGroupEntries::sequential((view_binding.clone(), target_binding.clone())),
+        );
+        commands
+            .entity(entity)
+            .insert(ViewParticleMaskBindGroup(bind_group));
+    }
+}
+
+fn run_particle_mask_pass(
+    view: ViewQuery<(
+        &ViewDepthTexture,
+        &Msaa,
+        &ViewUniformOffset,
+        &DynamicUniformIndex<ParticleMaskTarget>,
+        &ViewParticleMaskPipeline,
+        &ParticleMaskTexture,
+        &ViewParticleMaskBindGroup,
+    )>,
+    pipeline_cache: Res<PipelineCache>,
+    emitters: Res<MaskEmitterBindGroups>,
+    mut ctx: RenderContext,
+) {
+    let (depth, msaa, view_offset, target_index, pipeline, mask, bind_group) = view.into_inner();
+
+    // the mask is always cleared, so effects never read stale particles
+    let mut pass = ctx
+        .command_encoder()
+        .begin_render_pass(&RenderPassDescriptor {
+            label: Some("particle_mask_pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &mask.texture.default_view,
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(default()),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: (msaa.samples() == 1)
+                .then(|| depth.get_attachment(StoreOp::Store)),
+            ..default()
+        });
+
+    let Some(render_pipeline) = pipeline_cache.get_render_pipeline(pipeline.0) else {
+        return;
+    };
+    pass.set_pipeline(render_pipeline);
+    pass.set_bind_group(
+        0,
+        &bind_group.0,
+        &[view_offset.offset, target_index.index()],
+    );
+    for emitter in &emitters.draws {
+        pass.set_bind_group(1, &emitter.bind_group, &[emitter.dynamic_offset]);
+        pass.draw(0..emitter.vertex_count, 0..1);
+    }
+}
@@ -1,22 +1,34 @@
-pub use crate::SprinklesPlugin;
+pub use crate::{SprinklesPlugin, SprinklesRenderSystems, SprinklesSettings};
 
 pub use crate::asset::{
-    AnimatedVelocity, ColliderData, Curve, CurveEasing, CurveMode, CurvePoint, CurveTexture,
-    DrawOrder, DrawPassMaterial, EmissionShape, EmitterAccelerations, EmitterCollision,
-    EmitterCollisionMode, EmitterColors, EmitterData, EmitterDrawPass, EmitterEmission,
-    EmitterScale, EmitterTime, EmitterTrail, EmitterTurbulence, EmitterVelocities,
-    Gradient as ParticleGradient, GradientInterpolation, GradientStop, InitialTransform,
-    ParticleFlags, ParticleMesh, ParticlesAsset, ParticlesAuthors, ParticlesColliderShape3D,
-    ParticlesDimension, QuadOrientation, Range as ParticleRange, RibbonTrailShape,
+    AlphaFadeMode, AnimatedVelocity, ColliderData, Curve, CurveEasing, CurveMode, CurvePoint,
+    CurveTexture, CurveWrap, DrawOrder, DrawPassMaterial, DrawPassVisibility, EmissionMaskChannel,
+    EmissionOrientation, EmissionShape, EmitterAccelerations, EmitterCollision,
+    EmitterCollisionMode, EmitterColors, EmitterCycleRandomness, EmitterData, EmitterDrawPass,
+    EmitterEmission, EmitterExpressions, EmitterScale, EmitterStopAction, EmitterTarget,
+    EmitterTextureSheet, EmitterTime, EmitterTrail, EmitterTurbulence, EmitterVelocities,
+    EmitterWave, Expression, ExpressionVec3, Gradient as ParticleGradient, GradientInterpolation,
+    GradientStop, InitialColorSampling, InitialTransform, ParticleBlendMode, ParticleFlags,
+    ParticleMesh, ParticlesAsset, ParticlesAuthors, ParticlesColliderShape3D, ParticlesDimension,
+    PointGravity, QuadOrientation, Range as ParticleRange, RibbonTrailShape, RingEmissionOrder,
     SerializableAlphaMode, SerializableFace, SolidOrGradientColor, SprinklesEditorData,
-    StandardParticleMaterial, SubEmitterConfig, SubEmitterMode, TransformAlign, VisibilityAabb,
+    StandardParticleMaterial, SubEmitterAmountScale, SubEmitterConfig, SubEmitterKeepVelocity,
+    SubEmitterMode, TargetShape, TextureSheetRandomMode, TransformAlign, VisibilityAabb,
 };
+#[cfg(feature = "bevy_audio")]
+pub use crate::audio::{AudioBands, AudioReactive};
+#[cfg(feature = "metaballs")]
+pub use crate::metaball::MetaballSettings;
 #[cfg(feature = "preset-textures")]
 pub use crate::textures::preset::PresetTexture;
 pub use crate::textures::preset::TextureRef;
 
+pub use crate::post_process_mask::ParticleMaskTarget;
+
 pub use crate::runtime::{
-    ColliderEntity, EditorMode, EmitterEntity, EmitterRuntime, Finished, ParticleMaterial,
-    ParticleMaterialHandle, ParticleSystemRuntime, Particles2d, Particles3d, ParticlesCollider3D,
-    SubEmitterBufferHandle,
+    ColliderAffects, ColliderEntity, DisabledEmitter, EditorMode, EmitterEntity, EmitterRuntime,
+    EmitterStopped, Finished, ParticleAccumulationBuffer, ParticleAreaEmitter, ParticleMaterial,
+    ParticleMaterialHandle, ParticleMaterialOverrides, ParticleSeed, ParticleSpawnHook,
+    ParticleSystemChildren, ParticleSystemRuntime, Particles2d, Particles3d, ParticlesCollider3D,
+    ParticlesCollisionLayers, SpawnParams, SubEmitterBufferHandle, TurbulenceVolume,
 };
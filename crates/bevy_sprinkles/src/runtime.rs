@@ -7,8 +7,12 @@ use bevy::render::render_resource::{Buffer, ShaderType};
 use bevy::render::storage::ShaderBuffer;
 use bytemuck::{Pod, Zeroable};
 
-use crate::asset::{DrawPassMaterial, ParticleMesh, ParticlesAsset, ParticlesColliderShape3D};
+use crate::asset::{
+    DrawPassMaterial, EmitterStopAction, ParticleMesh, ParticlesAsset, ParticlesColliderShape3D,
+};
 use crate::material::ParticleMaterialExtension;
+use crate::timing;
+pub use crate::timing::{compute_phase, is_past_delay};
 
 #[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
 #[repr(C)]
@@ -70,6 +74,8 @@ impl AsAssetId for Particles3d {
 }
 
 /// GPU-side per-particle data, packed into `[f32; 4]` vectors for shader alignment.
+///
+/// Mirrors `Particle` in the `sprinkles::particles` WGSL module.
 #[derive(Clone, Copy, Default, Pod, Zeroable, ShaderType)]
 #[repr(C)]
 pub struct ParticleData {
@@ -81,9 +87,11 @@ pub struct ParticleData {
     pub color: [f32; 4],
     /// Particle age, phase, seed, and flags.
     pub custom: [f32; 4],
-    /// Particle direction for velocity-aligned transforms and angle.
+    /// Particle direction for velocity-aligned transforms, and the random factor picked for
+    /// its initial scale.
     pub alignment_dir: [f32; 4],
-    /// Reference "up" direction for parallel-transported velocity alignment.
+    /// Reference "up" direction for parallel-transported velocity alignment, and the random
+    /// factor picked for its initial angle.
     pub ref_up: [f32; 4],
     /// Per-axis rotation angles in radians (x, y, z).
     pub angles: [f32; 4],
@@ -107,6 +115,20 @@ impl ParticleData {
 #[derive(EntityEvent)]
 pub struct Finished(pub Entity);
 
+/// Triggered on an emitter entity when its
+/// [`stop_action`](crate::EmitterTime::stop_action) is
+/// [`Callback`](crate::asset::EmitterStopAction::Callback) and it has finished.
+///
+/// An emitter finishes once its one-shot emission has completed and all of its
+/// particles have died.
+#[derive(EntityEvent)]
+pub struct EmitterStopped(pub Entity);
+
+/// Added to an emitter hidden by [`EmitterStopAction::Disable`], holding the
+/// [`Visibility`] it had before so it can be shown again once it emits.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DisabledEmitter(pub Visibility);
+
 /// Marker component indicating this particle system is running inside an editor.
 #[derive(Component)]
 pub struct EditorMode;
@@ -115,11 +137,25 @@ pub struct EditorMode;
 #[derive(Component)]
 pub struct ParticleSystemRuntime {
     /// Whether the simulation is paused. Defaults to `false`.
+    ///
+    /// Paused systems skip their simulation and sorting passes entirely, so they cost no
+    /// GPU time beyond drawing. They only sort again if the camera or the system moves.
     pub paused: bool,
+    /// Whether hidden emitters also stop simulating. Defaults to `false`.
+    ///
+    /// Emitters are hidden along with the system entity, or on their own by setting the
+    /// [`Visibility`] of an emitter child to [`Hidden`](Visibility::Hidden). Hidden emitters
+    /// are never drawn, but keep simulating so they are up to date once shown again. When
+    /// this is `true`, they freeze instead and resume where they left off.
+    pub pause_when_hidden: bool,
     /// Whether one-shot emitters should loop continuously. Defaults to `true`.
     pub force_loop: bool,
     /// Global random seed for all emitters in this system.
+    ///
+    /// Only used to derive emitter seeds when the system was created
+    /// [`with_seed`](Self::with_seed).
     pub global_seed: u32,
+    pub(crate) seeded: bool,
     pub(crate) finished: bool,
 }
 
@@ -127,14 +163,37 @@ impl Default for ParticleSystemRuntime {
     fn default() -> Self {
         Self {
             paused: false,
+            pause_when_hidden: false,
             force_loop: true,
             global_seed: rand_seed(),
+            seeded: false,
             finished: false,
         }
     }
 }
 
 impl ParticleSystemRuntime {
+    /// Creates a runtime whose emitters all derive their seeds from `seed`.
+    ///
+    /// Insert it alongside [`Particles3d`] to replay the whole effect deterministically.
+    /// Emitters with a [`fixed_seed`](crate::EmitterTime::fixed_seed) keep using it.
+    /// Restarting an emitter picks a new random seed unless it is given
+    /// [`emitter_seed`](Self::emitter_seed) again.
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            global_seed: seed,
+            seeded: true,
+            ..default()
+        }
+    }
+
+    /// Returns the seed derived for the emitter at `emitter_index`, or `None` if this
+    /// system was not created [`with_seed`](Self::with_seed).
+    pub fn emitter_seed(&self, emitter_index: usize) -> Option<u32> {
+        self.seeded
+            .then(|| derive_emitter_seed(self.global_seed, emitter_index))
+    }
+
     /// Pauses the particle simulation.
     pub fn pause(&mut self) {
         self.paused = true;
@@ -149,6 +208,13 @@ impl ParticleSystemRuntime {
     pub fn toggle(&mut self) {
         self.paused = !self.paused;
     }
+
+    /// Returns `true` if an emitter with the given visibility should skip its simulation,
+    /// either because the system is paused or because it is hidden and
+    /// [`pause_when_hidden`](Self::pause_when_hidden) is set.
+    pub fn is_paused_for(&self, visibility: &InheritedVisibility) -> bool {
+        self.paused || (self.pause_when_hidden && !visibility.get())
+    }
 }
 
 /// A single simulation step to be processed by the compute shader.
@@ -191,6 +257,8 @@ pub struct EmitterRuntime {
     pub inactive_time: f32,
     /// Whether to clear all particles on the next frame.
     pub clear_requested: bool,
+    /// Whether the stop action has run since the emitter last started emitting.
+    pub(crate) stop_action_done: bool,
     /// Index of this emitter within the parent [`ParticlesAsset::emitters`].
     pub emitter_index: usize,
     /// Pending simulation steps to be dispatched to the GPU.
@@ -199,6 +267,14 @@ pub struct EmitterRuntime {
     pub trail_history_write_index: u32,
     /// Ring buffer size per particle for trail history.
     pub trail_history_frames: u32,
+    /// Fraction of [`particles_amount`](crate::EmitterEmission::particles_amount) that
+    /// spawns each cycle, from `0.0` to `1.0`. Particles skipped this way leave gaps in
+    /// the spawn schedule instead of shortening the cycle. Defaults to `1.0`.
+    pub amount_ratio: f32,
+    /// Multiplier applied to the initial velocity of newly spawned particles.
+    ///
+    /// Defaults to `1.0`.
+    pub speed_scale: f32,
 }
 
 impl EmitterRuntime {
@@ -219,10 +295,13 @@ impl EmitterRuntime {
             inactive: false,
             inactive_time: 0.0,
             clear_requested: false,
+            stop_action_done: false,
             emitter_index,
             simulation_steps: Vec::new(),
             trail_history_write_index: 0,
             trail_history_frames: 0,
+            amount_ratio: 1.0,
+            speed_scale: 1.0,
         }
     }
 
@@ -241,6 +320,37 @@ impl EmitterRuntime {
         is_past_delay(self.system_time, time)
     }
 
+    /// Returns `true` while the emitter is waiting out its
+    /// [`delay`](crate::EmitterTime::delay) at the start of the current cycle.
+    pub fn is_in_delay(&self, time: &crate::asset::EmitterTime) -> bool {
+        !is_past_delay(self.system_time, time)
+    }
+
+    /// Returns the number of emission cycles completed since the emitter last started.
+    pub fn cycle_count(&self) -> u32 {
+        self.cycle
+    }
+
+    /// Returns how many particles have spawned so far in the current emission cycle.
+    ///
+    /// Follows the same spawn schedule as the GPU, including
+    /// [`explosiveness`](crate::EmitterTime::explosiveness) and
+    /// [`spawn_time_randomness`](crate::EmitterTime::spawn_time_randomness), so it matches
+    /// what is on screen. Checks every particle, so it costs `O(particles_amount)`.
+    pub fn particles_spawned_this_cycle(&self, emitter: &crate::asset::EmitterData) -> u32 {
+        let amount = emitter.emission.particles_amount;
+        if self.one_shot_completed {
+            return amount;
+        }
+        if self.is_in_delay(&emitter.time) {
+            return 0;
+        }
+        let phase = self.system_phase(&emitter.time);
+        (0..amount)
+            .filter(|&index| timing::spawn_phase(index, amount, &emitter.time) < phase)
+            .count() as u32
+    }
+
     /// Returns `true` if the emitter is actively spawning particles.
     pub fn is_emitting(&self) -> bool {
         self.emitting
@@ -252,6 +362,7 @@ impl EmitterRuntime {
         if emitting {
             self.inactive = false;
             self.inactive_time = 0.0;
+            self.stop_action_done = false;
         }
     }
 
@@ -296,30 +407,86 @@ impl EmitterRuntime {
     }
 }
 
-/// Computes the emission phase (0.0–1.0) for the given time and emitter timing config.
-pub fn compute_phase(time: f32, emitter_time: &crate::asset::EmitterTime) -> f32 {
-    if emitter_time.lifetime <= 0.0 {
-        return 0.0;
+/// Spawn values a [`ParticleSpawnHook`] can override before they are sent to the GPU.
+#[derive(Debug, Clone)]
+pub struct SpawnParams {
+    /// Offset added to every spawn position, in the emitter's local space. Starts at
+    /// the emitter's [`offset`](crate::EmitterEmission::offset).
+    pub emission_offset: Vec3,
+    /// RGBA color new particles start with. Starts at the emitter's solid initial color,
+    /// and is ignored when the emitter uses an initial color gradient.
+    pub initial_color: [f32; 4],
+    emitter_index: usize,
+    cycle: u32,
+    system_phase: f32,
+    transform: GlobalTransform,
+}
+
+impl SpawnParams {
+    pub(crate) fn new(
+        emitter_index: usize,
+        cycle: u32,
+        system_phase: f32,
+        transform: GlobalTransform,
+        emission_offset: Vec3,
+        initial_color: [f32; 4],
+    ) -> Self {
+        Self {
+            emission_offset,
+            initial_color,
+            emitter_index,
+            cycle,
+            system_phase,
+            transform,
+        }
     }
-    let total_duration = emitter_time.total_duration();
-    if total_duration <= 0.0 {
-        return 0.0;
+
+    /// Index of the emitter within the parent [`ParticlesAsset::emitters`].
+    pub fn emitter_index(&self) -> usize {
+        self.emitter_index
     }
-    let time_in_cycle = time % total_duration;
-    if time_in_cycle < emitter_time.delay {
-        return 0.0;
+
+    /// The emission cycle being simulated.
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    /// The emitter's phase within the cycle at the end of this step, from `0.0` to `1.0`.
+    pub fn system_phase(&self) -> f32 {
+        self.system_phase
+    }
+
+    /// The emitter's world transform.
+    pub fn transform(&self) -> &GlobalTransform {
+        &self.transform
     }
-    (time_in_cycle - emitter_time.delay) / emitter_time.lifetime
 }
 
-/// Returns `true` if the given time is past the emitter's initial delay within the current cycle.
-pub fn is_past_delay(time: f32, emitter_time: &crate::asset::EmitterTime) -> bool {
-    let total_duration = emitter_time.total_duration();
-    if total_duration <= 0.0 {
-        return true;
+/// A CPU callback that adjusts spawn values each simulation step.
+///
+/// Add it to a [`Particles3d`] entity to affect every emitter in the system, or to an
+/// emitter entity to affect only that emitter. When both are present, the system hook
+/// runs first. Hooks run in the render world's extract schedule, so they should be
+/// cheap and must not rely on other ECS state.
+///
+/// ```ignore
+/// commands.entity(emitter).insert(ParticleSpawnHook::new(|params| {
+///     let ground = terrain_height(params.transform().translation());
+///     params.emission_offset.y = ground - params.transform().translation().y;
+/// }));
+/// ```
+#[derive(Component)]
+pub struct ParticleSpawnHook(Box<dyn Fn(&mut SpawnParams) + Send + Sync>);
+
+impl ParticleSpawnHook {
+    /// Creates a hook from the given callback.
+    pub fn new(hook: impl Fn(&mut SpawnParams) + Send + Sync + 'static) -> Self {
+        Self(Box::new(hook))
+    }
+
+    pub(crate) fn run(&self, params: &mut SpawnParams) {
+        (self.0)(params);
     }
-    let time_in_cycle = time % total_duration;
-    time_in_cycle >= emitter_time.delay
 }
 
 /// Marker component linking an emitter entity back to its parent particle system.
@@ -338,6 +505,42 @@ pub struct ColliderEntity {
     pub collider_index: usize,
 }
 
+/// Component on a particle system entity that maps emitter and collider indices
+/// to their spawned child entities.
+///
+/// Inserted alongside [`ParticleSystemRuntime`] once the system's children have
+/// been spawned. Entries follow the order of [`ParticlesAsset::emitters`] and
+/// [`ParticlesAsset::colliders`]. Emitters despawned by their
+/// [`stop_action`](crate::EmitterTime::stop_action) keep their entry, so check the
+/// entity still exists before using it.
+#[derive(Component, Debug, Clone, Default)]
+pub struct ParticleSystemChildren {
+    pub(crate) emitters: Vec<Entity>,
+    pub(crate) colliders: Vec<Entity>,
+}
+
+impl ParticleSystemChildren {
+    /// Returns the entity for the emitter at `index`, if any.
+    pub fn emitter(&self, index: usize) -> Option<Entity> {
+        self.emitters.get(index).copied()
+    }
+
+    /// Returns the entity for the collider at `index`, if any.
+    pub fn collider(&self, index: usize) -> Option<Entity> {
+        self.colliders.get(index).copied()
+    }
+
+    /// Returns all emitter entities, ordered by emitter index.
+    pub fn emitters(&self) -> &[Entity] {
+        &self.emitters
+    }
+
+    /// Returns all collider entities, ordered by collider index.
+    pub fn colliders(&self) -> &[Entity] {
+        &self.colliders
+    }
+}
+
 fn rand_seed() -> u32 {
     #[cfg(not(target_arch = "wasm32"))]
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -363,7 +566,8 @@ pub struct ParticleBufferHandle {
     pub emitter_uniforms_buffer: Handle<ShaderBuffer>,
     /// Maximum number of particle slots this buffer can hold.
     pub max_particles: u32,
-    /// Number of head particles in this emitter.
+    /// Number of head particle slots in this emitter, see
+    /// [`EmitterEmission::capacity`](crate::EmitterEmission::capacity).
     pub amount: u32,
     /// Number of trail segments per particle.
     pub trail_size: u32,
@@ -400,9 +604,53 @@ pub struct ParticleMeshHandle(pub Handle<Mesh>);
 pub type ParticleMaterial = ExtendedMaterial<StandardMaterial, ParticleMaterialExtension>;
 
 /// Handle to the particle material asset.
+///
+/// Every emitter owns its own material asset, even when several instances spawn from
+/// the same [`ParticlesAsset`], since the material binds the emitter's particle buffers.
+/// Editing it through `Assets<ParticleMaterial>`, for example to swap a texture, only
+/// affects this emitter. The material is rebuilt from the asset whenever the emitter's
+/// [`DrawPassMaterial`] changes, which discards such edits; prefer
+/// [`ParticleMaterialOverrides`] for values you animate every frame.
 #[derive(Component)]
 pub struct ParticleMaterialHandle(pub Handle<ParticleMaterial>);
 
+/// Per-instance adjustments to how an emitter's particle material is drawn.
+///
+/// Add it to an emitter entity, or to a [`Particles3d`] entity to affect all of its
+/// emitters; an emitter's own component takes precedence. Values are written to the
+/// emitter's uniforms every frame, so animating them never touches the material asset
+/// and other instances of the same effect are left unchanged:
+///
+/// ```ignore
+/// fn flash_on_hit(mut overrides: Query<&mut ParticleMaterialOverrides>, time: Res<Time>) {
+///     for mut overrides in &mut overrides {
+///         overrides.emissive_multiplier = 1.0 + (time.elapsed_secs() * 10.0).sin().max(0.0);
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ParticleMaterialOverrides {
+    /// Color multiplied into every particle's base color. Defaults to [`Color::WHITE`].
+    pub tint: Color,
+    /// Multiplier applied to the material's emissive color. Defaults to `1.0`.
+    pub emissive_multiplier: f32,
+    /// Added to the erosion threshold of every particle, dissolving the whole emitter
+    /// further as it rises to `1.0`. Only has an effect when the material's
+    /// [`alpha_fade`](crate::StandardParticleMaterial::alpha_fade) is erosion. Defaults to
+    /// `0.0`.
+    pub erosion_threshold: f32,
+}
+
+impl Default for ParticleMaterialOverrides {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            emissive_multiplier: 1.0,
+            erosion_threshold: 0.0,
+        }
+    }
+}
+
 /// Buffer handle for sub-emitter data exchange between parent and child emitters.
 #[derive(Component)]
 pub struct SubEmitterBufferHandle {
@@ -414,17 +662,55 @@ pub struct SubEmitterBufferHandle {
     pub max_particles: u32,
 }
 
+/// Buffer of collision hits recorded by an emitter with
+/// [`accumulation_capacity`](crate::EmitterCollision::accumulation_capacity) set.
+///
+/// Each particle removed by [`HideOnContact`](crate::EmitterCollisionMode::HideOnContact)
+/// collision appends its position, in the same space as the emitter's particles. The
+/// buffer persists for the emitter's lifetime and is never cleared by Sprinkles, so bind
+/// it in a terrain or decal material to build up snow cover, or splat it into a texture
+/// in a custom pass. Its layout is published in the `sprinkles::particles` WGSL module:
+///
+/// ```wgsl
+/// #import sprinkles::particles::ParticleAccumulation
+///
+/// @group(2) @binding(0) var<storage, read> hits: ParticleAccumulation;
+/// ```
+///
+/// `hits.count` is the total number of hits so far. Hits are written as a ring, so the
+/// most recent `min(count, capacity)` entries are valid.
+///
+/// Recording hits takes one extra storage buffer in the simulate shader, so devices with
+/// no slot to spare for it (DX11-level hardware, some mobile GPUs) simulate the emitter
+/// without writing to this buffer.
+#[derive(Component)]
+pub struct ParticleAccumulationBuffer {
+    /// Handle to the accumulation buffer.
+    pub buffer: Handle<ShaderBuffer>,
+    /// Maximum number of hits the buffer holds.
+    pub capacity: u32,
+}
+
 /// A 3D collider that particles can interact with at runtime.
 ///
 /// Add this component to an entity (alongside a [`Transform`]) to create a collision
 /// surface for particles. The collision behavior depends on each emitter's
 /// [`EmitterCollision`](crate::EmitterCollision) settings.
+///
+/// Colliders spawned from a [`ParticlesAsset`] and colliders placed directly in the
+/// scene are extracted together. When there are more than the supported maximum
+/// (32), colliders spawned from assets take precedence and the remaining scene
+/// colliders are ignored.
 #[derive(Component, Debug, Clone)]
 pub struct ParticlesCollider3D {
     /// Whether this collider is active.
     pub enabled: bool,
     /// The collision shape.
     pub shape: ParticlesColliderShape3D,
+    /// If `true`, particles are kept inside the shape instead of outside.
+    pub invert: bool,
+    /// Which particle systems this collider affects.
+    pub affects: ColliderAffects,
 }
 
 impl Default for ParticlesCollider3D {
@@ -432,10 +718,154 @@ impl Default for ParticlesCollider3D {
         Self {
             enabled: true,
             shape: ParticlesColliderShape3D::default(),
+            invert: false,
+            affects: ColliderAffects::All,
+        }
+    }
+}
+
+/// A region of the scene that turbulence is limited to.
+///
+/// Add this component to an entity (alongside a [`Transform`]) to mask the turbulence
+/// of emitters with [`use_volumes`](crate::EmitterTurbulence::use_volumes) enabled, so
+/// noise only moves particles inside a doorway draft or above a fire. Influence fades
+/// from full to zero over [`falloff`](Self::falloff) towards the shape's surface, and
+/// overlapping volumes use the strongest one.
+///
+/// Volumes are positioned in world space, so they line up with emitters that don't use
+/// [`use_local_coords`](crate::EmitterDrawPass::use_local_coords). Up to 16 volumes are
+/// supported; the rest are ignored.
+#[derive(Component, Debug, Clone)]
+pub struct TurbulenceVolume {
+    /// Whether this volume is active.
+    pub enabled: bool,
+    /// The shape of the volume.
+    pub shape: ParticlesColliderShape3D,
+    /// Distance inside the surface over which influence fades out. Defaults to `0.5`.
+    pub falloff: f32,
+    /// Multiplier applied to turbulence influence inside the volume. Defaults to `1.0`.
+    pub strength: f32,
+}
+
+impl Default for TurbulenceVolume {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shape: ParticlesColliderShape3D::default(),
+            falloff: 0.5,
+            strength: 1.0,
+        }
+    }
+}
+
+/// Keeps a particle system centered on the active camera, for ambient weather like rain,
+/// snow or fog that should cover a huge map with a small particle budget.
+///
+/// Add this component next to [`Particles3d`] on a root entity. Every frame, the system's
+/// [`Transform`] moves to the position of the highest-order active [`Camera3d`] plus
+/// [`offset`](Self::offset), so new particles spawn around the viewer.
+///
+/// Particles stay where they spawned in the world, so moving the camera doesn't drag
+/// them along. Instead, once a particle leaves the box of
+/// [`half_extents`](Self::half_extents) around the camera, it wraps to the opposite
+/// side, keeping the area filled without respawning. Size the emission shape to match
+/// the box. Wrapping only applies to emitters that don't use
+/// [`use_local_coords`](crate::EmitterDrawPass::use_local_coords), and trails jump
+/// across the box when their particle wraps.
+///
+/// ```ignore
+/// commands.spawn((
+///     Particles3d(asset_server.load("rain.ron")),
+///     ParticleAreaEmitter {
+///         half_extents: Vec3::new(30.0, 20.0, 30.0),
+///         offset: Vec3::Y * 10.0,
+///     },
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ParticleAreaEmitter {
+    /// Half the size of the box particles wrap around in. Defaults to `(20, 15, 20)`.
+    pub half_extents: Vec3,
+    /// Offset from the camera to the center of the box. Defaults to `Vec3::ZERO`.
+    pub offset: Vec3,
+}
+
+impl Default for ParticleAreaEmitter {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec3::new(20.0, 15.0, 20.0),
+            offset: Vec3::ZERO,
         }
     }
 }
 
+/// Seeds every emitter of a particle system instance from a single value.
+///
+/// Add this component next to [`Particles3d`] when spawning, so the instance replays
+/// identically on every machine, or so two instances can be compared with only their
+/// tuning changed. It takes priority over both
+/// [`fixed_seed`](crate::EmitterTime::fixed_seed) and
+/// [`ParticleSystemRuntime::with_seed`], and is only read when the system is set up.
+///
+/// ```ignore
+/// commands.spawn((Particles3d(asset_server.load("explosion.ron")), ParticleSeed(42)));
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub struct ParticleSeed(pub u32);
+
+impl ParticleSeed {
+    /// Returns the seed derived for the emitter at `emitter_index`.
+    pub fn emitter_seed(self, emitter_index: usize) -> u32 {
+        derive_emitter_seed(self.0, emitter_index)
+    }
+}
+
+fn derive_emitter_seed(root_seed: u32, emitter_index: usize) -> u32 {
+    timing::hash(root_seed ^ (emitter_index as u32 + 1).wrapping_mul(0x9e37_79b9))
+}
+
+/// Which particle systems a [`ParticlesCollider3D`] affects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColliderAffects {
+    /// Affects every particle system with collision enabled.
+    #[default]
+    All,
+    /// Only affects particle systems whose [`ParticlesCollisionLayers`] include this
+    /// layer. Layers range from `0` to `31`; higher values are clamped to `31`.
+    Tagged(u32),
+}
+
+impl ColliderAffects {
+    /// Returns the layer mask for this setting, or `0` if it affects every system.
+    pub(crate) fn layer_mask(self) -> u32 {
+        match self {
+            Self::All => 0,
+            Self::Tagged(layer) => 1 << layer.min(31),
+        }
+    }
+}
+
+/// Opts a particle system into [tagged](ColliderAffects::Tagged) colliders.
+///
+/// Add this component next to [`Particles3d`] to select which collision layers its
+/// emitters respond to, as a bitmask where bit `n` enables layer `n`. Colliders set
+/// to [`ColliderAffects::All`] always apply. Without this component, a system only
+/// collides with those.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct ParticlesCollisionLayers(pub u32);
+
+impl ParticlesCollisionLayers {
+    /// Returns a mask with only `layer` enabled.
+    pub fn layer(layer: u32) -> Self {
+        Self(1 << layer.min(31))
+    }
+
+    /// Returns a copy of this mask with `layer` also enabled.
+    pub fn with_layer(self, layer: u32) -> Self {
+        Self(self.0 | Self::layer(layer).0)
+    }
+}
+
 pub(crate) fn check_particle_system_finished(
     mut commands: Commands,
     assets: Res<Assets<ParticlesAsset>>,
@@ -485,3 +915,88 @@ pub(crate) fn check_particle_system_finished(
         }
     }
 }
+
+pub(crate) fn follow_area_emitter_cameras(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut systems: Query<(&ParticleAreaEmitter, &mut Transform), With<Particles3d>>,
+) {
+    let Some((_, camera_transform)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+    let camera_position = camera_transform.translation();
+
+    for (area, mut transform) in &mut systems {
+        let center = camera_position + area.offset;
+        if transform.translation != center {
+            transform.translation = center;
+        }
+    }
+}
+
+/// Runs the [`EmitterStopAction`] of every emitter that has just finished.
+pub fn run_emitter_stop_actions(
+    mut commands: Commands,
+    assets: Res<Assets<ParticlesAsset>>,
+    system_query: Query<(&Particles3d, Has<EditorMode>)>,
+    mut emitter_query: Query<(
+        Entity,
+        &EmitterEntity,
+        &mut EmitterRuntime,
+        Option<&Visibility>,
+    )>,
+) {
+    for (entity, emitter, mut runtime, visibility) in emitter_query.iter_mut() {
+        if runtime.stop_action_done || !runtime.one_shot_completed || !runtime.inactive {
+            continue;
+        }
+        let Ok((particle_system, is_editor)) = system_query.get(emitter.parent_system) else {
+            continue;
+        };
+        let Some(emitter_data) = assets
+            .get(particle_system)
+            .and_then(|asset| asset.emitters.get(runtime.emitter_index))
+        else {
+            continue;
+        };
+
+        runtime.stop_action_done = true;
+
+        match emitter_data.time.stop_action {
+            EmitterStopAction::None => {}
+            EmitterStopAction::Callback => {
+                commands.entity(entity).trigger(EmitterStopped);
+            }
+            // the editor keeps emitters around so they can be replayed and inspected
+            _ if is_editor => {}
+            EmitterStopAction::Despawn => {
+                commands.entity(entity).despawn();
+            }
+            EmitterStopAction::Disable => {
+                let previous = visibility.copied().unwrap_or_default();
+                commands
+                    .entity(entity)
+                    .insert((DisabledEmitter(previous), Visibility::Hidden));
+            }
+        }
+    }
+}
+
+/// Shows emitters hidden by [`EmitterStopAction::Disable`] again once they emit, for
+/// example after [`EmitterRuntime::restart`] or [`EmitterRuntime::play`].
+pub fn restore_disabled_emitters(
+    mut commands: Commands,
+    emitter_query: Query<(Entity, &EmitterRuntime, &DisabledEmitter)>,
+) {
+    for (entity, runtime, disabled) in &emitter_query {
+        if runtime.is_emitting() {
+            commands
+                .entity(entity)
+                .insert(disabled.0)
+                .remove::<DisabledEmitter>();
+        }
+    }
+}
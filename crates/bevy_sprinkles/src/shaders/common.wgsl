@@ -1,27 +1,5 @@
 #define_import_path bevy_sprinkles::common
 
-struct Particle {
-    position: vec4<f32>,       // xyz, scale
-    velocity: vec4<f32>,       // xyz, lifetime
-    color: vec4<f32>,
-    custom: vec4<f32>,         // age, spawn_index, seed, flags
-    alignment_dir: vec4<f32>,  // xyz direction for ALIGN_Y_TO_VELOCITY, w unused
-    ref_up: vec4<f32>,         // xyz reference up for parallel-transported alignment
-    angles: vec4<f32>,         // xyz = per-axis rotation angles in radians
-}
-
-const TRAIL_THICKNESS_CURVE_SAMPLES: u32 = 16u;
-
-struct ParticleEmitterUniforms {
-    emitter_transform: mat4x4<f32>,
-    max_particles: u32,
-    particle_flags: u32,
-    use_local_coords: u32,
-    trail_size: u32,
-    transform_align: u32,
-    trail_thickness_curve: array<f32, 16>,
-}
-
 struct CurveUniform {
     enabled: u32,
     min_x: f32,
@@ -30,23 +8,43 @@ struct CurveUniform {
     max_y: f32,
     min_z: f32,
     max_z: f32,
-    _pad: u32,
+    wrap: u32,
+    repeat: f32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
 }
 
-// per-particle flags (stored in particle.custom.w)
-const PARTICLE_FLAG_ACTIVE: u32 = 1u;
+const CURVE_WRAP_CLAMP: u32 = 0u;
+const CURVE_WRAP_LOOP: u32 = 1u;
+const CURVE_WRAP_PING_PONG: u32 = 2u;
+
+// maps a lifetime fraction to a position on the curve, repeating it as configured
+fn curve_position(curve: CurveUniform, t: f32) -> f32 {
+    let position = t * curve.repeat;
+    if (position >= 0.0 && position <= 1.0) {
+        return position;
+    }
+    switch curve.wrap {
+        case CURVE_WRAP_LOOP: {
+            return fract(position);
+        }
+        case CURVE_WRAP_PING_PONG: {
+            let mirrored = position - 2.0 * floor(position * 0.5);
+            return select(mirrored, 2.0 - mirrored, mirrored > 1.0);
+        }
+        default: {
+            return clamp(position, 0.0, 1.0);
+        }
+    }
+}
 
 // emitter-level particle flags (from EmitterParams.particle_flags)
 const EMITTER_FLAG_ROTATE_Y: u32 = 2u;
 const EMITTER_FLAG_DISABLE_Z: u32 = 4u;
 const EMITTER_FLAG_ANGLE_PER_AXIS: u32 = 8u;
-
-// transform align mode values
-const TRANSFORM_ALIGN_DISABLED: u32 = 0u;
-const TRANSFORM_ALIGN_BILLBOARD: u32 = 1u;
-const TRANSFORM_ALIGN_Y_TO_VELOCITY: u32 = 2u;
-const TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY: u32 = 3u;
-const TRANSFORM_ALIGN_BILLBOARD_FIXED_Y: u32 = 4u;
+const EMITTER_FLAG_DAMPING_AS_FRICTION: u32 = 16u;
+const EMITTER_FLAG_DISABLE_ROTATION: u32 = 32u;
 
 struct TrailHistoryEntry {
     position: vec4<f32>,
@@ -56,12 +54,15 @@ struct TrailHistoryEntry {
 // sub emitter emission buffer
 struct SubEmissionEntry {
     position: vec4<f32>,    // xyz + scale
-    velocity: vec4<f32>,    // xyz + w unused
+    velocity: vec4<f32>,    // xyz added to the child's own velocity scaled by w
     flags: u32,
+    // index of the emitting particle, for EMISSION_FLAG_HAS_PARENT
+    parent_index: u32,
 }
 
 const EMISSION_FLAG_HAS_POSITION: u32 = 1u;
 const EMISSION_FLAG_HAS_VELOCITY: u32 = 2u;
+const EMISSION_FLAG_HAS_PARENT: u32 = 4u;
 
 // sub emitter mode constants
 const SUB_EMITTER_MODE_DISABLED: u32 = 0u;
@@ -69,6 +70,11 @@ const SUB_EMITTER_MODE_CONSTANT: u32 = 1u;
 const SUB_EMITTER_MODE_AT_END: u32 = 2u;
 const SUB_EMITTER_MODE_AT_COLLISION: u32 = 3u;
 const SUB_EMITTER_MODE_AT_START: u32 = 4u;
+const SUB_EMITTER_MODE_TRAIL: u32 = 5u;
+
+const SUB_EMITTER_AMOUNT_SCALE_NONE: u32 = 0u;
+const SUB_EMITTER_AMOUNT_SCALE_SCALE: u32 = 1u;
+const SUB_EMITTER_AMOUNT_SCALE_LIFETIME: u32 = 2u;
 
 // multiply-xorshift integer hash
 // https://nullprogram.com/blog/2018/07/31/
@@ -0,0 +1,63 @@
+#import bevy_render::view::View
+#import sprinkles::particles::{
+    Particle,
+    ParticleEmitterUniforms,
+    particle_is_active,
+    particle_world_position,
+}
+
+struct ParticleMaskTarget {
+    radius: f32,
+}
+
+struct MaskEmitter {
+    value: u32,
+}
+
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> mask_target: ParticleMaskTarget;
+@group(1) @binding(0) var<storage, read> particles: array<Particle>;
+@group(1) @binding(1) var<storage, read> emitter: ParticleEmitterUniforms;
+@group(1) @binding(2) var<uniform> mask_emitter: MaskEmitter;
+
+struct MaskOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) offset: vec2<f32>,
+}
+
+@vertex
+fn mask_vertex(@builtin(vertex_index) vertex_index: u32) -> MaskOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0),
+        vec2(-1.0, 1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    );
+
+    var out: MaskOutput;
+
+    // only trail heads are drawn
+    let particle = particles[(vertex_index / 6u) * max(emitter.trail_size, 1u)];
+    if (!particle_is_active(particle)) {
+        // a degenerate quad produces no fragments
+        out.clip_position = vec4(0.0);
+        return out;
+    }
+
+    let corner = corners[vertex_index % 6u];
+    let radius = particle.position.w * mask_target.radius;
+    let right = view.world_from_view[0].xyz;
+    let up = view.world_from_view[1].xyz;
+    let world_position = particle_world_position(particle, emitter)
+        + (right * corner.x + up * corner.y) * radius;
+
+    out.clip_position = view.clip_from_world * vec4(world_position, 1.0);
+    out.offset = corner;
+    return out;
+}
+
+@fragment
+fn mask_fragment(in: MaskOutput) -> @location(0) vec4<u32> {
+    if (dot(in.offset, in.offset) > 1.0) {
+        discard;
+    }
+    return vec4(mask_emitter.value, 0u, 0u, 0u);
+}
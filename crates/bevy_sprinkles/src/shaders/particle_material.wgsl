@@ -1,4 +1,4 @@
-#import bevy_sprinkles::common::{
+#import sprinkles::particles::{
     Particle,
     ParticleEmitterUniforms,
     PARTICLE_FLAG_ACTIVE,
@@ -6,8 +6,13 @@
     TRANSFORM_ALIGN_Y_TO_VELOCITY,
     TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY,
     TRANSFORM_ALIGN_BILLBOARD_FIXED_Y,
+    TRANSFORM_ALIGN_FIXED_AXIS,
+    TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD,
     TRAIL_THICKNESS_CURVE_SAMPLES,
+    TEXTURE_SHEET_RANDOM_ROW,
+    TEXTURE_SHEET_RANDOM_COLUMN,
 }
+#import bevy_sprinkles::common::hash_to_float
 #import bevy_pbr::{
     mesh_functions,
     mesh_view_bindings::view,
@@ -48,6 +53,8 @@ const STANDARD_MATERIAL_FLAGS_UNLIT_BIT: u32 = 1u << 5u;
 #else
 @group(#{MATERIAL_BIND_GROUP}) @binding(100) var<storage, read> sorted_particles: array<Particle>;
 @group(#{MATERIAL_BIND_GROUP}) @binding(101) var<storage, read> emitter_uniforms: ParticleEmitterUniforms;
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var distortion_texture: texture_2d<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(103) var distortion_sampler: sampler;
 #endif
 
 // computes a shortest-arc rotation matrix that aligns the Y axis to a direction
@@ -104,6 +111,21 @@ fn rot_z(a: f32) -> mat3x3<f32> {
     return mat3x3<f32>(vec3(c, s, 0.0), vec3(-s, c, 0.0), vec3(0.0, 0.0, 1.0));
 }
 
+// builds a (right, up, forward) basis around `forward` whose up axis leans toward world up
+fn world_up_basis(forward: vec3<f32>) -> mat3x3<f32> {
+    var f = vec3(0.0, 0.0, 1.0);
+    if length(forward) > 0.0001 {
+        f = normalize(forward);
+    }
+    var world_up = vec3(0.0, 1.0, 0.0);
+    if abs(dot(f, world_up)) > 0.999 {
+        world_up = vec3(0.0, 0.0, 1.0);
+    }
+    let right = normalize(cross(world_up, f));
+    let up = cross(f, right);
+    return mat3x3<f32>(right, up, f);
+}
+
 #ifdef PREPASS_PIPELINE
 #ifndef PREPASS_FRAGMENT
 #ifdef MAY_DISCARD
@@ -269,21 +291,7 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
 
     let transform_align = emitter_uniforms.transform_align;
 
-    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
-        let alignment_dir = particle.alignment_dir.xyz;
-        let dir_length = length(alignment_dir);
-        if dir_length > 0.0 {
-            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
-            rotated_position = rotation_matrix * vertex.position;
-#ifdef VERTEX_NORMALS
-            rotated_normal = rotation_matrix * vertex.normal;
-#endif
-#ifdef VERTEX_TANGENTS
-            rotated_tangent = rotation_matrix * vertex.tangent.xyz;
-#endif
-        }
-    }
-
+    // the angle rotates the particle around its own axes, before it gets aligned
     let angles = particle.angles.xyz;
     var angle_rot = mat3x3<f32>(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
     if abs(angles.x) > 0.0001 { angle_rot = rot_x(angles.x) * angle_rot; }
@@ -298,13 +306,30 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
     rotated_tangent = angle_rot * rotated_tangent;
 #endif
 
+    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
+        let alignment_dir = particle.alignment_dir.xyz;
+        let dir_length = length(alignment_dir);
+        if dir_length > 0.0 {
+            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
+            rotated_position = rotation_matrix * rotated_position;
+#ifdef VERTEX_NORMALS
+            rotated_normal = rotation_matrix * rotated_normal;
+#endif
+#ifdef VERTEX_TANGENTS
+            rotated_tangent = rotation_matrix * rotated_tangent;
+#endif
+        }
+    }
+
     let emitter_scale = vec3(
         length(world_from_local[0].xyz),
         length(world_from_local[1].xyz),
         length(world_from_local[2].xyz),
     );
 
-    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
+    let keep_world_up = emitter_uniforms.keep_world_up != 0u;
+
+    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y || transform_align == TRANSFORM_ALIGN_FIXED_AXIS || transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
         let cam_right = normalize(view.world_from_view[0].xyz);
         let cam_up = normalize(view.world_from_view[1].xyz);
         let cam_forward = normalize(view.world_from_view[2].xyz);
@@ -355,6 +380,52 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
                 right * rotated_tangent.x + sv * rotated_tangent.y + cam_forward * rotated_tangent.z,
                 vertex.tangent.w
             );
+#endif
+        } else if transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
+            var v = particle.velocity.xyz;
+            if is_local {
+                let emitter_rotation = mat3x3<f32>(
+                    normalize(world_from_local[0].xyz),
+                    normalize(world_from_local[1].xyz),
+                    normalize(world_from_local[2].xyz)
+                );
+                v = emitter_rotation * v;
+            }
+            let speed = length(v);
+            var axis = cam_up;
+            if speed > 0.0001 {
+                axis = v / speed;
+            }
+
+            // rotate around the velocity axis only, toward this particle's view direction
+            let to_camera = normalize(view.world_position - particle_world_pos);
+            var right = cross(axis, to_camera);
+            if length(right) < 0.001 {
+                right = cam_right;
+            }
+            right = normalize(right);
+            let facing = cross(right, axis);
+
+            let stretch = 1.0 + speed * emitter_uniforms.velocity_stretch;
+            let scaled_vertex = rotated_position * scale * vec3(1.0, stretch, 1.0);
+            let pos = particle_world_pos
+                + right * scaled_vertex.x
+                + axis * scaled_vertex.y
+                + facing * scaled_vertex.z;
+
+            out.world_position = vec4(pos, 1.0);
+            out.position = position_world_to_clip(pos);
+
+#ifdef VERTEX_NORMALS
+            out.world_normal = right * rotated_normal.x
+                + axis * rotated_normal.y
+                + facing * rotated_normal.z;
+#endif
+#ifdef VERTEX_TANGENTS
+            out.world_tangent = vec4(
+                right * rotated_tangent.x + axis * rotated_tangent.y + facing * rotated_tangent.z,
+                vertex.tangent.w
+            );
 #endif
         } else if transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
             let world_up = vec3(0.0, 1.0, 0.0);
@@ -382,54 +453,62 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
             );
 #endif
         } else {
+            var basis = mat3x3<f32>(cam_right, cam_up, cam_forward);
+            if transform_align == TRANSFORM_ALIGN_FIXED_AXIS {
+                basis = world_up_basis(emitter_uniforms.transform_align_axis);
+            } else if keep_world_up {
+                basis = world_up_basis(cam_forward);
+            }
+
             let scaled_vertex = rotated_position * scale;
-            let billboard_pos = particle_world_pos
-                + cam_right * scaled_vertex.x
-                + cam_up * scaled_vertex.y
-                + cam_forward * scaled_vertex.z;
+            let billboard_pos = particle_world_pos + basis * scaled_vertex;
 
             out.world_position = vec4(billboard_pos, 1.0);
             out.position = position_world_to_clip(billboard_pos);
 
 #ifdef VERTEX_NORMALS
-            out.world_normal = cam_right * rotated_normal.x
-                + cam_up * rotated_normal.y
-                + cam_forward * rotated_normal.z;
+            out.world_normal = basis * rotated_normal;
 #endif
 #ifdef VERTEX_TANGENTS
-            out.world_tangent = vec4(
-                cam_right * rotated_tangent.x + cam_up * rotated_tangent.y + cam_forward * rotated_tangent.z,
-                vertex.tangent.w
-            );
+            out.world_tangent = vec4(basis * rotated_tangent, vertex.tangent.w);
 #endif
         }
     } else {
-        let emitter_rotation = mat3x3(
+        var emitter_rotation = mat3x3(
             normalize(world_from_local[0].xyz),
             normalize(world_from_local[1].xyz),
             normalize(world_from_local[2].xyz)
         );
+        // opting out of the emitter rotation keeps particles in a constant world orientation
+        if keep_world_up {
+            emitter_rotation = mat3x3(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
+        }
+        let use_mesh_transform = is_local && !keep_world_up;
 
-        if is_local {
+        if use_mesh_transform {
             let offset = rotated_position * particle_scale;
             let local_position = offset + particle_position;
             out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4(local_position, 1.0));
         } else {
+            var origin = particle_position;
+            if is_local {
+                origin = (world_from_local * vec4(particle_position, 1.0)).xyz;
+            }
             let offset = emitter_rotation * (rotated_position * particle_scale * emitter_scale);
-            out.world_position = vec4(particle_position + offset, 1.0);
+            out.world_position = vec4(origin + offset, 1.0);
         }
 
         out.position = position_world_to_clip(out.world_position.xyz);
 
 #ifdef VERTEX_NORMALS
-        if is_local {
+        if use_mesh_transform {
             out.world_normal = mesh_functions::mesh_normal_local_to_world(rotated_normal, vertex.instance_index);
         } else {
             out.world_normal = emitter_rotation * rotated_normal;
         }
 #endif
 #ifdef VERTEX_TANGENTS
-        if is_local {
+        if use_mesh_transform {
             out.world_tangent = mesh_functions::mesh_tangent_local_to_world(world_from_local, vec4(rotated_tangent, vertex.tangent.w), vertex.instance_index);
         } else {
             out.world_tangent = vec4(emitter_rotation * rotated_tangent, vertex.tangent.w);
@@ -608,21 +687,7 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
 
     let transform_align = emitter_uniforms.transform_align;
 
-    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
-        let alignment_dir = particle.alignment_dir.xyz;
-        let dir_length = length(alignment_dir);
-        if dir_length > 0.0 {
-            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
-            rotated_position = rotation_matrix * vertex.position;
-#ifdef VERTEX_NORMALS
-            rotated_normal = rotation_matrix * vertex.normal;
-#endif
-#ifdef VERTEX_TANGENTS
-            rotated_tangent = rotation_matrix * vertex.tangent.xyz;
-#endif
-        }
-    }
-
+    // the angle rotates the particle around its own axes, before it gets aligned
     let angles = particle.angles.xyz;
     var angle_rot = mat3x3<f32>(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
     if abs(angles.x) > 0.0001 { angle_rot = rot_x(angles.x) * angle_rot; }
@@ -637,13 +702,30 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
     rotated_tangent = angle_rot * rotated_tangent;
 #endif
 
+    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
+        let alignment_dir = particle.alignment_dir.xyz;
+        let dir_length = length(alignment_dir);
+        if dir_length > 0.0 {
+            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
+            rotated_position = rotation_matrix * rotated_position;
+#ifdef VERTEX_NORMALS
+            rotated_normal = rotation_matrix * rotated_normal;
+#endif
+#ifdef VERTEX_TANGENTS
+            rotated_tangent = rotation_matrix * rotated_tangent;
+#endif
+        }
+    }
+
     let emitter_scale = vec3(
         length(world_from_local[0].xyz),
         length(world_from_local[1].xyz),
         length(world_from_local[2].xyz),
     );
 
-    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
+    let keep_world_up = emitter_uniforms.keep_world_up != 0u;
+
+    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y || transform_align == TRANSFORM_ALIGN_FIXED_AXIS || transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
         let cam_right = normalize(view.world_from_view[0].xyz);
         let cam_up = normalize(view.world_from_view[1].xyz);
         let cam_forward = normalize(view.world_from_view[2].xyz);
@@ -694,6 +776,52 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
                 right * rotated_tangent.x + sv * rotated_tangent.y + cam_forward * rotated_tangent.z,
                 vertex.tangent.w
             );
+#endif
+        } else if transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
+            var v = particle.velocity.xyz;
+            if is_local {
+                let emitter_rotation = mat3x3<f32>(
+                    normalize(world_from_local[0].xyz),
+                    normalize(world_from_local[1].xyz),
+                    normalize(world_from_local[2].xyz)
+                );
+                v = emitter_rotation * v;
+            }
+            let speed = length(v);
+            var axis = cam_up;
+            if speed > 0.0001 {
+                axis = v / speed;
+            }
+
+            // rotate around the velocity axis only, toward this particle's view direction
+            let to_camera = normalize(view.world_position - particle_world_pos);
+            var right = cross(axis, to_camera);
+            if length(right) < 0.001 {
+                right = cam_right;
+            }
+            right = normalize(right);
+            let facing = cross(right, axis);
+
+            let stretch = 1.0 + speed * emitter_uniforms.velocity_stretch;
+            let scaled_vertex = rotated_position * scale * vec3(1.0, stretch, 1.0);
+            let pos = particle_world_pos
+                + right * scaled_vertex.x
+                + axis * scaled_vertex.y
+                + facing * scaled_vertex.z;
+
+            out.world_position = vec4(pos, 1.0);
+            out.position = position_world_to_clip(pos);
+
+#ifdef VERTEX_NORMALS
+            out.world_normal = right * rotated_normal.x
+                + axis * rotated_normal.y
+                + facing * rotated_normal.z;
+#endif
+#ifdef VERTEX_TANGENTS
+            out.world_tangent = vec4(
+                right * rotated_tangent.x + axis * rotated_tangent.y + facing * rotated_tangent.z,
+                vertex.tangent.w
+            );
 #endif
         } else if transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
             let world_up = vec3(0.0, 1.0, 0.0);
@@ -721,54 +849,62 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
             );
 #endif
         } else {
+            var basis = mat3x3<f32>(cam_right, cam_up, cam_forward);
+            if transform_align == TRANSFORM_ALIGN_FIXED_AXIS {
+                basis = world_up_basis(emitter_uniforms.transform_align_axis);
+            } else if keep_world_up {
+                basis = world_up_basis(cam_forward);
+            }
+
             let scaled_vertex = rotated_position * scale;
-            let billboard_pos = particle_world_pos
-                + cam_right * scaled_vertex.x
-                + cam_up * scaled_vertex.y
-                + cam_forward * scaled_vertex.z;
+            let billboard_pos = particle_world_pos + basis * scaled_vertex;
 
             out.world_position = vec4(billboard_pos, 1.0);
             out.position = position_world_to_clip(billboard_pos);
 
 #ifdef VERTEX_NORMALS
-            out.world_normal = cam_right * rotated_normal.x
-                + cam_up * rotated_normal.y
-                + cam_forward * rotated_normal.z;
+            out.world_normal = basis * rotated_normal;
 #endif
 #ifdef VERTEX_TANGENTS
-            out.world_tangent = vec4(
-                cam_right * rotated_tangent.x + cam_up * rotated_tangent.y + cam_forward * rotated_tangent.z,
-                vertex.tangent.w
-            );
+            out.world_tangent = vec4(basis * rotated_tangent, vertex.tangent.w);
 #endif
         }
     } else {
-        let emitter_rotation = mat3x3(
+        var emitter_rotation = mat3x3(
             normalize(world_from_local[0].xyz),
             normalize(world_from_local[1].xyz),
             normalize(world_from_local[2].xyz)
         );
+        // opting out of the emitter rotation keeps particles in a constant world orientation
+        if keep_world_up {
+            emitter_rotation = mat3x3(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
+        }
+        let use_mesh_transform = is_local && !keep_world_up;
 
-        if is_local {
+        if use_mesh_transform {
             let offset = rotated_position * particle_scale;
             let local_position = offset + particle_position;
             out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4(local_position, 1.0));
         } else {
+            var origin = particle_position;
+            if is_local {
+                origin = (world_from_local * vec4(particle_position, 1.0)).xyz;
+            }
             let offset = emitter_rotation * (rotated_position * particle_scale * emitter_scale);
-            out.world_position = vec4(particle_position + offset, 1.0);
+            out.world_position = vec4(origin + offset, 1.0);
         }
 
         out.position = position_world_to_clip(out.world_position.xyz);
 
 #ifdef VERTEX_NORMALS
-        if is_local {
+        if use_mesh_transform {
             out.world_normal = mesh_functions::mesh_normal_local_to_world(rotated_normal, vertex.instance_index);
         } else {
             out.world_normal = emitter_rotation * rotated_normal;
         }
 #endif
 #ifdef VERTEX_TANGENTS
-        if is_local {
+        if use_mesh_transform {
             out.world_tangent = mesh_functions::mesh_tangent_local_to_world(world_from_local, vec4(rotated_tangent, vertex.tangent.w), vertex.instance_index);
         } else {
             out.world_tangent = vec4(emitter_rotation * rotated_tangent, vertex.tangent.w);
@@ -947,21 +1083,7 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
 
     let transform_align = emitter_uniforms.transform_align;
 
-    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
-        let alignment_dir = particle.alignment_dir.xyz;
-        let dir_length = length(alignment_dir);
-        if dir_length > 0.0 {
-            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
-            rotated_position = rotation_matrix * vertex.position;
-#ifdef VERTEX_NORMALS
-            rotated_normal = rotation_matrix * vertex.normal;
-#endif
-#ifdef VERTEX_TANGENTS
-            rotated_tangent = rotation_matrix * vertex.tangent.xyz;
-#endif
-        }
-    }
-
+    // the angle rotates the particle around its own axes, before it gets aligned
     let angles = particle.angles.xyz;
     var angle_rot = mat3x3<f32>(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
     if abs(angles.x) > 0.0001 { angle_rot = rot_x(angles.x) * angle_rot; }
@@ -976,13 +1098,30 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
     rotated_tangent = angle_rot * rotated_tangent;
 #endif
 
+    if transform_align == TRANSFORM_ALIGN_Y_TO_VELOCITY {
+        let alignment_dir = particle.alignment_dir.xyz;
+        let dir_length = length(alignment_dir);
+        if dir_length > 0.0 {
+            let rotation_matrix = align_y_with_ref(alignment_dir, particle.ref_up.xyz);
+            rotated_position = rotation_matrix * rotated_position;
+#ifdef VERTEX_NORMALS
+            rotated_normal = rotation_matrix * rotated_normal;
+#endif
+#ifdef VERTEX_TANGENTS
+            rotated_tangent = rotation_matrix * rotated_tangent;
+#endif
+        }
+    }
+
     let emitter_scale = vec3(
         length(world_from_local[0].xyz),
         length(world_from_local[1].xyz),
         length(world_from_local[2].xyz),
     );
 
-    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
+    let keep_world_up = emitter_uniforms.keep_world_up != 0u;
+
+    if transform_align == TRANSFORM_ALIGN_BILLBOARD || transform_align == TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY || transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y || transform_align == TRANSFORM_ALIGN_FIXED_AXIS || transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
         let cam_right = normalize(view.world_from_view[0].xyz);
         let cam_up = normalize(view.world_from_view[1].xyz);
         let cam_forward = normalize(view.world_from_view[2].xyz);
@@ -1033,6 +1172,52 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
                 right * rotated_tangent.x + sv * rotated_tangent.y + cam_forward * rotated_tangent.z,
                 vertex.tangent.w
             );
+#endif
+        } else if transform_align == TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD {
+            var v = particle.velocity.xyz;
+            if is_local {
+                let emitter_rotation = mat3x3<f32>(
+                    normalize(world_from_local[0].xyz),
+                    normalize(world_from_local[1].xyz),
+                    normalize(world_from_local[2].xyz)
+                );
+                v = emitter_rotation * v;
+            }
+            let speed = length(v);
+            var axis = cam_up;
+            if speed > 0.0001 {
+                axis = v / speed;
+            }
+
+            // rotate around the velocity axis only, toward this particle's view direction
+            let to_camera = normalize(view.world_position - particle_world_pos);
+            var right = cross(axis, to_camera);
+            if length(right) < 0.001 {
+                right = cam_right;
+            }
+            right = normalize(right);
+            let facing = cross(right, axis);
+
+            let stretch = 1.0 + speed * emitter_uniforms.velocity_stretch;
+            let scaled_vertex = rotated_position * scale * vec3(1.0, stretch, 1.0);
+            let pos = particle_world_pos
+                + right * scaled_vertex.x
+                + axis * scaled_vertex.y
+                + facing * scaled_vertex.z;
+
+            out.world_position = vec4(pos, 1.0);
+            out.position = position_world_to_clip(pos);
+
+#ifdef VERTEX_NORMALS
+            out.world_normal = right * rotated_normal.x
+                + axis * rotated_normal.y
+                + facing * rotated_normal.z;
+#endif
+#ifdef VERTEX_TANGENTS
+            out.world_tangent = vec4(
+                right * rotated_tangent.x + axis * rotated_tangent.y + facing * rotated_tangent.z,
+                vertex.tangent.w
+            );
 #endif
         } else if transform_align == TRANSFORM_ALIGN_BILLBOARD_FIXED_Y {
             let world_up = vec3(0.0, 1.0, 0.0);
@@ -1060,54 +1245,62 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
             );
 #endif
         } else {
+            var basis = mat3x3<f32>(cam_right, cam_up, cam_forward);
+            if transform_align == TRANSFORM_ALIGN_FIXED_AXIS {
+                basis = world_up_basis(emitter_uniforms.transform_align_axis);
+            } else if keep_world_up {
+                basis = world_up_basis(cam_forward);
+            }
+
             let scaled_vertex = rotated_position * scale;
-            let billboard_pos = particle_world_pos
-                + cam_right * scaled_vertex.x
-                + cam_up * scaled_vertex.y
-                + cam_forward * scaled_vertex.z;
+            let billboard_pos = particle_world_pos + basis * scaled_vertex;
 
             out.world_position = vec4(billboard_pos, 1.0);
             out.position = position_world_to_clip(billboard_pos);
 
 #ifdef VERTEX_NORMALS
-            out.world_normal = cam_right * rotated_normal.x
-                + cam_up * rotated_normal.y
-                + cam_forward * rotated_normal.z;
+            out.world_normal = basis * rotated_normal;
 #endif
 #ifdef VERTEX_TANGENTS
-            out.world_tangent = vec4(
-                cam_right * rotated_tangent.x + cam_up * rotated_tangent.y + cam_forward * rotated_tangent.z,
-                vertex.tangent.w
-            );
+            out.world_tangent = vec4(basis * rotated_tangent, vertex.tangent.w);
 #endif
         }
     } else {
-        let emitter_rotation = mat3x3(
+        var emitter_rotation = mat3x3(
             normalize(world_from_local[0].xyz),
             normalize(world_from_local[1].xyz),
             normalize(world_from_local[2].xyz)
         );
+        // opting out of the emitter rotation keeps particles in a constant world orientation
+        if keep_world_up {
+            emitter_rotation = mat3x3(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, 1.0));
+        }
+        let use_mesh_transform = is_local && !keep_world_up;
 
-        if is_local {
+        if use_mesh_transform {
             let offset = rotated_position * particle_scale;
             let local_position = offset + particle_position;
             out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4(local_position, 1.0));
         } else {
+            var origin = particle_position;
+            if is_local {
+                origin = (world_from_local * vec4(particle_position, 1.0)).xyz;
+            }
             let offset = emitter_rotation * (rotated_position * particle_scale * emitter_scale);
-            out.world_position = vec4(particle_position + offset, 1.0);
+            out.world_position = vec4(origin + offset, 1.0);
         }
 
         out.position = position_world_to_clip(out.world_position.xyz);
 
 #ifdef VERTEX_NORMALS
-        if is_local {
+        if use_mesh_transform {
             out.world_normal = mesh_functions::mesh_normal_local_to_world(rotated_normal, vertex.instance_index);
         } else {
             out.world_normal = emitter_rotation * rotated_normal;
         }
 #endif
 #ifdef VERTEX_TANGENTS
-        if is_local {
+        if use_mesh_transform {
             out.world_tangent = mesh_functions::mesh_tangent_local_to_world(world_from_local, vec4(rotated_tangent, vertex.tangent.w), vertex.instance_index);
         } else {
             out.world_tangent = vec4(emitter_rotation * rotated_tangent, vertex.tangent.w);
@@ -1124,6 +1317,53 @@ fn particle_vertex_impl(vertex: Vertex) -> VertexOutput {
 
 #endif // not PREPASS_PIPELINE
 
+#ifndef PREPASS_PIPELINE
+// maps a uv into the texture sheet cell picked by the particle's seed
+fn texture_sheet_uv(uv: vec2<f32>, seed: u32) -> vec2<f32> {
+    let columns = emitter_uniforms.texture_sheet_columns;
+    let rows = emitter_uniforms.texture_sheet_rows;
+    let mode = emitter_uniforms.texture_sheet_mode;
+
+    var count = columns * rows;
+    if mode == TEXTURE_SHEET_RANDOM_ROW {
+        count = rows;
+    } else if mode == TEXTURE_SHEET_RANDOM_COLUMN {
+        count = columns;
+    }
+    let pick = min(u32(hash_to_float(seed + 140u) * f32(count)), count - 1u);
+
+    var cell = vec2(pick % columns, pick / columns);
+    if mode == TEXTURE_SHEET_RANDOM_ROW {
+        cell = vec2(0u, pick);
+    } else if mode == TEXTURE_SHEET_RANDOM_COLUMN {
+        cell = vec2(pick, 0u);
+    }
+    return (uv + vec2<f32>(cell)) / vec2(f32(columns), f32(rows));
+}
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+// particles have no previous frame transform, so last frame's position is
+// extrapolated back along the particle's velocity. only the particle drawn by this
+// vertex is read, so motion vectors stay correct when sorting reorders particles
+fn previous_particle_world_position(vertex: Vertex, world_position: vec4<f32>) -> vec4<f32> {
+    let particle_index = u32(round(vertex.uv_b.x));
+    let particle = sorted_particles[particle_index * max(emitter_uniforms.trail_size, 1u)];
+    let displacement = particle.velocity.xyz * emitter_uniforms.delta_time;
+    if emitter_uniforms.use_local_coords == 0u {
+        return world_position - vec4(displacement, 0.0);
+    }
+
+    // local particles also follow the emitter, so the particle center is moved through
+    // last frame's emitter transform and the vertex keeps its offset from it
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let previous_world_from_local = mesh_functions::get_previous_world_from_local(vertex.instance_index);
+    let center = world_from_local * vec4(particle.position.xyz, 1.0);
+    let previous_center = previous_world_from_local * vec4(particle.position.xyz - displacement, 1.0);
+    return previous_center + (world_position - center);
+}
+#endif
+
 @vertex
 fn vertex(vertex: Vertex) -> VertexOutput {
     var out: VertexOutput;
@@ -1142,9 +1382,19 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     return particle_vertex_impl(vertex);
 #endif
 #else
-    return particle_vertex_impl(vertex);
+    out = particle_vertex_impl(vertex);
+#ifdef MOTION_VECTOR_PREPASS
+    out.previous_world_position = previous_particle_world_position(vertex, out.world_position);
+#endif
+    return out;
 #endif
 #else
+    // shadow-only passes are skipped in the main pass but still reach the shadow maps
+    if emitter_uniforms.shadow_only != 0u {
+        out.position = vec4(2.0, 2.0, 0.0, 1.0);
+        out.world_position = vec4(0.0);
+        return out;
+    }
     return particle_vertex_impl(vertex);
 #endif
 }
@@ -1248,8 +1498,38 @@ fn fragment(
         discard;
     }
 
-    var pbr_input = pbr_input_from_standard_material(in, is_front);
-    pbr_input.material.base_color = pbr_input.material.base_color * particle_color;
+    var material_in = in;
+#ifdef VERTEX_UVS_A
+    if emitter_uniforms.distortion_strength != 0.0 {
+        // scroll the noise in its own uv space, then offset the material uvs by it
+        let noise_uv = fract(in.uv + emitter_uniforms.distortion_offset);
+        let noise = textureSample(distortion_texture, distortion_sampler, noise_uv).rg;
+        material_in.uv = in.uv + (noise * 2.0 - 1.0) * emitter_uniforms.distortion_strength;
+    }
+    if emitter_uniforms.texture_sheet_columns > 0u {
+        material_in.uv = texture_sheet_uv(material_in.uv, bitcast<u32>(particle.custom.z));
+    }
+#endif
+
+    var pbr_input = pbr_input_from_standard_material(material_in, is_front);
+    var fade_color = particle_color;
+    if emitter_uniforms.alpha_erosion != 0u {
+        // the particle alpha becomes a threshold, so low-alpha texels dissolve first
+        let threshold = 1.0 - particle_color.a + emitter_uniforms.erosion_threshold;
+        let softness = max(emitter_uniforms.alpha_erosion_softness, 0.0001);
+        fade_color.a = smoothstep(threshold, threshold + softness, pbr_input.material.base_color.a);
+    }
+    fade_color *= emitter_uniforms.tint;
+    pbr_input.material.emissive = vec4(
+        pbr_input.material.emissive.rgb * emitter_uniforms.emissive_multiplier,
+        pbr_input.material.emissive.a,
+    );
+    if emitter_uniforms.near_fade_distance > 0.0 {
+        // fade out as the camera approaches, so flying through effects doesn't flash
+        let camera_distance = distance(in.world_position.xyz, view.world_position.xyz);
+        fade_color.a *= saturate(camera_distance / emitter_uniforms.near_fade_distance);
+    }
+    pbr_input.material.base_color = pbr_input.material.base_color * fade_color;
     pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
 
     let particle_alpha = pbr_input.material.base_color.a;
@@ -1264,10 +1544,20 @@ fn fragment(
         out.color = apply_pbr_lighting(pbr_input);
     }
 
+    // fog is evaluated from the distance to the camera, so scaling that distance
+    // scales the density of every falloff, including atmospheric scattering
+    let view_position = view.world_position.xyz;
+    let fog_offset = (pbr_input.world_position.xyz - view_position) * emitter_uniforms.fog_density_multiplier;
+    pbr_input.world_position = vec4(view_position + fog_offset, pbr_input.world_position.w);
+
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
 #ifndef PREMULTIPLY_ALPHA
     out.color.a = particle_alpha;
 #endif
+#ifdef PARTICLE_BLEND_MIN
+    // fade transparent fragments toward white, which has no effect under min blending
+    out.color = vec4(out.color.rgb + (1.0 - out.color.a), out.color.a);
+#endif
 
     return out;
 }
@@ -0,0 +1,99 @@
+#import bevy_render::view::View
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+#import sprinkles::particles::{
+    Particle,
+    ParticleEmitterUniforms,
+    particle_is_active,
+    particle_world_position,
+}
+
+struct MetaballSettings {
+    radius: f32,
+    threshold: f32,
+    softness: f32,
+    specular: f32,
+}
+
+// splat pass
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> settings: MetaballSettings;
+@group(1) @binding(0) var<storage, read> particles: array<Particle>;
+@group(1) @binding(1) var<storage, read> emitter: ParticleEmitterUniforms;
+
+// composite pass, sharing the settings binding
+@group(0) @binding(2) var density_texture: texture_2d<f32>;
+
+struct SplatOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) offset: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn splat_vertex(@builtin(vertex_index) vertex_index: u32) -> SplatOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(-1.0, 1.0),
+        vec2(-1.0, 1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+    );
+
+    var out: SplatOutput;
+
+    // only trail heads are splatted
+    let particle = particles[(vertex_index / 6u) * max(emitter.trail_size, 1u)];
+    if (!particle_is_active(particle)) {
+        // a degenerate quad produces no fragments
+        out.clip_position = vec4(0.0);
+        return out;
+    }
+
+    let corner = corners[vertex_index % 6u];
+    let radius = particle.position.w * settings.radius;
+    let right = view.world_from_view[0].xyz;
+    let up = view.world_from_view[1].xyz;
+    let world_position = particle_world_position(particle, emitter)
+        + (right * corner.x + up * corner.y) * radius;
+
+    out.clip_position = view.clip_from_world * vec4(world_position, 1.0);
+    out.offset = corner;
+    out.color = particle.color;
+    return out;
+}
+
+@fragment
+fn splat_fragment(in: SplatOutput) -> @location(0) vec4<f32> {
+    // smooth kernel that reaches zero at the edge of the splat
+    let falloff = max(1.0 - dot(in.offset, in.offset), 0.0);
+    let weight = falloff * falloff * in.color.a;
+    return vec4(in.color.rgb * weight, weight);
+}
+
+fn density_at(pixel: vec2<i32>, size: vec2<i32>) -> f32 {
+    return textureLoad(density_texture, clamp(pixel, vec2(0), size - 1), 0).a;
+}
+
+@fragment
+fn composite_fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(density_texture));
+    let pixel = vec2<i32>(in.position.xy);
+    let density = textureLoad(density_texture, pixel, 0);
+
+    let edge = settings.threshold * (1.0 - clamp(settings.softness, 0.0, 1.0));
+    let coverage = smoothstep(edge, settings.threshold, density.a);
+    if (coverage <= 0.0) {
+        discard;
+    }
+
+    // the surface normal follows the density gradient; texture y points down while
+    // view space y points up
+    let dx = density_at(pixel + vec2(1, 0), size) - density_at(pixel - vec2(1, 0), size);
+    let dy = density_at(pixel + vec2(0, 1), size) - density_at(pixel - vec2(0, 1), size);
+    let normal = normalize(vec3(-dx, dy, max(settings.threshold, 1e-3) * 0.5));
+
+    let light_dir = normalize(vec3(0.4, 0.6, 0.7));
+    let half_dir = normalize(light_dir + vec3(0.0, 0.0, 1.0));
+    let base_color = density.rgb / max(density.a, 1e-4);
+    let diffuse = 0.35 + 0.65 * max(dot(normal, light_dir), 0.0);
+    let highlight = settings.specular * pow(max(dot(normal, half_dir), 0.0), 48.0);
+
+    return vec4(base_color * diffuse + highlight, coverage);
+}
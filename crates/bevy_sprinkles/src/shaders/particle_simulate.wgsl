@@ -1,20 +1,26 @@
 #import bevy_render::maths::PI
+#import sprinkles::particles::{Particle, PARTICLE_FLAG_ACTIVE}
 #import bevy_sprinkles::common::{
-    Particle,
     CurveUniform,
+    curve_position,
     SubEmissionEntry,
     TrailHistoryEntry,
-    PARTICLE_FLAG_ACTIVE,
     EMITTER_FLAG_ROTATE_Y,
     EMITTER_FLAG_DISABLE_Z,
     EMITTER_FLAG_ANGLE_PER_AXIS,
+    EMITTER_FLAG_DAMPING_AS_FRICTION,
+    EMITTER_FLAG_DISABLE_ROTATION,
     EMISSION_FLAG_HAS_POSITION,
     EMISSION_FLAG_HAS_VELOCITY,
+    EMISSION_FLAG_HAS_PARENT,
     SUB_EMITTER_MODE_DISABLED,
     SUB_EMITTER_MODE_CONSTANT,
     SUB_EMITTER_MODE_AT_END,
     SUB_EMITTER_MODE_AT_COLLISION,
     SUB_EMITTER_MODE_AT_START,
+    SUB_EMITTER_MODE_TRAIL,
+    SUB_EMITTER_AMOUNT_SCALE_SCALE,
+    SUB_EMITTER_AMOUNT_SCALE_LIFETIME,
     hash,
     hash_to_float,
 }
@@ -57,7 +63,7 @@ struct EmitterParams {
     spawn_time_randomness: f32,
 
     emission_offset: vec3<f32>,
-    _pad1: f32,
+    emission_rate_scale: f32,
 
     emission_scale: vec3<f32>,
     _pad2: f32,
@@ -68,6 +74,49 @@ struct EmitterParams {
     emission_ring_axis: vec3<f32>,
     _pad4: f32,
 
+    emission_position_randomness: vec3<f32>,
+    emission_shell_thickness: f32,
+
+    emission_ring_arc: f32,
+    emission_ring_order: u32,
+    emission_edge_lifetime_scale: f32,
+    emission_edge_speed_scale: f32,
+
+    emission_image_size: vec2<f32>,
+    emission_image_sample_count: u32,
+    emission_image_inherit_color: u32,
+
+    gravity_scale_min: f32,
+    gravity_scale_max: f32,
+    damping_min: f32,
+    damping_max: f32,
+
+    point_gravity_position: vec3<f32>,
+    point_gravity_strength: f32,
+
+    radial_acceleration: AnimatedVelocity,
+
+    // wave
+    wave_axis: vec3<f32>,
+    wave_amplitude: f32,
+    wave_frequency: f32,
+    wave_phase_randomness: f32,
+    _wave_pad0: f32,
+    _wave_pad1: f32,
+
+    // spatially coherent randomness
+    spatial_randomness: f32,
+    spatial_randomness_scale: f32,
+    _spatial_pad0: f32,
+    _spatial_pad1: f32,
+
+    // shape morphing target
+    target_mode: u32,
+    target_count: u32,
+    target_image_size: vec2<f32>,
+
+    target_blend_over_lifetime: CurveUniform,
+
     direction: vec3<f32>,
     _pad5: f32,
 
@@ -84,7 +133,7 @@ struct EmitterParams {
     use_initial_color_gradient: u32,
     turbulence_enabled: u32,
     particle_flags: u32,
-    _pad7: u32,
+    amount_ratio: f32,
 
     initial_color: vec4<f32>,
 
@@ -102,6 +151,11 @@ struct EmitterParams {
 
     turbulence_influence_over_lifetime: CurveUniform,
 
+    turbulence_use_volumes: u32,
+    turbulence_volume_count: u32,
+    _turbulence_pad0: u32,
+    _turbulence_pad1: u32,
+
     radial_velocity: AnimatedVelocity,
 
     // collision
@@ -112,8 +166,13 @@ struct EmitterParams {
 
     collision_bounce: f32,
     collider_count: u32,
-    _collision_pad0: f32,
-    _collision_pad1: f32,
+    collision_max_substeps: u32,
+    collision_layers: u32,
+
+    ground_plane_enabled: u32,
+    ground_plane_height: f32,
+    _ground_plane_pad0: f32,
+    _ground_plane_pad1: f32,
 
     // angle
     angle_min: f32,
@@ -136,11 +195,21 @@ struct EmitterParams {
     sub_emitter_keep_velocity: u32,
 
     is_sub_emitter_target: u32,
+    sub_emitter_inherit_ratio: f32,
+    sub_emitter_speed_min: f32,
+    sub_emitter_speed_max: f32,
+
+    sub_emitter_direction: vec3<f32>,
+    sub_emitter_direction_enabled: u32,
+
+    sub_emitter_amount_scale: u32,
     _sub_emitter_pad0: u32,
     _sub_emitter_pad1: u32,
     _sub_emitter_pad2: u32,
 
     emitter_transform: mat4x4<f32>,
+    // emitter transform at the start of this step, for sub-frame spawn interpolation
+    prev_emitter_transform: mat4x4<f32>,
 
     // trail
     trail_size: u32,
@@ -152,6 +221,25 @@ struct EmitterParams {
     trail_effective_fps: f32,
     _trail_pad0: u32,
     _trail_pad1: u32,
+
+    // per-cycle variation
+    cycle_color_pick: f32,
+    cycle_color_randomness: f32,
+    initial_color_sampling: u32,
+    _cycle_pad1: f32,
+
+    // head particle slots, at least amount
+    capacity: u32,
+    _capacity_pad0: u32,
+    _capacity_pad1: u32,
+    _capacity_pad2: u32,
+
+    // camera-following area, world-space particles wrap around it
+    area_center: vec3<f32>,
+    area_wrap_enabled: u32,
+    area_half_extents: vec3<f32>,
+    // hits kept in accumulation_buffer, 0 when disabled
+    accumulation_capacity: u32,
 }
 
 struct Collider {
@@ -159,19 +247,49 @@ struct Collider {
     inverse_transform: mat4x4<f32>,
     extents: vec3<f32>,
     collider_type: u32,
+    velocity_transform: mat4x4<f32>,
+    inverted: u32,
+    layer_mask: u32,
+    _pad1: u32,
+    _pad2: u32,
 }
 
 struct ColliderArray {
     colliders: array<Collider, 32>,
 }
 
+struct TurbulenceVolume {
+    inverse_transform: mat4x4<f32>,
+    extents: vec3<f32>,
+    shape_type: u32,
+    falloff: f32,
+    strength: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+struct TurbulenceVolumeArray {
+    volumes: array<TurbulenceVolume, 16>,
+}
+
 const EMISSION_SHAPE_POINT: u32 = 0u;
 const EMISSION_SHAPE_SPHERE: u32 = 1u;
 const EMISSION_SHAPE_SPHERE_SURFACE: u32 = 2u;
 const EMISSION_SHAPE_BOX: u32 = 3u;
 const EMISSION_SHAPE_RING: u32 = 4u;
+const EMISSION_SHAPE_IMAGE: u32 = 5u;
+
+const RING_ORDER_RANDOM: u32 = 0u;
+const RING_ORDER_CLOCKWISE: u32 = 1u;
+const RING_ORDER_COUNTER_CLOCKWISE: u32 = 2u;
 
-const DRAW_ORDER_INDEX: u32 = 0u;
+const INITIAL_COLOR_SAMPLING_RANDOM: u32 = 0u;
+const INITIAL_COLOR_SAMPLING_SPAWN_ORDER: u32 = 1u;
+const INITIAL_COLOR_SAMPLING_EMISSION_POSITION: u32 = 2u;
+
+const TARGET_MODE_DISABLED: u32 = 0u;
+const TARGET_MODE_POINTS: u32 = 1u;
+const TARGET_MODE_IMAGE: u32 = 2u;
 
 // collision constants
 const COLLIDER_TYPE_SPHERE: u32 = 0u;
@@ -205,7 +323,15 @@ const COLLISION_EPSILON: f32 = 0.001;
 @group(0) @binding(21) var orbit_velocity_curve_sampler: sampler;
 @group(0) @binding(22) var directional_velocity_curve_texture: texture_2d<f32>;
 @group(0) @binding(23) var directional_velocity_curve_sampler: sampler;
+// bindings of optional features are only present in permutations that use them
+#ifdef HAS_COLLISION
+// devices with few storage buffer slots bind colliders as a uniform instead
+#ifdef COLLIDERS_UNIFORM
+@group(0) @binding(24) var<uniform> colliders: ColliderArray;
+#else
 @group(0) @binding(24) var<storage, read> colliders: ColliderArray;
+#endif
+#endif
 
 struct SubEmissionBuffer {
     particle_count: atomic<i32>,
@@ -213,9 +339,39 @@ struct SubEmissionBuffer {
     data: array<SubEmissionEntry>,
 }
 
+#ifdef HAS_SUBEMITTER
 @group(0) @binding(25) var<storage, read_write> dst_emission_buffer: SubEmissionBuffer;
+#endif
+#ifdef HAS_SUBEMITTER_TARGET
 @group(0) @binding(26) var<storage, read_write> src_emission_buffer: SubEmissionBuffer;
+#endif
 @group(0) @binding(27) var<storage, read_write> trail_history: array<TrailHistoryEntry>;
+@group(0) @binding(28) var radial_acceleration_curve_texture: texture_2d<f32>;
+@group(0) @binding(29) var radial_acceleration_curve_sampler: sampler;
+// baked spawn points: rg = pixel center uv, ba = pixel size in uv
+@group(0) @binding(30) var emission_mask_texture: texture_2d<f32>;
+// target positions: xyz per point, or baked spawn points in the same layout as the emission mask
+@group(0) @binding(31) var target_texture: texture_2d<f32>;
+@group(0) @binding(32) var target_blend_curve_texture: texture_2d<f32>;
+@group(0) @binding(33) var target_blend_curve_sampler: sampler;
+#ifdef HAS_TURBULENCE
+@group(0) @binding(34) var<uniform> turbulence_volumes: TurbulenceVolumeArray;
+#endif
+
+// only bound for emitters that record hits, so devices with few storage buffer slots can
+// still simulate everything else
+#ifdef HAS_ACCUMULATION
+// matches `ParticleAccumulation` in sprinkles::particles
+struct AccumulationBuffer {
+    count: atomic<u32>,
+    capacity: u32,
+    _pad0: u32,
+    _pad1: u32,
+    entries: array<vec4<f32>>,
+}
+
+@group(0) @binding(35) var<storage, read_write> accumulation_buffer: AccumulationBuffer;
+#endif
 
 @compute @workgroup_size(64)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
@@ -224,7 +380,7 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     // trail pass 1: process trail segments (copy head state, offset position)
     if (params.trail_size > 1u && params.trail_pass == 1u) {
         // thread_idx maps to trail segments (skipping heads)
-        if (thread_idx >= params.amount * (params.trail_size - 1u)) {
+        if (thread_idx >= params.capacity * (params.trail_size - 1u)) {
             return;
         }
 
@@ -289,16 +445,17 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             let age = head.custom.x;
             let dt = section_frac * min(age, params.trail_stretch_time);
             segment_age = age - dt;
-            let past_pos = head.position.xyz - vel * dt + 0.5 * params.gravity * dt * dt;
+            let gravity = get_particle_gravity(bitcast<u32>(head.custom.z));
+            let past_pos = head.position.xyz - vel * dt + 0.5 * gravity * dt * dt;
             p.position = vec4(past_pos, head.position.w);
         }
 
         // recompute color/alpha for this trail segment's effective age
         let seed = bitcast<u32>(head.custom.z);
         let lifetime = head.velocity.w;
-        let initial_alpha = get_initial_alpha(seed);
+        let initial_alpha = get_initial_alpha(seed, particle_idx);
         p.color.a = get_alpha_at_lifetime(initial_alpha, segment_age, lifetime);
-        let initial_rgb = get_initial_color_rgb(seed);
+        let initial_rgb = get_initial_color_rgb(seed, particle_idx);
         let emission = get_emission_at_lifetime(segment_age, lifetime);
         let col_life = get_color_over_lifetime(segment_age, lifetime);
         p.color = vec4(initial_rgb * emission * col_life.rgb, p.color.a * col_life.a);
@@ -311,13 +468,13 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     var idx: u32;
     if (params.trail_size > 1u) {
         // remap thread index to head particle slot
-        if (thread_idx >= params.amount) {
+        if (thread_idx >= params.capacity) {
             return;
         }
         idx = thread_idx * params.trail_size;
     } else {
         idx = thread_idx;
-        if (idx >= params.amount) {
+        if (idx >= params.capacity) {
             return;
         }
     }
@@ -352,52 +509,72 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let phase = base_phase + hash_to_float(particle_idx) * params.spawn_time_randomness;
     let adjusted_phase = fract(phase * (1.0 - params.explosiveness));
 
-    if (params.is_sub_emitter_target != 0u) {
-        // sub-emitter target mode: inactive particles consume from source buffer
-        if (!is_active) {
-            let src_index = atomicAdd(&src_emission_buffer.particle_count, -1) - 1;
+#ifdef HAS_SUBEMITTER_TARGET
+    // sub-emitter target mode: inactive particles consume from source buffer
+    if (!is_active) {
+        let src_index = atomicAdd(&src_emission_buffer.particle_count, -1) - 1;
 
-            if (src_index >= 0) {
-                let entry = src_emission_buffer.data[src_index];
-                p = spawn_particle(particle_idx);
+        if (src_index >= 0) {
+            let entry = src_emission_buffer.data[src_index];
+            p = spawn_particle(particle_idx, 1.0);
 
-                if ((entry.flags & EMISSION_FLAG_HAS_POSITION) != 0u) {
-                    p.position = vec4(entry.position.xyz, p.position.w);
-                }
+            if ((entry.flags & EMISSION_FLAG_HAS_POSITION) != 0u) {
+                p.position = vec4(entry.position.xyz, p.position.w);
+            }
+
+            if ((entry.flags & EMISSION_FLAG_HAS_PARENT) != 0u) {
+                p.angles.w = bitcast<f32>(entry.parent_index + 1u);
+            }
 
-                if ((entry.flags & EMISSION_FLAG_HAS_VELOCITY) != 0u) {
-                    p.velocity = vec4(entry.velocity.xyz, p.velocity.w);
-                    if length(entry.velocity.xyz) > 0.0 {
-                        p.alignment_dir = vec4(normalize(entry.velocity.xyz), p.alignment_dir.w);
-                    }
+            if ((entry.flags & EMISSION_FLAG_HAS_VELOCITY) != 0u) {
+                let velocity = p.velocity.xyz * entry.velocity.w + entry.velocity.xyz;
+                p.velocity = vec4(velocity, p.velocity.w);
+                if length(velocity) > 0.0 {
+                    p.alignment_dir = vec4(normalize(velocity), p.alignment_dir.w);
                 }
             }
-        } else {
-            p = update_particle(p);
         }
     } else {
-        // normal phase-based emission
-        var should_restart = false;
-        if (params.emitting != 0u) {
-            if (params.system_phase < params.prev_system_phase) {
-                // phase wrapped around
-                should_restart = adjusted_phase >= params.prev_system_phase ||
-                               adjusted_phase < params.system_phase;
-            } else {
-                should_restart = adjusted_phase >= params.prev_system_phase &&
-                               adjusted_phase < params.system_phase;
-            }
+        p = update_particle(p, particle_idx);
+    }
+#else
+    // normal phase-based emission
+    var should_restart = false;
+    // a fixed per-particle roll decides which slots amount_ratio leaves out
+    let in_amount_ratio = params.amount_ratio >= 1.0 ||
+                          hash_to_float(particle_idx ^ 0x5bd1e995u) < params.amount_ratio;
+    // slots past amount are extra capacity that phase-based emission never uses
+    if (params.emitting != 0u && in_amount_ratio && particle_idx < params.amount) {
+        if (params.system_phase < params.prev_system_phase) {
+            // phase wrapped around
+            should_restart = adjusted_phase >= params.prev_system_phase ||
+                           adjusted_phase < params.system_phase;
+        } else {
+            should_restart = adjusted_phase >= params.prev_system_phase &&
+                           adjusted_phase < params.system_phase;
         }
+    }
+    // rate_over_time thins out spawns with a roll that changes every cycle
+    if (should_restart && params.emission_rate_scale < 1.0) {
+        let rate_roll = hash_to_float(particle_idx ^ hash(params.cycle + params.random_seed));
+        should_restart = rate_roll < params.emission_rate_scale;
+    }
 
-        if (should_restart) {
-            p = spawn_particle(particle_idx);
-        } else if (is_active) {
-            p = update_particle(p);
-        }
+    if (should_restart) {
+        p = spawn_particle(particle_idx, spawn_step_fraction(adjusted_phase));
+    } else if (is_active) {
+        p = update_particle(p, particle_idx);
     }
+#endif
 
     particles[idx] = p;
 
+#ifdef HAS_SUBEMITTER
+    if (params.sub_emitter_mode == SUB_EMITTER_MODE_TRAIL) {
+        write_trail_parent_slot(particle_idx, p);
+    }
+#endif
+
     // write position history for trail readback
     if (params.trail_history_size > 0u) {
         let now_active = (bitcast<u32>(p.custom.w) & PARTICLE_FLAG_ACTIVE) != 0u;
@@ -415,6 +592,23 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 }
 
+// how far through this step a phase-based spawn happened (0 = start, 1 = end)
+fn spawn_step_fraction(adjusted_phase: f32) -> f32 {
+    var span = params.system_phase - params.prev_system_phase;
+    var offset = adjusted_phase - params.prev_system_phase;
+    // phase wrapped around
+    if (span < 0.0) {
+        span += 1.0;
+    }
+    if (offset < 0.0) {
+        offset += 1.0;
+    }
+    if (span <= 0.0) {
+        return 1.0;
+    }
+    return clamp(offset / span, 0.0, 1.0);
+}
+
 // transforms a local-space position to spawn space via emitter_transform
 // (identity for local coords, world matrix for global coords)
 fn transform_point(p: vec3<f32>) -> vec3<f32> {
@@ -484,7 +678,58 @@ fn transport_ref_up(ref_up: vec3<f32>, old_dir: vec3<f32>, new_dir: vec3<f32>) -
     return ref_up;
 }
 
-fn get_emission_offset(seed: u32) -> vec3<f32> {
+// random radial offset within the shell band, centered on the surface
+fn shell_offset(seed: u32) -> f32 {
+    return (hash_to_float(seed) - 0.5) * params.emission_shell_thickness;
+}
+
+// angle around the ring: random within the arc, or evenly spaced in particle index order
+fn get_ring_angle(seed: u32, idx: u32) -> f32 {
+    let arc = params.emission_ring_arc;
+    if (params.emission_ring_order == RING_ORDER_RANDOM) {
+        return hash_to_float(seed) * arc;
+    }
+
+    // a full circle leaves a gap after the last particle so it doesn't overlap the first
+    let count = f32(max(params.amount, 1u));
+    let full_circle = arc >= 2.0 * PI - 0.0001;
+    let divisions = select(max(count - 1.0, 1.0), count, full_circle);
+    let angle = f32(idx) / divisions * arc;
+    return select(angle, -angle, params.emission_ring_order == RING_ORDER_CLOCKWISE);
+}
+
+// picks a baked spawn point and jitters it within its source pixel
+fn get_emission_mask_uv(seed: u32) -> vec2<f32> {
+    let width = textureDimensions(emission_mask_texture).x;
+    let i = hash(seed) % params.emission_image_sample_count;
+    let texel = textureLoad(emission_mask_texture, vec2(i % width, i / width), 0);
+    let jitter = vec2(hash_to_float(seed + 1u), hash_to_float(seed + 2u)) - 0.5;
+    return texel.xy + jitter * texel.zw;
+}
+
+// color of the source pixel a spawn was picked from, baked right after the spawn points
+// offset that moves a position leaving the area box to the opposite side
+fn get_area_wrap_offset(position: vec3<f32>) -> vec3<f32> {
+    if (params.area_wrap_enabled == 0u) {
+        return vec3(0.0);
+    }
+    let size = params.area_half_extents * 2.0;
+    let wraps = floor((position - params.area_center + params.area_half_extents) / max(size, vec3(1e-6)));
+    return select(-wraps * size, vec3(0.0), size <= vec3(0.0));
+}
+
+fn get_inherited_color(seed: u32) -> vec4<f32> {
+    if (params.emission_shape != EMISSION_SHAPE_IMAGE
+        || params.emission_image_sample_count == 0u
+        || params.emission_image_inherit_color == 0u) {
+        return vec4(1.0);
+    }
+    let width = textureDimensions(emission_mask_texture).x;
+    let i = hash(seed) % params.emission_image_sample_count + params.emission_image_sample_count;
+    return textureLoad(emission_mask_texture, vec2(i % width, i / width), 0);
+}
+
+fn get_emission_offset(seed: u32, idx: u32) -> vec3<f32> {
     var pos = vec3(0.0);
 
     switch params.emission_shape {
@@ -514,7 +759,7 @@ fn get_emission_offset(seed: u32) -> vec3<f32> {
 
             let theta = 2.0 * PI * u;
             let phi = acos(2.0 * v - 1.0);
-            let r = params.emission_sphere_radius;
+            let r = params.emission_sphere_radius + shell_offset(seed + 3u);
 
             pos = vec3(
                 r * sin(phi) * cos(theta),
@@ -530,24 +775,41 @@ fn get_emission_offset(seed: u32) -> vec3<f32> {
             pos = vec3(u, v, w) * params.emission_box_extents;
         }
         case EMISSION_SHAPE_RING: {
-            let u = hash_to_float(seed);
             let v = hash_to_float(seed + 1u);
             let h = hash_to_float(seed + 2u);
 
-            let theta = 2.0 * PI * u;
+            let theta = get_ring_angle(seed, idx);
             let r_range = params.emission_ring_radius - params.emission_ring_inner_radius;
-            let r = params.emission_ring_inner_radius + sqrt(v) * r_range;
+            let r = params.emission_ring_inner_radius + sqrt(v) * r_range + shell_offset(seed + 3u);
             let height_offset = (h - 0.5) * params.emission_ring_height;
 
             // ring local space (ring lies in XY plane, axis is Z)
             let local_pos = vec3(r * cos(theta), r * sin(theta), height_offset);
             pos = rotate_to_axis(local_pos, params.emission_ring_axis);
         }
+        case EMISSION_SHAPE_IMAGE: {
+            if (params.emission_image_sample_count > 0u) {
+                // image lies in the XY plane with v pointing down
+                let uv = get_emission_mask_uv(seed);
+                pos = vec3(
+                    (uv.x - 0.5) * params.emission_image_size.x,
+                    (0.5 - uv.y) * params.emission_image_size.y,
+                    0.0,
+                );
+            }
+        }
         default: {
             pos = vec3(0.0);
         }
     }
 
+    let jitter = vec3(
+        hash_to_float(seed + 5u),
+        hash_to_float(seed + 6u),
+        hash_to_float(seed + 7u),
+    ) * 2.0 - 1.0;
+    pos += jitter * params.emission_position_randomness;
+
     let result = zero_z_if(
         pos * params.emission_scale + params.emission_offset,
         (params.particle_flags & EMITTER_FLAG_DISABLE_Z) != 0u,
@@ -556,6 +818,44 @@ fn get_emission_offset(seed: u32) -> vec3<f32> {
     return result;
 }
 
+// normalized distance of a spawn from the center of the emission shape (0 = center, 1 = edge),
+// recomputed from the same hashes get_emission_offset uses
+fn get_emission_edge_factor(seed: u32) -> f32 {
+    switch params.emission_shape {
+        case EMISSION_SHAPE_SPHERE: {
+            return pow(hash_to_float(seed + 2u), 1.0 / 3.0);
+        }
+        case EMISSION_SHAPE_SPHERE_SURFACE: {
+            return 1.0;
+        }
+        case EMISSION_SHAPE_BOX: {
+            let u = abs(hash_to_float(seed) * 2.0 - 1.0);
+            let v = abs(hash_to_float(seed + 1u) * 2.0 - 1.0);
+            let w = abs(hash_to_float(seed + 2u) * 2.0 - 1.0);
+            return max(u, max(v, w));
+        }
+        case EMISSION_SHAPE_RING: {
+            if (params.emission_ring_radius <= 0.0) {
+                return 1.0;
+            }
+            let v = hash_to_float(seed + 1u);
+            let r_range = params.emission_ring_radius - params.emission_ring_inner_radius;
+            let r = params.emission_ring_inner_radius + sqrt(v) * r_range;
+            return clamp(r / params.emission_ring_radius, 0.0, 1.0);
+        }
+        case EMISSION_SHAPE_IMAGE: {
+            if (params.emission_image_sample_count == 0u) {
+                return 0.0;
+            }
+            let d = abs(get_emission_mask_uv(seed) - 0.5) * 2.0;
+            return clamp(max(d.x, d.y), 0.0, 1.0);
+        }
+        default: {
+            return 0.0;
+        }
+    }
+}
+
 fn rotate_to_axis(v: vec3<f32>, axis: vec3<f32>) -> vec3<f32> {
     let z_axis = vec3(0.0, 0.0, 1.0);
     let target_axis = normalize(axis);
@@ -577,7 +877,8 @@ fn rotate_to_axis(v: vec3<f32>, axis: vec3<f32>) -> vec3<f32> {
     return v * cos_angle + cross(rot_axis, v) * sin_angle + rot_axis * dot(rot_axis, v) * (1.0 - cos_angle);
 }
 
-fn get_emission_velocity(seed: u32) -> vec3<f32> {
+// speed_t picks the speed within the initial velocity range
+fn get_emission_velocity(seed: u32, speed_t: f32) -> vec3<f32> {
     var dir = normalize(params.direction);
     if (length(params.direction) < 0.0001) {
         dir = vec3(1.0, 0.0, 0.0);
@@ -612,21 +913,30 @@ fn get_emission_velocity(seed: u32) -> vec3<f32> {
         dir = normalize(dir);
     }
 
-    let vel_t = hash_to_float(seed + 2u);
-    let speed = mix(params.initial_velocity_min, params.initial_velocity_max, vel_t);
+    let speed = mix(params.initial_velocity_min, params.initial_velocity_max, speed_t);
 
     let result = zero_z_if(dir * speed, (params.particle_flags & EMITTER_FLAG_DISABLE_Z) != 0u);
 
     return result;
 }
 
-fn get_initial_scale(seed: u32) -> f32 {
-    let t = hash_to_float(seed);
+// random value in [0, 1] for a spawn attribute, blended toward a noise sample of the
+// local spawn position so nearby particles share tendencies
+fn spatial_random(seed: u32, local_pos: vec3<f32>, channel: f32) -> f32 {
+    let rng = hash_to_float(seed);
+    if (params.spatial_randomness <= 0.0) {
+        return rng;
+    }
+    let coord = vec4(local_pos / max(params.spatial_randomness_scale, 0.0001), channel * 17.0);
+    let noise = clamp(noise_4d(coord) * 0.5 + 0.5, 0.0, 1.0);
+    return mix(rng, noise, params.spatial_randomness);
+}
+
+fn get_initial_scale(t: f32) -> f32 {
     return mix(params.scale_min, params.scale_max, t);
 }
 
-fn get_initial_angle(seed: u32) -> f32 {
-    let t = hash_to_float(seed);
+fn get_initial_angle(t: f32) -> f32 {
     return mix(params.angle_min, params.angle_max, t);
 }
 
@@ -635,14 +945,19 @@ fn get_initial_angular_velocity(seed: u32) -> f32 {
     return mix(params.angular_velocity.min, params.angular_velocity.max, t);
 }
 
-fn compute_angles(seed: u32, age: f32, lifetime: f32) -> vec3<f32> {
+// angle_t picks the initial angle within the angle range
+fn compute_angles(seed: u32, angle_t: f32, age: f32, lifetime: f32) -> vec3<f32> {
+    if (params.particle_flags & EMITTER_FLAG_DISABLE_ROTATION) != 0u {
+        return vec3(0.0);
+    }
+
     let lifetime_frac = clamp(age / lifetime, 0.0, 1.0);
-    let base_angle = get_initial_angle(seed);
+    let base_angle = get_initial_angle(angle_t);
     let is_per_axis = (params.particle_flags & EMITTER_FLAG_ANGLE_PER_AXIS) != 0u;
 
     var angles = vec3(base_angle);
 
-    if (params.angle_over_lifetime.enabled != 0u) {
+    if (curve_enabled(params.angle_over_lifetime)) {
         if (is_per_axis) {
             let curve_xyz = sample_spline_curve_xyz(
                 angle_over_lifetime_texture,
@@ -664,7 +979,7 @@ fn compute_angles(seed: u32, age: f32, lifetime: f32) -> vec3<f32> {
 
     let angular_vel = get_initial_angular_velocity(seed + 1u);
     if (abs(angular_vel) > 0.0001) {
-        if (params.angular_velocity.curve.enabled != 0u) {
+        if (curve_enabled(params.angular_velocity.curve)) {
             let vel_curve = sample_spline_curve(
                 angular_velocity_curve_texture,
                 angular_velocity_curve_sampler,
@@ -800,13 +1115,22 @@ fn get_turbulence_influence(seed: u32) -> f32 {
     return mix(params.turbulence_influence_min, params.turbulence_influence_max, t);
 }
 
+// curve sampling is compiled out entirely for emitters that don't use any curves
+fn curve_enabled(curve: CurveUniform) -> bool {
+#ifdef HAS_CURVES
+    return curve.enabled != 0u;
+#else
+    return false;
+#endif
+}
+
 fn sample_spline_curve(
     tex: texture_2d<f32>,
     samp: sampler,
     curve: CurveUniform,
     t: f32
 ) -> f32 {
-    let raw = textureSampleLevel(tex, samp, vec2(t, 0.5), 0.0).r;
+    let raw = textureSampleLevel(tex, samp, vec2(curve_position(curve, t), 0.5), 0.0).r;
     return mix(curve.min_x, curve.max_x, raw);
 }
 
@@ -816,14 +1140,38 @@ fn sample_spline_curve_xyz(
     curve: CurveUniform,
     t: f32
 ) -> vec3<f32> {
-    let raw = textureSampleLevel(tex, samp, vec2(t, 0.5), 0.0).rgb;
+    let raw = textureSampleLevel(tex, samp, vec2(curve_position(curve, t), 0.5), 0.0).rgb;
     let min_val = vec3(curve.min_x, curve.min_y, curve.min_z);
     let max_val = vec3(curve.max_x, curve.max_y, curve.max_z);
     return mix(min_val, max_val, raw);
 }
 
+#ifdef HAS_TURBULENCE
+// strongest turbulence volume weight at a world position, fading to zero at each surface
+fn get_turbulence_volume_mask(pos: vec3<f32>) -> f32 {
+    var mask = 0.0;
+    for (var i = 0u; i < params.turbulence_volume_count; i++) {
+        let volume = turbulence_volumes.volumes[i];
+        let local_pos = (volume.inverse_transform * vec4(pos, 1.0)).xyz;
+        var signed_distance = 0.0;
+        if (volume.shape_type == COLLIDER_TYPE_SPHERE) {
+            signed_distance = length(local_pos) - volume.extents.x;
+        } else {
+            let q = abs(local_pos) - volume.extents;
+            signed_distance = length(max(q, vec3(0.0))) + min(max(q.x, max(q.y, q.z)), 0.0);
+        }
+        var weight = select(0.0, 1.0, signed_distance <= 0.0);
+        if (volume.falloff > 0.0) {
+            weight = clamp(-signed_distance / volume.falloff, 0.0, 1.0);
+        }
+        mask = max(mask, weight * volume.strength);
+    }
+    return mask;
+}
+#endif
+
 fn get_turbulence_influence_at_lifetime(base_influence: f32, age: f32, lifetime: f32) -> f32 {
-    if (params.turbulence_influence_over_lifetime.enabled == 0u) {
+    if (!curve_enabled(params.turbulence_influence_over_lifetime)) {
         return base_influence;
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -837,7 +1185,7 @@ fn get_turbulence_influence_at_lifetime(base_influence: f32, age: f32, lifetime:
 }
 
 fn get_scale_at_lifetime(initial_scale: f32, age: f32, lifetime: f32) -> f32 {
-    if (params.scale_over_lifetime.enabled == 0u) {
+    if (!curve_enabled(params.scale_over_lifetime)) {
         return initial_scale;
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -850,26 +1198,43 @@ fn get_scale_at_lifetime(initial_scale: f32, age: f32, lifetime: f32) -> f32 {
     return initial_scale * curve_value;
 }
 
-fn get_initial_alpha(seed: u32) -> f32 {
-    if (params.use_initial_color_gradient == 0u) {
-        return params.initial_color.a;
-    } else {
-        let t = hash_to_float(seed + 30u);
-        return textureSampleLevel(gradient_texture, gradient_sampler, vec2(t, 0.5), 0.0).a;
+fn get_initial_alpha(seed: u32, idx: u32) -> f32 {
+    return get_initial_color(seed, idx).a;
+}
+
+fn get_initial_color_rgb(seed: u32, idx: u32) -> vec3<f32> {
+    return get_initial_color(seed, idx).rgb;
+}
+
+fn get_initial_color(seed: u32, idx: u32) -> vec4<f32> {
+    var color = params.initial_color;
+    if (params.use_initial_color_gradient != 0u) {
+        let t = get_initial_color_t(seed, idx);
+        color = textureSampleLevel(gradient_texture, gradient_sampler, vec2(t, 0.5), 0.0);
     }
+    return color * get_inherited_color(seed);
 }
 
-fn get_initial_color_rgb(seed: u32) -> vec3<f32> {
-    if (params.use_initial_color_gradient == 0u) {
-        return params.initial_color.rgb;
-    } else {
-        let t = hash_to_float(seed + 30u);
-        return textureSampleLevel(gradient_texture, gradient_sampler, vec2(t, 0.5), 0.0).rgb;
+// position in the initial color gradient, pulled toward the cycle's shared pick
+fn get_initial_color_t(seed: u32, idx: u32) -> f32 {
+    var t: f32;
+    switch params.initial_color_sampling {
+        case INITIAL_COLOR_SAMPLING_SPAWN_ORDER: {
+            // the same slot spawns at the same point of every cycle
+            t = f32(idx % max(params.amount, 1u)) / f32(max(params.amount, 1u));
+        }
+        case INITIAL_COLOR_SAMPLING_EMISSION_POSITION: {
+            t = get_emission_edge_factor(seed);
+        }
+        default: {
+            t = hash_to_float(seed + 30u);
+        }
     }
+    return mix(t, params.cycle_color_pick, params.cycle_color_randomness);
 }
 
 fn get_alpha_at_lifetime(initial_alpha: f32, age: f32, lifetime: f32) -> f32 {
-    if (params.alpha_over_lifetime.enabled == 0u) {
+    if (!curve_enabled(params.alpha_over_lifetime)) {
         return initial_alpha;
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -883,7 +1248,7 @@ fn get_alpha_at_lifetime(initial_alpha: f32, age: f32, lifetime: f32) -> f32 {
 }
 
 fn get_emission_at_lifetime(age: f32, lifetime: f32) -> f32 {
-    if (params.emission_over_lifetime.enabled == 0u) {
+    if (!curve_enabled(params.emission_over_lifetime)) {
         return 1.0;
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -907,7 +1272,7 @@ fn get_initial_radial_velocity(seed: u32) -> f32 {
 }
 
 fn get_radial_velocity_curve_multiplier(age: f32, lifetime: f32) -> f32 {
-    if (params.radial_velocity.curve.enabled == 0u) {
+    if (!curve_enabled(params.radial_velocity.curve)) {
         return 1.0;
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -919,6 +1284,31 @@ fn get_radial_velocity_curve_multiplier(age: f32, lifetime: f32) -> f32 {
     );
 }
 
+// velocity of the sinusoidal sway, the derivative of amplitude * sin(omega * age + phase)
+fn get_wave_displacement(seed: u32, age: f32) -> vec3<f32> {
+    if (params.wave_amplitude == 0.0) {
+        return vec3(0.0);
+    }
+    let phase = hash_to_float(seed) * 2.0 * PI * params.wave_phase_randomness;
+    let omega = 2.0 * PI * params.wave_frequency;
+    let speed = params.wave_amplitude * omega * cos(omega * age + phase);
+    return transform_direction(params.wave_axis) * speed;
+}
+
+fn get_radial_acceleration(seed: u32, age: f32, lifetime: f32) -> f32 {
+    let base = mix(params.radial_acceleration.min, params.radial_acceleration.max, hash_to_float(seed));
+    if (!curve_enabled(params.radial_acceleration.curve)) {
+        return base;
+    }
+    let t = clamp(age / lifetime, 0.0, 1.0);
+    return base * sample_spline_curve(
+        radial_acceleration_curve_texture,
+        radial_acceleration_curve_sampler,
+        params.radial_acceleration.curve,
+        t
+    );
+}
+
 // computes radial displacement (movement away from or toward velocity_pivot)
 fn get_radial_displacement(
     position: vec3<f32>,
@@ -974,13 +1364,32 @@ fn get_radial_displacement(
     return radial_displacement;
 }
 
+// gravity scaled by the particle's random gravity multiplier (negative values rise)
+fn get_particle_gravity(seed: u32) -> vec3<f32> {
+    let t = hash_to_float(seed + 100u);
+    return params.gravity * mix(params.gravity_scale_min, params.gravity_scale_max, t);
+}
+
+fn apply_damping(velocity: vec3<f32>, seed: u32, dt: f32) -> vec3<f32> {
+    let damping = mix(params.damping_min, params.damping_max, hash_to_float(seed + 140u));
+    let speed = length(velocity);
+    if (damping <= 0.0 || speed < 0.0001) {
+        return velocity;
+    }
+    var deceleration = damping;
+    if ((params.particle_flags & EMITTER_FLAG_DAMPING_AS_FRICTION) != 0u) {
+        deceleration *= speed * speed;
+    }
+    return velocity * (max(speed - deceleration * dt, 0.0) / speed);
+}
+
 fn get_initial_orbit_velocity(seed: u32) -> f32 {
     let t = hash_to_float(seed);
     return mix(params.orbit_velocity.min, params.orbit_velocity.max, t);
 }
 
 fn get_orbit_velocity_curve_multiplier(age: f32, lifetime: f32) -> vec3<f32> {
-    if (params.orbit_velocity.curve.enabled == 0u) {
+    if (!curve_enabled(params.orbit_velocity.curve)) {
         return vec3(1.0);
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -1062,7 +1471,7 @@ fn get_initial_directional_velocity(seed: u32) -> f32 {
 }
 
 fn get_directional_velocity_curve_value(age: f32, lifetime: f32) -> vec3<f32> {
-    if (params.directional_velocity.curve.enabled == 0u) {
+    if (!curve_enabled(params.directional_velocity.curve)) {
         return vec3(1.0);
     }
     let t = clamp(age / lifetime, 0.0, 1.0);
@@ -1082,7 +1491,7 @@ fn get_directional_displacement(
 ) -> vec3<f32> {
     let curve_value = get_directional_velocity_curve_value(age, lifetime);
 
-    if (params.directional_velocity.curve.enabled != 0u) {
+    if (curve_enabled(params.directional_velocity.curve)) {
         return curve_value * directional_velocity;
     }
 
@@ -1094,12 +1503,15 @@ fn get_directional_displacement(
     return emission_direction * effective_velocity;
 }
 
+#ifdef HAS_COLLISION
 // collision detection
 
 struct CollisionResult {
     collided: bool,
     normal: vec3<f32>,
     depth: f32,
+    // velocity of the collider surface at the contact
+    velocity: vec3<f32>,
 }
 
 fn get_particle_collision_size(scale: f32) -> f32 {
@@ -1124,6 +1536,18 @@ fn check_sphere_collision(
     let collider_radius = collider.extents.x;
 
     let dist = length(local_pos);
+
+    // inverted: keep the particle inside, pushing it back toward the center
+    if (collider.inverted != 0u) {
+        let overflow = dist + particle_radius - collider_radius;
+        if (overflow >= -COLLISION_EPSILON && dist > COLLISION_EPSILON) {
+            result.collided = true;
+            result.depth = max(overflow, 0.0);
+            result.normal = normalize((collider.transform * vec4(-local_pos / dist, 0.0)).xyz);
+        }
+        return result;
+    }
+
     let penetration = dist - (particle_radius + collider_radius);
 
     if (penetration <= COLLISION_EPSILON) {
@@ -1157,6 +1581,29 @@ fn check_box_collision(
     let abs_pos = abs(local_pos);
     let sgn_pos = sign(local_pos);
 
+    // inverted: keep the particle inside, pushing it back across the face it overflows most
+    if (collider.inverted != 0u) {
+        let overflow = abs_pos + particle_radius - extents;
+        var local_normal: vec3<f32>;
+        var depth: f32;
+        if (overflow.x >= overflow.y && overflow.x >= overflow.z) {
+            local_normal = vec3(-sgn_pos.x, 0.0, 0.0);
+            depth = overflow.x;
+        } else if (overflow.y >= overflow.z) {
+            local_normal = vec3(0.0, -sgn_pos.y, 0.0);
+            depth = overflow.y;
+        } else {
+            local_normal = vec3(0.0, 0.0, -sgn_pos.z);
+            depth = overflow.z;
+        }
+        if (depth >= -COLLISION_EPSILON && any(local_normal != vec3(0.0))) {
+            result.collided = true;
+            result.depth = max(depth, 0.0);
+            result.normal = normalize((collider.transform * vec4(local_normal, 0.0)).xyz);
+        }
+        return result;
+    }
+
     // point outside box
     if (any(abs_pos > extents)) {
         let closest = min(abs_pos, extents);
@@ -1208,9 +1655,15 @@ fn process_collisions(
     final_result.collided = false;
     final_result.normal = vec3(0.0);
     final_result.depth = 0.0;
+    final_result.velocity = vec3(0.0);
+    var deepest_depth = 0.0;
 
     for (var i = 0u; i < params.collider_count; i++) {
         let collider = colliders.colliders[i];
+        // tagged colliders only affect systems that opted into their layer
+        if (collider.layer_mask != 0u && (collider.layer_mask & params.collision_layers) == 0u) {
+            continue;
+        }
         var col_result: CollisionResult;
 
         switch collider.collider_type {
@@ -1226,9 +1679,14 @@ fn process_collisions(
         }
 
         if (col_result.collided) {
+            col_result.velocity = (collider.velocity_transform * vec4(particle_pos, 1.0)).xyz;
             if (!final_result.collided) {
                 final_result = col_result;
             } else {
+                // the deepest contact decides how the particle is carried
+                if (col_result.depth > deepest_depth) {
+                    final_result.velocity = col_result.velocity;
+                }
                 // accumulate multiple collisions
                 let c = final_result.normal * final_result.depth;
                 let new_c = c + col_result.normal * max(0.0, col_result.depth - dot(col_result.normal, c));
@@ -1237,13 +1695,95 @@ fn process_collisions(
                     final_result.normal = normalize(new_c);
                 }
             }
+            deepest_depth = max(deepest_depth, col_result.depth);
+        }
+    }
+
+    if (params.ground_plane_enabled != 0u) {
+        let depth = params.ground_plane_height + particle_radius - particle_pos.y;
+        if (depth >= -COLLISION_EPSILON) {
+            let ground_depth = max(depth, 0.0);
+            if (!final_result.collided) {
+                final_result.collided = true;
+                final_result.normal = vec3(0.0, 1.0, 0.0);
+                final_result.depth = ground_depth;
+            } else {
+                let c = final_result.normal * final_result.depth;
+                let new_c = c + vec3(0.0, 1.0, 0.0) * max(0.0, ground_depth - c.y);
+                final_result.depth = length(new_c);
+                if (final_result.depth > COLLISION_EPSILON) {
+                    final_result.normal = normalize(new_c);
+                }
+            }
         }
     }
 
     return final_result;
 }
 
-fn emit_subparticle(position: vec3<f32>, scale: f32, velocity: vec3<f32>, flags: u32) -> bool {
+struct SweptCollision {
+    collision: CollisionResult,
+    position: vec3<f32>,
+}
+
+// tests points along a step's travel, at most one particle radius apart, so fast particles
+// can't skip over colliders thinner than the distance they move in one step
+fn sweep_collisions(from: vec3<f32>, to: vec3<f32>, particle_radius: f32) -> SweptCollision {
+    let travel = length(to - from);
+    let needed = u32(ceil(travel / max(particle_radius, COLLISION_EPSILON)));
+    let substeps = clamp(needed, 1u, max(params.collision_max_substeps, 1u));
+
+    var result: SweptCollision;
+    for (var i = 1u; i <= substeps; i++) {
+        result.position = mix(from, to, f32(i) / f32(substeps));
+        result.collision = process_collisions(result.position, particle_radius);
+        if (result.collision.collided) {
+            break;
+        }
+    }
+    return result;
+}
+#endif
+
+#ifdef HAS_SUBEMITTER
+// packs the launch velocity so the child computes own_velocity * w + xyz, blending its
+// own emission velocity towards the inherited one and applying the speed multiplier
+fn get_sub_emitter_velocity(parent_velocity: vec3<f32>, seed: u32) -> vec4<f32> {
+    var inherited = parent_velocity;
+    if (params.sub_emitter_direction_enabled != 0u) {
+        inherited = params.sub_emitter_direction * length(parent_velocity);
+    }
+    let multiplier = mix(
+        params.sub_emitter_speed_min,
+        params.sub_emitter_speed_max,
+        hash_to_float(seed),
+    );
+    let ratio = params.sub_emitter_inherit_ratio;
+    return vec4(inherited * ratio * multiplier, (1.0 - ratio) * multiplier);
+}
+
+// trail sub-emitters keep one slot per parent particle after the emission entries,
+// which attached children read to move along with their parent
+fn write_trail_parent_slot(particle_idx: u32, p: Particle) {
+    let is_active = (bitcast<u32>(p.custom.w) & PARTICLE_FLAG_ACTIVE) != 0u;
+    let slot = dst_emission_buffer.particle_max + particle_idx;
+    dst_emission_buffer.data[slot].position = vec4(p.position.xyz, select(0.0, 1.0, is_active));
+    dst_emission_buffer.data[slot].velocity = vec4(p.velocity.xyz, 0.0);
+}
+#endif
+
+fn get_trail_parent_slot(p: Particle) -> u32 {
+    return bitcast<u32>(p.angles.w);
+}
+
+#ifdef HAS_SUBEMITTER
+fn emit_subparticle(
+    position: vec3<f32>,
+    scale: f32,
+    velocity: vec3<f32>,
+    flags: u32,
+    parent_index: u32,
+) -> bool {
     if (params.sub_emitter_mode == SUB_EMITTER_MODE_DISABLED) {
         return false;
     }
@@ -1255,39 +1795,80 @@ fn emit_subparticle(position: vec3<f32>, scale: f32, velocity: vec3<f32>, flags:
     }
 
     dst_emission_buffer.data[dst_index].position = vec4(position, scale);
-    dst_emission_buffer.data[dst_index].velocity = vec4(velocity, 0.0);
+    let seed = hash(params.random_seed + bitcast<u32>(position.x) + u32(dst_index));
+    dst_emission_buffer.data[dst_index].velocity = get_sub_emitter_velocity(velocity, seed);
     dst_emission_buffer.data[dst_index].flags = flags;
+    dst_emission_buffer.data[dst_index].parent_index = parent_index;
 
     return true;
 }
+#endif
 
-fn emit_sub_particles(position: vec3<f32>, scale: f32, velocity: vec3<f32>) {
+#ifdef HAS_ACCUMULATION
+// appends a collision hit to the accumulation ring, overwriting the oldest when full
+fn record_accumulation_hit(position: vec3<f32>, scale: f32) {
+    if (params.accumulation_capacity == 0u) {
+        return;
+    }
+    let index = atomicAdd(&accumulation_buffer.count, 1u) % params.accumulation_capacity;
+    accumulation_buffer.entries[index] = vec4(position, scale);
+}
+#endif
+
+#ifdef HAS_SUBEMITTER
+fn emit_sub_particles(position: vec3<f32>, scale: f32, velocity: vec3<f32>, amount: u32) {
     var flags = EMISSION_FLAG_HAS_POSITION;
     if (params.sub_emitter_keep_velocity != 0u) {
         flags |= EMISSION_FLAG_HAS_VELOCITY;
     }
-    for (var i = 0u; i < params.sub_emitter_amount; i++) {
-        emit_subparticle(position, scale, velocity, flags);
+    for (var i = 0u; i < amount; i++) {
+        emit_subparticle(position, scale, velocity, flags, 0u);
+    }
+}
+
+// bigger or longer-lived parents burst into more particles at the end of their life
+fn get_sub_emitter_end_amount(p: Particle) -> u32 {
+    var ratio = 1.0;
+    switch params.sub_emitter_amount_scale {
+        case SUB_EMITTER_AMOUNT_SCALE_SCALE: {
+            ratio = get_initial_scale(p.alignment_dir.w) / max(params.scale_max, 1e-6);
+        }
+        case SUB_EMITTER_AMOUNT_SCALE_LIFETIME: {
+            ratio = p.velocity.w / max(params.lifetime, 1e-6);
+        }
+        default: {}
     }
+    let amount = round(f32(params.sub_emitter_amount) * clamp(ratio, 0.0, 1.0));
+    return max(u32(amount), 1u);
 }
+#endif
 
-fn spawn_particle(idx: u32) -> Particle {
+// step_fraction places the particle along the emitter's movement during this step,
+// so fast-moving emitters leave a continuous stream instead of per-frame clumps
+fn spawn_particle(idx: u32, step_fraction: f32) -> Particle {
     var p: Particle;
     // per-particle seed: base_seed + 1 + index + (cycle * amount)
     let seed = hash(params.random_seed + 1u + idx + params.cycle * params.amount);
 
-    let local_emission_pos = get_emission_offset(seed);
-    let initial_scale = get_initial_scale(seed + 20u);
+    let local_emission_pos = get_emission_offset(seed, idx);
+    let scale_t = spatial_random(seed + 20u, local_emission_pos, 0.0);
+    let angle_t = spatial_random(seed + 70u, local_emission_pos, 1.0);
+    let speed_t = spatial_random(seed + 12u, local_emission_pos, 2.0);
+    let initial_scale = get_initial_scale(scale_t);
     // for constant curve, use initial scale directly; for curves, start at eased t=0
     let scale = get_scale_at_lifetime(initial_scale, 0.0, 1.0);
 
-    var local_vel = get_emission_velocity(seed + 10u);
+    let edge = get_emission_edge_factor(seed);
+    var local_vel = get_emission_velocity(seed + 10u, speed_t) * mix(1.0, params.emission_edge_speed_scale, edge);
 
-    let emission_pos = transform_point(local_emission_pos);
+    let prev_emission_pos = (params.prev_emitter_transform * vec4(local_emission_pos, 1.0)).xyz;
+    let emission_pos = mix(prev_emission_pos, transform_point(local_emission_pos), step_fraction);
     var vel = transform_direction(local_vel);
 
     p.position = vec4(emission_pos, scale);
-    let lifetime = params.lifetime * (1.0 - hash_to_float(seed + 4u) * params.lifetime_randomness);
+    let lifetime = params.lifetime
+        * (1.0 - hash_to_float(seed + 4u) * params.lifetime_randomness)
+        * mix(1.0, params.emission_edge_lifetime_scale, edge);
 
     // include radial velocity at spawn for correct initial alignment
     let initial_radial_velocity = get_initial_radial_velocity(seed + 60u);
@@ -1329,12 +1910,7 @@ fn spawn_particle(idx: u32) -> Particle {
 
     p.velocity = vec4(vel, lifetime);
 
-    if (params.use_initial_color_gradient == 0u) {
-        p.color = params.initial_color;
-    } else {
-        let t = hash_to_float(seed + 30u);
-        p.color = textureSampleLevel(gradient_texture, gradient_sampler, vec2(t, 0.5), 0.0);
-    }
+    p.color = get_initial_color(seed, idx);
 
     let initial_alpha = p.color.a;
     p.color.a = get_alpha_at_lifetime(initial_alpha, 0.0, 1.0);
@@ -1343,33 +1919,66 @@ fn spawn_particle(idx: u32) -> Particle {
     let col_life = get_color_over_lifetime(0.0, 1.0);
     p.color = vec4(p.color.rgb * emission * col_life.rgb, p.color.a * col_life.a);
 
-    // spawn_index tracks total spawns across all cycles for depth ordering
-    var spawn_index = 0.0;
-    if (params.draw_order == DRAW_ORDER_INDEX) {
-        spawn_index = f32(params.cycle * params.amount + idx);
-    }
+    // spawn_index counts spawns across all cycles, for material shaders
+    let spawn_index = f32(params.cycle * params.amount + idx);
     p.custom = vec4(0.0, spawn_index, bitcast<f32>(seed), bitcast<f32>(PARTICLE_FLAG_ACTIVE));
 
-    p.angles = vec4(compute_angles(seed + 70u, 0.0, lifetime), 0.0);
+    p.angles = vec4(compute_angles(seed + 70u, angle_t, 0.0, lifetime), 0.0);
 
     if length(full_vel) > 0.0 {
         let init_dir = normalize(full_vel);
-        p.alignment_dir = vec4(init_dir, 0.0);
-        p.ref_up = vec4(init_ref_up(init_dir), 0.0);
+        p.alignment_dir = vec4(init_dir, scale_t);
+        p.ref_up = vec4(init_ref_up(init_dir), angle_t);
     } else {
-        p.alignment_dir = vec4(0.0, 1.0, 0.0, 0.0);
-        p.ref_up = vec4(0.0, 0.0, 1.0, 0.0);
+        p.alignment_dir = vec4(0.0, 1.0, 0.0, scale_t);
+        p.ref_up = vec4(0.0, 0.0, 1.0, angle_t);
     }
 
     // sub emitter: at start trigger
+#ifdef HAS_SUBEMITTER
     if (params.sub_emitter_mode == SUB_EMITTER_MODE_AT_START) {
-        emit_sub_particles(p.position.xyz, p.position.w, vel);
+        emit_sub_particles(p.position.xyz, p.position.w, vel, params.sub_emitter_amount);
     }
+#endif
 
     return p;
 }
 
-fn update_particle(p_in: Particle) -> Particle {
+// a particle's assigned position in the target shape, in emitter space
+fn get_target_position(seed: u32, particle_idx: u32) -> vec3<f32> {
+    let width = textureDimensions(target_texture).x;
+    if (params.target_mode == TARGET_MODE_POINTS) {
+        let i = particle_idx % params.target_count;
+        return textureLoad(target_texture, vec2(i % width, i / width), 0).xyz;
+    }
+
+    // spread particles evenly over the baked samples so the image fills in uniformly
+    let frac = f32(particle_idx) / f32(max(params.amount, 1u));
+    let i = min(u32(frac * f32(params.target_count)), params.target_count - 1u);
+    let texel = textureLoad(target_texture, vec2(i % width, i / width), 0);
+    let jitter = vec2(hash_to_float(seed), hash_to_float(seed + 1u)) - 0.5;
+    let uv = texel.xy + jitter * texel.zw;
+    return vec3(
+        (uv.x - 0.5) * params.target_image_size.x,
+        (0.5 - uv.y) * params.target_image_size.y,
+        0.0,
+    );
+}
+
+fn get_target_blend(age: f32, lifetime: f32) -> f32 {
+    let t = clamp(age / lifetime, 0.0, 1.0);
+    if (!curve_enabled(params.target_blend_over_lifetime)) {
+        return t;
+    }
+    return clamp(sample_spline_curve(
+        target_blend_curve_texture,
+        target_blend_curve_sampler,
+        params.target_blend_over_lifetime,
+        t
+    ), 0.0, 1.0);
+}
+
+fn update_particle(p_in: Particle, particle_idx: u32) -> Particle {
     var p = p_in;
     let dt = params.delta_time;
     let age = p.custom.x + dt;
@@ -1377,28 +1986,42 @@ fn update_particle(p_in: Particle) -> Particle {
 
     let lifetime = p.velocity.w;
 
-    // sub emitter: constant mode - emit once per frequency interval
-    if (params.sub_emitter_mode == SUB_EMITTER_MODE_CONSTANT) {
+    // sub emitter: constant and trail modes - emit once per frequency interval
+#ifdef HAS_SUBEMITTER
+    if (params.sub_emitter_mode == SUB_EMITTER_MODE_CONSTANT
+        || params.sub_emitter_mode == SUB_EMITTER_MODE_TRAIL) {
         let prev_age = age - dt;
         let interval = params.sub_emitter_frequency;
         if (interval > 0.0) {
             let interval_rem = interval - fract(prev_age / interval) * interval;
             if (dt >= interval_rem) {
+                let is_trail = params.sub_emitter_mode == SUB_EMITTER_MODE_TRAIL;
                 emit_subparticle(
                     p.position.xyz,
                     p.position.w,
                     p.velocity.xyz,
-                    EMISSION_FLAG_HAS_POSITION | select(0u, EMISSION_FLAG_HAS_VELOCITY, params.sub_emitter_keep_velocity != 0u)
+                    EMISSION_FLAG_HAS_POSITION
+                        | select(0u, EMISSION_FLAG_HAS_VELOCITY, params.sub_emitter_keep_velocity != 0u)
+                        | select(0u, EMISSION_FLAG_HAS_PARENT, is_trail),
+                    particle_idx,
                 );
             }
         }
     }
+#endif
 
     if (age >= lifetime) {
         // sub emitter: at end trigger
+#ifdef HAS_SUBEMITTER
         if (params.sub_emitter_mode == SUB_EMITTER_MODE_AT_END) {
-            emit_sub_particles(p.position.xyz, p.position.w, p.velocity.xyz);
+            emit_sub_particles(
+                p.position.xyz,
+                p.position.w,
+                p.velocity.xyz,
+                get_sub_emitter_end_amount(p),
+            );
         }
+#endif
         p.custom.w = bitcast<f32>(0u); // deactivate
         return p;
     }
@@ -1410,8 +2033,8 @@ fn update_particle(p_in: Particle) -> Particle {
     let pivot = transform_point(params.velocity_pivot);
     let disable_z = (params.particle_flags & EMITTER_FLAG_DISABLE_Z) != 0u;
 
-    // reconstruct emission direction from seed for directional velocity
-    let local_emission_vel = get_emission_velocity(seed + 10u);
+    // reconstruct emission direction from seed for directional velocity (speed is irrelevant)
+    let local_emission_vel = get_emission_velocity(seed + 10u, 1.0);
     let emission_vel = transform_direction(local_emission_vel);
     let emission_dir = select(normalize(emission_vel), vec3(0.0), length(emission_vel) < 0.0001);
 
@@ -1449,12 +2072,32 @@ fn update_particle(p_in: Particle) -> Particle {
             lifetime,
         ), disable_z);
 
-        physics_velocity = stored_velocity - prev_radial - prev_orbit - prev_directional;
+        let prev_wave = zero_z_if(get_wave_displacement(seed + 120u, prev_age), disable_z);
+
+        physics_velocity = stored_velocity - prev_radial - prev_orbit - prev_directional - prev_wave;
     }
 
-    let gravity = zero_z_if(params.gravity, disable_z);
+    let gravity = zero_z_if(get_particle_gravity(seed), disable_z);
     physics_velocity = physics_velocity + gravity * dt;
 
+    // radial acceleration away from (or toward, if negative) the pivot
+    let from_pivot = p.position.xyz - pivot;
+    if (length(from_pivot) > 0.0001) {
+        let radial_accel = get_radial_acceleration(seed + 110u, age, lifetime);
+        physics_velocity += zero_z_if(normalize(from_pivot) * radial_accel, disable_z) * dt;
+    }
+
+    // point gravity toward a fixed position in emitter space
+    if (params.point_gravity_strength != 0.0) {
+        let to_point = transform_point(params.point_gravity_position) - p.position.xyz;
+        if (length(to_point) > 0.0001) {
+            let point_accel = normalize(to_point) * params.point_gravity_strength;
+            physics_velocity += zero_z_if(point_accel, disable_z) * dt;
+        }
+    }
+
+    physics_velocity = apply_damping(physics_velocity, seed, dt);
+
     let radial_displacement = zero_z_if(get_radial_displacement(
         p.position.xyz,
         pivot,
@@ -1481,10 +2124,16 @@ fn update_particle(p_in: Particle) -> Particle {
         lifetime,
     ), disable_z);
 
+    let wave_displacement = zero_z_if(get_wave_displacement(seed + 120u, age), disable_z);
+
     // turbulence
+#ifdef HAS_TURBULENCE
     if (params.turbulence_enabled != 0u) {
         let base_influence = get_turbulence_influence(seed + 40u);
-        let influence = get_turbulence_influence_at_lifetime(base_influence, age, lifetime);
+        var influence = get_turbulence_influence_at_lifetime(base_influence, age, lifetime);
+        if (params.turbulence_use_volumes != 0u) {
+            influence *= get_turbulence_volume_mask(p.position.xyz);
+        }
         let random_offset = hash_to_float(seed + 41u);
         let noise_direction = get_noise_direction(p.position.xyz, age, random_offset);
         let vel_magnitude = length(physics_velocity);
@@ -1492,48 +2141,96 @@ fn update_particle(p_in: Particle) -> Particle {
             physics_velocity = mix(physics_velocity, noise_direction * vel_magnitude, influence);
         }
     }
+#endif
 
     physics_velocity = zero_z_if(physics_velocity, disable_z);
 
     // combine physics velocity with controlled displacements
-    let effective_velocity = physics_velocity + radial_displacement + orbit_displacement + directional_displacement;
+    let effective_velocity = physics_velocity + radial_displacement + orbit_displacement + directional_displacement + wave_displacement;
 
     p.velocity = vec4(effective_velocity, lifetime);
 
-    p.angles = vec4(compute_angles(seed + 70u, age, lifetime), 0.0);
+    p.angles = vec4(compute_angles(seed + 70u, p.ref_up.w, age, lifetime), p.angles.w);
 
     if length(effective_velocity) > 0.0 {
         let new_dir = normalize(effective_velocity);
         let old_dir = normalize(p.alignment_dir.xyz);
-        p.ref_up = vec4(transport_ref_up(p.ref_up.xyz, old_dir, new_dir), 0.0);
-        p.alignment_dir = vec4(new_dir, 0.0);
+        p.ref_up = vec4(transport_ref_up(p.ref_up.xyz, old_dir, new_dir), p.ref_up.w);
+        p.alignment_dir = vec4(new_dir, p.alignment_dir.w);
     }
 
-    let new_position = zero_z_if(p.position.xyz + effective_velocity * dt, disable_z);
+    var new_position = zero_z_if(p.position.xyz + effective_velocity * dt, disable_z);
+
+#ifdef HAS_SUBEMITTER_TARGET
+    // particles from a trail sub-emitter ride along with their parent until it dies
+    let parent_slot = get_trail_parent_slot(p);
+    if (parent_slot != 0u) {
+        let parent = src_emission_buffer.data[src_emission_buffer.particle_max + parent_slot - 1u];
+        if (parent.position.w > 0.0) {
+            new_position = zero_z_if(new_position + parent.velocity.xyz * dt, disable_z);
+        } else {
+            p.angles.w = bitcast<f32>(0u);
+        }
+    }
+#endif
+
+    // shape morphing: close the remaining gap by the blend gained this step, so a
+    // particle lands exactly on its target once the blend reaches 1
+    if (params.target_mode != TARGET_MODE_DISABLED && params.target_count > 0u) {
+        let blend = get_target_blend(age, lifetime);
+        let prev_blend = get_target_blend(age - dt, lifetime);
+        let remaining = 1.0 - prev_blend;
+        let step_blend = select(1.0, clamp((blend - prev_blend) / remaining, 0.0, 1.0), remaining > 0.0001);
+        let target_pos = transform_point(get_target_position(seed + 130u, particle_idx));
+        new_position = zero_z_if(mix(new_position, target_pos, step_blend), disable_z);
+    }
 
-    let initial_scale = get_initial_scale(seed + 20u);
+    let initial_scale = get_initial_scale(p.alignment_dir.w);
     let scale = get_scale_at_lifetime(initial_scale, age, lifetime);
 
+    let wrap_offset = get_area_wrap_offset(new_position);
+    new_position += wrap_offset;
+
+#ifdef HAS_COLLISION
+    let previous_position = p.position.xyz + wrap_offset;
+#endif
     p.position = vec4(new_position, scale);
 
     // collision handling
-    if (params.collision_mode != COLLISION_MODE_DISABLED && params.collider_count > 0u) {
+#ifdef HAS_COLLISION
+    if (params.collision_mode != COLLISION_MODE_DISABLED
+        && (params.collider_count > 0u || params.ground_plane_enabled != 0u)) {
         let particle_radius = get_particle_collision_size(scale);
-        let collision = process_collisions(p.position.xyz, particle_radius);
+        let swept = sweep_collisions(previous_position, p.position.xyz, particle_radius);
+        let collision = swept.collision;
 
         if (collision.collided) {
+            // stop at the first point along the step that collided
+            p.position = vec4(swept.position, scale);
+
             // sub emitter: at collision trigger
+#ifdef HAS_SUBEMITTER
             if (params.sub_emitter_mode == SUB_EMITTER_MODE_AT_COLLISION) {
-                emit_sub_particles(p.position.xyz, p.position.w, p.velocity.xyz);
+                emit_sub_particles(
+                    p.position.xyz,
+                    p.position.w,
+                    p.velocity.xyz,
+                    params.sub_emitter_amount,
+                );
             }
+#endif
 
             if (params.collision_mode == COLLISION_MODE_HIDE_ON_CONTACT) {
+#ifdef HAS_ACCUMULATION
+                record_accumulation_hit(p.position.xyz, scale);
+#endif
                 p.custom.w = bitcast<f32>(0u);
                 return p;
             }
 
             // COLLISION_MODE_RIGID
-            var velocity = p.velocity.xyz;
+            // respond in the collider's frame, so moving colliders carry and push particles
+            var velocity = p.velocity.xyz - collision.velocity;
             let collision_response = dot(collision.normal, velocity);
 
             // adaptive bounce threshold
@@ -1550,6 +2247,7 @@ fn update_particle(p_in: Particle) -> Particle {
 
             // add bounce velocity
             col_velocity -= collision.normal * collision_response * params.collision_bounce * should_bounce;
+            col_velocity += collision.velocity;
             col_position = zero_z_if(col_position, disable_z);
             col_velocity = zero_z_if(col_velocity, disable_z);
 
@@ -1560,16 +2258,17 @@ fn update_particle(p_in: Particle) -> Particle {
             if length(col_velocity) > 0.0 {
                 let col_dir = normalize(col_velocity);
                 let old_dir = normalize(p.alignment_dir.xyz);
-                p.ref_up = vec4(transport_ref_up(p.ref_up.xyz, old_dir, col_dir), 0.0);
+                p.ref_up = vec4(transport_ref_up(p.ref_up.xyz, old_dir, col_dir), p.ref_up.w);
                 p.alignment_dir = vec4(col_dir, p.alignment_dir.w);
             }
         }
     }
+#endif
 
-    let initial_alpha = get_initial_alpha(seed);
+    let initial_alpha = get_initial_alpha(seed, particle_idx);
     p.color.a = get_alpha_at_lifetime(initial_alpha, age, lifetime);
 
-    let initial_rgb = get_initial_color_rgb(seed);
+    let initial_rgb = get_initial_color_rgb(seed, particle_idx);
     let emission = get_emission_at_lifetime(age, lifetime);
     let col_life = get_color_over_lifetime(age, lifetime);
     p.color = vec4(initial_rgb * emission * col_life.rgb, p.color.a * col_life.a);
@@ -1,9 +1,10 @@
-#import bevy_sprinkles::common::{Particle, PARTICLE_FLAG_ACTIVE}
+#import sprinkles::particles::{Particle, PARTICLE_FLAG_ACTIVE}
 
 const DRAW_ORDER_INDEX: u32 = 0u;
 const DRAW_ORDER_LIFETIME: u32 = 1u;
 const DRAW_ORDER_REVERSE_LIFETIME: u32 = 2u;
 const DRAW_ORDER_VIEW_DEPTH: u32 = 3u;
+const DRAW_ORDER_CAMERA_DISTANCE: u32 = 4u;
 
 struct SortParams {
     amount: u32,
@@ -16,9 +17,10 @@ struct SortParams {
     _pad2: f32,
     emitter_transform: mat4x4<f32>,
     trail_size: u32,
-    _trail_pad0: u32,
-    _trail_pad1: u32,
-    _trail_pad2: u32,
+    compacted: u32,
+    cull_particles: u32,
+    cull_margin: f32,
+    frustum_planes: array<vec4<f32>, 6>,
 }
 
 @group(0) @binding(0) var<uniform> params: SortParams;
@@ -26,7 +28,45 @@ struct SortParams {
 @group(0) @binding(2) var<storage, read_write> indices: array<u32>;
 // output buffer: particle data written in sorted order for rendering
 @group(0) @binding(3) var<storage, read_write> sorted_particles: array<Particle>;
+// compaction[0] holds the number of drawn trail groups, followed by one offset per
+// workgroup into the drawn list
+@group(0) @binding(4) var<storage, read_write> compaction: array<u32>;
 
+const COMPACT_WORKGROUP_SIZE: u32 = 256u;
+
+var<workgroup> drawn_in_block: atomic<u32>;
+var<workgroup> scan_scratch: array<u32, COMPACT_WORKGROUP_SIZE>;
+
+fn get_group_count() -> u32 {
+    return params.amount / max(params.trail_size, 1u);
+}
+
+fn is_in_frustum(particle: Particle) -> bool {
+    let world_pos = (params.emitter_transform * vec4(particle.position.xyz, 1.0)).xyz;
+    let radius = abs(particle.position.w) + params.cull_margin;
+    for (var i = 0u; i < 6u; i++) {
+        let plane = params.frustum_planes[i];
+        if (dot(plane.xyz, world_pos) + plane.w < -radius) {
+            return false;
+        }
+    }
+    return true;
+}
+
+// whether a trail group is alive and, when culling, inside the camera frustum
+fn is_group_drawn(group_idx: u32) -> bool {
+    if (group_idx >= get_group_count()) {
+        return false;
+    }
+    let particle = particles[group_idx * max(params.trail_size, 1u)];
+    if ((bitcast<u32>(particle.custom.w) & PARTICLE_FLAG_ACTIVE) == 0u) {
+        return false;
+    }
+    return params.cull_particles == 0u || is_in_frustum(particle);
+}
+
+// the sort key and bitonic sort are only compiled into the sort pipeline
+#ifdef HAS_SORTING
 fn get_sort_key(particle_index: u32) -> f32 {
     // for trail segments, use the head particle's sort key
     var head_index = particle_index;
@@ -71,6 +111,12 @@ fn get_sort_key(particle_index: u32) -> f32 {
             let depth = dot(to_particle, params.camera_forward);
             return -depth;
         }
+        case DRAW_ORDER_CAMERA_DISTANCE: {
+            // euclidean distance to the camera (farthest first for transparency)
+            let local_pos = particle.position.xyz;
+            let world_pos = (params.emitter_transform * vec4(local_pos, 1.0)).xyz;
+            return -distance(world_pos, params.camera_position);
+        }
         default: {
             return f32(head_index);
         }
@@ -79,10 +125,10 @@ fn get_sort_key(particle_index: u32) -> f32 {
 
 // bitonic sort: compare and swap based on current stage and step
 // when trail_size > 1, sort operates on trail groups (indices store head slot indices)
+// only the compacted drawn groups at the front of the indices are sorted
 @compute @workgroup_size(256)
 fn sort(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let ts = params.trail_size;
-    let group_count = params.amount / max(ts, 1u);
+    let group_count = compaction[0];
     let idx = global_id.x;
     if (idx >= group_count) {
         return;
@@ -122,17 +168,72 @@ fn sort(@builtin(global_invocation_id) global_id: vec3<u32>) {
         indices[partner] = idx_a;
     }
 }
+#endif
 
-// initialize indices: one entry per trail group, storing the head slot index
+// compaction pass 1: count the drawn trail groups in each workgroup
 @compute @workgroup_size(256)
-fn init_indices(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let idx = global_id.x;
-    let ts = params.trail_size;
-    let group_count = params.amount / max(ts, 1u);
-    if (idx >= group_count) {
+fn count_drawn(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_index) local_idx: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    if (is_group_drawn(global_id.x)) {
+        atomicAdd(&drawn_in_block, 1u);
+    }
+    workgroupBarrier();
+    if (local_idx == 0u) {
+        compaction[1u + workgroup_id.x] = atomicLoad(&drawn_in_block);
+    }
+}
+
+// compaction pass 2: exclusive prefix sum over the per-workgroup counts, run by a
+// single thread since there is only one count per 256 groups
+@compute @workgroup_size(1)
+fn scan_blocks() {
+    let block_count = (get_group_count() + COMPACT_WORKGROUP_SIZE - 1u) / COMPACT_WORKGROUP_SIZE;
+    var total = 0u;
+    for (var block = 0u; block < block_count; block++) {
+        let count = compaction[1u + block];
+        compaction[1u + block] = total;
+        total += count;
+    }
+    compaction[0] = total;
+}
+
+// compaction pass 3: write one entry per trail group, storing the head slot index,
+// with drawn groups first and the rest after them, both in slot order
+@compute @workgroup_size(256)
+fn scatter_indices(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_index) local_idx: u32,
+    @builtin(workgroup_id) workgroup_id: vec3<u32>,
+) {
+    let group_idx = global_id.x;
+    let drawn = u32(is_group_drawn(group_idx));
+
+    // inclusive scan of the drawn flags within the workgroup
+    scan_scratch[local_idx] = drawn;
+    workgroupBarrier();
+    for (var offset = 1u; offset < COMPACT_WORKGROUP_SIZE; offset <<= 1u) {
+        var value = scan_scratch[local_idx];
+        if (local_idx >= offset) {
+            value += scan_scratch[local_idx - offset];
+        }
+        workgroupBarrier();
+        scan_scratch[local_idx] = value;
+        workgroupBarrier();
+    }
+
+    if (group_idx >= get_group_count()) {
         return;
     }
-    indices[idx] = idx * ts;
+
+    let drawn_before = compaction[1u + workgroup_id.x] + scan_scratch[local_idx] - drawn;
+    var destination = drawn_before;
+    if (drawn == 0u) {
+        destination = compaction[0] + group_idx - drawn_before;
+    }
+    indices[destination] = group_idx * max(params.trail_size, 1u);
 }
 
 // copy particle data to sorted output buffer, expanding trail groups
@@ -144,6 +245,12 @@ fn copy_sorted(@builtin(global_invocation_id) global_id: vec3<u32>) {
     }
 
     let ts = params.trail_size;
+    if (params.compacted != 0u && idx >= compaction[0] * max(ts, 1u)) {
+        // dead and culled groups only need their flags cleared to be skipped by the renderer
+        sorted_particles[idx].custom.w = bitcast<f32>(0u);
+        return;
+    }
+
     if (ts <= 1u) {
         let particle_index = indices[idx];
         sorted_particles[idx] = particles[particle_index];
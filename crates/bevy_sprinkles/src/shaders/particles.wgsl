@@ -0,0 +1,123 @@
+#define_import_path sprinkles::particles
+
+// Public particle buffer layouts, for render features that read particle data
+// outside of the built-in material (post effects, debug views, custom passes).
+//
+// Stability: the layout of `Particle` and the meaning of its fields, the existing
+// fields of `ParticleEmitterUniforms`, and every constant in this module only change
+// in a breaking release of the crate. New uniform fields are only ever appended to
+// the end of `ParticleEmitterUniforms`.
+//
+// Usage:
+//
+//     #import sprinkles::particles::{Particle, ParticleEmitterUniforms, particle_is_active}
+//
+//     @group(2) @binding(0) var<storage, read> particles: array<Particle>;
+//     @group(2) @binding(1) var<storage, read> emitter: ParticleEmitterUniforms;
+//
+// The Rust-side buffers are `ParticleBufferHandle::sorted_particles_buffer` and
+// `ParticleBufferHandle::emitter_uniforms_buffer`.
+
+// A single particle slot. Inactive slots keep stale data and must be skipped with
+// `particle_is_active`. With trails, each particle owns `trail_size` consecutive
+// slots, the first of which is the head.
+struct Particle {
+    position: vec4<f32>,       // xyz, scale
+    velocity: vec4<f32>,       // xyz, lifetime
+    color: vec4<f32>,
+    custom: vec4<f32>,         // age, spawn_index, seed, flags
+    alignment_dir: vec4<f32>,  // xyz direction for ALIGN_Y_TO_VELOCITY, w = initial scale factor
+    ref_up: vec4<f32>,         // xyz reference up for parallel-transported alignment, w = initial angle factor
+    angles: vec4<f32>,         // xyz = per-axis rotation angles in radians, w = trail sub-emitter parent slot + 1 (bitcast u32)
+}
+
+// per-particle flags (stored in particle.custom.w)
+const PARTICLE_FLAG_ACTIVE: u32 = 1u;
+
+const TRAIL_THICKNESS_CURVE_SAMPLES: u32 = 16u;
+
+// Per-emitter uniforms. Positions and velocities in `Particle` are in the emitter's
+// local space when `use_local_coords` is set, and in world space otherwise.
+struct ParticleEmitterUniforms {
+    emitter_transform: mat4x4<f32>,
+    max_particles: u32,
+    particle_flags: u32,
+    use_local_coords: u32,
+    trail_size: u32,
+    transform_align: u32,
+    trail_thickness_curve: array<f32, 16>,
+    transform_align_axis: vec3<f32>,
+    keep_world_up: u32,
+    shadow_only: u32,
+    delta_time: f32,
+    alpha_erosion: u32,
+    alpha_erosion_softness: f32,
+    distortion_offset: vec2<f32>,
+    distortion_strength: f32,
+    texture_sheet_columns: u32,
+    texture_sheet_rows: u32,
+    texture_sheet_mode: u32,
+    fog_density_multiplier: f32,
+    near_fade_distance: f32,
+    velocity_stretch: f32,
+    tint: vec4<f32>,
+    emissive_multiplier: f32,
+    erosion_threshold: f32,
+    amount: u32,
+}
+
+// Collision hits recorded for `ParticleAccumulationBuffer`. `count` keeps growing past
+// `capacity`; hit `i` is stored at `entries[i % capacity]`.
+struct ParticleAccumulation {
+    count: u32,
+    capacity: u32,
+    _pad0: u32,
+    _pad1: u32,
+    entries: array<vec4<f32>>, // xyz position, w scale
+}
+
+// transform align mode values
+const TRANSFORM_ALIGN_DISABLED: u32 = 0u;
+const TRANSFORM_ALIGN_BILLBOARD: u32 = 1u;
+const TRANSFORM_ALIGN_Y_TO_VELOCITY: u32 = 2u;
+const TRANSFORM_ALIGN_BILLBOARD_Y_TO_VELOCITY: u32 = 3u;
+const TRANSFORM_ALIGN_BILLBOARD_FIXED_Y: u32 = 4u;
+const TRANSFORM_ALIGN_FIXED_AXIS: u32 = 5u;
+const TRANSFORM_ALIGN_VELOCITY_STRETCHED_BILLBOARD: u32 = 6u;
+
+// texture sheet random mode values
+const TEXTURE_SHEET_RANDOM_ROW: u32 = 1u;
+const TEXTURE_SHEET_RANDOM_COLUMN: u32 = 2u;
+
+fn particle_is_active(particle: Particle) -> bool {
+    return (bitcast<u32>(particle.custom.w) & PARTICLE_FLAG_ACTIVE) != 0u;
+}
+
+fn particle_age(particle: Particle) -> f32 {
+    return particle.custom.x;
+}
+
+fn particle_lifetime(particle: Particle) -> f32 {
+    return particle.velocity.w;
+}
+
+// how many particles the emitter spawned before this one, across all cycles. exact for
+// the first 2^24 spawns
+fn particle_spawn_index(particle: Particle) -> u32 {
+    return u32(particle.custom.y);
+}
+
+// where in its emission cycle the particle spawned, from 0.0 for the first particle of a
+// cycle towards 1.0 for the last
+fn particle_cycle_position(particle: Particle, emitter: ParticleEmitterUniforms) -> f32 {
+    let amount = max(emitter.amount, 1u);
+    return f32(particle_spawn_index(particle) % amount) / f32(amount);
+}
+
+// world-space position of a particle, resolving local coordinates
+fn particle_world_position(particle: Particle, emitter: ParticleEmitterUniforms) -> vec3<f32> {
+    if emitter.use_local_coords != 0u {
+        return (emitter.emitter_transform * vec4(particle.position.xyz, 1.0)).xyz;
+    }
+    return particle.position.xyz;
+}
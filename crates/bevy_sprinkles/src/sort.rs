@@ -1,29 +1,34 @@
 use bevy::{
-    core_pipeline::schedule::camera_driver,
+    diagnostic::FrameCount,
+    ecs::entity::{EntityHashMap, EntityHashSet},
     prelude::*,
     render::{
         Render, RenderApp, RenderStartup, RenderSystems,
         render_asset::RenderAssets,
         render_resource::{
             BindGroup, BindGroupEntries, BindGroupLayoutDescriptor, BindGroupLayoutEntries, Buffer,
-            CachedComputePipelineId, CachedPipelineState, ComputePassDescriptor,
-            ComputePipelineDescriptor, DynamicUniformBuffer, PipelineCache, ShaderStages,
-            ShaderType,
+            BufferDescriptor, BufferId, BufferUsages, CachedComputePipelineId, CachedPipelineState,
+            ComputePassDescriptor, ComputePipelineDescriptor, DynamicUniformBuffer, PipelineCache,
+            ShaderStages, ShaderType,
             binding_types::{storage_buffer, uniform_buffer},
         },
-        renderer::{RenderContext, RenderDevice, RenderGraph, RenderGraphSystems, RenderQueue},
+        renderer::{RenderContext, RenderDevice, RenderGraph, RenderQueue},
         storage::GpuShaderBuffer,
     },
 };
 use std::borrow::Cow;
 
-use crate::compute::ParticleComputeLabel;
 use crate::extract::ExtractedParticleSystem;
 use crate::runtime::ParticleData;
+use crate::{SprinklesRenderSystems, SprinklesSettings};
 
 const SHADER_ASSET_PATH: &str = "embedded://bevy_sprinkles/shaders/particle_sort.wgsl";
 const WORKGROUP_SIZE: u32 = 256;
 
+/// Storage buffers bound by the sort shader: particles, indices, sorted particles and
+/// compaction counts.
+const SORT_STORAGE_BUFFERS: u32 = 4;
+
 #[derive(Clone, Copy, Default, ShaderType)]
 pub struct SortParams {
     pub amount: u32,
@@ -36,18 +41,19 @@ pub struct SortParams {
     pub _pad2: f32,
     pub emitter_transform: Mat4,
     pub trail_size: u32,
-    pub _trail_pad0: u32,
-    pub _trail_pad1: u32,
-    pub _trail_pad2: u32,
+    pub compacted: u32,
+    pub cull_particles: u32,
+    pub cull_margin: f32,
+    pub frustum_planes: [Vec4; 6],
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
-pub struct ParticleSortLabel;
-
 #[derive(Resource)]
 pub struct ParticleSortPipeline {
     pub bind_group_layout: BindGroupLayoutDescriptor,
-    pub init_pipeline: CachedComputePipelineId,
+    pub count_pipeline: CachedComputePipelineId,
+    pub scan_pipeline: CachedComputePipelineId,
+    pub scatter_pipeline: CachedComputePipelineId,
+    #[cfg(feature = "sorting")]
     pub sort_pipeline: CachedComputePipelineId,
     pub copy_pipeline: CachedComputePipelineId,
 }
@@ -56,7 +62,18 @@ pub fn init_particle_sort_pipeline(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     pipeline_cache: Res<PipelineCache>,
+    render_device: Res<RenderDevice>,
 ) {
+    let max_storage_buffers = render_device.limits().max_storage_buffers_per_shader_stage;
+    if max_storage_buffers < SORT_STORAGE_BUFFERS {
+        error!(
+            "Particle sorting needs at least {SORT_STORAGE_BUFFERS} storage buffers per shader \
+             stage, but this device only supports {max_storage_buffers}. Particles will not be \
+             sorted."
+        );
+        return;
+    }
+
     let bind_group_layout = BindGroupLayoutDescriptor::new(
         "ParticleSortBindGroup",
         &BindGroupLayoutEntries::sequential(
@@ -66,6 +83,7 @@ pub fn init_particle_sort_pipeline(
                 storage_buffer::<ParticleData>(false),
                 storage_buffer::<u32>(false),
                 storage_buffer::<ParticleData>(false),
+                storage_buffer::<u32>(false),
             ),
         ),
     );
@@ -82,13 +100,26 @@ pub fn init_particle_sort_pipeline(
         })
     };
 
-    let init_pipeline = queue_pipeline("particle_sort_init_pipeline", "init_indices");
-    let sort_pipeline = queue_pipeline("particle_sort_pipeline", "sort");
+    let count_pipeline = queue_pipeline("particle_compact_count_pipeline", "count_drawn");
+    let scan_pipeline = queue_pipeline("particle_compact_scan_pipeline", "scan_blocks");
+    let scatter_pipeline = queue_pipeline("particle_compact_scatter_pipeline", "scatter_indices");
     let copy_pipeline = queue_pipeline("particle_sort_copy_pipeline", "copy_sorted");
+    #[cfg(feature = "sorting")]
+    let sort_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+        label: Some("particle_sort_pipeline".into()),
+        layout: vec![bind_group_layout.clone()],
+        shader: shader.clone(),
+        shader_defs: vec!["HAS_SORTING".into()],
+        entry_point: Some(Cow::from("sort")),
+        ..default()
+    });
 
     commands.insert_resource(ParticleSortPipeline {
         bind_group_layout,
-        init_pipeline,
+        count_pipeline,
+        scan_pipeline,
+        scatter_pipeline,
+        #[cfg(feature = "sorting")]
         sort_pipeline,
         copy_pipeline,
     });
@@ -103,11 +134,32 @@ struct SortDispatch {
 #[derive(Resource, Default)]
 pub struct ParticleSortBindGroups {
     bind_groups: Vec<BindGroup>,
-    init_dispatches: Vec<SortDispatch>,
+    count_dispatches: Vec<SortDispatch>,
+    scan_dispatches: Vec<SortDispatch>,
+    scatter_dispatches: Vec<SortDispatch>,
+    #[cfg(feature = "sorting")]
     sort_levels: Vec<Vec<SortDispatch>>,
     copy_dispatches: Vec<SortDispatch>,
 }
 
+/// Sort bind groups kept across frames, keyed by emitter entity along with the uniform,
+/// particle, indices, sorted particle and compaction buffers they bind. The uniform
+/// buffer is reused too, so it is only reallocated when it needs to grow.
+#[derive(Resource, Default)]
+pub struct ParticleSortBindGroupCache {
+    uniforms: DynamicUniformBuffer<SortParams>,
+    entries: EntityHashMap<([BufferId; 5], BindGroup)>,
+    /// Per-emitter compaction buffers, holding the drawn group count followed by one offset
+    /// per workgroup. Only reallocated when an emitter needs more workgroups.
+    compaction: EntityHashMap<(u32, Buffer)>,
+    /// Indices buffer each emitter was last sorted into, so frames that skip sorting
+    /// never copy through indices that were never written.
+    sorted_indices: EntityHashMap<BufferId>,
+    /// Camera and emitter transform each emitter was last sorted with, so paused emitters
+    /// only sort again when the view changes.
+    sorted_views: EntityHashMap<(Vec3, Vec3, Mat4)>,
+}
+
 pub fn prepare_particle_sort_bind_groups(
     mut commands: Commands,
     pipeline: Res<ParticleSortPipeline>,
@@ -116,12 +168,47 @@ pub fn prepare_particle_sort_bind_groups(
     render_queue: Res<RenderQueue>,
     extracted_systems: Res<ExtractedParticleSystem>,
     gpu_storage_buffers: Res<RenderAssets<GpuShaderBuffer>>,
+    settings: Res<SprinklesSettings>,
+    frame_count: Res<FrameCount>,
+    mut bind_group_cache: ResMut<ParticleSortBindGroupCache>,
 ) {
     let mut result = ParticleSortBindGroups::default();
-    let mut dynamic_uniform = DynamicUniformBuffer::<SortParams>::default();
-    let mut emitter_buffers: Vec<(Buffer, Buffer, Buffer)> = Vec::new();
+    let ParticleSortBindGroupCache {
+        uniforms: dynamic_uniform,
+        entries: cached_bind_groups,
+        compaction: compaction_buffers,
+        sorted_indices,
+        sorted_views,
+    } = &mut *bind_group_cache;
+    dynamic_uniform.clear();
+    // mobile mode sorts every other frame and draws with the previous order in between
+    let skip_sorting = settings.mobile_mode && frame_count.0 % 2 == 1;
+    let mut emitter_buffers: Vec<(Entity, Buffer, Buffer, Buffer, Buffer)> = Vec::new();
+    let mut frozen_emitters = EntityHashSet::default();
+
+    // particles are only sorted within their emitter; emitters are drawn as separate
+    // transparent meshes, ordered against each other by the transparent phase
+    for (entity, emitter_data) in &extracted_systems.emitters {
+        let view = (
+            Vec3::from_array(emitter_data.camera_position),
+            Vec3::from_array(emitter_data.camera_forward),
+            emitter_data.emitter_transform,
+        );
+        // paused emitters keep their sorted buffer from the last frame, unless the
+        // camera or the emitter moved since
+        if emitter_data.frozen
+            && sorted_views.get(entity) == Some(&view)
+            && gpu_storage_buffers
+                .get(&emitter_data.indices_buffer_handle)
+                .is_some_and(|indices_buf| {
+                    sorted_indices.get(entity) == Some(&indices_buf.buffer.id())
+                })
+        {
+            frozen_emitters.insert(*entity);
+            continue;
+        }
+        sorted_views.insert(*entity, view);
 
-    for (_entity, emitter_data) in &extracted_systems.emitters {
         let Some(particle_buf) = gpu_storage_buffers.get(&emitter_data.particle_buffer_handle)
         else {
             continue;
@@ -142,10 +229,26 @@ pub fn prepare_particle_sort_bind_groups(
         let group_workgroups = (group_count + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
         let total_workgroups = (total_slots + WORKGROUP_SIZE - 1) / WORKGROUP_SIZE;
 
+        let compaction_buf = match compaction_buffers.get(entity) {
+            Some((blocks, buffer)) if *blocks >= group_workgroups => buffer.clone(),
+            _ => {
+                let buffer = render_device.create_buffer(&BufferDescriptor {
+                    label: Some("particle_compaction_buffer"),
+                    size: (1 + group_workgroups.max(1) as u64) * 4,
+                    usage: BufferUsages::STORAGE,
+                    mapped_at_creation: false,
+                });
+                compaction_buffers.insert(*entity, (group_workgroups, buffer.clone()));
+                buffer
+            }
+        };
+
         emitter_buffers.push((
+            *entity,
             particle_buf.buffer.clone(),
             indices_buf.buffer.clone(),
             sorted_buf.buffer.clone(),
+            compaction_buf,
         ));
 
         let base_params = SortParams {
@@ -159,19 +262,45 @@ pub fn prepare_particle_sort_bind_groups(
             _pad2: 0.0,
             emitter_transform: emitter_data.emitter_transform,
             trail_size,
-            _trail_pad0: 0,
-            _trail_pad1: 0,
-            _trail_pad2: 0,
+            compacted: 0,
+            cull_particles: emitter_data.frustum_cull.is_some() as u32,
+            cull_margin: emitter_data.frustum_cull.map_or(0.0, |(_, margin)| margin),
+            frustum_planes: emitter_data
+                .frustum_cull
+                .map_or([Vec4::ZERO; 6], |(planes, _)| planes),
         };
 
-        let init_offset = dynamic_uniform.push(&base_params);
-        result.init_dispatches.push(SortDispatch {
-            emitter_index: emitter_idx,
-            dynamic_offset: init_offset,
-            workgroups: group_workgroups,
-        });
+        let indices_id = indices_buf.buffer.id();
+        // emitters with a sort interval are staggered so they don't all sort on the
+        // same frame
+        let skip_interval =
+            frame_count.0.wrapping_add(emitter_idx as u32) % emitter_data.sort_interval != 0;
+        let needs_sort =
+            !(skip_sorting || skip_interval) || sorted_indices.get(entity) != Some(&indices_id);
+        if needs_sort {
+            sorted_indices.insert(*entity, indices_id);
+
+            // partition the indices so drawn groups come first, in slot order
+            let compact_offset = dynamic_uniform.push(&base_params);
+            result.count_dispatches.push(SortDispatch {
+                emitter_index: emitter_idx,
+                dynamic_offset: compact_offset,
+                workgroups: group_workgroups,
+            });
+            result.scan_dispatches.push(SortDispatch {
+                emitter_index: emitter_idx,
+                dynamic_offset: compact_offset,
+                workgroups: 1,
+            });
+            result.scatter_dispatches.push(SortDispatch {
+                emitter_index: emitter_idx,
+                dynamic_offset: compact_offset,
+                workgroups: group_workgroups,
+            });
+        }
 
-        if emitter_data.draw_order != 0 {
+        #[cfg(feature = "sorting")]
+        if needs_sort && emitter_data.draw_order != 0 {
             let n = group_count.next_power_of_two();
             let num_stages = (n as f32).log2().ceil() as u32;
 
@@ -199,7 +328,12 @@ pub fn prepare_particle_sort_bind_groups(
             }
         }
 
-        let copy_offset = dynamic_uniform.push(&base_params);
+        // frames that skip sorting copy every slot, so particles spawned since the last
+        // compaction still show up
+        let copy_offset = dynamic_uniform.push(&SortParams {
+            compacted: needs_sort as u32,
+            ..base_params
+        });
         result.copy_dispatches.push(SortDispatch {
             emitter_index: emitter_idx,
             dynamic_offset: copy_offset,
@@ -209,10 +343,26 @@ pub fn prepare_particle_sort_bind_groups(
 
     dynamic_uniform.write_buffer(&render_device, &render_queue);
 
-    if let Some(uniform_binding) = dynamic_uniform.binding() {
+    if let (Some(uniform_buffer), Some(uniform_binding)) =
+        (dynamic_uniform.buffer(), dynamic_uniform.binding())
+    {
         let bind_group_layout = pipeline_cache.get_bind_group_layout(&pipeline.bind_group_layout);
 
-        for (particle_buf, indices_buf, sorted_buf) in &emitter_buffers {
+        for (entity, particle_buf, indices_buf, sorted_buf, compaction_buf) in &emitter_buffers {
+            let key = [
+                uniform_buffer.id(),
+                particle_buf.id(),
+                indices_buf.id(),
+                sorted_buf.id(),
+                compaction_buf.id(),
+            ];
+            if let Some((cached_key, bind_group)) = cached_bind_groups.get(entity)
+                && *cached_key == key
+            {
+                result.bind_groups.push(bind_group.clone());
+                continue;
+            }
+
             let bind_group = render_device.create_bind_group(
                 Some("particle_sort_bind_group"),
                 &bind_group_layout,
@@ -221,12 +371,24 @@ pub fn prepare_particle_sort_bind_groups(
                     particle_buf.as_entire_binding(),
                     indices_buf.as_entire_binding(),
                     sorted_buf.as_entire_binding(),
+                    compaction_buf.as_entire_binding(),
                 )),
             );
+            cached_bind_groups.insert(*entity, (key, bind_group.clone()));
             result.bind_groups.push(bind_group);
         }
     }
 
+    let live_emitters: EntityHashSet = emitter_buffers
+        .iter()
+        .map(|(entity, ..)| *entity)
+        .chain(frozen_emitters)
+        .collect();
+    cached_bind_groups.retain(|entity, _| live_emitters.contains(entity));
+    compaction_buffers.retain(|entity, _| live_emitters.contains(entity));
+    sorted_indices.retain(|entity, _| live_emitters.contains(entity));
+    sorted_views.retain(|entity, _| live_emitters.contains(entity));
+
     commands.insert_resource(result);
 }
 
@@ -243,17 +405,32 @@ pub fn run_particle_sort_node(
         )
     };
 
-    if !(is_ready(pipeline.init_pipeline)
-        && is_ready(pipeline.sort_pipeline)
+    #[cfg(feature = "sorting")]
+    if !is_ready(pipeline.sort_pipeline) {
+        return;
+    }
+    if !(is_ready(pipeline.count_pipeline)
+        && is_ready(pipeline.scan_pipeline)
+        && is_ready(pipeline.scatter_pipeline)
         && is_ready(pipeline.copy_pipeline))
     {
         return;
     }
 
-    let Some(init_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.init_pipeline) else {
+    let Some(count_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.count_pipeline) else {
         return;
     };
 
+    let Some(scan_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.scan_pipeline) else {
+        return;
+    };
+
+    let Some(scatter_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.scatter_pipeline)
+    else {
+        return;
+    };
+
+    #[cfg(feature = "sorting")]
     let Some(sort_pipeline) = pipeline_cache.get_compute_pipeline(pipeline.sort_pipeline) else {
         return;
     };
@@ -285,11 +462,22 @@ pub fn run_particle_sort_node(
     };
 
     run_pass(
-        "particle_sort_init_pass",
-        init_pipeline,
-        &sort_bind_groups.init_dispatches,
+        "particle_compact_count_pass",
+        count_pipeline,
+        &sort_bind_groups.count_dispatches,
+    );
+    run_pass(
+        "particle_compact_scan_pass",
+        scan_pipeline,
+        &sort_bind_groups.scan_dispatches,
+    );
+    run_pass(
+        "particle_compact_scatter_pass",
+        scatter_pipeline,
+        &sort_bind_groups.scatter_dispatches,
     );
 
+    #[cfg(feature = "sorting")]
     for level in &sort_bind_groups.sort_levels {
         run_pass("particle_sort_pass", sort_pipeline, level);
     }
@@ -311,18 +499,19 @@ impl Plugin for ParticleSortPlugin {
 
         render_app
             .init_resource::<ParticleSortBindGroups>()
+            .init_resource::<ParticleSortBindGroupCache>()
             .add_systems(RenderStartup, init_particle_sort_pipeline)
             .add_systems(
                 Render,
-                prepare_particle_sort_bind_groups.in_set(RenderSystems::PrepareBindGroups),
+                prepare_particle_sort_bind_groups
+                    .in_set(RenderSystems::PrepareBindGroups)
+                    .run_if(resource_exists::<ParticleSortPipeline>),
             )
             .add_systems(
                 RenderGraph,
                 run_particle_sort_node
-                    .in_set(ParticleSortLabel)
-                    .in_set(RenderGraphSystems::Render)
-                    .after(ParticleComputeLabel)
-                    .before(camera_driver),
+                    .in_set(SprinklesRenderSystems::Sort)
+                    .run_if(resource_exists::<ParticleSortPipeline>),
             );
     }
 }
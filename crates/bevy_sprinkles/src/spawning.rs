@@ -1,20 +1,29 @@
 use bevy::{
-    light::NotShadowCaster, pbr::ExtendedMaterial, prelude::*, render::storage::ShaderBuffer,
+    camera::visibility::RenderLayers, light::NotShadowCaster, pbr::ExtendedMaterial, prelude::*,
+    render::storage::ShaderBuffer,
 };
 
+#[cfg(feature = "collision")]
+use crate::runtime::ParticlesCollider3D;
+#[cfg(feature = "sub-emitters")]
+use crate::{asset::SubEmitterMode, runtime::SubEmitterBufferHandle};
 use crate::{
-    asset::{DrawPassMaterial, EmitterData, EmitterTrail, ParticlesAsset},
+    asset::{
+        AlphaFadeMode, DrawPassMaterial, DrawPassVisibility, EmitterData, EmitterDrawPass,
+        EmitterTrail, ParticlesAsset, StandardParticleMaterial, TextureSheetRandomMode,
+    },
     material::{ParticleEmitterUniforms, ParticleMaterialExtension, TRAIL_THICKNESS_CURVE_SAMPLES},
     mesh::ParticleMeshCache,
     runtime::{
         ColliderEntity, CurrentMaterialConfig, CurrentMeshConfig, EditorMode, EmitterEntity,
-        EmitterRuntime, ParticleBufferHandle, ParticleData, ParticleMaterial,
-        ParticleMaterialHandle, ParticleMeshHandle, ParticleSystemRuntime, Particles3d,
-        ParticlesCollider3D, SimulationStep, SubEmitterBufferHandle, TrailHistoryEntry,
+        EmitterRuntime, ParticleAccumulationBuffer, ParticleBufferHandle, ParticleData,
+        ParticleMaterial, ParticleMaterialHandle, ParticleMaterialOverrides, ParticleMeshHandle,
+        ParticleSeed, ParticleSystemChildren, ParticleSystemRuntime, Particles3d, SimulationStep,
+        TrailHistoryEntry,
     },
+    timing,
 };
 
-const MAX_FRAME_DELTA: f32 = 0.1;
 const INACTIVE_GRACE_FACTOR: f32 = 1.2;
 const MAX_TRAIL_HISTORY_FPS: f32 = 240.0;
 fn create_trail_history_buffer(
@@ -30,6 +39,16 @@ fn create_trail_history_buffer(
     }
 }
 
+fn create_accumulation_buffer(
+    capacity: u32,
+    buffers: &mut Assets<ShaderBuffer>,
+) -> Handle<ShaderBuffer> {
+    // header (count, capacity, padding) followed by one vec4 per hit
+    let mut data = vec![0u32; 4 + 4 * capacity as usize];
+    data[1] = capacity;
+    buffers.add(ShaderBuffer::from(data))
+}
+
 fn compute_trail_history_frames(emitter: &EmitterData) -> u32 {
     let trail_size = emitter.trail_size();
     if trail_size <= 1 {
@@ -81,9 +100,9 @@ pub fn update_particle_time(
     time: Res<Time>,
     assets: Res<Assets<ParticlesAsset>>,
     system_query: Query<(&Particles3d, &ParticleSystemRuntime)>,
-    mut emitter_query: Query<(&EmitterEntity, &mut EmitterRuntime)>,
+    mut emitter_query: Query<(&EmitterEntity, &mut EmitterRuntime, &InheritedVisibility)>,
 ) {
-    for (emitter, mut runtime) in emitter_query.iter_mut() {
+    for (emitter, mut runtime, visibility) in emitter_query.iter_mut() {
         let Ok((particle_system, system_runtime)) = system_query.get(emitter.parent_system) else {
             continue;
         };
@@ -101,7 +120,7 @@ pub fn update_particle_time(
         let clear_requested = runtime.clear_requested;
         runtime.clear_requested = false;
 
-        if runtime.inactive || system_runtime.paused {
+        if runtime.inactive || system_runtime.is_paused_for(visibility) {
             if clear_requested {
                 let step = SimulationStep {
                     prev_system_time: runtime.system_time,
@@ -116,54 +135,22 @@ pub fn update_particle_time(
             continue;
         }
 
-        let fixed_fps = emitter_data.time.fixed_fps;
-        let total_duration = emitter_data.time.total_duration();
-
-        if fixed_fps > 0 {
-            let fixed_delta = 1.0 / fixed_fps as f32;
-            let frame_delta = time.delta_secs().min(MAX_FRAME_DELTA);
-            runtime.accumulated_delta += frame_delta;
-
-            while runtime.accumulated_delta >= fixed_delta
-                || (clear_requested && runtime.simulation_steps.is_empty())
-            {
-                runtime.accumulated_delta -= fixed_delta;
-
-                let prev_time = runtime.system_time;
-                runtime.system_time += fixed_delta;
-
-                if runtime.system_time >= total_duration && total_duration > 0.0 {
-                    runtime.system_time = runtime.system_time % total_duration;
-                    runtime.cycle += 1;
-                }
-
-                let step = SimulationStep {
-                    prev_system_time: prev_time,
-                    system_time: runtime.system_time,
-                    cycle: runtime.cycle,
-                    delta_time: fixed_delta,
-                    clear_requested: if runtime.simulation_steps.is_empty() {
-                        clear_requested
-                    } else {
-                        false
-                    },
-                    trail_history_write_index: runtime.trail_history_write_index,
-                };
-                runtime.advance_trail_history();
-                runtime.simulation_steps.push(step);
-            }
-
-            if !runtime.simulation_steps.is_empty() {
-                runtime.prev_system_time = runtime.simulation_steps[0].prev_system_time;
-            }
-        } else {
-            let delta = time.delta_secs();
-            let prev_time = runtime.system_time;
+        let steps = timing::frame_steps(
+            time.delta_secs(),
+            &mut runtime.accumulated_delta,
+            clear_requested,
+            &emitter_data.time,
+        );
+        if steps.count > 0 {
             runtime.prev_system_time = runtime.system_time;
-            runtime.system_time += delta;
+        }
 
-            if runtime.system_time >= total_duration && total_duration > 0.0 {
-                runtime.system_time = runtime.system_time % total_duration;
+        for step_index in 0..steps.count {
+            let prev_time = runtime.system_time;
+            let (system_time, wrapped) =
+                timing::advance_time(prev_time, steps.delta, &emitter_data.time);
+            runtime.system_time = system_time;
+            if wrapped {
                 runtime.cycle += 1;
             }
 
@@ -171,8 +158,8 @@ pub fn update_particle_time(
                 prev_system_time: prev_time,
                 system_time: runtime.system_time,
                 cycle: runtime.cycle,
-                delta_time: delta,
-                clear_requested,
+                delta_time: steps.delta,
+                clear_requested: clear_requested && step_index == 0,
                 trail_history_write_index: runtime.trail_history_write_index,
             };
             runtime.advance_trail_history();
@@ -186,7 +173,10 @@ pub fn update_particle_time(
 
         if !runtime.emitting {
             runtime.inactive_time += time.delta_secs();
-            let grace = emitter_data.time.lifetime * INACTIVE_GRACE_FACTOR;
+            // rim particles may outlive the base lifetime
+            let max_lifetime =
+                emitter_data.time.lifetime * emitter_data.emission.edge_lifetime_scale.max(1.0);
+            let grace = max_lifetime * INACTIVE_GRACE_FACTOR;
             if runtime.inactive_time > grace {
                 runtime.inactive = true;
             }
@@ -204,28 +194,64 @@ fn transform_align_to_u32(align: Option<crate::asset::TransformAlign>) -> u32 {
         Some(TransformAlign::YToVelocity) => 2,
         Some(TransformAlign::BillboardYToVelocity) => 3,
         Some(TransformAlign::BillboardFixedY) => 4,
+        Some(TransformAlign::FixedAxis(_)) => 5,
+        Some(TransformAlign::VelocityStretchedBillboard(_)) => 6,
+    }
+}
+
+fn transform_align_axis(align: Option<crate::asset::TransformAlign>) -> Vec3 {
+    match align {
+        Some(crate::asset::TransformAlign::FixedAxis(axis)) => axis,
+        _ => Vec3::Z,
+    }
+}
+
+fn transform_align_velocity_stretch(align: Option<crate::asset::TransformAlign>) -> f32 {
+    match align {
+        Some(crate::asset::TransformAlign::VelocityStretchedBillboard(stretch)) => stretch.max(0.0),
+        _ => 0.0,
     }
 }
 
 fn create_particle_material_from_config(
-    config: &DrawPassMaterial,
+    draw_pass: &EmitterDrawPass,
     sorted_particles_buffer: Handle<ShaderBuffer>,
     emitter_uniforms_buffer: Handle<ShaderBuffer>,
     asset_server: &AssetServer,
     assets_folders: &[String],
 ) -> ParticleMaterial {
-    let base = match config {
+    let config = &draw_pass.material;
+    let (blend_mode, distortion_texture) = match config {
+        DrawPassMaterial::Standard(mat) => (
+            mat.blend_mode,
+            mat.distortion_texture
+                .as_ref()
+                .map(|texture| texture.load(asset_server, assets_folders)),
+        ),
+        DrawPassMaterial::CustomShader { .. } => (None, None),
+    };
+    let mut base = match config {
         DrawPassMaterial::Standard(mat) => mat.to_standard_material(asset_server, assets_folders),
         DrawPassMaterial::CustomShader { .. } => {
             todo!("custom shader support not yet implemented")
         }
     };
 
+    // the material depth bias also offsets the transparent sort distance, the
+    // extension restores the original bias for the depth test itself
+    let depth_bias = base.depth_bias;
+    base.depth_bias += draw_pass.sort_bias;
+
     ExtendedMaterial {
         base,
         extension: ParticleMaterialExtension {
             sorted_particles: sorted_particles_buffer,
             emitter_uniforms: emitter_uniforms_buffer,
+            depth_prepass: draw_pass.depth_prepass,
+            blend_mode,
+            distortion_texture,
+            sort_bias: draw_pass.sort_bias,
+            depth_bias,
         },
     }
 }
@@ -243,7 +269,19 @@ fn bake_thickness_curve(trail: &EmitterTrail) -> [f32; TRAIL_THICKNESS_CURVE_SAM
 
 pub fn setup_particle_systems(
     mut commands: Commands,
-    query: Query<(Entity, &Particles3d, Has<EditorMode>), Without<ParticleSystemRuntime>>,
+    query: Query<
+        (
+            Entity,
+            &Particles3d,
+            Option<&ParticleSeed>,
+            Option<&ParticleSystemRuntime>,
+            Has<EditorMode>,
+        ),
+        Or<(
+            Without<ParticleSystemRuntime>,
+            Without<ParticleSystemChildren>,
+        )>,
+    >,
     assets: Res<Assets<ParticlesAsset>>,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -251,7 +289,7 @@ pub fn setup_particle_systems(
     mut buffers: ResMut<Assets<ShaderBuffer>>,
     mut materials: ResMut<Assets<ParticleMaterial>>,
 ) {
-    for (system_entity, particle_system, is_editor) in query.iter() {
+    for (system_entity, particle_system, instance_seed, system_runtime, is_editor) in query.iter() {
         let Some(asset) = assets.get(particle_system) else {
             continue;
         };
@@ -271,15 +309,17 @@ pub fn setup_particle_systems(
         commands
             .entity(system_entity)
             .queue_silenced(move |mut entity: EntityWorldMut| {
-                entity
-                    .insert(ParticleSystemRuntime::default())
-                    .insert_if_new((initial_transform, Visibility::default()));
+                entity.insert_if_new((
+                    ParticleSystemRuntime::default(),
+                    initial_transform,
+                    Visibility::default(),
+                ));
             });
 
         let mut emitter_entities: Vec<Entity> = Vec::new();
 
         for (emitter_index, emitter) in asset.emitters.iter().enumerate() {
-            let amount = emitter.emission.particles_amount;
+            let amount = emitter.emission.capacity();
             let trail_size = emitter.trail_size();
             let total_slots = amount * trail_size;
 
@@ -305,7 +345,7 @@ pub fn setup_particle_systems(
                 max_particles: total_slots,
                 particle_flags: emitter.particle_flags.bits(),
                 trail_size,
-                transform_align: transform_align_to_u32(emitter.draw_pass.transform_align),
+                transform_align: transform_align_to_u32(emitter.transform_align()),
                 ..default()
             };
             let mut emitter_uniforms_ssbo = ShaderBuffer::default();
@@ -314,19 +354,23 @@ pub fn setup_particle_systems(
 
             let current_mesh = emitter.draw_pass.mesh.clone();
             let current_material = emitter.draw_pass.material.clone();
-            let shadow_caster = emitter.draw_pass.shadow_caster;
+            let shadow_caster = emitter.draw_pass.casts_shadows();
 
             let particle_mesh_handle = mesh_cache.get_or_create(&current_mesh, amount, &mut meshes);
 
             let material_handle = materials.add(create_particle_material_from_config(
-                &current_material,
+                &emitter.draw_pass,
                 sorted_particles_buffer_handle.clone(),
                 emitter_uniforms_buffer_handle.clone(),
                 &asset_server,
                 assets_folders,
             ));
 
-            let mut runtime = EmitterRuntime::new(emitter_index, emitter.time.fixed_seed);
+            let seed = instance_seed
+                .map(|seed| seed.emitter_seed(emitter_index))
+                .or(emitter.time.fixed_seed)
+                .or_else(|| system_runtime.and_then(|runtime| runtime.emitter_seed(emitter_index)));
+            let mut runtime = EmitterRuntime::new(emitter_index, seed);
             runtime.trail_history_frames = trail_history_frames;
 
             let mut emitter_cmds = commands.spawn((
@@ -359,6 +403,19 @@ pub fn setup_particle_systems(
                 emitter_cmds.insert(NotShadowCaster);
             }
 
+            // metaball emitters are drawn by their own pass instead of their mesh. They keep
+            // their visibility, so they can still be hidden like any other emitter
+            if emitter.draw_pass.draws_metaballs() {
+                emitter_cmds.insert(RenderLayers::none());
+            }
+
+            if let Some(capacity) = emitter.collision.accumulation_capacity.filter(|&c| c > 0) {
+                emitter_cmds.insert(ParticleAccumulationBuffer {
+                    buffer: create_accumulation_buffer(capacity, &mut buffers),
+                    capacity,
+                });
+            }
+
             let emitter_entity = emitter_cmds.id();
 
             emitter_entities.push(emitter_entity);
@@ -369,6 +426,7 @@ pub fn setup_particle_systems(
                 });
         }
 
+        #[cfg(feature = "sub-emitters")]
         for (emitter_index, emitter) in asset.emitters.iter().enumerate() {
             if let Some(ref sub_config) = emitter.sub_emitter {
                 let target_index = sub_config.target_emitter;
@@ -376,8 +434,14 @@ pub fn setup_particle_systems(
                     continue;
                 }
 
-                let target_amount = asset.emitters[target_index].emission.particles_amount;
-                let buffer_len = 4 + 12 * target_amount as usize;
+                let target_amount = asset.emitters[target_index].emission.capacity();
+                // trail sub-emitters also keep one slot per parent particle after the
+                // emission entries, tracking where each parent is
+                let parent_slots = match sub_config.mode {
+                    SubEmitterMode::Trail => emitter.emission.capacity(),
+                    _ => 0,
+                };
+                let buffer_len = 4 + 12 * (target_amount + parent_slots) as usize;
                 let mut initial_data = vec![0u32; buffer_len];
                 initial_data[1] = target_amount;
                 let mut buffer = ShaderBuffer::from(initial_data);
@@ -398,31 +462,60 @@ pub fn setup_particle_systems(
             }
         }
 
-        for (collider_index, collider_data) in asset.colliders.iter().enumerate() {
-            let collider_entity = commands
-                .spawn((
-                    ColliderEntity {
-                        parent_system: system_entity,
-                        collider_index,
-                    },
-                    ParticlesCollider3D {
-                        enabled: collider_data.enabled,
-                        shape: collider_data.shape.clone(),
-                    },
-                    collider_data.initial_transform.to_transform(),
-                    Name::new(collider_data.name.clone()),
-                ))
-                .id();
+        #[cfg(feature = "collision")]
+        let collider_entities = spawn_colliders(&mut commands, system_entity, asset);
+        #[cfg(not(feature = "collision"))]
+        let collider_entities = Vec::new();
 
-            commands
-                .entity(system_entity)
-                .queue_silenced(move |mut entity: EntityWorldMut| {
-                    entity.add_child(collider_entity);
-                });
-        }
+        let children = ParticleSystemChildren {
+            emitters: emitter_entities,
+            colliders: collider_entities,
+        };
+        commands
+            .entity(system_entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                entity.insert(children);
+            });
     }
 }
 
+#[cfg(feature = "collision")]
+fn spawn_colliders(
+    commands: &mut Commands,
+    system_entity: Entity,
+    asset: &ParticlesAsset,
+) -> Vec<Entity> {
+    let mut collider_entities = Vec::new();
+
+    for (collider_index, collider_data) in asset.colliders.iter().enumerate() {
+        let collider_entity = commands
+            .spawn((
+                ColliderEntity {
+                    parent_system: system_entity,
+                    collider_index,
+                },
+                ParticlesCollider3D {
+                    enabled: collider_data.enabled,
+                    shape: collider_data.shape.clone(),
+                    invert: collider_data.invert,
+                    ..default()
+                },
+                collider_data.initial_transform.to_transform(),
+                Name::new(collider_data.name.clone()),
+            ))
+            .id();
+
+        collider_entities.push(collider_entity);
+        commands
+            .entity(system_entity)
+            .queue_silenced(move |mut entity: EntityWorldMut| {
+                entity.add_child(collider_entity);
+            });
+    }
+
+    collider_entities
+}
+
 pub fn cleanup_particle_entities(
     mut commands: Commands,
     mut removed_systems: RemovedComponents<Particles3d>,
@@ -447,6 +540,7 @@ pub fn cleanup_particle_entities(
     }
 }
 
+#[cfg(feature = "collision")]
 pub fn sync_collider_data(
     particle_systems: Query<&Particles3d>,
     assets: Res<Assets<ParticlesAsset>>,
@@ -466,6 +560,7 @@ pub fn sync_collider_data(
 
         collider3d.enabled = collider_data.enabled;
         collider3d.shape = collider_data.shape.clone();
+        collider3d.invert = collider_data.invert;
         *transform = collider_data.initial_transform.to_transform();
     }
 }
@@ -550,7 +645,7 @@ pub(crate) fn sync_particle_buffers(
             continue;
         };
 
-        let new_amount = emitter_data.emission.particles_amount;
+        let new_amount = emitter_data.emission.capacity();
         let new_trail_size = emitter_data.trail_size();
         let new_trail_history_frames = compute_trail_history_frames(emitter_data);
 
@@ -576,7 +671,7 @@ pub(crate) fn sync_particle_buffers(
             max_particles: new_total,
             particle_flags: emitter_data.particle_flags.bits(),
             trail_size: new_trail_size,
-            transform_align: transform_align_to_u32(emitter_data.draw_pass.transform_align),
+            transform_align: transform_align_to_u32(emitter_data.transform_align()),
             trail_thickness_curve: bake_thickness_curve(&emitter_data.trail),
             ..default()
         };
@@ -603,7 +698,7 @@ pub(crate) fn sync_particle_buffers(
             get_editor_assets_folders(emitter.parent_system, is_editor, &particle_systems, &assets);
 
         let new_material = materials.add(create_particle_material_from_config(
-            &emitter_data.draw_pass.material,
+            &emitter_data.draw_pass,
             new_sorted_buf,
             new_uniforms_buf,
             &asset_server,
@@ -628,11 +723,14 @@ pub fn write_emitter_uniforms(
         &EmitterRuntime,
         &ParticleBufferHandle,
         &GlobalTransform,
+        Option<&ParticleMaterialOverrides>,
     )>,
+    system_overrides: Query<&ParticleMaterialOverrides>,
     assets: Res<Assets<ParticlesAsset>>,
+    time: Res<Time>,
     mut buffers: ResMut<Assets<ShaderBuffer>>,
 ) {
-    for (emitter, runtime, buffer_handle, global_transform) in emitter_query.iter() {
+    for (emitter, runtime, buffer_handle, global_transform, overrides) in emitter_query.iter() {
         let Some(emitter_data) = get_emitter_data(
             emitter.parent_system,
             runtime.emitter_index,
@@ -644,6 +742,32 @@ pub fn write_emitter_uniforms(
 
         let trail_size = emitter_data.trail_size();
         let trail_thickness_curve = bake_thickness_curve(&emitter_data.trail);
+        let alpha_erosion_softness = match &emitter_data.draw_pass.material {
+            DrawPassMaterial::Standard(StandardParticleMaterial {
+                alpha_fade: AlphaFadeMode::Erosion { softness },
+                ..
+            }) => Some(*softness),
+            _ => None,
+        };
+        let (distortion_offset, distortion_strength) = match &emitter_data.draw_pass.material {
+            DrawPassMaterial::Standard(mat) if mat.distortion_texture.is_some() => (
+                (mat.distortion_speed * time.elapsed_secs()).fract(),
+                mat.distortion_strength,
+            ),
+            _ => (Vec2::ZERO, 0.0),
+        };
+        let (fog_density_multiplier, near_fade_distance) = match &emitter_data.draw_pass.material {
+            DrawPassMaterial::Standard(mat) => (
+                mat.fog_density_multiplier.max(0.0),
+                mat.near_fade_distance.max(0.0),
+            ),
+            DrawPassMaterial::CustomShader { .. } => (1.0, 0.0),
+        };
+        let texture_sheet = &emitter_data.texture_sheet;
+        let overrides = overrides
+            .or_else(|| system_overrides.get(emitter.parent_system).ok())
+            .copied()
+            .unwrap_or_default();
 
         let uniforms = ParticleEmitterUniforms {
             emitter_transform: global_transform.to_matrix(),
@@ -651,8 +775,35 @@ pub fn write_emitter_uniforms(
             particle_flags: emitter_data.particle_flags.bits(),
             use_local_coords: emitter_data.draw_pass.use_local_coords as u32,
             trail_size,
-            transform_align: transform_align_to_u32(emitter_data.draw_pass.transform_align),
+            transform_align: transform_align_to_u32(emitter_data.transform_align()),
             trail_thickness_curve,
+            transform_align_axis: transform_align_axis(emitter_data.transform_align()),
+            keep_world_up: emitter_data.draw_pass.billboard_keep_world_up as u32,
+            shadow_only: (emitter_data.draw_pass.visibility == DrawPassVisibility::ShadowOnly)
+                as u32,
+            delta_time: time.delta_secs(),
+            alpha_erosion: alpha_erosion_softness.is_some() as u32,
+            alpha_erosion_softness: alpha_erosion_softness.unwrap_or(0.0),
+            distortion_offset,
+            distortion_strength,
+            texture_sheet_columns: if texture_sheet.enabled {
+                texture_sheet.columns.max(1)
+            } else {
+                0
+            },
+            texture_sheet_rows: texture_sheet.rows.max(1),
+            texture_sheet_mode: match texture_sheet.random_mode {
+                TextureSheetRandomMode::Cell => 0,
+                TextureSheetRandomMode::Row => 1,
+                TextureSheetRandomMode::Column => 2,
+            },
+            fog_density_multiplier,
+            near_fade_distance,
+            velocity_stretch: transform_align_velocity_stretch(emitter_data.transform_align()),
+            tint: overrides.tint.to_linear().to_vec4(),
+            emissive_multiplier: overrides.emissive_multiplier,
+            erosion_threshold: overrides.erosion_threshold,
+            amount: emitter_data.emission.particles_amount,
         };
 
         if let Some(mut buffer) = buffers.get_mut(&buffer_handle.emitter_uniforms_buffer) {
@@ -688,8 +839,13 @@ pub fn sync_particle_material(
         };
 
         let new_material = emitter_data.draw_pass.material.clone();
+        let draw_pass = &emitter_data.draw_pass;
+        let extension_changed = materials.get(&material_handle.0).is_some_and(|material| {
+            material.extension.depth_prepass != draw_pass.depth_prepass
+                || material.extension.sort_bias != draw_pass.sort_bias
+        });
 
-        if current_config.0.cache_key() != new_material.cache_key() {
+        if current_config.0.cache_key() != new_material.cache_key() || extension_changed {
             let (sorted_particles_handle, emitter_uniforms_handle) = {
                 let Some(existing_material) = materials.get(&material_handle.0) else {
                     continue;
@@ -709,7 +865,7 @@ pub fn sync_particle_material(
             );
 
             let new_material_handle = materials.add(create_particle_material_from_config(
-                &new_material,
+                draw_pass,
                 sorted_particles_handle,
                 emitter_uniforms_handle,
                 &asset_server,
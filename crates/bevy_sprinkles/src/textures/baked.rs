@@ -147,7 +147,7 @@ pub fn prepare_gradient_textures(
             if let SolidOrGradientColor::Gradient { gradient } = &emitter.colors.initial_color {
                 cache.get_or_create(gradient, &mut images);
             }
-            cache.get_or_create(&emitter.colors.color_over_lifetime, &mut images);
+            cache.get_or_create(&emitter.colors.color_over_lifetime_gradient(), &mut images);
         }
     }
 }
@@ -257,6 +257,14 @@ pub fn prepare_curve_textures(
                     .velocity_over_lifetime,
                 &mut images,
             );
+            cache.prepare_optional(
+                &emitter
+                    .accelerations
+                    .radial_acceleration
+                    .velocity_over_lifetime,
+                &mut images,
+            );
+            cache.prepare_optional(&emitter.target.blend_over_lifetime, &mut images);
         }
     }
 }
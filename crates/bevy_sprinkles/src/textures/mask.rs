@@ -0,0 +1,241 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+};
+use std::collections::HashMap;
+
+use crate::asset::{EmissionMaskChannel, EmissionShape, ParticlesAsset, TargetShape};
+use crate::runtime::{EditorMode, Particles3d};
+use crate::textures::preset::TextureRef;
+
+const MASK_TEXTURE_WIDTH: u32 = 64;
+
+/// Number of spawn points baked for every emission mask.
+pub const EMISSION_MASK_SAMPLE_COUNT: u32 = MASK_TEXTURE_WIDTH * MASK_TEXTURE_WIDTH;
+
+struct EmissionMaskEntry {
+    source: Handle<Image>,
+    baked: Option<Handle<Image>>,
+    dirty: bool,
+}
+
+/// Cache for baked emission masks used by [`EmissionShape::Image`] and
+/// [`TargetShape::Image`].
+///
+/// Each unique texture and channel pair is loaded once, then baked into a table of
+/// spawn points that is reused across all emitters that reference it. The table is
+/// followed by the linear color of each spawn point's source pixel, which emitters with
+/// `inherit_color` enabled read back. Masks are rebaked whenever their source image is
+/// modified.
+#[derive(Resource, Default)]
+pub struct EmissionMaskCache {
+    cache: HashMap<(TextureRef, EmissionMaskChannel), EmissionMaskEntry>,
+}
+
+impl EmissionMaskCache {
+    /// Returns the baked spawn point texture for the mask, if its source image has
+    /// loaded and contains any visible pixels.
+    pub fn get(&self, texture: &TextureRef, channel: EmissionMaskChannel) -> Option<Handle<Image>> {
+        self.cache
+            .get(&(texture.clone(), channel))
+            .and_then(|entry| entry.baked.clone())
+    }
+
+    /// Loads the mask's source image and bakes it once loaded, returning whether a new
+    /// bake happened.
+    fn prepare(
+        &mut self,
+        texture: &TextureRef,
+        channel: EmissionMaskChannel,
+        asset_server: &AssetServer,
+        assets_folders: &[String],
+        images: &mut Assets<Image>,
+    ) -> bool {
+        let entry = self
+            .cache
+            .entry((texture.clone(), channel))
+            .or_insert_with(|| EmissionMaskEntry {
+                source: texture.load(asset_server, assets_folders),
+                baked: None,
+                dirty: true,
+            });
+
+        if !entry.dirty {
+            return false;
+        }
+        let Some(source) = images.get(&entry.source) else {
+            return false;
+        };
+
+        // rebakes overwrite the previous image in place, so reloading a mask doesn't leave
+        // stale bakes behind
+        let image = bake_emission_mask(source, channel);
+        match (entry.baked.take(), image) {
+            (Some(handle), Some(image)) => {
+                if let Some(mut baked) = images.get_mut(&handle) {
+                    *baked = image;
+                    entry.baked = Some(handle);
+                } else {
+                    entry.baked = Some(images.add(image));
+                }
+            }
+            (Some(handle), None) => {
+                images.remove(&handle);
+            }
+            (None, image) => entry.baked = image.map(|image| images.add(image)),
+        }
+        entry.dirty = false;
+        true
+    }
+
+    fn mark_modified(&mut self, id: AssetId<Image>) {
+        for entry in self.cache.values_mut() {
+            if entry.source.id() == id {
+                entry.dirty = true;
+            }
+        }
+    }
+}
+
+fn mask_weight(color: Color, channel: EmissionMaskChannel) -> f32 {
+    let linear = color.to_linear();
+    let weight = match channel {
+        EmissionMaskChannel::Alpha => linear.alpha,
+        EmissionMaskChannel::Luminance => {
+            (0.2126 * linear.red + 0.7152 * linear.green + 0.0722 * linear.blue) * linear.alpha
+        }
+    };
+    weight.max(0.0)
+}
+
+fn bake_emission_mask(source: &Image, channel: EmissionMaskChannel) -> Option<Image> {
+    let width = source.width();
+    let height = source.height();
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut cdf = Vec::with_capacity((width * height) as usize);
+    let mut total = 0.0;
+    for y in 0..height {
+        for x in 0..width {
+            let weight = source
+                .get_color_at(x, y)
+                .map(|color| mask_weight(color, channel))
+                .unwrap_or(0.0);
+            total += weight;
+            cdf.push(total);
+        }
+    }
+    if total <= 0.0 {
+        return None;
+    }
+
+    // stratified samples over the cdf, so every pixel gets a share of the table
+    // proportional to its weight
+    let texel_size = [1.0 / width as f32, 1.0 / height as f32];
+    let mut data = Vec::with_capacity((EMISSION_MASK_SAMPLE_COUNT * 32) as usize);
+    let mut colors = Vec::with_capacity((EMISSION_MASK_SAMPLE_COUNT * 16) as usize);
+    for i in 0..EMISSION_MASK_SAMPLE_COUNT {
+        let target = (i as f32 + 0.5) / EMISSION_MASK_SAMPLE_COUNT as f32 * total;
+        let pixel = cdf.partition_point(|&c| c <= target).min(cdf.len() - 1) as u32;
+        let x = pixel % width;
+        let y = pixel / width;
+        let uv = [
+            (x as f32 + 0.5) * texel_size[0],
+            (y as f32 + 0.5) * texel_size[1],
+        ];
+        for value in [uv[0], uv[1], texel_size[0], texel_size[1]] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let color = source
+            .get_color_at(x, y)
+            .map(|color| color.to_linear().to_f32_array())
+            .unwrap_or([1.0; 4]);
+        for value in color {
+            colors.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    // colors live in the bottom half, at the spawn point's index plus the sample count
+    data.extend(colors);
+
+    let mut image = Image::new(
+        Extent3d {
+            width: MASK_TEXTURE_WIDTH,
+            height: MASK_TEXTURE_WIDTH * 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba32Float,
+        default(),
+    );
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST;
+    Some(image)
+}
+
+/// Loads and bakes emission masks for all active particle systems.
+pub fn prepare_emission_masks(
+    mut cache: ResMut<EmissionMaskCache>,
+    mut images: ResMut<Assets<Image>>,
+    mut image_events: MessageReader<AssetEvent<Image>>,
+    asset_server: Res<AssetServer>,
+    particle_systems: Query<(&Particles3d, Has<EditorMode>)>,
+    assets: Res<Assets<ParticlesAsset>>,
+) {
+    // only flag the cache as changed when a mask is baked, so extraction can tell when
+    // emitters need to pick up new masks
+    let mut baked = false;
+    let masks = cache.bypass_change_detection();
+
+    for event in image_events.read() {
+        if let AssetEvent::Modified { id } = event {
+            masks.mark_modified(*id);
+        }
+    }
+
+    for (system, is_editor) in &particle_systems {
+        let Some(asset) = assets.get(system) else {
+            continue;
+        };
+        let assets_folders: &[String] = if is_editor {
+            &asset.sprinkles_editor.assets_folder
+        } else {
+            &[]
+        };
+        for emitter in &asset.emitters {
+            if let EmissionShape::Image {
+                texture: Some(texture),
+                channel,
+                ..
+            } = &emitter.emission.shape
+            {
+                baked |= masks.prepare(
+                    texture,
+                    *channel,
+                    &asset_server,
+                    assets_folders,
+                    &mut images,
+                );
+            }
+            if let TargetShape::Image {
+                texture: Some(texture),
+                channel,
+                ..
+            } = &emitter.target.shape
+            {
+                baked |= masks.prepare(
+                    texture,
+                    *channel,
+                    &asset_server,
+                    assets_folders,
+                    &mut images,
+                );
+            }
+        }
+    }
+
+    if baked {
+        cache.set_changed();
+    }
+}
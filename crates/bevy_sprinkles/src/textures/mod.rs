@@ -30,7 +30,27 @@
 /// A [`FallbackGradientTexture`] and [`FallbackCurveTexture`] (1x1 white)
 /// are created at startup so shaders always have a valid texture binding.
 pub mod baked;
+/// Spawn point tables baked from images for
+/// [`EmissionShape::Image`](crate::asset::EmissionShape::Image) and
+/// [`TargetShape::Image`](crate::asset::TargetShape::Image).
+///
+/// Every pixel of the source image is weighted by its alpha or luminance, and
+/// a fixed number of spawn points are drawn from the resulting distribution
+/// into a 64x64 `Rgba32Float` texture. Each texel stores the UV of a pixel
+/// center in `rg` and the size of one source pixel in `ba`, letting the
+/// compute shader pick a random texel and jitter within that pixel. See
+/// [`EmissionMaskCache`].
+pub mod mask;
+/// Target position tables for
+/// [`TargetShape::Points`](crate::asset::TargetShape::Points).
+///
+/// A list of positions is baked into a 64-wide `Rgba32Float` texture with one
+/// position per texel in `rgb`, so the compute shader can look up a particle's
+/// target by index. See [`TargetPointsCache`].
+pub mod points;
 /// Preset particle textures and texture reference types.
 pub mod preset;
 
 pub use baked::*;
+pub use mask::*;
+pub use points::*;
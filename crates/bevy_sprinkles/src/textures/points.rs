@@ -0,0 +1,102 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use crate::asset::{ParticlesAsset, TargetShape};
+use crate::runtime::Particles3d;
+
+const POINTS_TEXTURE_WIDTH: u32 = 64;
+
+/// Cache for baked target point lists used by [`TargetShape::Points`].
+///
+/// Each unique list of positions is baked into an `Rgba32Float` texture once and
+/// reused across all emitters that reference it.
+#[derive(Resource, Default)]
+pub struct TargetPointsCache {
+    cache: HashMap<u64, Handle<Image>>,
+}
+
+impl TargetPointsCache {
+    /// Returns a cached texture handle for the positions, creating and baking a new
+    /// texture if one doesn't already exist. Returns `None` for an empty list.
+    pub fn get_or_create(
+        &mut self,
+        positions: &[Vec3],
+        images: &mut Assets<Image>,
+    ) -> Option<Handle<Image>> {
+        if positions.is_empty() {
+            return None;
+        }
+        let key = points_cache_key(positions);
+        if let Some(handle) = self.cache.get(&key) {
+            return Some(handle.clone());
+        }
+        let handle = images.add(bake_points_texture(positions));
+        self.cache.insert(key, handle.clone());
+        Some(handle)
+    }
+
+    /// Returns the cached texture handle for the positions, if it exists.
+    pub fn get(&self, positions: &[Vec3]) -> Option<Handle<Image>> {
+        self.cache.get(&points_cache_key(positions)).cloned()
+    }
+}
+
+fn points_cache_key(positions: &[Vec3]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    positions.len().hash(&mut hasher);
+    for position in positions {
+        position.x.to_bits().hash(&mut hasher);
+        position.y.to_bits().hash(&mut hasher);
+        position.z.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn bake_points_texture(positions: &[Vec3]) -> Image {
+    let height = (positions.len() as u32).div_ceil(POINTS_TEXTURE_WIDTH);
+    let texel_count = (POINTS_TEXTURE_WIDTH * height) as usize;
+    let mut data = Vec::with_capacity(texel_count * 16);
+    for i in 0..texel_count {
+        let position = positions.get(i).copied().unwrap_or(Vec3::ZERO);
+        for value in [position.x, position.y, position.z, 0.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let mut image = Image::new(
+        Extent3d {
+            width: POINTS_TEXTURE_WIDTH,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba32Float,
+        default(),
+    );
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST;
+    image
+}
+
+/// Bakes target point textures for all active particle systems.
+pub fn prepare_target_points(
+    mut cache: ResMut<TargetPointsCache>,
+    mut images: ResMut<Assets<Image>>,
+    particle_systems: Query<&Particles3d>,
+    assets: Res<Assets<ParticlesAsset>>,
+) {
+    for system in &particle_systems {
+        let Some(asset) = assets.get(system) else {
+            continue;
+        };
+        for emitter in &asset.emitters {
+            if let TargetShape::Points { positions } = &emitter.target.shape {
+                cache.get_or_create(positions, &mut images);
+            }
+        }
+    }
+}
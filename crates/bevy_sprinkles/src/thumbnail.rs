@@ -0,0 +1,205 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    camera::{CameraPlugin, RenderTarget},
+    core_pipeline::CorePipelinePlugin,
+    image::ImagePlugin,
+    light::{LightPlugin, light_consts::lux},
+    mesh::MeshPlugin,
+    pbr::PbrPlugin,
+    prelude::*,
+    render::{
+        RenderPlugin,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::screenshot::{Screenshot, ScreenshotCaptured},
+    },
+    time::TimeUpdateStrategy,
+    transform::TransformPlugin,
+    window::{ExitCondition, WindowPlugin},
+};
+use thiserror::Error;
+
+use crate::{
+    SprinklesPlugin,
+    asset::ParticlesAsset,
+    runtime::{ParticleSystemRuntime, Particles3d},
+};
+
+/// Frame rate the effect is simulated at before the thumbnail is captured.
+pub const THUMBNAIL_FPS: f32 = 60.0;
+
+/// Seed every thumbnail is rendered with, so the same asset always gives the same image.
+pub const THUMBNAIL_SEED: u32 = 0;
+
+// updates spent loading shaders and creating buffers before the simulation starts
+const WARMUP_UPDATES: u32 = 16;
+// updates to wait for the screenshot readback before giving up
+const CAPTURE_UPDATES: u32 = 16;
+
+const CAMERA_DISTANCE: f32 = 8.0;
+const CAMERA_OFFSET: Vec3 = Vec3::new(1.0, 0.75, 1.0);
+
+/// Errors that can occur when rendering a thumbnail.
+#[derive(Debug, Error)]
+pub enum ThumbnailError {
+    /// The rendered frame was never read back from the GPU.
+    #[error("the thumbnail frame was not captured")]
+    NotCaptured,
+}
+
+/// Renders a preview image of `asset` after simulating it for `frame` frames.
+///
+/// This builds a headless app with its own renderer, so it is meant for tools such as
+/// editors, launchers and asset browsers, not for use inside a running game. The effect
+/// is simulated at [`THUMBNAIL_FPS`] with [`THUMBNAIL_SEED`] and framed from the same
+/// angle the editor opens with, on a transparent background.
+///
+/// A `frame` of `0` captures the effect right as it starts, which is empty for most
+/// effects. Use the asset's [`preview_frame`](ParticlesAsset::preview_frame) when it
+/// specifies one.
+///
+/// Requires the `thumbnails` feature and a GPU adapter.
+pub fn render_thumbnail(
+    asset: ParticlesAsset,
+    frame: u32,
+    size: UVec2,
+) -> Result<Image, ThumbnailError> {
+    render_thumbnail_sequence(asset, &[frame], size).map(|mut images| images.remove(0))
+}
+
+/// Renders one preview image of `asset` for each of `frames`, from a single run of the
+/// effect.
+///
+/// Works like [`render_thumbnail`], but keeps simulating between captures, so the images
+/// show how the effect plays out over time. `frames` are expected in increasing order,
+/// and a frame earlier than the previous one is captured at the previous one.
+///
+/// Requires the `thumbnails` feature and a GPU adapter.
+pub fn render_thumbnail_sequence(
+    asset: ParticlesAsset,
+    frames: &[u32],
+    size: UVec2,
+) -> Result<Vec<Image>, ThumbnailError> {
+    // only the plugins needed to render a 3d scene into an image, without a window, event
+    // loop or pipelined rendering, so `app.update()` renders each frame synchronously
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((
+            TransformPlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            },
+            AssetPlugin::default(),
+            RenderPlugin {
+                synchronous_pipeline_compilation: true,
+                ..default()
+            },
+            ImagePlugin::default(),
+            MeshPlugin,
+            CameraPlugin,
+            LightPlugin,
+            CorePipelinePlugin,
+            PbrPlugin::default(),
+        ))
+        .add_plugins(SprinklesPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            1.0 / THUMBNAIL_FPS,
+        )));
+
+    let target = create_render_target(&mut app, size);
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<ParticlesAsset>>()
+        .add(asset);
+
+    let system = app
+        .world_mut()
+        .spawn((
+            Particles3d(handle),
+            ParticleSystemRuntime {
+                paused: true,
+                ..ParticleSystemRuntime::with_seed(THUMBNAIL_SEED)
+            },
+        ))
+        .id();
+    app.world_mut().spawn((
+        Camera3d::default(),
+        Camera {
+            clear_color: ClearColorConfig::Custom(Color::NONE),
+            ..default()
+        },
+        RenderTarget::Image(target.clone().into()),
+        Transform::from_translation(CAMERA_OFFSET.normalize() * CAMERA_DISTANCE)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    app.world_mut().spawn((
+        DirectionalLight {
+            illuminance: lux::OVERCAST_DAY,
+            ..default()
+        },
+        Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.4, 0.0)),
+    ));
+
+    for _ in 0..WARMUP_UPDATES {
+        app.update();
+    }
+
+    let mut images = Vec::with_capacity(frames.len());
+    let mut simulated = 0;
+    for &frame in frames {
+        set_paused(&mut app, system, false);
+        for _ in simulated..frame {
+            app.update();
+        }
+        simulated = simulated.max(frame);
+        set_paused(&mut app, system, true);
+        images.push(capture(&mut app, &target)?);
+    }
+    Ok(images)
+}
+
+fn capture(app: &mut App, target: &Handle<Image>) -> Result<Image, ThumbnailError> {
+    let captured = Arc::new(Mutex::new(None));
+    let sink = captured.clone();
+    app.world_mut()
+        .spawn(Screenshot::image(target.clone()))
+        .observe(move |event: On<ScreenshotCaptured>| {
+            *sink.lock().unwrap() = Some(event.image.clone());
+        });
+
+    for _ in 0..CAPTURE_UPDATES {
+        app.update();
+        if let Some(image) = captured.lock().unwrap().take() {
+            return Ok(image);
+        }
+    }
+    Err(ThumbnailError::NotCaptured)
+}
+
+fn create_render_target(app: &mut App, size: UVec2) -> Handle<Image> {
+    let mut image = Image::new_uninit(
+        Extent3d {
+            width: size.x.max(1),
+            height: size.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::all(),
+    );
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+        | TextureUsages::COPY_SRC
+        | TextureUsages::COPY_DST
+        | TextureUsages::RENDER_ATTACHMENT;
+    app.world_mut().resource_mut::<Assets<Image>>().add(image)
+}
+
+fn set_paused(app: &mut App, system: Entity, paused: bool) {
+    if let Some(mut runtime) = app.world_mut().get_mut::<ParticleSystemRuntime>(system) {
+        runtime.paused = paused;
+    }
+}
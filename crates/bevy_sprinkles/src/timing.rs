@@ -0,0 +1,264 @@
+//! Every emitter runs in repeating cycles of [`delay`](EmitterTime::delay) followed by
+//! [`lifetime`](EmitterTime::lifetime) seconds. Within a cycle, the emitter's
+//! *phase* goes from `0.0` at the end of the delay to `1.0` at the end of the cycle, and
+//! each particle spawns once the phase crosses its [`spawn_phase`].
+//!
+//! These functions are pure, so custom spawners and tools can reproduce exactly when
+//! Sprinkles spawns each particle without running the simulation. [`SpawnTimeline`] ties
+//! them together into a CPU reference of the whole spawn schedule, frame by frame.
+
+use crate::asset::EmitterTime;
+
+/// Frame deltas longer than this are clamped before being fed to fixed-FPS emitters.
+pub const MAX_FRAME_DELTA: f32 = 0.1;
+/// Target step rate of [`frame_rate_independent`](EmitterTime::frame_rate_independent)
+/// emitters.
+pub const SUBSTEP_FPS: f32 = 60.0;
+/// Maximum number of substeps a frame is split into.
+pub const MAX_SUBSTEPS: u32 = 8;
+
+/// Computes the emission phase (0.0–1.0) for the given time and emitter timing config.
+///
+/// Returns `0.0` during the delay window and for emitters with a non-positive
+/// lifetime.
+pub fn compute_phase(time: f32, emitter_time: &EmitterTime) -> f32 {
+    if emitter_time.lifetime <= 0.0 {
+        return 0.0;
+    }
+    let total_duration = emitter_time.total_duration();
+    if total_duration <= 0.0 {
+        return 0.0;
+    }
+    let time_in_cycle = time % total_duration;
+    if time_in_cycle < emitter_time.delay {
+        return 0.0;
+    }
+    (time_in_cycle - emitter_time.delay) / emitter_time.lifetime
+}
+
+/// Returns `true` if the given time is past the emitter's initial delay within the current cycle.
+pub fn is_past_delay(time: f32, emitter_time: &EmitterTime) -> bool {
+    let total_duration = emitter_time.total_duration();
+    if total_duration <= 0.0 {
+        return true;
+    }
+    let time_in_cycle = time % total_duration;
+    time_in_cycle >= emitter_time.delay
+}
+
+/// Advances an emitter's system time by `delta` seconds.
+///
+/// Returns the new time, wrapped into the current cycle, and whether a cycle boundary
+/// was crossed. A single call counts at most one boundary, however large `delta` is.
+pub fn advance_time(time: f32, delta: f32, emitter_time: &EmitterTime) -> (f32, bool) {
+    let total_duration = emitter_time.total_duration();
+    let time = time + delta;
+    if time >= total_duration && total_duration > 0.0 {
+        (time % total_duration, true)
+    } else {
+        (time, false)
+    }
+}
+
+/// Returns the phase within a cycle at which the particle at `index` spawns.
+///
+/// Particles are spread evenly over the cycle, offset by
+/// [`spawn_time_randomness`](EmitterTime::spawn_time_randomness) and compressed
+/// towards the start by [`explosiveness`](EmitterTime::explosiveness). The result is in
+/// `0.0..1.0` for explosiveness values from `0.0` to `1.0`.
+pub fn spawn_phase(index: u32, amount: u32, emitter_time: &EmitterTime) -> f32 {
+    let base_phase = index as f32 / amount as f32;
+    let phase = base_phase + hash_to_float(index) * emitter_time.spawn_time_randomness;
+    (phase * (1.0 - emitter_time.explosiveness)).fract()
+}
+
+/// Returns `true` if the particle at `index` spawns during a step that moved the phase
+/// from `prev_phase` to `phase`, including steps where the phase wrapped around.
+pub fn spawns_between(
+    index: u32,
+    amount: u32,
+    prev_phase: f32,
+    phase: f32,
+    emitter_time: &EmitterTime,
+) -> bool {
+    let spawn = spawn_phase(index, amount, emitter_time);
+    if phase < prev_phase {
+        spawn >= prev_phase || spawn < phase
+    } else {
+        spawn >= prev_phase && spawn < phase
+    }
+}
+
+/// Counts the particles out of `amount` that spawn during a step that moved the phase
+/// from `prev_phase` to `phase`.
+pub fn spawn_count(amount: u32, prev_phase: f32, phase: f32, emitter_time: &EmitterTime) -> u32 {
+    (0..amount)
+        .filter(|&index| spawns_between(index, amount, prev_phase, phase, emitter_time))
+        .count() as u32
+}
+
+/// Returns the random seed the particle at `index` is spawned with during `cycle`.
+///
+/// Every property randomized at spawn time is derived from this seed, so two spawns with
+/// the same seed start out identical.
+pub fn particle_seed(random_seed: u32, index: u32, cycle: u32, amount: u32) -> u32 {
+    hash(
+        random_seed
+            .wrapping_add(1)
+            .wrapping_add(index)
+            .wrapping_add(cycle.wrapping_mul(amount)),
+    )
+}
+
+/// How a single frame is split into simulation steps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameSteps {
+    /// Number of steps to simulate this frame. May be `0` for fixed-FPS emitters.
+    pub count: u32,
+    /// Duration of each step, in seconds.
+    pub delta: f32,
+}
+
+/// Splits a frame of `frame_delta` seconds into simulation steps.
+///
+/// Emitters with a [`fixed_fps`](EmitterTime::fixed_fps) carry the leftover time over to the
+/// next frame in `accumulated_delta`, and `force_step` makes them take at least one step.
+/// Other emitters take a single step, or up to [`MAX_SUBSTEPS`] steps when
+/// [`frame_rate_independent`](EmitterTime::frame_rate_independent) is set.
+pub fn frame_steps(
+    frame_delta: f32,
+    accumulated_delta: &mut f32,
+    force_step: bool,
+    emitter_time: &EmitterTime,
+) -> FrameSteps {
+    if emitter_time.fixed_fps > 0 {
+        let delta = 1.0 / emitter_time.fixed_fps as f32;
+        *accumulated_delta += frame_delta.min(MAX_FRAME_DELTA);
+        let mut count = 0;
+        while *accumulated_delta >= delta || (force_step && count == 0) {
+            *accumulated_delta -= delta;
+            count += 1;
+        }
+        FrameSteps { count, delta }
+    } else {
+        let count = if emitter_time.frame_rate_independent {
+            ((frame_delta * SUBSTEP_FPS).ceil() as u32).clamp(1, MAX_SUBSTEPS)
+        } else {
+            1
+        };
+        FrameSteps {
+            count,
+            delta: frame_delta / count as f32,
+        }
+    }
+}
+
+/// A particle spawn reported by [`SpawnTimeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnEvent {
+    /// Index of the particle slot that spawned.
+    pub index: u32,
+    /// Emission cycle the particle spawned in.
+    pub cycle: u32,
+    /// Random seed the particle spawned with, see [`particle_seed`].
+    pub seed: u32,
+}
+
+/// CPU reference of an emitter's spawn schedule.
+///
+/// Steps through frames the same way the simulation does, and reports which particles
+/// spawn on each one. It models a freshly started emitter with an
+/// [`amount_ratio`](crate::runtime::EmitterRuntime::amount_ratio) of `1.0` and no
+/// [`rate_over_time`](crate::asset::EmitterEmission::rate_over_time) curve.
+#[derive(Debug, Clone)]
+pub struct SpawnTimeline {
+    emitter_time: EmitterTime,
+    amount: u32,
+    random_seed: u32,
+    system_time: f32,
+    cycle: u32,
+    accumulated_delta: f32,
+    emitting: bool,
+}
+
+impl SpawnTimeline {
+    /// Creates a timeline for an emitter of `amount` particles that just started emitting.
+    pub fn new(emitter_time: EmitterTime, amount: u32, random_seed: u32) -> Self {
+        Self {
+            emitter_time,
+            amount,
+            random_seed,
+            system_time: 0.0,
+            cycle: 0,
+            accumulated_delta: 0.0,
+            emitting: true,
+        }
+    }
+
+    /// The emitter's time within the current cycle, in seconds.
+    pub fn system_time(&self) -> f32 {
+        self.system_time
+    }
+
+    /// The current emission cycle.
+    pub fn cycle(&self) -> u32 {
+        self.cycle
+    }
+
+    /// Returns `false` once a [`one_shot`](EmitterTime::one_shot) emitter has finished.
+    pub fn is_emitting(&self) -> bool {
+        self.emitting
+    }
+
+    /// Advances the timeline by a frame of `frame_delta` seconds and returns the particles
+    /// that spawned during it, in step order.
+    pub fn advance(&mut self, frame_delta: f32) -> Vec<SpawnEvent> {
+        let steps = frame_steps(
+            frame_delta,
+            &mut self.accumulated_delta,
+            false,
+            &self.emitter_time,
+        );
+        let mut events = Vec::new();
+        for _ in 0..steps.count {
+            let prev_time = self.system_time;
+            let (system_time, wrapped) = advance_time(prev_time, steps.delta, &self.emitter_time);
+            self.system_time = system_time;
+            if wrapped {
+                self.cycle += 1;
+            }
+            if !self.emitting || !is_past_delay(system_time, &self.emitter_time) {
+                continue;
+            }
+            let prev_phase = compute_phase(prev_time, &self.emitter_time);
+            let phase = compute_phase(system_time, &self.emitter_time);
+            events.extend(
+                (0..self.amount)
+                    .filter(|&index| {
+                        spawns_between(index, self.amount, prev_phase, phase, &self.emitter_time)
+                    })
+                    .map(|index| SpawnEvent {
+                        index,
+                        cycle: self.cycle,
+                        seed: particle_seed(self.random_seed, index, self.cycle, self.amount),
+                    }),
+            );
+        }
+        if self.emitter_time.one_shot && self.cycle > 0 {
+            self.emitting = false;
+        }
+        events
+    }
+}
+
+// same multiply-xorshift hash as `hash` in common.wgsl
+pub(crate) fn hash(n: u32) -> u32 {
+    let mut x = n;
+    x = ((x >> 16) ^ x).wrapping_mul(0x45d9f3b);
+    x = ((x >> 16) ^ x).wrapping_mul(0x45d9f3b);
+    (x >> 16) ^ x
+}
+
+pub(crate) fn hash_to_float(n: u32) -> f32 {
+    hash(n) as f32 / u32::MAX as f32
+}
@@ -0,0 +1,137 @@
+//! Emitters and colliders can sit under any chain of scaled and rotated parents. A
+//! non-uniformly scaled parent shears the children it rotates, so their
+//! [`GlobalTransform`] can't be split into scale, rotation and translation without losing
+//! that shear. These functions work on the full affine matrix instead.
+//!
+//! They are pure, so tools and tests can check where particles spawn and collide without
+//! running the simulation.
+
+use bevy::math::{Mat3, Mat4, Quat, Vec3};
+use bevy::transform::components::GlobalTransform;
+
+use crate::asset::EmissionOrientation;
+
+/// Upper bound on the iterations spent splitting the rotation off a sheared transform.
+const POLAR_DECOMPOSITION_ITERATIONS: usize = 32;
+
+/// Interpolates an emitter's transform between the start (`t = 0.0`) and the end
+/// (`t = 1.0`) of a frame, so each simulation step spawns along the path it covers.
+///
+/// The origin moves in a straight line, while rotation and scale are interpolated as the
+/// change between the two transforms. This keeps the shear that a non-uniformly scaled
+/// parent gives a rotated emitter intact, and both ends return the given transforms
+/// exactly.
+pub fn interpolate_transform(from: Mat4, to: Mat4, t: f32) -> Mat4 {
+    if t <= 0.0 {
+        return from;
+    }
+    let from_linear = Mat3::from_mat4(from);
+    if t >= 1.0 || from_linear.determinant().abs() <= f32::EPSILON {
+        return to;
+    }
+    let change = Mat3::from_mat4(to) * from_linear.inverse();
+    let (scale, rotation, _) = Mat4::from_mat3(change).to_scale_rotation_translation();
+    let linear = Mat3::from_quat(Quat::IDENTITY.slerp(rotation, t))
+        * Mat3::from_diagonal(Vec3::ONE.lerp(scale, t))
+        * from_linear;
+    let translation = from.w_axis.truncate().lerp(to.w_axis.truncate(), t);
+    Mat4::from_cols(
+        linear.x_axis.extend(0.0),
+        linear.y_axis.extend(0.0),
+        linear.z_axis.extend(0.0),
+        translation.extend(1.0),
+    )
+}
+
+/// Returns the transforms an emitter spawns its particles with and draws them with, as
+/// `(spawn_transform, render_transform)`.
+///
+/// Spawn positions, including the [emission offset](crate::EmitterEmission::offset),
+/// go through the spawn transform, and simulated particles are drawn through the render
+/// transform, so a particle spawned at `position` lands at
+/// `render_transform * spawn_transform * position` in world space. With
+/// `use_local_coords`, particles spawn in the emitter's local space and follow it
+/// afterwards, otherwise they spawn in world space and stay where they were emitted.
+pub fn emitter_transforms(
+    global_transform: &GlobalTransform,
+    orientation: EmissionOrientation,
+    use_local_coords: bool,
+) -> (Mat4, Mat4) {
+    let world_matrix = global_transform.to_matrix();
+    match orientation {
+        EmissionOrientation::Emitter if use_local_coords => (Mat4::IDENTITY, world_matrix),
+        EmissionOrientation::Emitter => (world_matrix, Mat4::IDENTITY),
+        EmissionOrientation::World => {
+            // keep the scale and shear of the full matrix but drop its rotation, then undo
+            // the mesh transform in local mode so the world-aligned spawn space survives
+            // rendering
+            let (_, stretch) = split_rotation(Mat3::from_mat4(world_matrix));
+            let aligned =
+                Mat4::from_translation(global_transform.translation()) * Mat4::from_mat3(stretch);
+            if use_local_coords {
+                (world_matrix.inverse() * aligned, world_matrix)
+            } else {
+                (aligned, Mat4::IDENTITY)
+            }
+        }
+    }
+}
+
+/// Splits `linear` into `(rotation, stretch)`, with `linear = rotation * stretch`.
+///
+/// Unlike a quaternion and a per-axis scale, the stretch keeps any shear in `linear`, so
+/// it sizes and skews things exactly like `linear` without turning them. Mirroring stays
+/// in the stretch.
+fn split_rotation(linear: Mat3) -> (Mat3, Mat3) {
+    let determinant = linear.determinant();
+    if determinant.abs() <= f32::EPSILON {
+        return (Mat3::IDENTITY, linear);
+    }
+    // mirror first so the closest orthogonal matrix is a proper rotation
+    let mirror = Mat3::from_diagonal(Vec3::new(determinant.signum(), 1.0, 1.0));
+    let mut rotation = linear * mirror;
+    // Newton iteration towards the polar decomposition's rotation
+    for _ in 0..POLAR_DECOMPOSITION_ITERATIONS {
+        let next = (rotation + rotation.inverse().transpose()) * 0.5;
+        let converged = next.abs_diff_eq(rotation, 1e-6);
+        rotation = next;
+        if converged {
+            break;
+        }
+    }
+    (rotation, rotation.transpose() * linear)
+}
+
+/// World-space matrices of a collider, as the simulation shader receives them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColliderTransforms {
+    /// Maps the collider's local space to world space.
+    pub transform: Mat4,
+    /// Maps world space to the collider's local space, where its shape is tested.
+    pub inverse_transform: Mat4,
+    /// Maps a world-space point on the collider to the velocity it moved at since the
+    /// previous frame, so moving colliders push particles along.
+    pub velocity_transform: Mat4,
+}
+
+impl ColliderTransforms {
+    /// Builds the matrices of a collider at `global_transform` that was at `previous`
+    /// `delta` seconds ago. Without a previous transform or a positive `delta`, the
+    /// collider is treated as stationary.
+    pub fn new(global_transform: &GlobalTransform, previous: Option<Mat4>, delta: f32) -> Self {
+        let transform = global_transform.to_matrix();
+        let inverse_transform = transform.inverse();
+        // a point p on the collider now was at previous * inverse * p last frame
+        let velocity_transform = match previous {
+            Some(previous) if delta > 0.0 => {
+                (Mat4::IDENTITY - previous * inverse_transform) * delta.recip()
+            }
+            _ => Mat4::ZERO,
+        };
+        Self {
+            transform,
+            inverse_transform,
+            velocity_transform,
+        }
+    }
+}
@@ -0,0 +1,1155 @@
+use bevy::math::{Vec2, Vec3, Vec3A};
+use proptest::prelude::*;
+use std::path::Path;
+
+use bevy_sprinkles::TextureRef;
+use bevy_sprinkles::asset::versions;
+use bevy_sprinkles::asset::{
+    AlphaFadeMode, AnimatedVelocity, ColliderData, Curve, CurveEasing, CurveMode, CurvePoint,
+    CurveTexture, CurveWrap, DrawOrder, DrawPassMaterial, DrawPassVisibility, EmissionMaskChannel,
+    EmissionOrientation, EmissionShape, EmitterAccelerations, EmitterAngle, EmitterCollision,
+    EmitterCollisionMode, EmitterColors, EmitterCycleRandomness, EmitterData, EmitterDrawPass,
+    EmitterEmission, EmitterExpressions, EmitterScale, EmitterStopAction, EmitterTarget,
+    EmitterTextureSheet, EmitterTime, EmitterTrail, EmitterTurbulence, EmitterVelocities,
+    EmitterWave, Expression, ExpressionVec3, Gradient, GradientInterpolation, GradientStop,
+    InitialColorSampling, InitialTransform, ParticleBlendMode, ParticleFlags, ParticleMesh,
+    ParticlesAsset, ParticlesAuthors, ParticlesColliderShape3D, ParticlesDimension, PointGravity,
+    QuadOrientation, Range, RibbonTrailShape, RingEmissionOrder, SerializableAlphaMode,
+    SerializableFace, SolidOrGradientColor, StandardParticleMaterial, SubEmitterAmountScale,
+    SubEmitterConfig, SubEmitterKeepVelocity, SubEmitterMode, TargetShape, TextureSheetRandomMode,
+    TransformAlign, VisibilityAabb,
+};
+
+// each value is either the field's default, so skip-default attributes are exercised, or
+// an arbitrary one
+
+fn scalar(default: f32) -> impl Strategy<Value = f32> {
+    prop_oneof![Just(default), -100.0f32..100.0]
+}
+
+fn unit(default: f32) -> impl Strategy<Value = f32> {
+    prop_oneof![Just(default), 0.0f32..1.0]
+}
+
+fn count(default: u32) -> impl Strategy<Value = u32> {
+    prop_oneof![Just(default), 0u32..64]
+}
+
+fn vec2(default: Vec2) -> impl Strategy<Value = Vec2> {
+    prop_oneof![
+        Just(default),
+        (-100.0f32..100.0, -100.0f32..100.0).prop_map(|(x, y)| Vec2::new(x, y)),
+    ]
+}
+
+fn vec3(default: Vec3) -> impl Strategy<Value = Vec3> {
+    prop_oneof![
+        Just(default),
+        (-100.0f32..100.0, -100.0f32..100.0, -100.0f32..100.0)
+            .prop_map(|(x, y, z)| Vec3::new(x, y, z)),
+    ]
+}
+
+fn color(default: [f32; 4]) -> impl Strategy<Value = [f32; 4]> {
+    prop_oneof![Just(default), prop::array::uniform4(0.0f32..1.0)]
+}
+
+fn range(default: Range) -> impl Strategy<Value = Range> {
+    prop_oneof![
+        Just(default),
+        (-100.0f32..100.0, -100.0f32..100.0).prop_map(|(a, b)| Range::new(a.min(b), a.max(b))),
+    ]
+}
+
+fn name() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9 _-]{0,16}"
+}
+
+fn texture_ref() -> impl Strategy<Value = Option<TextureRef>> {
+    prop::option::of(prop_oneof![
+        "[a-z_/]{1,16}\\.png".prop_map(TextureRef::Asset),
+        "/[a-z_/]{1,16}\\.png".prop_map(TextureRef::Local),
+    ])
+}
+
+fn curve() -> impl Strategy<Value = Curve> {
+    let point = (
+        0.0f32..1.0,
+        -10.0f64..10.0,
+        prop_oneof![
+            Just(CurveMode::SingleCurve),
+            Just(CurveMode::DoubleCurve),
+            Just(CurveMode::Hold),
+            Just(CurveMode::Stairs),
+            Just(CurveMode::SmoothStairs),
+            Just(CurveMode::Bezier),
+        ],
+        prop_oneof![Just(0.0f64), -1.0f64..1.0],
+        prop_oneof![
+            Just(CurveEasing::Power),
+            Just(CurveEasing::Sine),
+            Just(CurveEasing::Expo),
+            Just(CurveEasing::Circ),
+        ],
+        vec2(Vec2::ZERO),
+        vec2(Vec2::ZERO),
+    )
+        .prop_map(
+            |(position, value, mode, tension, easing, in_tangent, out_tangent)| {
+                CurvePoint::new(position, value)
+                    .with_mode(mode)
+                    .with_tension(tension)
+                    .with_easing(easing)
+                    .with_tangents(in_tangent, out_tangent)
+            },
+        );
+
+    (prop::collection::vec(point, 1..4), range(Range::default()))
+        .prop_map(|(points, range)| Curve::new(points).with_range(range))
+}
+
+fn curve_texture() -> impl Strategy<Value = Option<CurveTexture>> {
+    prop::option::of(
+        (
+            // an empty name is written as no name at all
+            prop::option::of("[A-Za-z ]{1,12}"),
+            curve(),
+            prop::option::of(curve()),
+            prop::option::of(curve()),
+            prop_oneof![
+                Just(CurveWrap::Clamp),
+                Just(CurveWrap::Loop),
+                Just(CurveWrap::PingPong),
+            ],
+            prop_oneof![Just(1.0f32), 0.0f32..8.0],
+        )
+            .prop_map(|(name, x, y, z, wrap, repeat)| CurveTexture {
+                name,
+                x,
+                y,
+                z,
+                wrap,
+                repeat,
+            }),
+    )
+}
+
+fn gradient() -> impl Strategy<Value = Gradient> {
+    let stop = (color([1.0; 4]), 0.0f32..1.0)
+        .prop_map(|(color, position)| GradientStop { color, position });
+
+    (
+        prop::collection::vec(stop, 1..4),
+        prop_oneof![
+            Just(GradientInterpolation::Steps),
+            Just(GradientInterpolation::Linear),
+            Just(GradientInterpolation::Smoothstep),
+        ],
+    )
+        .prop_map(|(stops, interpolation)| Gradient {
+            stops,
+            interpolation,
+        })
+}
+
+fn solid_or_gradient() -> impl Strategy<Value = SolidOrGradientColor> {
+    prop_oneof![
+        color([1.0; 4]).prop_map(SolidOrGradientColor::solid),
+        gradient().prop_map(|gradient| SolidOrGradientColor::Gradient { gradient }),
+    ]
+}
+
+fn animated_velocity() -> impl Strategy<Value = AnimatedVelocity> {
+    let d = AnimatedVelocity::default();
+    (range(d.velocity), curve_texture()).prop_map(|(velocity, velocity_over_lifetime)| {
+        AnimatedVelocity {
+            velocity,
+            velocity_over_lifetime,
+        }
+    })
+}
+
+fn expression() -> impl Strategy<Value = Option<Expression>> {
+    prop::option::of(
+        prop_oneof![
+            Just("sin(TIME)"),
+            Just("PHASE * 2.0"),
+            Just("clamp(CYCLE % 3.0, 0.0, 1.0)"),
+            Just("mix(1.0, -1.0, smoothstep(0.0, TAU, TIME))"),
+        ]
+        .prop_map(|source| Expression::new(source).unwrap()),
+    )
+}
+
+fn expression_vec3() -> impl Strategy<Value = ExpressionVec3> {
+    (expression(), expression(), expression()).prop_map(|(x, y, z)| ExpressionVec3 { x, y, z })
+}
+
+fn initial_transform() -> impl Strategy<Value = InitialTransform> {
+    (vec3(Vec3::ZERO), vec3(Vec3::ZERO), vec3(Vec3::ONE)).prop_map(
+        |(translation, rotation, scale)| InitialTransform {
+            translation,
+            rotation,
+            scale,
+        },
+    )
+}
+
+fn emitter_time() -> impl Strategy<Value = EmitterTime> {
+    let d = EmitterTime::default();
+    (
+        scalar(d.lifetime),
+        unit(d.lifetime_randomness),
+        scalar(d.delay),
+        any::<bool>(),
+        prop_oneof![
+            Just(EmitterStopAction::None),
+            Just(EmitterStopAction::Despawn),
+            Just(EmitterStopAction::Disable),
+            Just(EmitterStopAction::Callback),
+        ],
+        unit(d.explosiveness),
+        unit(d.spawn_time_randomness),
+        count(d.fixed_fps),
+        any::<bool>(),
+        prop::option::of(any::<u32>()),
+    )
+        .prop_map(
+            |(
+                lifetime,
+                lifetime_randomness,
+                delay,
+                one_shot,
+                stop_action,
+                explosiveness,
+                spawn_time_randomness,
+                fixed_fps,
+                frame_rate_independent,
+                fixed_seed,
+            )| EmitterTime {
+                lifetime,
+                lifetime_randomness,
+                delay,
+                one_shot,
+                stop_action,
+                explosiveness,
+                spawn_time_randomness,
+                fixed_fps,
+                frame_rate_independent,
+                fixed_seed,
+            },
+        )
+}
+
+fn cycle_randomness() -> impl Strategy<Value = EmitterCycleRandomness> {
+    let d = EmitterCycleRandomness::default();
+    (unit(d.direction), unit(d.color), unit(d.explosiveness)).prop_map(
+        |(direction, color, explosiveness)| EmitterCycleRandomness {
+            direction,
+            color,
+            explosiveness,
+        },
+    )
+}
+
+fn mesh() -> impl Strategy<Value = ParticleMesh> {
+    prop_oneof![
+        (
+            prop_oneof![
+                Just(QuadOrientation::FaceX),
+                Just(QuadOrientation::FaceY),
+                Just(QuadOrientation::FaceZ),
+            ],
+            vec2(Vec2::ONE),
+            vec2(Vec2::ZERO),
+        )
+            .prop_map(|(orientation, size, subdivide)| ParticleMesh::Quad {
+                orientation,
+                size,
+                subdivide,
+            }),
+        (scalar(1.0), count(32), count(16)).prop_map(|(radius, segments, rings)| {
+            ParticleMesh::Sphere {
+                radius,
+                segments,
+                rings,
+            }
+        }),
+        vec3(Vec3::splat(0.5)).prop_map(|half_size| ParticleMesh::Cuboid { half_size }),
+        (
+            scalar(0.5),
+            scalar(0.5),
+            scalar(1.0),
+            count(16),
+            count(1),
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(
+                    top_radius,
+                    bottom_radius,
+                    height,
+                    radial_segments,
+                    rings,
+                    cap_top,
+                    cap_bottom,
+                )| {
+                    ParticleMesh::Cylinder {
+                        top_radius,
+                        bottom_radius,
+                        height,
+                        radial_segments,
+                        rings,
+                        cap_top,
+                        cap_bottom,
+                    }
+                },
+            ),
+        (unit(0.5), vec3(Vec3::ONE), vec3(Vec3::ZERO)).prop_map(
+            |(left_to_right, size, subdivide)| ParticleMesh::Prism {
+                left_to_right,
+                size,
+                subdivide,
+            }
+        ),
+        (scalar(0.5), count(8), count(8), count(1)).prop_map(
+            |(radius, radial_steps, sections, section_rings)| ParticleMesh::TubeTrail {
+                radius,
+                radial_steps,
+                sections,
+                section_rings,
+            }
+        ),
+        (
+            scalar(1.0),
+            count(8),
+            count(1),
+            prop_oneof![Just(RibbonTrailShape::Flat), Just(RibbonTrailShape::Cross)],
+        )
+            .prop_map(
+                |(size, sections, section_rings, shape)| ParticleMesh::RibbonTrail {
+                    size,
+                    sections,
+                    section_rings,
+                    shape,
+                }
+            ),
+    ]
+}
+
+fn standard_material() -> impl Strategy<Value = StandardParticleMaterial> {
+    let d = StandardParticleMaterial::default();
+    (
+        (
+            color(d.base_color),
+            texture_ref(),
+            color(d.emissive),
+            texture_ref(),
+            prop_oneof![
+                Just(SerializableAlphaMode::Opaque),
+                unit(0.5).prop_map(|cutoff| SerializableAlphaMode::Mask { cutoff }),
+                Just(SerializableAlphaMode::Blend),
+                Just(SerializableAlphaMode::Premultiplied),
+                Just(SerializableAlphaMode::Add),
+                Just(SerializableAlphaMode::Multiply),
+                Just(SerializableAlphaMode::AlphaToCoverage),
+            ],
+            prop::option::of(prop_oneof![
+                Just(ParticleBlendMode::AdditiveWithAlpha),
+                Just(ParticleBlendMode::Screen),
+                Just(ParticleBlendMode::Subtract),
+                Just(ParticleBlendMode::Min),
+                Just(ParticleBlendMode::Max),
+            ]),
+            prop_oneof![
+                Just(AlphaFadeMode::Uniform),
+                unit(0.1).prop_map(|softness| AlphaFadeMode::Erosion { softness }),
+            ],
+        ),
+        (
+            unit(d.perceptual_roughness),
+            unit(d.metallic),
+            any::<bool>(),
+            prop::option::of(prop_oneof![
+                Just(SerializableFace::Front),
+                Just(SerializableFace::Back),
+            ]),
+            any::<bool>(),
+            any::<bool>(),
+            texture_ref(),
+            vec2(d.distortion_speed),
+            vec2(d.texture_region_offset),
+            vec2(d.texture_region_size),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    base_color,
+                    base_color_texture,
+                    emissive,
+                    emissive_texture,
+                    alpha_mode,
+                    blend_mode,
+                    alpha_fade,
+                ),
+                (
+                    perceptual_roughness,
+                    metallic,
+                    double_sided,
+                    cull_mode,
+                    unlit,
+                    fog_enabled,
+                    distortion_texture,
+                    distortion_speed,
+                    texture_region_offset,
+                    texture_region_size,
+                ),
+            )| StandardParticleMaterial {
+                base_color,
+                base_color_texture,
+                emissive,
+                emissive_texture,
+                alpha_mode,
+                blend_mode,
+                alpha_fade,
+                perceptual_roughness,
+                metallic,
+                double_sided,
+                cull_mode,
+                unlit,
+                fog_enabled,
+                distortion_texture,
+                distortion_speed,
+                texture_region_offset,
+                texture_region_size,
+                ..Default::default()
+            },
+        )
+}
+
+fn material() -> impl Strategy<Value = DrawPassMaterial> {
+    prop_oneof![
+        standard_material().prop_map(DrawPassMaterial::Standard),
+        (
+            prop::option::of("[a-z_/]{1,16}\\.wgsl"),
+            prop::option::of("[a-z_/]{1,16}\\.wgsl"),
+        )
+            .prop_map(
+                |(vertex_shader, fragment_shader)| DrawPassMaterial::CustomShader {
+                    vertex_shader,
+                    fragment_shader,
+                }
+            ),
+    ]
+}
+
+fn draw_pass() -> impl Strategy<Value = EmitterDrawPass> {
+    let d = EmitterDrawPass::default();
+    (
+        (
+            prop_oneof![
+                Just(DrawOrder::Index),
+                Just(DrawOrder::Lifetime),
+                Just(DrawOrder::ReverseLifetime),
+                Just(DrawOrder::ViewDepth),
+                Just(DrawOrder::CameraDistance),
+            ],
+            mesh(),
+            material(),
+            any::<bool>(),
+            prop_oneof![
+                Just(DrawPassVisibility::Normal),
+                Just(DrawPassVisibility::ShadowOnly),
+                Just(DrawPassVisibility::NoShadow),
+            ],
+            any::<bool>(),
+            scalar(d.sort_bias),
+            count(d.sort_interval),
+        ),
+        (
+            prop::option::of(prop_oneof![
+                Just(TransformAlign::Billboard),
+                Just(TransformAlign::YToVelocity),
+                Just(TransformAlign::BillboardYToVelocity),
+                Just(TransformAlign::BillboardFixedY),
+                vec3(Vec3::Z).prop_map(TransformAlign::FixedAxis),
+                scalar(1.0).prop_map(TransformAlign::VelocityStretchedBillboard),
+            ]),
+            any::<bool>(),
+            any::<bool>(),
+            prop::option::of(0.0f32..10.0),
+            any::<bool>(),
+            prop::option::of(any::<u32>()),
+            (vec3(Vec3::ZERO), vec3(Vec3::ONE)).prop_map(|(center, half_extents)| VisibilityAabb {
+                center: Vec3A::from(center),
+                half_extents: Vec3A::from(half_extents),
+            }),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    draw_order,
+                    mesh,
+                    material,
+                    shadow_caster,
+                    visibility,
+                    depth_prepass,
+                    sort_bias,
+                    sort_interval,
+                ),
+                (
+                    transform_align,
+                    billboard_keep_world_up,
+                    use_local_coords,
+                    frustum_cull_margin,
+                    metaball,
+                    post_process_mask,
+                    visibility_aabb,
+                ),
+            )| EmitterDrawPass {
+                draw_order,
+                mesh,
+                material,
+                shadow_caster,
+                visibility,
+                depth_prepass,
+                sort_bias,
+                sort_interval,
+                transform_align,
+                billboard_keep_world_up,
+                use_local_coords,
+                frustum_cull_margin,
+                metaball,
+                post_process_mask,
+                visibility_aabb,
+            },
+        )
+}
+
+fn emission_shape() -> impl Strategy<Value = EmissionShape> {
+    let mask_channel = prop_oneof![
+        Just(EmissionMaskChannel::Alpha),
+        Just(EmissionMaskChannel::Luminance),
+    ];
+    prop_oneof![
+        Just(EmissionShape::Point),
+        scalar(1.0).prop_map(|radius| EmissionShape::Sphere { radius }),
+        scalar(1.0).prop_map(|radius| EmissionShape::SphereSurface { radius }),
+        vec3(Vec3::ONE).prop_map(|extents| EmissionShape::Box { extents }),
+        (
+            vec3(Vec3::Z),
+            scalar(1.0),
+            scalar(1.0),
+            scalar(0.0),
+            scalar(360.0),
+            prop_oneof![
+                Just(RingEmissionOrder::Random),
+                Just(RingEmissionOrder::Clockwise),
+                Just(RingEmissionOrder::CounterClockwise),
+            ],
+        )
+            .prop_map(|(axis, height, radius, inner_radius, arc, order)| {
+                EmissionShape::Ring {
+                    axis,
+                    height,
+                    radius,
+                    inner_radius,
+                    arc,
+                    order,
+                }
+            }),
+        (texture_ref(), vec2(Vec2::ONE), mask_channel, any::<bool>()).prop_map(
+            |(texture, size, channel, inherit_color)| EmissionShape::Image {
+                texture,
+                size,
+                channel,
+                inherit_color,
+            }
+        ),
+    ]
+}
+
+fn emission() -> impl Strategy<Value = EmitterEmission> {
+    let d = EmitterEmission::default();
+    (
+        (
+            vec3(d.offset),
+            vec3(d.scale),
+            emission_shape(),
+            vec3(d.position_randomness),
+            unit(d.shell_thickness),
+            scalar(d.edge_lifetime_scale),
+        ),
+        (
+            scalar(d.edge_speed_scale),
+            unit(d.spatial_randomness),
+            scalar(d.spatial_randomness_scale),
+            count(d.particles_amount),
+            prop::option::of(0u32..4096),
+            curve_texture(),
+            prop_oneof![
+                Just(EmissionOrientation::Emitter),
+                Just(EmissionOrientation::World),
+            ],
+        ),
+    )
+        .prop_map(
+            |(
+                (offset, scale, shape, position_randomness, shell_thickness, edge_lifetime_scale),
+                (
+                    edge_speed_scale,
+                    spatial_randomness,
+                    spatial_randomness_scale,
+                    particles_amount,
+                    max_particles,
+                    rate_over_time,
+                    orientation,
+                ),
+            )| EmitterEmission {
+                offset,
+                scale,
+                shape,
+                orientation,
+                position_randomness,
+                shell_thickness,
+                edge_lifetime_scale,
+                edge_speed_scale,
+                spatial_randomness,
+                spatial_randomness_scale,
+                particles_amount,
+                max_particles,
+                rate_over_time,
+            },
+        )
+}
+
+fn scale() -> impl Strategy<Value = EmitterScale> {
+    let d = EmitterScale::default();
+    (range(d.range), curve_texture()).prop_map(|(range, scale_over_lifetime)| EmitterScale {
+        range,
+        scale_over_lifetime,
+    })
+}
+
+fn angle() -> impl Strategy<Value = EmitterAngle> {
+    (range(Range::new(0.0, 0.0)), curve_texture()).prop_map(|(range, angle_over_lifetime)| {
+        EmitterAngle {
+            range,
+            angle_over_lifetime,
+        }
+    })
+}
+
+fn colors() -> impl Strategy<Value = EmitterColors> {
+    (
+        solid_or_gradient(),
+        prop_oneof![
+            Just(InitialColorSampling::Random),
+            Just(InitialColorSampling::SpawnOrder),
+            Just(InitialColorSampling::EmissionPosition),
+        ],
+        gradient(),
+        curve_texture(),
+        curve_texture(),
+        curve_texture(),
+    )
+        .prop_map(
+            |(
+                initial_color,
+                initial_color_sampling,
+                color_over_lifetime,
+                color_over_lifetime_curves,
+                alpha_over_lifetime,
+                emission_over_lifetime,
+            )| {
+                EmitterColors {
+                    initial_color,
+                    initial_color_sampling,
+                    color_over_lifetime,
+                    color_over_lifetime_curves,
+                    alpha_over_lifetime,
+                    emission_over_lifetime,
+                }
+            },
+        )
+}
+
+fn texture_sheet() -> impl Strategy<Value = EmitterTextureSheet> {
+    (
+        any::<bool>(),
+        count(1),
+        count(1),
+        prop_oneof![
+            Just(TextureSheetRandomMode::Cell),
+            Just(TextureSheetRandomMode::Row),
+            Just(TextureSheetRandomMode::Column),
+        ],
+    )
+        .prop_map(
+            |(enabled, columns, rows, random_mode)| EmitterTextureSheet {
+                enabled,
+                columns,
+                rows,
+                random_mode,
+            },
+        )
+}
+
+fn velocities() -> impl Strategy<Value = EmitterVelocities> {
+    let d = EmitterVelocities::default();
+    (
+        vec3(d.initial_direction),
+        scalar(d.spread),
+        unit(d.flatness),
+        range(d.initial_velocity),
+        animated_velocity(),
+        animated_velocity(),
+        animated_velocity(),
+        animated_velocity(),
+        vec3(d.pivot),
+        unit(d.inherit_ratio),
+    )
+        .prop_map(
+            |(
+                initial_direction,
+                spread,
+                flatness,
+                initial_velocity,
+                radial_velocity,
+                angular_velocity,
+                orbit_velocity,
+                directional_velocity,
+                pivot,
+                inherit_ratio,
+            )| EmitterVelocities {
+                initial_direction,
+                spread,
+                flatness,
+                initial_velocity,
+                radial_velocity,
+                angular_velocity,
+                orbit_velocity,
+                directional_velocity,
+                pivot,
+                inherit_ratio,
+            },
+        )
+}
+
+fn accelerations() -> impl Strategy<Value = EmitterAccelerations> {
+    let d = EmitterAccelerations::default();
+    (
+        vec3(d.gravity),
+        range(d.gravity_scale),
+        animated_velocity(),
+        (
+            vec3(d.point_gravity.position),
+            scalar(d.point_gravity.strength),
+        )
+            .prop_map(|(position, strength)| PointGravity { position, strength }),
+        range(d.damping),
+    )
+        .prop_map(
+            |(gravity, gravity_scale, radial_acceleration, point_gravity, damping)| {
+                EmitterAccelerations {
+                    gravity,
+                    gravity_scale,
+                    radial_acceleration,
+                    point_gravity,
+                    damping,
+                }
+            },
+        )
+}
+
+fn turbulence() -> impl Strategy<Value = EmitterTurbulence> {
+    let d = EmitterTurbulence::default();
+    (
+        any::<bool>(),
+        scalar(d.noise_strength),
+        scalar(d.noise_scale),
+        vec3(d.noise_speed),
+        unit(d.noise_speed_random),
+        range(d.influence),
+        curve_texture(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(
+                enabled,
+                noise_strength,
+                noise_scale,
+                noise_speed,
+                noise_speed_random,
+                influence,
+                influence_over_lifetime,
+                use_volumes,
+            )| EmitterTurbulence {
+                enabled,
+                noise_strength,
+                noise_scale,
+                noise_speed,
+                noise_speed_random,
+                influence,
+                influence_over_lifetime,
+                use_volumes,
+            },
+        )
+}
+
+fn wave() -> impl Strategy<Value = EmitterWave> {
+    let d = EmitterWave::default();
+    (
+        any::<bool>(),
+        scalar(d.amplitude),
+        scalar(d.frequency),
+        unit(d.phase_randomness),
+        vec3(d.axis),
+    )
+        .prop_map(
+            |(enabled, amplitude, frequency, phase_randomness, axis)| EmitterWave {
+                enabled,
+                amplitude,
+                frequency,
+                phase_randomness,
+                axis,
+            },
+        )
+}
+
+fn target() -> impl Strategy<Value = EmitterTarget> {
+    let shape = prop_oneof![
+        prop::collection::vec(vec3(Vec3::ZERO), 0..4)
+            .prop_map(|positions| TargetShape::Points { positions }),
+        (
+            texture_ref(),
+            vec2(Vec2::ONE),
+            prop_oneof![
+                Just(EmissionMaskChannel::Alpha),
+                Just(EmissionMaskChannel::Luminance),
+            ],
+        )
+            .prop_map(|(texture, size, channel)| TargetShape::Image {
+                texture,
+                size,
+                channel,
+            }),
+    ];
+    (any::<bool>(), shape, curve_texture()).prop_map(|(enabled, shape, blend_over_lifetime)| {
+        EmitterTarget {
+            enabled,
+            shape,
+            blend_over_lifetime,
+        }
+    })
+}
+
+fn collision() -> impl Strategy<Value = EmitterCollision> {
+    let d = EmitterCollision::default();
+    (
+        prop::option::of(prop_oneof![
+            (unit(0.0), unit(0.0))
+                .prop_map(|(friction, bounce)| EmitterCollisionMode::Rigid { friction, bounce }),
+            Just(EmitterCollisionMode::HideOnContact),
+        ]),
+        any::<bool>(),
+        scalar(d.base_size),
+        count(d.max_substeps),
+        prop::option::of(-100.0f32..100.0),
+        prop::option::of(0u32..4096),
+    )
+        .prop_map(
+            |(mode, use_scale, base_size, max_substeps, ground_plane, accumulation_capacity)| {
+                EmitterCollision {
+                    mode,
+                    use_scale,
+                    base_size,
+                    max_substeps,
+                    ground_plane,
+                    accumulation_capacity,
+                }
+            },
+        )
+}
+
+fn sub_emitter() -> impl Strategy<Value = Option<SubEmitterConfig>> {
+    let d = SubEmitterConfig::default();
+    let keep_velocity = (
+        unit(d.keep_velocity.inherit_ratio),
+        prop::option::of(vec3(Vec3::Y)),
+        range(d.keep_velocity.speed_multiplier),
+    )
+        .prop_map(|(inherit_ratio, extra_direction, speed_multiplier)| {
+            SubEmitterKeepVelocity {
+                inherit_ratio,
+                extra_direction,
+                speed_multiplier,
+            }
+        });
+    prop::option::of(
+        (
+            prop_oneof![
+                Just(SubEmitterMode::Constant),
+                Just(SubEmitterMode::AtEnd),
+                Just(SubEmitterMode::AtCollision),
+                Just(SubEmitterMode::AtStart),
+                Just(SubEmitterMode::Trail),
+            ],
+            0usize..4,
+            scalar(d.frequency),
+            count(d.amount),
+            prop_oneof![
+                Just(SubEmitterAmountScale::None),
+                Just(SubEmitterAmountScale::Scale),
+                Just(SubEmitterAmountScale::Lifetime),
+            ],
+            keep_velocity,
+        )
+            .prop_map(
+                |(mode, target_emitter, frequency, amount, amount_scale, keep_velocity)| {
+                    SubEmitterConfig {
+                        mode,
+                        target_emitter,
+                        frequency,
+                        amount,
+                        amount_scale,
+                        keep_velocity,
+                    }
+                },
+            ),
+    )
+}
+
+fn trail() -> impl Strategy<Value = EmitterTrail> {
+    let d = EmitterTrail::default();
+    (any::<bool>(), scalar(d.stretch_time), curve_texture()).prop_map(
+        |(enabled, stretch_time, thickness_curve)| EmitterTrail {
+            enabled,
+            stretch_time,
+            thickness_curve,
+        },
+    )
+}
+
+fn expressions() -> impl Strategy<Value = EmitterExpressions> {
+    (expression_vec3(), expression_vec3(), expression()).prop_map(|(offset, direction, scale)| {
+        EmitterExpressions {
+            offset,
+            direction,
+            scale,
+        }
+    })
+}
+
+fn emitter_data() -> impl Strategy<Value = EmitterData> {
+    (
+        (
+            name(),
+            any::<bool>(),
+            initial_transform(),
+            emitter_time(),
+            cycle_randomness(),
+            draw_pass(),
+            emission(),
+            scale(),
+            angle(),
+            colors(),
+            texture_sheet(),
+        ),
+        (
+            velocities(),
+            accelerations(),
+            turbulence(),
+            wave(),
+            target(),
+            collision(),
+            sub_emitter(),
+            trail(),
+            expressions(),
+            any::<u32>().prop_map(ParticleFlags::from_bits_truncate),
+        ),
+    )
+        .prop_map(
+            |(
+                (
+                    name,
+                    enabled,
+                    initial_transform,
+                    time,
+                    cycle_randomness,
+                    draw_pass,
+                    emission,
+                    scale,
+                    angle,
+                    colors,
+                    texture_sheet,
+                ),
+                (
+                    velocities,
+                    accelerations,
+                    turbulence,
+                    wave,
+                    target,
+                    collision,
+                    sub_emitter,
+                    trail,
+                    expressions,
+                    particle_flags,
+                ),
+            )| EmitterData {
+                name,
+                enabled,
+                initial_transform,
+                time,
+                cycle_randomness,
+                draw_pass,
+                emission,
+                scale,
+                angle,
+                colors,
+                texture_sheet,
+                velocities,
+                accelerations,
+                turbulence,
+                wave,
+                target,
+                collision,
+                sub_emitter,
+                trail,
+                expressions,
+                particle_flags,
+            },
+        )
+}
+
+fn collider_data() -> impl Strategy<Value = ColliderData> {
+    let d = ColliderData::default();
+    (
+        name(),
+        any::<bool>(),
+        prop_oneof![
+            vec3(Vec3::ONE).prop_map(|size| ParticlesColliderShape3D::Box { size }),
+            scalar(1.0).prop_map(|radius| ParticlesColliderShape3D::Sphere { radius }),
+        ],
+        any::<bool>(),
+        initial_transform(),
+        color(d.gizmo_color),
+    )
+        .prop_map(
+            |(name, enabled, shape, invert, initial_transform, gizmo_color)| ColliderData {
+                name,
+                enabled,
+                shape,
+                invert,
+                initial_transform,
+                gizmo_color,
+            },
+        )
+}
+
+fn particles_asset() -> impl Strategy<Value = ParticlesAsset> {
+    (
+        name(),
+        prop_oneof![Just(ParticlesDimension::D3), Just(ParticlesDimension::D2)],
+        initial_transform(),
+        prop::collection::vec(emitter_data(), 1..3),
+        prop::collection::vec(collider_data(), 0..3),
+        any::<bool>(),
+        ("[A-Za-z ]{0,12}", "[A-Za-z ]{0,12}").prop_map(|(inspired_by, submitted_by)| {
+            ParticlesAuthors {
+                inspired_by,
+                submitted_by,
+            }
+        }),
+        "[A-Za-z ]{0,24}",
+        prop::collection::vec("[a-z]{1,8}", 0..4),
+        prop::option::of(0u32..600),
+    )
+        .prop_map(
+            |(
+                name,
+                dimension,
+                initial_transform,
+                emitters,
+                colliders,
+                despawn_on_finish,
+                authors,
+                description,
+                tags,
+                preview_frame,
+            )| {
+                let mut asset = ParticlesAsset::new(
+                    name,
+                    dimension,
+                    initial_transform,
+                    emitters,
+                    colliders,
+                    despawn_on_finish,
+                    authors,
+                );
+                asset.description = description;
+                asset.tags = tags;
+                asset.preview_frame = preview_frame;
+                asset
+            },
+        )
+}
+
+fn to_ron(asset: &ParticlesAsset) -> String {
+    ron::ser::to_string_pretty(asset, ron::ser::PrettyConfig::default()).unwrap()
+}
+
+// not every asset type implements `PartialEq`, but all of them derive `Debug`, which
+// prints floats with enough precision to tell any two values apart
+fn assert_same_asset(expected: &ParticlesAsset, actual: &ParticlesAsset) {
+    assert_eq!(format!("{expected:#?}"), format!("{actual:#?}"));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_asset_round_trips_through_ron(asset in particles_asset()) {
+        let result = versions::migrate_str(&to_ron(&asset)).unwrap();
+        prop_assert!(!result.was_migrated);
+        assert_same_asset(&asset, &result.asset);
+    }
+
+    #[test]
+    fn test_asset_serialization_is_stable(asset in particles_asset()) {
+        let ron = to_ron(&asset);
+        let reloaded = versions::migrate_str(&ron).unwrap().asset;
+        prop_assert_eq!(ron, to_ron(&reloaded));
+    }
+
+    #[test]
+    fn test_v0_2_asset_migrates_identically(asset in particles_asset()) {
+        let current = format!("\"{}\"", versions::current_format_version());
+        let ron = to_ron(&asset).replacen(&current, "\"0.2\"", 1);
+
+        let result = versions::migrate_str(&ron).unwrap();
+        prop_assert!(result.was_migrated);
+        assert_same_asset(&asset, &result.asset);
+    }
+}
+
+#[test]
+fn test_v0_1_asset_round_trips_after_migration() {
+    let ron = std::fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("v0_1_particle_system.ron"),
+    )
+    .unwrap();
+
+    let migrated = versions::migrate_str(&ron).unwrap();
+    assert!(migrated.was_migrated);
+
+    let reloaded = versions::migrate_str(&to_ron(&migrated.asset)).unwrap();
+    assert!(!reloaded.was_migrated);
+    assert_same_asset(&migrated.asset, &reloaded.asset);
+}
@@ -0,0 +1,95 @@
+use bevy_sprinkles::asset::{EmitterExpressions, Expression, ExpressionContext, ExpressionError};
+
+fn eval(source: &str, context: ExpressionContext) -> f32 {
+    Expression::new(source)
+        .unwrap()
+        .compile()
+        .unwrap()
+        .evaluate(&context)
+}
+
+fn eval_constant(source: &str) -> f32 {
+    eval(source, ExpressionContext::default())
+}
+
+#[test]
+fn test_operator_precedence() {
+    assert_eq!(eval_constant("1 + 2 * 3"), 7.0);
+    assert_eq!(eval_constant("(1 + 2) * 3"), 9.0);
+    assert_eq!(eval_constant("10 - 4 - 3"), 3.0);
+    assert_eq!(eval_constant("-2 * -3"), 6.0);
+    assert_eq!(eval_constant("7 % 4"), 3.0);
+    assert_eq!(eval_constant("1.5e1 / 3"), 5.0);
+}
+
+#[test]
+fn test_variables_read_from_context() {
+    let context = ExpressionContext {
+        time: 2.0,
+        phase: 0.25,
+        cycle: 3,
+    };
+    assert_eq!(eval("TIME * 2.0", context), 4.0);
+    assert_eq!(eval("PHASE + CYCLE", context), 3.25);
+    assert!((eval("sin(TIME * PI / 4.0)", context) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_functions() {
+    assert_eq!(eval_constant("clamp(5, 0, 1)"), 1.0);
+    assert_eq!(eval_constant("mix(2, 4, 0.5)"), 3.0);
+    assert_eq!(eval_constant("step(0.5, 0.7)"), 1.0);
+    assert_eq!(eval_constant("smoothstep(0, 1, 0.5)"), 0.5);
+    assert_eq!(eval_constant("fract(-0.25)"), 0.75);
+    assert_eq!(eval_constant("max(min(3, 2), pow(2, 0))"), 2.0);
+}
+
+#[test]
+fn test_invalid_expressions_fail_to_parse() {
+    assert_eq!(
+        Expression::new("FOO"),
+        Err(ExpressionError::UnknownVariable("FOO".into()))
+    );
+    assert_eq!(
+        Expression::new("wobble(1)"),
+        Err(ExpressionError::UnknownFunction("wobble".into()))
+    );
+    assert_eq!(Expression::new("1 +"), Err(ExpressionError::UnexpectedEnd));
+    assert_eq!(
+        Expression::new("1 $ 2"),
+        Err(ExpressionError::UnexpectedCharacter('$', 2))
+    );
+    assert!(matches!(
+        Expression::new("pow(2)"),
+        Err(ExpressionError::WrongArgumentCount {
+            expected: 2,
+            found: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Expression::new("(1 + 2"),
+        Err(ExpressionError::UnexpectedEnd)
+    ));
+}
+
+#[test]
+fn test_expressions_round_trip_through_ron() {
+    let ron = r#"(offset: (y: "sin(TIME * 2.0) * 0.5"), scale: "1.0 + PHASE")"#;
+    let expressions: EmitterExpressions = ron::from_str(ron).unwrap();
+    assert_eq!(
+        expressions.offset.y.as_ref().map(Expression::source),
+        Some("sin(TIME * 2.0) * 0.5")
+    );
+    assert!(expressions.offset.x.is_none());
+    assert!(expressions.direction.is_empty());
+
+    let serialized = ron::to_string(&expressions).unwrap();
+    let reparsed: EmitterExpressions = ron::from_str(&serialized).unwrap();
+    assert_eq!(reparsed, expressions);
+}
+
+#[test]
+fn test_invalid_expression_fails_to_deserialize() {
+    assert!(ron::from_str::<EmitterExpressions>(r#"(scale: "1 +")"#).is_err());
+}
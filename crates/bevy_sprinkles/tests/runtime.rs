@@ -0,0 +1,160 @@
+use bevy::asset::AssetPlugin;
+use bevy::camera::visibility::InheritedVisibility;
+use bevy::prelude::*;
+use bevy_sprinkles::asset::{
+    EmitterData, EmitterStopAction, InitialTransform, ParticlesAsset, ParticlesAuthors,
+    ParticlesDimension,
+};
+use bevy_sprinkles::runtime::{
+    DisabledEmitter, EmitterEntity, EmitterRuntime, ParticleSeed, ParticleSystemRuntime,
+    Particles3d, restore_disabled_emitters, run_emitter_stop_actions,
+};
+
+#[test]
+fn test_system_seed_derives_emitter_seeds() {
+    let system = ParticleSystemRuntime::with_seed(7);
+    let seeds: Vec<u32> = (0..4).map(|i| system.emitter_seed(i).unwrap()).collect();
+    assert_eq!(
+        seeds,
+        (0..4)
+            .map(|i| ParticleSystemRuntime::with_seed(7).emitter_seed(i).unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        seeds
+            .iter()
+            .enumerate()
+            .all(|(i, seed)| !seeds[..i].contains(seed))
+    );
+    assert_ne!(
+        ParticleSystemRuntime::with_seed(8).emitter_seed(0),
+        Some(seeds[0])
+    );
+    assert_eq!(ParticleSystemRuntime::default().emitter_seed(0), None);
+}
+
+#[test]
+fn test_instance_seed_matches_system_seed() {
+    let system = ParticleSystemRuntime::with_seed(42);
+    for index in 0..4 {
+        assert_eq!(
+            Some(ParticleSeed(42).emitter_seed(index)),
+            system.emitter_seed(index)
+        );
+    }
+}
+
+#[test]
+fn test_hidden_emitters_pause_only_when_requested() {
+    let mut system = ParticleSystemRuntime::default();
+    assert!(!system.is_paused_for(&InheritedVisibility::VISIBLE));
+    assert!(!system.is_paused_for(&InheritedVisibility::HIDDEN));
+
+    system.pause_when_hidden = true;
+    assert!(!system.is_paused_for(&InheritedVisibility::VISIBLE));
+    assert!(system.is_paused_for(&InheritedVisibility::HIDDEN));
+
+    system.pause_when_hidden = false;
+    system.pause();
+    assert!(system.is_paused_for(&InheritedVisibility::VISIBLE));
+}
+
+fn disabling_emitter_app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<ParticlesAsset>()
+        .add_systems(
+            Update,
+            (restore_disabled_emitters, run_emitter_stop_actions).chain(),
+        );
+
+    let mut emitter = EmitterData::default();
+    emitter.time.one_shot = true;
+    emitter.time.stop_action = EmitterStopAction::Disable;
+    let asset = ParticlesAsset::new(
+        "disable".into(),
+        ParticlesDimension::default(),
+        InitialTransform::default(),
+        vec![emitter],
+        Vec::new(),
+        false,
+        ParticlesAuthors::default(),
+    );
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<ParticlesAsset>>()
+        .add(asset);
+    let mut system_runtime = ParticleSystemRuntime::default();
+    system_runtime.pause_when_hidden = true;
+    let system = app
+        .world_mut()
+        .spawn((Particles3d(handle), system_runtime))
+        .id();
+
+    // a one-shot emitter whose particles have all died
+    let mut runtime = EmitterRuntime::new(0, None);
+    runtime.set_emitting(false);
+    runtime.one_shot_completed = true;
+    runtime.inactive = true;
+    let emitter = app
+        .world_mut()
+        .spawn((
+            EmitterEntity {
+                parent_system: system,
+            },
+            runtime,
+            Visibility::Visible,
+        ))
+        .id();
+    (app, emitter)
+}
+
+#[test]
+fn test_disabled_emitter_is_shown_again_after_restart() {
+    let (mut app, emitter) = disabling_emitter_app();
+
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(emitter),
+        Some(&Visibility::Hidden)
+    );
+    assert!(app.world().get::<DisabledEmitter>(emitter).is_some());
+
+    // staying idle keeps it hidden
+    app.update();
+    assert_eq!(
+        app.world().get::<Visibility>(emitter),
+        Some(&Visibility::Hidden)
+    );
+
+    app.world_mut()
+        .get_mut::<EmitterRuntime>(emitter)
+        .unwrap()
+        .restart(None);
+    app.update();
+
+    let world = app.world();
+    assert_eq!(world.get::<Visibility>(emitter), Some(&Visibility::Visible));
+    assert!(world.get::<DisabledEmitter>(emitter).is_none());
+    // visible and active again, so pause_when_hidden no longer freezes its simulation
+    let runtime = world.get::<EmitterRuntime>(emitter).unwrap();
+    assert!(runtime.is_emitting());
+    assert!(!runtime.inactive);
+}
+
+#[test]
+fn test_disabled_emitter_is_shown_again_when_emitting() {
+    let (mut app, emitter) = disabling_emitter_app();
+    app.update();
+
+    app.world_mut()
+        .get_mut::<EmitterRuntime>(emitter)
+        .unwrap()
+        .play();
+    app.update();
+
+    assert_eq!(
+        app.world().get::<Visibility>(emitter),
+        Some(&Visibility::Visible)
+    );
+}
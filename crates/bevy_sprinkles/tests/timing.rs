@@ -0,0 +1,263 @@
+use bevy_sprinkles::asset::EmitterTime;
+use bevy_sprinkles::timing::{
+    FrameSteps, SpawnEvent, SpawnTimeline, advance_time, compute_phase, frame_steps, is_past_delay,
+    particle_seed, spawn_count, spawn_phase, spawns_between,
+};
+
+fn timing_configs() -> Vec<EmitterTime> {
+    let mut configs = Vec::new();
+    for lifetime in [0.1, 0.5, 1.0, 3.7] {
+        for delay in [0.0, 0.25, 2.0] {
+            for explosiveness in [0.0, 0.3, 0.99] {
+                for spawn_time_randomness in [0.0, 0.5, 1.0] {
+                    configs.push(EmitterTime {
+                        lifetime,
+                        delay,
+                        explosiveness,
+                        spawn_time_randomness,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+    configs
+}
+
+fn sample_times(emitter_time: &EmitterTime) -> impl Iterator<Item = f32> {
+    let total = emitter_time.total_duration();
+    (0..200).map(move |i| i as f32 / 200.0 * total * 3.0)
+}
+
+#[test]
+fn test_phase_stays_in_unit_range() {
+    for config in timing_configs() {
+        for time in sample_times(&config) {
+            let phase = compute_phase(time, &config);
+            assert!(
+                (0.0..=1.0).contains(&phase),
+                "phase {phase} out of range at time {time}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_phase_is_zero_during_delay() {
+    for config in timing_configs() {
+        for time in sample_times(&config) {
+            if !is_past_delay(time, &config) {
+                assert_eq!(compute_phase(time, &config), 0.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_phase_repeats_every_cycle() {
+    for config in timing_configs() {
+        let total = config.total_duration();
+        for time in sample_times(&config).filter(|t| *t < total) {
+            let phase = compute_phase(time, &config);
+            let next_cycle = compute_phase(time + total, &config);
+            assert!((phase - next_cycle).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn test_advance_time_wraps_once_per_cycle() {
+    for config in timing_configs() {
+        let total = config.total_duration();
+        let delta = total / 7.0;
+        let mut time = 0.0;
+        let mut cycles = 0;
+        for _ in 0..70 {
+            let (next, wrapped) = advance_time(time, delta, &config);
+            assert!((0.0..total).contains(&next));
+            cycles += wrapped as u32;
+            time = next;
+        }
+        assert!(
+            (9..=10).contains(&cycles),
+            "expected ~10 cycles, got {cycles}"
+        );
+    }
+}
+
+#[test]
+fn test_spawn_phase_stays_in_unit_range() {
+    for config in timing_configs() {
+        for index in 0..64 {
+            let phase = spawn_phase(index, 64, &config);
+            assert!((0.0..1.0).contains(&phase));
+        }
+    }
+}
+
+#[test]
+fn test_full_explosiveness_spawns_everything_at_once() {
+    let config = EmitterTime {
+        explosiveness: 1.0,
+        spawn_time_randomness: 0.7,
+        ..Default::default()
+    };
+    for index in 0..64 {
+        assert_eq!(spawn_phase(index, 64, &config), 0.0);
+    }
+}
+
+#[test]
+fn test_every_particle_spawns_once_per_cycle() {
+    let amount = 50;
+    for config in timing_configs() {
+        let steps = 13;
+        let mut spawns = vec![0u32; amount as usize];
+        for step in 0..steps {
+            let prev_phase = step as f32 / steps as f32;
+            let phase = (step + 1) as f32 / steps as f32 % 1.0;
+            for index in 0..amount {
+                if spawns_between(index, amount, prev_phase, phase, &config) {
+                    spawns[index as usize] += 1;
+                }
+            }
+        }
+        assert!(spawns.iter().all(|&count| count == 1), "{spawns:?}");
+    }
+}
+
+// golden values below mirror the GPU spawn math, any change to them is a timing regression
+
+#[test]
+fn test_particle_seed_golden() {
+    assert_eq!(particle_seed(0, 0, 0, 8), 824515495);
+    assert_eq!(particle_seed(7, 3, 2, 16), 1543550320);
+    assert_eq!(particle_seed(u32::MAX, 0, 0, 4), 0);
+}
+
+#[test]
+fn test_spawn_count_golden() {
+    let config = EmitterTime::default();
+    assert_eq!(spawn_count(8, 0.0, 0.25, &config), 2);
+    assert_eq!(spawn_count(8, 0.75, 0.0, &config), 2);
+    assert_eq!(spawn_count(8, 0.5, 0.5, &config), 0);
+}
+
+#[test]
+fn test_frame_steps_golden() {
+    let variable = EmitterTime::default();
+    let mut accumulated = 0.0;
+    assert_eq!(
+        frame_steps(0.5, &mut accumulated, false, &variable),
+        FrameSteps {
+            count: 1,
+            delta: 0.5
+        }
+    );
+
+    let substepped = EmitterTime {
+        frame_rate_independent: true,
+        ..Default::default()
+    };
+    for (frame_delta, count) in [(0.0, 1), (0.01, 1), (0.05, 3), (0.5, 8)] {
+        let steps = frame_steps(frame_delta, &mut accumulated, false, &substepped);
+        assert_eq!(steps.count, count, "frame delta {frame_delta}");
+        assert_eq!(steps.delta, frame_delta / count as f32);
+    }
+
+    let fixed = EmitterTime {
+        fixed_fps: 30,
+        ..Default::default()
+    };
+    let counts: Vec<u32> = (0..12)
+        .map(|_| frame_steps(1.0 / 60.0, &mut accumulated, false, &fixed).count)
+        .collect();
+    assert_eq!(counts, [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1]);
+
+    let mut accumulated = 0.0;
+    let forced = frame_steps(0.0, &mut accumulated, true, &fixed);
+    assert_eq!(forced.count, 1);
+    assert!(accumulated < 0.0);
+}
+
+#[test]
+fn test_spawn_timeline_even_spread_golden() {
+    let mut timeline = SpawnTimeline::new(EmitterTime::default(), 8, 0);
+    let frames: Vec<Vec<SpawnEvent>> = (0..5).map(|_| timeline.advance(0.25)).collect();
+    let event = |index, cycle, seed| SpawnEvent { index, cycle, seed };
+    assert_eq!(
+        frames,
+        [
+            vec![event(0, 0, 824515495), event(1, 0, 1722258072)],
+            vec![event(2, 0, 3753300549), event(3, 0, 3444516145)],
+            vec![event(4, 0, 592649042), event(5, 0, 3284760808)],
+            // the last step of a cycle wraps, so it already belongs to the next one
+            vec![event(6, 1, 4042162429), event(7, 1, 966621358)],
+            vec![event(0, 1, 80224587), event(1, 1, 1185298084)],
+        ]
+    );
+    assert_eq!(timeline.cycle(), 1);
+    assert_eq!(timeline.system_time(), 0.25);
+}
+
+#[test]
+fn test_spawn_timeline_one_shot_golden() {
+    let config = EmitterTime {
+        delay: 0.5,
+        explosiveness: 0.5,
+        one_shot: true,
+        ..Default::default()
+    };
+    let mut timeline = SpawnTimeline::new(config, 16, 7);
+    let counts: Vec<usize> = (0..16).map(|_| timeline.advance(0.125).len()).collect();
+    assert_eq!(counts, [0, 0, 0, 0, 4, 4, 4, 4, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(!timeline.is_emitting());
+}
+
+#[test]
+fn test_spawn_timeline_randomized_golden() {
+    let config = EmitterTime {
+        lifetime: 2.0,
+        spawn_time_randomness: 0.5,
+        frame_rate_independent: true,
+        ..Default::default()
+    };
+    let mut timeline = SpawnTimeline::new(config, 32, 42);
+    let frames: Vec<Vec<SpawnEvent>> = (0..25).map(|_| timeline.advance(0.1)).collect();
+    assert_eq!(
+        frames[0],
+        [
+            SpawnEvent {
+                index: 0,
+                cycle: 0,
+                seed: 1543550320
+            },
+            SpawnEvent {
+                index: 19,
+                cycle: 0,
+                seed: 3926364933
+            },
+        ]
+    );
+    let counts: Vec<usize> = frames.iter().map(Vec::len).collect();
+    assert_eq!(
+        counts,
+        [
+            2, 0, 4, 0, 2, 3, 1, 2, 0, 2, 3, 3, 3, 0, 1, 1, 0, 2, 2, 1, 2, 0, 4, 0, 2
+        ]
+    );
+}
+
+#[test]
+fn test_spawn_timeline_fixed_fps_golden() {
+    let config = EmitterTime {
+        fixed_fps: 10,
+        ..Default::default()
+    };
+    let mut timeline = SpawnTimeline::new(config, 10, 3);
+    let counts: Vec<usize> = (0..30)
+        .map(|_| timeline.advance(1.0 / 60.0).len())
+        .collect();
+    let expected: Vec<usize> = (0..30).map(|frame| (frame % 6 == 5) as usize).collect();
+    assert_eq!(counts, expected);
+}
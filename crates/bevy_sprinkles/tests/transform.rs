@@ -0,0 +1,175 @@
+use bevy::math::{Mat3, Mat4, Quat, Vec3};
+use bevy::transform::components::{GlobalTransform, Transform};
+use bevy_sprinkles::asset::EmissionOrientation;
+use bevy_sprinkles::transform::{ColliderTransforms, emitter_transforms, interpolate_transform};
+
+// an emitter rotated under a non-uniformly scaled, rotated grandparent, whose global
+// transform is sheared and can't be rebuilt from scale, rotation and translation alone
+fn nested_emitter(parent_translation: Vec3, parent_angle: f32) -> GlobalTransform {
+    let grandparent = GlobalTransform::from(
+        Transform::from_translation(parent_translation)
+            .with_rotation(Quat::from_rotation_y(parent_angle))
+            .with_scale(Vec3::new(3.0, 0.5, 1.0)),
+    );
+    let parent = grandparent.mul_transform(Transform::from_rotation(Quat::from_rotation_z(0.7)));
+    parent
+        .mul_transform(Transform::from_xyz(0.0, 2.0, 0.0).with_rotation(Quat::from_rotation_x(0.4)))
+}
+
+fn nested_emitter_transform(parent_translation: Vec3, parent_angle: f32) -> Mat4 {
+    nested_emitter(parent_translation, parent_angle).to_matrix()
+}
+
+fn assert_mat4_near(actual: Mat4, expected: Mat4) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-5),
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+fn assert_vec3_near(actual: Vec3, expected: Vec3) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-3),
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+const SURFACE_POINTS: [Vec3; 3] = [Vec3::X, Vec3::Y, Vec3::new(0.0, 0.6, 0.8)];
+
+#[test]
+fn test_nested_emitter_transform_is_sheared() {
+    let transform = nested_emitter_transform(Vec3::ZERO, 0.5);
+    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+    let rebuilt = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+    assert!(!rebuilt.abs_diff_eq(transform, 1e-3));
+}
+
+#[test]
+fn test_interpolate_transform_endpoints_are_exact() {
+    let from = nested_emitter_transform(Vec3::ZERO, 0.0);
+    let to = nested_emitter_transform(Vec3::new(4.0, 1.0, -2.0), 1.2);
+    assert_eq!(interpolate_transform(from, to, 0.0), from);
+    assert_eq!(interpolate_transform(from, to, 1.0), to);
+}
+
+#[test]
+fn test_interpolate_transform_keeps_stationary_shear() {
+    let transform = nested_emitter_transform(Vec3::new(1.0, 2.0, 3.0), 0.5);
+    for t in [0.25, 0.5, 0.75] {
+        assert_mat4_near(interpolate_transform(transform, transform, t), transform);
+    }
+}
+
+#[test]
+fn test_interpolate_transform_moves_sheared_emitter_linearly() {
+    let from = nested_emitter_transform(Vec3::ZERO, 0.3);
+    let to = nested_emitter_transform(Vec3::new(6.0, 0.0, -3.0), 0.3);
+    for t in [0.25, 0.5, 0.75] {
+        let expected = Mat4::from_cols(
+            from.x_axis,
+            from.y_axis,
+            from.z_axis,
+            from.w_axis.lerp(to.w_axis, t),
+        );
+        assert_mat4_near(interpolate_transform(from, to, t), expected);
+    }
+}
+
+#[test]
+fn test_interpolate_transform_rotating_parent_keeps_emitter_on_path() {
+    let from = nested_emitter_transform(Vec3::ZERO, 0.0);
+    let to = nested_emitter_transform(Vec3::ZERO, 1.0);
+    let halfway = interpolate_transform(from, to, 0.5);
+    assert!(
+        halfway
+            .w_axis
+            .abs_diff_eq(from.w_axis.lerp(to.w_axis, 0.5), 1e-5)
+    );
+    // the world-space change between the ends is a pure rotation about Y, so halfway is
+    // the same sheared emitter turned by half that angle
+    let expected_linear = Mat4::from_quat(Quat::from_rotation_y(0.5)) * from;
+    assert!(halfway.x_axis.abs_diff_eq(expected_linear.x_axis, 1e-4));
+    assert!(halfway.y_axis.abs_diff_eq(expected_linear.y_axis, 1e-4));
+    assert!(halfway.z_axis.abs_diff_eq(expected_linear.z_axis, 1e-4));
+}
+
+#[test]
+fn test_emission_offset_follows_nested_parents() {
+    let global_transform = nested_emitter(Vec3::new(4.0, 1.0, -2.0), 0.8);
+    let offset = Vec3::new(1.5, -0.5, 2.0);
+    for use_local_coords in [false, true] {
+        let (spawn_transform, render_transform) = emitter_transforms(
+            &global_transform,
+            EmissionOrientation::Emitter,
+            use_local_coords,
+        );
+        assert_vec3_near(
+            (render_transform * spawn_transform).transform_point3(offset),
+            global_transform.transform_point(offset),
+        );
+    }
+}
+
+#[test]
+fn test_collider_placement_follows_nested_parents() {
+    let global_transform = nested_emitter(Vec3::new(-3.0, 0.0, 5.0), 1.1);
+    let transforms = ColliderTransforms::new(&global_transform, None, 0.0);
+    for local in SURFACE_POINTS {
+        let world = global_transform.transform_point(local);
+        assert_vec3_near(transforms.transform.transform_point3(local), world);
+        assert_vec3_near(transforms.inverse_transform.transform_point3(world), local);
+    }
+    assert_eq!(transforms.velocity_transform, Mat4::ZERO);
+}
+
+#[test]
+fn test_collider_velocity_follows_moving_nested_parents() {
+    let delta = 0.1;
+    let previous = nested_emitter(Vec3::ZERO, 0.0);
+    let current = nested_emitter(Vec3::new(0.5, 0.0, 0.0), 0.2);
+    let transforms = ColliderTransforms::new(&current, Some(previous.to_matrix()), delta);
+    for local in SURFACE_POINTS {
+        let now = current.transform_point(local);
+        let before = previous.transform_point(local);
+        assert_vec3_near(
+            transforms.velocity_transform.transform_point3(now),
+            (now - before) / delta,
+        );
+    }
+}
+
+#[test]
+fn test_world_orientation_keeps_sheared_scale_without_rotation() {
+    for use_local_coords in [false, true] {
+        let mut linears = Vec::new();
+        for angle in [0.0, 0.9, 2.3] {
+            let global_transform = nested_emitter(Vec3::new(2.0, -1.0, 3.0), angle);
+            let (spawn_transform, render_transform) = emitter_transforms(
+                &global_transform,
+                EmissionOrientation::World,
+                use_local_coords,
+            );
+            let spawn_space = render_transform * spawn_transform;
+            let linear = Mat3::from_mat4(spawn_space);
+            let full = Mat3::from_mat4(global_transform.to_matrix());
+            // the same lengths and angles as the full transform, so scale and shear survive
+            assert!(
+                (linear.transpose() * linear).abs_diff_eq(full.transpose() * full, 1e-3),
+                "{linear:?} doesn't match the shape of {full:?}"
+            );
+            // with the rotation taken out, what is left is a symmetric stretch
+            assert!(linear.abs_diff_eq(linear.transpose(), 1e-3));
+            assert_vec3_near(
+                spawn_space.w_axis.truncate(),
+                global_transform.translation(),
+            );
+            linears.push(linear);
+        }
+        // turning the root parent doesn't turn world-aligned emission
+        assert!(
+            linears
+                .iter()
+                .all(|linear| linear.abs_diff_eq(linears[0], 1e-3))
+        );
+    }
+}
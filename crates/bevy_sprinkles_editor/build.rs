@@ -8,10 +8,9 @@ fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let out_path = Path::new(&out_dir);
 
-    // rerun when examples are added/removed/modified
+    // rerun when example thumbnails are added/removed/modified
     println!("cargo:rerun-if-changed={}", examples_dir.display());
 
-    let mut ron_stems: Vec<String> = Vec::new();
     let mut jpg_stems: Vec<String> = Vec::new();
 
     let entries = fs::read_dir(&examples_dir)
@@ -22,36 +21,16 @@ fn main() {
         let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
             continue;
         };
-        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+        if path.extension().and_then(|e| e.to_str()) != Some("jpg") {
             continue;
-        };
-
-        let target = match ext {
-            "ron" => &mut ron_stems,
-            "jpg" => &mut jpg_stems,
-            _ => continue,
-        };
+        }
 
         println!("cargo:rerun-if-changed={}", path.display());
-        target.push(stem.to_string());
+        jpg_stems.push(stem.to_string());
     }
 
-    ron_stems.sort();
     jpg_stems.sort();
 
-    let mut bundled = String::new();
-    bundled.push_str("const BUNDLED_EXAMPLES: &[(&str, &str)] = &[\n");
-    for stem in &ron_stems {
-        let abs_path = examples_dir.join(format!("{stem}.ron"));
-        bundled.push_str(&format!(
-            "    (\"{stem}.ron\", include_str!(r\"{}\")),\n",
-            abs_path.display()
-        ));
-    }
-    bundled.push_str("];\n");
-
-    fs::write(out_path.join("bundled_examples.rs"), bundled).unwrap();
-
     let thumbs_dest = out_path.join("assets/examples");
     fs::create_dir_all(&thumbs_dest).unwrap();
 
@@ -1,11 +1,11 @@
-use std::fs;
 use std::path::Path;
 
 use bevy::asset::embedded_asset;
 use bevy::prelude::*;
 use bevy::shader::load_shader_library;
+use sprinkles_examples::ParticlesExample;
 
-include!(concat!(env!("OUT_DIR"), "/bundled_examples.rs"));
+use crate::io::{list_files, remove_file, write_file};
 
 pub fn plugin(app: &mut App) {
     // font
@@ -68,21 +68,22 @@ pub fn plugin(app: &mut App) {
 
 pub fn extract_examples(examples_dir: &Path) {
     // remove stale examples that are no longer bundled
-    let bundled_names: std::collections::HashSet<&str> =
-        BUNDLED_EXAMPLES.iter().map(|(name, _)| *name).collect();
-    if let Ok(entries) = fs::read_dir(examples_dir) {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let Some(name) = name.to_str() else { continue };
-            if name.ends_with(".ron") && !bundled_names.contains(name) {
-                let _ = fs::remove_file(entry.path());
-            }
+    let bundled_names: std::collections::HashSet<String> = ParticlesExample::ALL
+        .iter()
+        .map(|example| example.file_name())
+        .collect();
+    for path in list_files(examples_dir, "ron") {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if !bundled_names.contains(name) {
+            remove_file(&path);
         }
     }
 
     // extract (or overwrite) all bundled examples
-    for (filename, contents) in BUNDLED_EXAMPLES {
-        let _ = fs::write(examples_dir.join(filename), contents);
+    for example in ParticlesExample::ALL {
+        let _ = write_file(&examples_dir.join(example.file_name()), example.source());
     }
 }
 
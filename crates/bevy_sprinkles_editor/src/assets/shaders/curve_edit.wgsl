@@ -28,6 +28,14 @@ struct CurveUniforms {
     tensions_high: vec4<f32>,
     easings_low: vec4<u32>,
     easings_high: vec4<u32>,
+    in_tangents_x_low: vec4<f32>,
+    in_tangents_x_high: vec4<f32>,
+    in_tangents_y_low: vec4<f32>,
+    in_tangents_y_high: vec4<f32>,
+    out_tangents_x_low: vec4<f32>,
+    out_tangents_x_high: vec4<f32>,
+    out_tangents_y_low: vec4<f32>,
+    out_tangents_y_high: vec4<f32>,
 }
 
 @group(1) @binding(0)
@@ -58,6 +66,19 @@ fn get_easing(i: u32) -> u32 {
     return uniforms.easings_high[i - 4u];
 }
 
+fn get_in_tangent(i: u32) -> vec2<f32> {
+    if i < 4u { return vec2<f32>(uniforms.in_tangents_x_low[i], uniforms.in_tangents_y_low[i]); }
+    return vec2<f32>(uniforms.in_tangents_x_high[i - 4u], uniforms.in_tangents_y_high[i - 4u]);
+}
+
+fn get_out_tangent(i: u32) -> vec2<f32> {
+    if i < 4u { return vec2<f32>(uniforms.out_tangents_x_low[i], uniforms.out_tangents_y_low[i]); }
+    return vec2<f32>(uniforms.out_tangents_x_high[i - 4u], uniforms.out_tangents_y_high[i - 4u]);
+}
+
+const CURVE_MODE_BEZIER: u32 = 5u;
+const BEZIER_ITERATIONS: u32 = 16u;
+
 const PI: f32 = 3.14159265359;
 
 fn apply_power(t: f32, tension: f32) -> f32 {
@@ -163,6 +184,35 @@ fn apply_curve(t: f32, mode: u32, easing: u32, tension: f32) -> f32 {
     }
 }
 
+fn cubic_bezier(p0: f32, p1: f32, p2: f32, p3: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    return u * u * u * p0 + 3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s * p3;
+}
+
+// solves x(s) = t by bisection; handles are clamped inside the segment so x(s) is monotonic
+fn sample_bezier(left_idx: u32, right_idx: u32, t: f32) -> f32 {
+    let x0 = get_position(left_idx);
+    let x3 = get_position(right_idx);
+    let out_tangent = get_out_tangent(left_idx);
+    let in_tangent = get_in_tangent(right_idx);
+    let x1 = clamp(x0 + out_tangent.x, x0, x3);
+    let x2 = clamp(x3 + in_tangent.x, x0, x3);
+    let y0 = get_value(left_idx);
+    let y3 = get_value(right_idx);
+
+    var lo = 0.0;
+    var hi = 1.0;
+    for (var i = 0u; i < BEZIER_ITERATIONS; i++) {
+        let mid = (lo + hi) * 0.5;
+        if cubic_bezier(x0, x1, x2, x3, mid) < t {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    return cubic_bezier(y0, y0 + out_tangent.y, y3 + in_tangent.y, y3, (lo + hi) * 0.5);
+}
+
 fn sample_curve(x: f32) -> f32 {
     if uniforms.point_count == 0u {
         return 1.0;
@@ -205,6 +255,10 @@ fn sample_curve(x: f32) -> f32 {
         return left_val;
     }
 
+    if right_mode == CURVE_MODE_BEZIER {
+        return sample_bezier(left_idx, right_idx, t);
+    }
+
     let local_t = (t - left_pos) / segment_range;
 
     // adjust tension based on slope direction so positive tension always bends down
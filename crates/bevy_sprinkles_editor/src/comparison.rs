@@ -0,0 +1,430 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use bevy::asset::AssetId;
+use bevy::camera::{RenderTarget, Viewport};
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+use bevy_sprinkles::prelude::*;
+
+use crate::io::{picked_file_path, projects_dir};
+use crate::project::load_project_from_path;
+use crate::state::{EditorState, PlaybackSeekEvent};
+use crate::ui::components::toasts::ToastEvent;
+use crate::utils::{MAX_DISPLAY_PATH_LEN, simplify_path, truncate_path};
+use crate::viewport::{EditorCamera, EditorFloor, EditorParticlePreview, sync_playback_state};
+
+/// Where the reference particle system lives, far enough away that neither
+/// camera can see the other system.
+const COMPARISON_ORIGIN: Vec3 = Vec3::new(0.0, 0.0, 4096.0);
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ComparisonState>()
+        .add_observer(on_toggle_comparison_event)
+        .add_observer(on_browse_comparison_event)
+        .add_observer(on_compare_with_file_event)
+        .add_observer(on_playback_seek_event)
+        .add_systems(
+            Update,
+            (
+                poll_browse_comparison_result,
+                disable_comparison_on_project_change,
+                spawn_comparison_view,
+                despawn_comparison_view,
+                sync_comparison_cameras,
+                sync_comparison_playback.after(sync_playback_state),
+            ),
+        );
+}
+
+/// Side-by-side comparison of the current project against a reference asset.
+///
+/// The left half of the viewport shows the project being edited and the right
+/// half shows the reference, with seeds, pause state and seeking mirrored from
+/// the left side so both play the same timeline.
+#[derive(Resource, Default)]
+pub struct ComparisonState {
+    pub enabled: bool,
+    pub reference: Option<Handle<ParticlesAsset>>,
+    pub reference_path: Option<PathBuf>,
+    project: Option<AssetId<ParticlesAsset>>,
+}
+
+/// Toggles comparing the current project against its last saved version.
+#[derive(Event)]
+pub struct ToggleComparisonEvent;
+
+/// Opens a file dialog to pick an arbitrary project to compare against.
+#[derive(Event)]
+pub struct BrowseComparisonEvent;
+
+/// Starts comparing the current project against the file at the given path.
+#[derive(Event)]
+pub struct CompareWithFileEvent(pub PathBuf);
+
+#[derive(Resource, Clone)]
+pub struct BrowseComparisonResult(pub Arc<Mutex<Option<PathBuf>>>);
+
+#[derive(Component)]
+pub struct ComparisonPreview;
+
+#[derive(Component)]
+struct ComparisonCamera;
+
+#[derive(Component)]
+struct ComparisonFloor;
+
+fn on_toggle_comparison_event(
+    _event: On<ToggleComparisonEvent>,
+    editor_state: Res<EditorState>,
+    mut comparison: ResMut<ComparisonState>,
+    mut commands: Commands,
+) {
+    if comparison.enabled {
+        comparison.enabled = false;
+        return;
+    }
+
+    let Some(path) = editor_state.current_project_path.clone() else {
+        commands.trigger(ToastEvent::error(
+            "Save the project first to compare it against its saved version",
+        ));
+        return;
+    };
+
+    commands.trigger(CompareWithFileEvent(path));
+}
+
+fn on_browse_comparison_event(_event: On<BrowseComparisonEvent>, mut commands: Commands) {
+    let path_result = Arc::new(Mutex::new(None));
+    let path_result_clone = path_result.clone();
+
+    let task = rfd::AsyncFileDialog::new()
+        .set_title("Compare With Project")
+        .set_directory(projects_dir())
+        .add_filter("RON files", &["ron"])
+        .pick_file();
+
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Some(file_handle) = task.await
+                && let Some(path) = picked_file_path(file_handle).await
+                && let Ok(mut guard) = path_result_clone.lock()
+            {
+                *guard = Some(path);
+            }
+        })
+        .detach();
+
+    commands.insert_resource(BrowseComparisonResult(path_result));
+}
+
+fn poll_browse_comparison_result(
+    result: Option<Res<BrowseComparisonResult>>,
+    mut commands: Commands,
+) {
+    let Some(result) = result else {
+        return;
+    };
+
+    let path = {
+        let Ok(mut guard) = result.0.lock() else {
+            return;
+        };
+        guard.take()
+    };
+
+    if let Some(path) = path {
+        commands.trigger(CompareWithFileEvent(path));
+        commands.remove_resource::<BrowseComparisonResult>();
+    }
+}
+
+fn on_compare_with_file_event(
+    event: On<CompareWithFileEvent>,
+    editor_state: Res<EditorState>,
+    mut comparison: ResMut<ComparisonState>,
+    mut assets: ResMut<Assets<ParticlesAsset>>,
+    existing: Query<Entity, With<ComparisonPreview>>,
+    mut commands: Commands,
+) {
+    let path = &event.0;
+    let Ok(result) = load_project_from_path(path) else {
+        let display = truncate_path(&simplify_path(path), MAX_DISPLAY_PATH_LEN);
+        commands.trigger(ToastEvent::error(format!(
+            "Could not load \"{display}\" for comparison"
+        )));
+        return;
+    };
+
+    for entity in &existing {
+        commands.entity(entity).despawn();
+    }
+    if let Some(previous) = comparison.reference.take() {
+        assets.remove(&previous);
+    }
+
+    comparison.enabled = true;
+    comparison.reference = Some(assets.add(result.asset));
+    comparison.reference_path = Some(path.clone());
+    comparison.project = editor_state.current_project.as_ref().map(|h| h.id());
+}
+
+fn disable_comparison_on_project_change(
+    editor_state: Res<EditorState>,
+    mut comparison: ResMut<ComparisonState>,
+) {
+    if !editor_state.is_changed() || !comparison.enabled {
+        return;
+    }
+
+    let project = editor_state.current_project.as_ref().map(|h| h.id());
+    if project != comparison.project {
+        comparison.enabled = false;
+    }
+}
+
+fn spawn_comparison_view(
+    mut commands: Commands,
+    comparison: Res<ComparisonState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    existing: Query<(), With<ComparisonPreview>>,
+    cameras: Query<(), With<ComparisonCamera>>,
+    main_camera: Query<(&RenderTarget, Option<&DistanceFog>), With<EditorCamera>>,
+    floor: Query<(&Mesh3d, &MeshMaterial3d<StandardMaterial>, &Transform), With<EditorFloor>>,
+) {
+    if !comparison.enabled || !existing.is_empty() {
+        return;
+    }
+
+    let Some(handle) = &comparison.reference else {
+        return;
+    };
+    let Some(asset) = assets.get(handle) else {
+        return;
+    };
+    let Ok((render_target, fog)) = main_camera.single() else {
+        return;
+    };
+
+    let mut transform = asset.initial_transform.to_transform();
+    transform.translation += COMPARISON_ORIGIN;
+
+    commands.spawn((
+        Particles3d(handle.clone()),
+        transform,
+        Visibility::default(),
+        EditorMode,
+        ComparisonPreview,
+        Name::new("Comparison Preview"),
+    ));
+
+    if !cameras.is_empty() {
+        return;
+    }
+
+    let mut camera = commands.spawn((
+        ComparisonCamera,
+        Name::new("ComparisonCamera"),
+        Camera3d::default(),
+        Camera {
+            order: -1,
+            // the main camera already clears the whole shared target
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        render_target.clone(),
+        Transform::from_translation(COMPARISON_ORIGIN),
+        Msaa::Off,
+        MetaballSettings::default(),
+    ));
+    if let Some(fog) = fog {
+        camera.insert(fog.clone());
+    }
+
+    if let Ok((mesh, material, floor_transform)) = floor.single() {
+        let mut floor_transform = *floor_transform;
+        floor_transform.translation += COMPARISON_ORIGIN;
+        commands.spawn((
+            ComparisonFloor,
+            mesh.clone(),
+            material.clone(),
+            Name::new("ComparisonFloor"),
+            floor_transform,
+            Visibility::default(),
+        ));
+    }
+}
+
+fn despawn_comparison_view(
+    mut commands: Commands,
+    comparison: Res<ComparisonState>,
+    entities: Query<
+        Entity,
+        Or<(
+            With<ComparisonPreview>,
+            With<ComparisonCamera>,
+            With<ComparisonFloor>,
+        )>,
+    >,
+    mut main_camera: Query<&mut Camera, With<EditorCamera>>,
+) {
+    if !comparison.is_changed() || comparison.enabled {
+        return;
+    }
+
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+
+    for mut camera in &mut main_camera {
+        camera.viewport = None;
+    }
+}
+
+fn sync_comparison_cameras(
+    comparison: Res<ComparisonState>,
+    mut main_camera: Query<
+        (&mut Camera, &Transform, &Tonemapping),
+        (With<EditorCamera>, Without<ComparisonCamera>),
+    >,
+    mut comparison_camera: Query<
+        (&mut Camera, &mut Transform, &mut Tonemapping),
+        With<ComparisonCamera>,
+    >,
+) {
+    if !comparison.enabled {
+        return;
+    }
+
+    let Ok((mut main, main_transform, main_tonemapping)) = main_camera.single_mut() else {
+        return;
+    };
+    let Ok((mut reference, mut reference_transform, mut reference_tonemapping)) =
+        comparison_camera.single_mut()
+    else {
+        return;
+    };
+
+    *reference_transform = *main_transform;
+    reference_transform.translation += COMPARISON_ORIGIN;
+    if *reference_tonemapping != *main_tonemapping {
+        *reference_tonemapping = *main_tonemapping;
+    }
+
+    let Some(target_size) = main.physical_target_size() else {
+        return;
+    };
+    let half_width = (target_size.x / 2).max(1);
+    let size = UVec2::new(half_width, target_size.y.max(1));
+
+    let left = Viewport {
+        physical_position: UVec2::ZERO,
+        physical_size: size,
+        ..default()
+    };
+    let right = Viewport {
+        physical_position: UVec2::new(half_width, 0),
+        physical_size: size,
+        ..default()
+    };
+
+    if !viewport_matches(main.viewport.as_ref(), &left) {
+        main.viewport = Some(left);
+    }
+    if !viewport_matches(reference.viewport.as_ref(), &right) {
+        reference.viewport = Some(right);
+    }
+}
+
+fn viewport_matches(current: Option<&Viewport>, target: &Viewport) -> bool {
+    current.is_some_and(|v| {
+        v.physical_position == target.physical_position && v.physical_size == target.physical_size
+    })
+}
+
+fn sync_comparison_playback(
+    comparison: Res<ComparisonState>,
+    main_systems: Query<(Entity, &ParticleSystemRuntime), With<EditorParticlePreview>>,
+    mut reference_systems: Query<
+        (Entity, &mut ParticleSystemRuntime),
+        (With<ComparisonPreview>, Without<EditorParticlePreview>),
+    >,
+    mut emitters: Query<(&EmitterEntity, &mut EmitterRuntime)>,
+) {
+    if !comparison.enabled {
+        return;
+    }
+
+    let Ok((main_entity, main_runtime)) = main_systems.single() else {
+        return;
+    };
+    let Ok((reference_entity, mut reference_runtime)) = reference_systems.single_mut() else {
+        return;
+    };
+
+    if reference_runtime.paused != main_runtime.paused {
+        reference_runtime.paused = main_runtime.paused;
+    }
+    if reference_runtime.force_loop != main_runtime.force_loop {
+        reference_runtime.force_loop = main_runtime.force_loop;
+    }
+
+    let main_states: Vec<(usize, u32, bool)> = emitters
+        .iter()
+        .filter(|(emitter, _)| emitter.parent_system == main_entity)
+        .map(|(_, runtime)| {
+            (
+                runtime.emitter_index,
+                runtime.random_seed,
+                runtime.is_emitting(),
+            )
+        })
+        .collect();
+
+    for (emitter, mut runtime) in &mut emitters {
+        if emitter.parent_system != reference_entity {
+            continue;
+        }
+        let Some(&(_, seed, emitting)) = main_states
+            .iter()
+            .find(|(index, _, _)| *index == runtime.emitter_index)
+        else {
+            continue;
+        };
+
+        // a new seed on the main side means it was restarted or stopped
+        if runtime.random_seed != seed {
+            if emitting {
+                runtime.restart(Some(seed));
+            } else {
+                runtime.stop(Some(seed));
+            }
+            continue;
+        }
+
+        if emitting && !runtime.is_emitting() && !runtime.one_shot_completed {
+            runtime.play();
+        }
+    }
+}
+
+fn on_playback_seek_event(
+    trigger: On<PlaybackSeekEvent>,
+    comparison: Res<ComparisonState>,
+    systems: Query<Entity, With<ComparisonPreview>>,
+    mut emitters: Query<(&EmitterEntity, &mut EmitterRuntime)>,
+) {
+    if !comparison.enabled {
+        return;
+    }
+
+    for system_entity in &systems {
+        for (emitter, mut runtime) in &mut emitters {
+            if emitter.parent_system == system_entity {
+                runtime.seek(trigger.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+use bevy_sprinkles::prelude::*;
+use inflector::Inflector;
+
+use crate::io::{pick_save_path, projects_dir};
+use crate::project::{SaveResult, write_project_file};
+use crate::state::EditorState;
+use crate::ui::components::toasts::ToastEvent;
+use crate::ui::widgets::texture_edit::classify_texture_path;
+
+const SIGNIFICANT_DIGITS: i32 = 5;
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(on_save_optimized_copy_event)
+        .add_systems(Update, handle_export_keyboard_shortcut);
+}
+
+/// Writes a cleaned-up copy of the current project without touching the open file.
+#[derive(Event)]
+pub struct SaveOptimizedCopyEvent;
+
+pub(crate) struct OptimizedAsset {
+    pub contents: String,
+    pub warnings: Vec<String>,
+}
+
+pub(crate) enum OptimizeError {
+    /// The asset could not be serialized.
+    Serialize,
+    /// The optimized RON could not be loaded back.
+    Validate,
+}
+
+/// Produces the smallest clean RON for `asset`.
+///
+/// Editor metadata is dropped, absolute texture paths are rewritten relative to the
+/// assets folder, floats are rounded to [`SIGNIFICANT_DIGITS`] and empty sections are
+/// removed. The result is loaded back to make sure it still describes the same effect.
+pub(crate) fn optimize_asset(asset: &ParticlesAsset) -> Result<OptimizedAsset, OptimizeError> {
+    let mut asset = asset.clone();
+    let assets_folders = std::mem::take(&mut asset.sprinkles_editor).assets_folder;
+    let mut warnings = Vec::new();
+
+    let emitter_count = asset.emitters.len();
+    for (index, emitter) in asset.emitters.iter_mut().enumerate() {
+        if let DrawPassMaterial::Standard(material) = &mut emitter.draw_pass.material {
+            for texture in [
+                &mut material.base_color_texture,
+                &mut material.emissive_texture,
+                &mut material.metallic_roughness_texture,
+                &mut material.normal_map_texture,
+                &mut material.occlusion_texture,
+            ] {
+                bake_texture_path(texture, &assets_folders, &emitter.name, &mut warnings);
+            }
+        }
+
+        if emitter.emission.particles_amount == 0 {
+            warnings.push(format!("Emitter \"{}\" emits no particles", emitter.name));
+        }
+        if emitter.time.lifetime <= 0.0 {
+            warnings.push(format!("Emitter \"{}\" has no lifetime", emitter.name));
+        }
+        if let Some(sub_emitter) = &emitter.sub_emitter
+            && (sub_emitter.target_emitter >= emitter_count || sub_emitter.target_emitter == index)
+        {
+            warnings.push(format!(
+                "Emitter \"{}\" has an invalid sub-emitter target",
+                emitter.name
+            ));
+        }
+    }
+
+    let pretty = ron::ser::to_string_pretty(&asset, ron::ser::PrettyConfig::default())
+        .map_err(|_| OptimizeError::Serialize)?;
+    let rounded = round_float_literals(&pretty);
+    let stripped = strip_empty_sections(&rounded);
+
+    let reference = reload(&rounded).ok_or(OptimizeError::Validate)?;
+    // only keep the stripped output when it loads back to exactly the same asset
+    let contents = match reload(&stripped) {
+        Some(reloaded) if reloaded == reference => stripped,
+        _ => rounded,
+    };
+
+    Ok(OptimizedAsset { contents, warnings })
+}
+
+fn reload(contents: &str) -> Option<String> {
+    let result = bevy_sprinkles::asset::versions::migrate_str(contents).ok()?;
+    ron::ser::to_string(&result.asset).ok()
+}
+
+fn bake_texture_path(
+    texture: &mut Option<TextureRef>,
+    assets_folders: &[String],
+    emitter_name: &str,
+    warnings: &mut Vec<String>,
+) {
+    match texture {
+        Some(TextureRef::Asset(path)) if path.is_empty() => *texture = None,
+        Some(TextureRef::Asset(path)) => {
+            *path = path.replace('\\', "/").trim_start_matches("./").to_string();
+        }
+        Some(TextureRef::Local(path)) => {
+            let path = path.replace('\\', "/");
+            let relative = assets_folders
+                .iter()
+                .find_map(|folder| path.strip_prefix(folder.as_str()))
+                .map(|relative| TextureRef::Asset(relative.to_string()));
+
+            match relative.unwrap_or_else(|| classify_texture_path(&path).0) {
+                TextureRef::Local(_) => warnings.push(format!(
+                    "Emitter \"{emitter_name}\" uses a texture outside the assets folder"
+                )),
+                baked => *texture = Some(baked),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rounds every decimal float literal in `text`, leaving strings and identifiers untouched.
+fn round_float_literals(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '"' {
+            output.push(c);
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                output.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    break;
+                }
+            }
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            output.push(c);
+            while let Some(&c) = chars.peek() {
+                if !c.is_ascii_alphanumeric() && c != '_' {
+                    break;
+                }
+                output.push(c);
+                chars.next();
+            }
+        } else if c.is_ascii_digit() {
+            let mut literal = String::from(c);
+            while let Some(&c) = chars.peek() {
+                let after_exponent = literal.ends_with(['e', 'E']) && matches!(c, '+' | '-');
+                if !c.is_ascii_alphanumeric() && c != '.' && c != '_' && !after_exponent {
+                    break;
+                }
+                literal.push(c);
+                chars.next();
+            }
+            output.push_str(&round_literal(&literal));
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+fn round_literal(literal: &str) -> String {
+    if !literal.contains('.') || literal.contains(['e', 'E']) {
+        return literal.to_string();
+    }
+    let Ok(value) = literal.parse::<f64>() else {
+        return literal.to_string();
+    };
+    if value == 0.0 || !value.is_finite() {
+        return literal.to_string();
+    }
+
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (SIGNIFICANT_DIGITS - 1 - magnitude).max(0);
+    let factor = 10f64.powi(decimals);
+    let rounded = (value * factor).round() / factor;
+
+    let formatted = rounded.to_string();
+    if formatted.contains('.') {
+        formatted
+    } else {
+        format!("{formatted}.0")
+    }
+}
+
+/// Drops `field: ()` and `field: []` lines left behind by fully-default sections.
+fn strip_empty_sections(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let is_empty_section = trimmed
+            .strip_suffix("(),")
+            .or_else(|| trimmed.strip_suffix("[],"))
+            .and_then(|rest| rest.strip_suffix(": "))
+            .is_some_and(|name| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+        if !is_empty_section {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn on_save_optimized_copy_event(
+    _event: On<SaveOptimizedCopyEvent>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    mut commands: Commands,
+) {
+    let Some(handle) = &editor_state.current_project else {
+        return;
+    };
+    let Some(asset) = assets.get(handle) else {
+        return;
+    };
+
+    let optimized = match optimize_asset(asset) {
+        Ok(optimized) => optimized,
+        Err(OptimizeError::Serialize) => {
+            commands.trigger(ToastEvent::error("Cannot save project with invalid data"));
+            return;
+        }
+        Err(OptimizeError::Validate) => {
+            commands.trigger(ToastEvent::error(
+                "Optimized copy failed validation and was not saved",
+            ));
+            return;
+        }
+    };
+
+    if let Some(first) = optimized.warnings.first() {
+        let message = match optimized.warnings.len() {
+            1 => first.clone(),
+            count => format!("{first} (and {} more warnings)", count - 1),
+        };
+        commands.trigger(ToastEvent::error(message));
+    }
+
+    let directory = editor_state
+        .current_project_path
+        .as_ref()
+        .and_then(|path| path.parent())
+        .map(|path| path.to_path_buf())
+        .unwrap_or_else(projects_dir);
+    let default_name = format!("{}.ron", asset.name.to_kebab_case());
+    let contents = optimized.contents;
+
+    let save_result = Arc::new(Mutex::new(None));
+    let save_result_clone = save_result.clone();
+
+    let task = pick_save_path("Save Optimized Copy", &directory, &default_name);
+
+    IoTaskPool::get()
+        .spawn(async move {
+            if let Some(path) = task.await {
+                write_project_file(path, contents, save_result_clone);
+            }
+        })
+        .detach();
+
+    commands.insert_resource(SaveResult(save_result));
+}
+
+fn handle_export_keyboard_shortcut(keyboard: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
+    let ctrl_or_cmd = keyboard.pressed(KeyCode::SuperLeft)
+        || keyboard.pressed(KeyCode::SuperRight)
+        || keyboard.pressed(KeyCode::ControlLeft)
+        || keyboard.pressed(KeyCode::ControlRight);
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
+    if ctrl_or_cmd && shift && keyboard.just_pressed(KeyCode::KeyE) {
+        commands.trigger(SaveOptimizedCopyEvent);
+    }
+}
@@ -1,7 +1,6 @@
 use std::env;
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
 
 use bevy::prelude::*;
 use bevy::tasks::IoTaskPool;
@@ -41,6 +40,12 @@ pub struct EditorSettings {
     pub show_aabb_gizmos: bool,
     #[serde(default = "default_frustum_culling")]
     pub frustum_culling: bool,
+    #[serde(default)]
+    pub preview_motion: Option<EditorPreviewMotion>,
+    #[serde(default = "default_preview_motion_speed")]
+    pub preview_motion_speed: f32,
+    #[serde(default = "default_preview_motion_radius")]
+    pub preview_motion_radius: f32,
 }
 
 fn default_show_fps() -> bool {
@@ -71,6 +76,14 @@ fn default_frustum_culling() -> bool {
     true
 }
 
+fn default_preview_motion_speed() -> f32 {
+    4.0
+}
+
+fn default_preview_motion_radius() -> f32 {
+    3.0
+}
+
 impl Default for EditorSettings {
     fn default() -> Self {
         Self {
@@ -81,6 +94,9 @@ impl Default for EditorSettings {
             anti_aliasing: default_anti_aliasing(),
             show_aabb_gizmos: default_show_aabb_gizmos(),
             frustum_culling: default_frustum_culling(),
+            preview_motion: None,
+            preview_motion_speed: default_preview_motion_speed(),
+            preview_motion_radius: default_preview_motion_radius(),
         }
     }
 }
@@ -115,14 +131,28 @@ pub enum EditorSmaaPreset {
     Ultra,
 }
 
+/// Path the preview particle system follows to test effects on moving objects.
+#[derive(Serialize, Deserialize, Reflect, Clone, Default, PartialEq)]
+pub enum EditorPreviewMotion {
+    #[default]
+    Circle,
+    FigureEight,
+    BackAndForth,
+}
+
 #[derive(Serialize, Deserialize, Default)]
 pub struct EditorCache {
     pub last_opened_project: Option<String>,
     pub recent_projects: Vec<String>,
+    #[serde(default)]
+    pub recent_colors: Vec<[f32; 4]>,
+    #[serde(default)]
+    pub pinned_projects: Vec<String>,
 }
 
 impl EditorCache {
     const MAX_RECENT_PROJECTS: usize = 10;
+    const MAX_RECENT_COLORS: usize = 8;
 
     pub fn add_recent_project(&mut self, path: String) {
         let new_canonical = canonicalize_path(&path);
@@ -138,15 +168,42 @@ impl EditorCache {
         self.recent_projects
             .retain(|p| canonicalize_path(p) != canonical);
     }
+
+    pub fn is_pinned(&self, path: &str) -> bool {
+        let canonical = canonicalize_path(path);
+        self.pinned_projects
+            .iter()
+            .any(|p| canonicalize_path(p) == canonical)
+    }
+
+    /// Pins `path` to the top of the project selector, or unpins it if already pinned.
+    pub fn toggle_pinned_project(&mut self, path: &str) {
+        if self.is_pinned(path) {
+            let canonical = canonicalize_path(path);
+            self.pinned_projects
+                .retain(|p| canonicalize_path(p) != canonical);
+        } else {
+            self.pinned_projects.push(path.to_string());
+        }
+    }
+
+    pub fn add_recent_color(&mut self, color: [f32; 4]) {
+        self.recent_colors.retain(|c| *c != color);
+        self.recent_colors.insert(0, color);
+        self.recent_colors.truncate(Self::MAX_RECENT_COLORS);
+    }
 }
 
 pub fn data_dir() -> PathBuf {
     #[cfg(unix)]
     let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
-    #[cfg(not(unix))]
+    #[cfg(all(not(unix), not(target_arch = "wasm32")))]
     let home = env::var_os("USERPROFILE")
         .map(PathBuf::from)
         .unwrap_or_default();
+    // browser storage keys keep the `~/` prefix so stored paths match the ones shown in the UI
+    #[cfg(target_arch = "wasm32")]
+    let home = PathBuf::from("~");
     home.join(".sprinkles")
 }
 
@@ -154,6 +211,17 @@ pub fn projects_dir() -> PathBuf {
     data_dir().join("projects")
 }
 
+pub fn thumbnails_dir() -> PathBuf {
+    data_dir().join("thumbnails")
+}
+
+/// Location of the viewport thumbnail captured for the project at `path`.
+pub fn thumbnail_path(path: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    canonicalize_path(path).hash(&mut hasher);
+    thumbnails_dir().join(format!("{:016x}.png", hasher.finish()))
+}
+
 pub fn examples_dir() -> PathBuf {
     data_dir().join("examples")
 }
@@ -169,6 +237,7 @@ pub fn working_dir() -> PathBuf {
 fn ensure_data_dirs() {
     let _ = std::fs::create_dir_all(projects_dir());
     let _ = std::fs::create_dir_all(examples_dir());
+    let _ = std::fs::create_dir_all(thumbnails_dir());
 }
 
 fn canonicalize_path(path: &str) -> PathBuf {
@@ -198,15 +267,10 @@ pub fn project_path(relative_path: &str) -> PathBuf {
 }
 
 pub fn load_editor_data() -> EditorData {
-    let path = editor_data_path();
-    if path.exists() {
-        std::fs::read_to_string(&path)
-            .ok()
-            .and_then(|contents| ron::from_str(&contents).ok())
-            .unwrap_or_default()
-    } else {
-        EditorData::default()
-    }
+    read_file(&editor_data_path())
+        .ok()
+        .and_then(|contents| ron::from_str(&contents).ok())
+        .unwrap_or_default()
 }
 
 pub fn save_editor_data(data: &EditorData) {
@@ -217,9 +281,111 @@ pub fn save_editor_data(data: &EditorData) {
 
     IoTaskPool::get()
         .spawn(async move {
-            let mut file = File::create(&path).expect("failed to create editor data file");
-            file.write_all(contents.as_bytes())
-                .expect("failed to write editor data");
+            if let Err(err) = write_file(&path, &contents) {
+                error!("Failed to write editor data: {err}");
+            }
         })
         .detach();
 }
+
+/// Reads a text file. The web build reads from browser storage instead of the disk.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_file(path: &Path) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read_file(path: &Path) -> std::io::Result<String> {
+    crate::web::read_file(path).ok_or_else(|| std::io::ErrorKind::NotFound.into())
+}
+
+/// Writes a text file. The web build writes to browser storage instead of the disk.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if crate::web::write_file(path, contents) {
+        Ok(())
+    } else {
+        Err(std::io::ErrorKind::StorageFull.into())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove_file(path: &Path) {
+    let _ = std::fs::remove_file(path);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn remove_file(path: &Path) {
+    crate::web::remove_file(path);
+}
+
+/// Files directly inside `dir` with the given extension.
+pub fn list_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    #[cfg(target_arch = "wasm32")]
+    let paths: Vec<PathBuf> = crate::web::list_files(dir)
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|path| path.parent() == Some(dir))
+        .collect();
+
+    paths
+        .into_iter()
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(extension))
+        .collect()
+}
+
+/// Resolves a file picked in an open dialog to a path [`read_file`] can load.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn picked_file_path(handle: rfd::FileHandle) -> Option<PathBuf> {
+    Some(handle.path().to_path_buf())
+}
+
+/// Resolves a file picked in an open dialog to a path [`read_file`] can load.
+///
+/// Browsers don't expose file paths, so the picked file is copied into browser storage
+/// under its file name.
+#[cfg(target_arch = "wasm32")]
+pub async fn picked_file_path(handle: rfd::FileHandle) -> Option<PathBuf> {
+    let path = PathBuf::from(handle.file_name());
+    let contents = String::from_utf8(handle.read().await).ok()?;
+    write_file(&path, &contents).ok()?;
+    Some(path)
+}
+
+/// Asks where to save a RON file, suggesting `file_name` inside `directory`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pick_save_path(
+    title: &str,
+    directory: &Path,
+    file_name: &str,
+) -> impl Future<Output = Option<PathBuf>> + use<> {
+    let task = rfd::AsyncFileDialog::new()
+        .set_title(title)
+        .set_directory(directory)
+        .set_file_name(file_name)
+        .add_filter("RON files", &["ron"])
+        .save_file();
+    async move { task.await.map(|handle| handle.path().to_path_buf()) }
+}
+
+/// Asks where to save a RON file, suggesting `file_name` inside `directory`.
+///
+/// Browsers choose where downloads go, so the file is saved under `file_name` directly.
+#[cfg(target_arch = "wasm32")]
+pub fn pick_save_path(
+    _title: &str,
+    _directory: &Path,
+    file_name: &str,
+) -> impl Future<Output = Option<PathBuf>> + use<> {
+    let path = PathBuf::from(file_name);
+    async move { Some(path) }
+}
@@ -1,11 +1,17 @@
 mod assets;
+mod comparison;
+mod export;
 mod io;
 mod plugin;
 mod project;
 mod state;
+mod thumbnails;
 mod ui;
 mod utils;
 mod viewport;
+mod watch;
+#[cfg(target_arch = "wasm32")]
+mod web;
 
 use bevy::asset::UnapprovedPathMode;
 use bevy::prelude::*;
@@ -30,6 +36,12 @@ fn main() {
                         title: "Sprinkles Editor".into(),
                         resolution: WindowResolution::new(1366, 768),
                         present_mode,
+                        #[cfg(target_arch = "wasm32")]
+                        canvas: Some("#sprinkles".into()),
+                        #[cfg(target_arch = "wasm32")]
+                        fit_canvas_to_parent: true,
+                        #[cfg(target_arch = "wasm32")]
+                        prevent_default_event_handling: true,
                         ..default()
                     }),
                     ..default()
@@ -7,8 +7,9 @@ use crate::io::{EditorData, project_path, save_editor_data, working_dir};
 use crate::project::load_project_from_path;
 use crate::state::{DirtyState, EditorState, Inspectable, Inspecting};
 use crate::viewport::{
-    AabbGeneration, CameraSettings, ViewportInputState, despawn_preview_on_project_change,
-    draw_collider_gizmos, handle_generate_aabb_request, handle_playback_play_event,
+    AabbGeneration, CameraBookmarks, CameraSettings, ViewportInputState, animate_preview_motion,
+    despawn_preview_on_project_change, draw_collider_gizmos, handle_camera_shortcuts,
+    handle_frame_inspected_event, handle_generate_aabb_request, handle_playback_play_event,
     handle_playback_reset_event, handle_playback_seek_event, handle_respawn_colliders,
     handle_respawn_emitters, orbit_camera, respawn_preview_on_emitter_change,
     setup_aabb_gizmo_config, setup_camera, setup_floor, spawn_preview_particle_system,
@@ -40,7 +41,12 @@ impl Plugin for SprinklesEditorPlugin {
             .add_plugins(crate::io::plugin)
             .add_plugins(crate::state::plugin)
             .add_plugins(crate::project::plugin)
+            .add_plugins(crate::comparison::plugin)
+            .add_plugins(crate::export::plugin)
+            .add_plugins(crate::thumbnails::plugin)
+            .add_plugins(crate::watch::plugin)
             .init_resource::<CameraSettings>()
+            .init_resource::<CameraBookmarks>()
             .init_resource::<ViewportInputState>()
             .init_resource::<AabbGeneration>()
             .insert_resource(ClearColor(ZINC_950.into()))
@@ -51,6 +57,7 @@ impl Plugin for SprinklesEditorPlugin {
             .add_observer(handle_playback_reset_event)
             .add_observer(handle_playback_seek_event)
             .add_observer(handle_generate_aabb_request)
+            .add_observer(handle_frame_inspected_event)
             .add_systems(
                 Startup,
                 (
@@ -65,7 +72,9 @@ impl Plugin for SprinklesEditorPlugin {
                 (
                     orbit_camera,
                     zoom_camera,
+                    handle_camera_shortcuts,
                     spawn_preview_particle_system,
+                    animate_preview_motion,
                     despawn_preview_on_project_change,
                     sync_playback_state,
                     sync_viewport_settings,
@@ -1,5 +1,3 @@
-use std::fs::File;
-use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -8,8 +6,12 @@ use bevy::tasks::IoTaskPool;
 use bevy_sprinkles::prelude::*;
 use inflector::Inflector;
 
-use crate::io::{EditorData, is_example_path, project_path, projects_dir, save_editor_data};
+use crate::io::{
+    EditorData, is_example_path, pick_save_path, picked_file_path, project_path, projects_dir,
+    read_file, save_editor_data, write_file,
+};
 use crate::state::{DirtyState, EditorState, Inspectable, Inspecting};
+use crate::thumbnails::CaptureThumbnailEvent;
 use crate::ui::components::toasts::ToastEvent;
 use crate::utils::{MAX_DISPLAY_PATH_LEN, simplify_path, truncate_path};
 
@@ -70,7 +72,7 @@ pub(crate) enum LoadProjectError {
 pub(crate) fn load_project_from_path(
     path: &std::path::Path,
 ) -> Result<bevy_sprinkles::asset::versions::MigrationResult, LoadProjectError> {
-    let contents = std::fs::read_to_string(path).map_err(|err| {
+    let contents = read_file(path).map_err(|err| {
         error!("Failed to read project file: {path:?}");
         error!("{err}");
         LoadProjectError::Read(err)
@@ -175,11 +177,11 @@ fn on_browse_open_project_event(_event: On<BrowseOpenProjectEvent>, mut commands
 
     IoTaskPool::get()
         .spawn(async move {
-            if let Some(file_handle) = task.await {
-                let path = file_handle.path().to_path_buf();
-                if let Ok(mut guard) = path_result_clone.lock() {
-                    *guard = Some(path);
-                }
+            if let Some(file_handle) = task.await
+                && let Some(path) = picked_file_path(file_handle).await
+                && let Ok(mut guard) = path_result_clone.lock()
+            {
+                *guard = Some(path);
             }
         })
         .detach();
@@ -217,22 +219,32 @@ pub fn save_project_to_path(
         return;
     };
 
+    write_project_file(path, contents, result);
+}
+
+pub fn write_project_file(
+    path: PathBuf,
+    contents: String,
+    result: Arc<Mutex<Option<SaveResultStatus>>>,
+) {
     IoTaskPool::get()
         .spawn(async move {
-            let status = match File::create(&path) {
-                Ok(mut file) => {
-                    let filename = path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "".to_string());
-                    if file.write_all(contents.as_bytes()).is_ok() {
-                        SaveResultStatus::Success(filename)
-                    } else {
-                        SaveResultStatus::WriteError(filename)
-                    }
+            let filename = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "".to_string());
+            let status = match write_file(&path, &contents) {
+                Ok(()) => SaveResultStatus::Success(filename),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    SaveResultStatus::CreateError
                 }
-                Err(_) => SaveResultStatus::CreateError,
+                Err(_) => SaveResultStatus::WriteError(filename),
             };
+            // browsers can't write to the user's disk, so saved projects are also downloaded
+            #[cfg(target_arch = "wasm32")]
+            if let SaveResultStatus::Success(filename) = &status {
+                crate::web::download(filename, &contents);
+            }
             if let Ok(mut guard) = result.lock() {
                 *guard = Some(status);
             }
@@ -258,6 +270,7 @@ fn on_save_project_event(
         let result = Arc::new(Mutex::new(None));
         save_project_to_path(path.clone(), asset, result.clone());
         commands.insert_resource(SaveResult(result));
+        commands.trigger(CaptureThumbnailEvent(path.clone()));
         dirty_state.has_unsaved_changes = false;
     } else {
         commands.trigger(SaveProjectAsEvent);
@@ -287,17 +300,11 @@ fn on_save_project_as_event(
     let save_result = Arc::new(Mutex::new(None));
     let save_result_clone = save_result.clone();
 
-    let task = rfd::AsyncFileDialog::new()
-        .set_title("Save Project As")
-        .set_directory(&projects_dir)
-        .set_file_name(&default_name)
-        .add_filter("RON files", &["ron"])
-        .save_file();
+    let task = pick_save_path("Save Project As", &projects_dir, &default_name);
 
     IoTaskPool::get()
         .spawn(async move {
-            if let Some(file_handle) = task.await {
-                let path = file_handle.path().to_path_buf();
+            if let Some(path) = task.await {
                 save_project_to_path(path.clone(), &asset_clone, save_result_clone);
                 if let Ok(mut guard) = path_result_clone.lock() {
                     *guard = Some(path);
@@ -334,6 +341,7 @@ fn poll_save_as_result(
         editor_data.cache.add_recent_project(simplify_path(&path));
         save_editor_data(&editor_data);
         dirty_state.has_unsaved_changes = false;
+        commands.trigger(CaptureThumbnailEvent(path));
         commands.remove_resource::<SaveAsResult>();
     }
 }
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::IoTaskPool;
+
+use crate::io::thumbnail_path;
+use crate::utils::simplify_path;
+use crate::viewport::EditorCamera;
+
+const THUMBNAIL_WIDTH: u32 = 96;
+const THUMBNAIL_HEIGHT: u32 = 64;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<ProjectThumbnails>()
+        .add_observer(on_capture_thumbnail_event);
+}
+
+/// Captures the viewport as the thumbnail shown for the project saved at this path.
+#[derive(Event)]
+pub struct CaptureThumbnailEvent(pub PathBuf);
+
+/// Thumbnails already loaded or captured this session, keyed by thumbnail file.
+#[derive(Resource, Default)]
+pub struct ProjectThumbnails(HashMap<PathBuf, Handle<Image>>);
+
+impl ProjectThumbnails {
+    /// Returns the thumbnail for `project`, loading it from disk the first time.
+    pub fn get(&mut self, project: &str, asset_server: &AssetServer) -> Option<Handle<Image>> {
+        let path = thumbnail_path(project);
+        if let Some(handle) = self.0.get(&path) {
+            return Some(handle.clone());
+        }
+        if !path.exists() {
+            return None;
+        }
+        let handle: Handle<Image> = asset_server.load(path.clone());
+        self.0.insert(path, handle.clone());
+        Some(handle)
+    }
+}
+
+fn on_capture_thumbnail_event(
+    event: On<CaptureThumbnailEvent>,
+    mut commands: Commands,
+    cameras: Query<&RenderTarget, With<EditorCamera>>,
+) {
+    let Ok(RenderTarget::Image(target)) = cameras.single() else {
+        return;
+    };
+    let path = thumbnail_path(&simplify_path(&event.0));
+
+    commands
+        .spawn(Screenshot::image(target.handle.clone()))
+        .observe(
+            move |captured: On<ScreenshotCaptured>,
+                  mut images: ResMut<Assets<Image>>,
+                  mut thumbnails: ResMut<ProjectThumbnails>| {
+                let Ok(image) = captured.image.clone().try_into_dynamic() else {
+                    return;
+                };
+                let thumbnail = image.thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT);
+
+                let handle = images.add(Image::from_dynamic(
+                    thumbnail.clone(),
+                    true,
+                    RenderAssetUsages::default(),
+                ));
+                thumbnails.0.insert(path.clone(), handle);
+
+                let path = path.clone();
+                IoTaskPool::get()
+                    .spawn(async move {
+                        if let Err(err) = thumbnail.to_rgb8().save(&path) {
+                            warn!("Failed to save project thumbnail: {err}");
+                        }
+                    })
+                    .detach();
+            },
+        );
+}
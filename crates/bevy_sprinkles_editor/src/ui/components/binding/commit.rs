@@ -122,10 +122,15 @@ const RESPAWN_FIELD_PATHS: &[&str] = &[
     "enabled",
     "draw_pass.material.unlit",
     "draw_pass.material.alpha_mode",
+    "draw_pass.material.blend_mode",
     "draw_pass.transform_align",
     "draw_pass.shadow_caster",
+    "draw_pass.visibility",
     "draw_pass.use_local_coords",
+    "draw_pass.metaball",
+    "collision.accumulation_capacity",
     "emission.particles_amount",
+    "emission.max_particles",
     "initial_transform.translation",
     "initial_transform.rotation",
     "initial_transform.scale",
@@ -445,6 +445,7 @@ impl ReflectPath {
 pub(super) enum FieldValue {
     None,
     F32(f32),
+    OptionalF32(Option<f32>),
     U32(u32),
     OptionalU32(Option<u32>),
     Bool(bool),
@@ -466,6 +467,7 @@ impl FieldValue {
                 FieldKind::F32OrInfinity if v.is_infinite() => None,
                 _ => Some(format_f32(*v)),
             },
+            FieldValue::OptionalF32(v) => v.map(format_f32),
             FieldValue::U32(v) => match kind {
                 FieldKind::U32OrEmpty if *v == 0 => None,
                 _ => Some(v.to_string()),
@@ -529,6 +531,16 @@ pub(super) fn parse_field_value(text: &str, kind: &FieldKind) -> FieldValue {
             };
             parsed.map(FieldValue::F32).unwrap_or(FieldValue::None)
         }
+        FieldKind::OptionalF32 => {
+            let parsed: Option<Option<f32>> = if text.is_empty() {
+                Some(None)
+            } else {
+                text.parse::<f32>().ok().map(Some)
+            };
+            parsed
+                .map(FieldValue::OptionalF32)
+                .unwrap_or(FieldValue::None)
+        }
         FieldKind::U32 | FieldKind::U32OrEmpty => {
             let parsed: Option<u32> = if text.is_empty() && matches!(kind, FieldKind::U32OrEmpty) {
                 Some(0)
@@ -596,6 +608,9 @@ fn reflect_to_field_value(value: &dyn PartialReflect, kind: &FieldKind) -> Field
     if let Some(v) = value.try_downcast_ref::<Vec3A>() {
         return FieldValue::Vec3(Vec3::from(*v));
     }
+    if let Some(v) = value.try_downcast_ref::<Option<f32>>() {
+        return FieldValue::OptionalF32(*v);
+    }
     if let Some(v) = value.try_downcast_ref::<Option<u32>>() {
         return FieldValue::OptionalU32(*v);
     }
@@ -637,6 +652,7 @@ fn apply_field_value_to_reflect(target: &mut dyn PartialReflect, value: &FieldVa
     match value {
         FieldValue::F32(v) => apply_with_change_check(target, v),
         FieldValue::U32(v) => apply_with_change_check(target, v),
+        FieldValue::OptionalF32(v) => apply_with_change_check(target, v),
         FieldValue::OptionalU32(v) => apply_with_change_check(target, v),
         FieldValue::Bool(v) => apply_with_change_check(target, v),
         FieldValue::String(v) => apply_with_change_check(target, v),
@@ -680,8 +696,10 @@ pub(crate) fn resolve_variant_field_ref<'a>(
     value: &'a dyn PartialReflect,
     field_name: &str,
 ) -> Option<&'a dyn PartialReflect> {
-    let ReflectRef::Enum(enum_ref) = value.reflect_ref() else {
-        return None;
+    let enum_ref = match value.reflect_ref() {
+        ReflectRef::Enum(enum_ref) => enum_ref,
+        ReflectRef::Struct(struct_ref) => return struct_ref.field(field_name),
+        _ => return None,
     };
     if let Some(field) = enum_ref.field(field_name) {
         return Some(field);
@@ -708,8 +726,10 @@ pub(super) fn with_variant_field_mut<F, R>(
 where
     F: FnOnce(&mut dyn PartialReflect) -> R,
 {
-    let ReflectMut::Enum(enum_mut) = value.reflect_mut() else {
-        return None;
+    let enum_mut = match value.reflect_mut() {
+        ReflectMut::Enum(enum_mut) => enum_mut,
+        ReflectMut::Struct(struct_mut) => return struct_mut.field_mut(field_name).map(f),
+        _ => return None,
     };
     if let Some(field) = enum_mut.field_mut(field_name) {
         return Some(f(field));
@@ -831,6 +851,8 @@ fn default_for_type_id(type_id: std::any::TypeId) -> Option<Box<dyn PartialRefle
         Some(Box::new(0i32))
     } else if type_id == std::any::TypeId::of::<String>() {
         Some(Box::new(String::new()))
+    } else if type_id == std::any::TypeId::of::<Vec3>() {
+        Some(Box::new(Vec3::ZERO))
     } else {
         None
     }
@@ -26,6 +26,8 @@ pub fn plugin(app: &mut App) {
     app.init_resource::<LastLoadedProject>()
         .add_observer(on_item_click)
         .add_observer(on_item_menu_change)
+        .add_observer(on_duplicate_item)
+        .add_observer(on_delete_item)
         .add_observer(on_rename_commit)
         .add_observer(on_delete_confirmed)
         .add_observer(on_add_emitter)
@@ -88,6 +90,14 @@ struct PendingDelete {
     index: u8,
 }
 
+/// Duplicates an emitter or collider, inserting the copy right after it.
+#[derive(Event)]
+pub struct DuplicateItemEvent(pub Inspecting);
+
+/// Asks for confirmation, then deletes an emitter or collider.
+#[derive(Event)]
+pub struct DeleteItemEvent(pub Inspecting);
+
 #[derive(Event)]
 struct AddEmitterEvent;
 
@@ -358,10 +368,8 @@ fn on_item_click(
 fn on_item_menu_change(
     event: On<ComboBoxChangeEvent>,
     mut commands: Commands,
-    mut editor_state: ResMut<EditorState>,
-    mut assets: ResMut<Assets<ParticlesAsset>>,
-    mut dirty_state: ResMut<DirtyState>,
-    mut last_project: ResMut<LastLoadedProject>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
     menus: Query<&ChildOf, With<ItemMenu>>,
     items: Query<(Entity, &InspectableItem, &Children), Without<Renaming>>,
     mut buttons: Query<&mut Node, With<ItemButton>>,
@@ -372,53 +380,18 @@ fn on_item_menu_change(
     let Ok((item_entity, item, children)) = items.get(child_of.parent()) else {
         return;
     };
-
-    let item_name = get_item_name(&editor_state, &assets, item);
-    let Some(item_name) = item_name else {
-        return;
+    let target = Inspecting {
+        kind: item.kind,
+        index: item.index,
     };
 
     match event.label.as_str() {
-        "Duplicate" => {
-            let Some(handle) = &editor_state.current_project else {
-                return;
-            };
-            let Some(mut asset) = assets.get_mut(handle) else {
+        "Duplicate" => commands.trigger(DuplicateItemEvent(target)),
+        "Rename" => {
+            let Some(item_name) = get_item_name(&editor_state, &assets, item.kind, item.index)
+            else {
                 return;
             };
-
-            let (base, _) = strip_trailing_number(&item_name);
-            let insert_index = item.index as usize + 1;
-
-            match item.kind {
-                Inspectable::Emitter => {
-                    let Some(source) = asset.emitters.get(item.index as usize) else {
-                        return;
-                    };
-                    let mut new_item = source.clone();
-                    let existing: Vec<&str> =
-                        asset.emitters.iter().map(|e| e.name.as_str()).collect();
-                    new_item.name = next_unique_name(base, &existing);
-                    asset.emitters.insert(insert_index, new_item);
-                }
-                Inspectable::Collider => {
-                    let Some(source) = asset.colliders.get(item.index as usize) else {
-                        return;
-                    };
-                    let mut new_item = source.clone();
-                    let existing: Vec<&str> =
-                        asset.colliders.iter().map(|c| c.name.as_str()).collect();
-                    new_item.name = next_unique_name(base, &existing);
-                    asset.colliders.insert(insert_index, new_item);
-                }
-            }
-
-            dirty_state.has_unsaved_changes = true;
-            adjust_inspecting_after_insert(&mut editor_state.inspecting, item.kind, insert_index);
-            trigger_respawn(&mut commands, item.kind);
-            last_project.handle = None;
-        }
-        "Rename" => {
             let button_entity = children.iter().find(|c| buttons.get(*c).is_ok());
             if let Some(button_entity) = button_entity {
                 if let Ok(mut btn_node) = buttons.get_mut(button_entity) {
@@ -427,22 +400,83 @@ fn on_item_menu_change(
             }
             start_rename(&mut commands, item_entity, &item_name);
         }
-        "Delete" => {
-            let label = match item.kind {
-                Inspectable::Emitter => "Delete emitter",
-                Inspectable::Collider => "Delete collider",
+        "Delete" => commands.trigger(DeleteItemEvent(target)),
+        _ => {}
+    }
+}
+
+fn on_duplicate_item(
+    event: On<DuplicateItemEvent>,
+    mut commands: Commands,
+    mut editor_state: ResMut<EditorState>,
+    mut assets: ResMut<Assets<ParticlesAsset>>,
+    mut dirty_state: ResMut<DirtyState>,
+    mut last_project: ResMut<LastLoadedProject>,
+) {
+    let item = event.0;
+    let Some(item_name) = get_item_name(&editor_state, &assets, item.kind, item.index) else {
+        return;
+    };
+    let Some(handle) = &editor_state.current_project else {
+        return;
+    };
+    let Some(mut asset) = assets.get_mut(handle) else {
+        return;
+    };
+
+    let (base, _) = strip_trailing_number(&item_name);
+    let insert_index = item.index as usize + 1;
+
+    match item.kind {
+        Inspectable::Emitter => {
+            let Some(source) = asset.emitters.get(item.index as usize) else {
+                return;
+            };
+            let mut new_item = source.clone();
+            let existing: Vec<&str> = asset.emitters.iter().map(|e| e.name.as_str()).collect();
+            new_item.name = next_unique_name(base, &existing);
+            asset.emitters.insert(insert_index, new_item);
+        }
+        Inspectable::Collider => {
+            let Some(source) = asset.colliders.get(item.index as usize) else {
+                return;
             };
-            commands.insert_resource(PendingDelete {
-                kind: item.kind,
-                index: item.index,
-            });
-            commands.trigger(
-                OpenConfirmationDialogEvent::new(label, "Delete")
-                    .with_description(format!("Are you sure you want to delete {}?", item_name)),
-            );
+            let mut new_item = source.clone();
+            let existing: Vec<&str> = asset.colliders.iter().map(|c| c.name.as_str()).collect();
+            new_item.name = next_unique_name(base, &existing);
+            asset.colliders.insert(insert_index, new_item);
         }
-        _ => {}
     }
+
+    dirty_state.has_unsaved_changes = true;
+    adjust_inspecting_after_insert(&mut editor_state.inspecting, item.kind, insert_index);
+    trigger_respawn(&mut commands, item.kind);
+    last_project.handle = None;
+}
+
+fn on_delete_item(
+    event: On<DeleteItemEvent>,
+    mut commands: Commands,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+) {
+    let item = event.0;
+    let Some(item_name) = get_item_name(&editor_state, &assets, item.kind, item.index) else {
+        return;
+    };
+
+    let label = match item.kind {
+        Inspectable::Emitter => "Delete emitter",
+        Inspectable::Collider => "Delete collider",
+    };
+    commands.insert_resource(PendingDelete {
+        kind: item.kind,
+        index: item.index,
+    });
+    commands.trigger(
+        OpenConfirmationDialogEvent::new(label, "Delete")
+            .with_description(format!("Are you sure you want to delete {}?", item_name)),
+    );
 }
 
 fn handle_item_right_click(
@@ -566,17 +600,18 @@ fn strip_trailing_number(name: &str) -> (&str, Option<u32>) {
 fn get_item_name(
     editor_state: &EditorState,
     assets: &Assets<ParticlesAsset>,
-    item: &InspectableItem,
+    kind: Inspectable,
+    index: u8,
 ) -> Option<String> {
     let handle = editor_state.current_project.as_ref()?;
     let asset = assets.get(handle)?;
-    match item.kind {
+    match kind {
         Inspectable::Emitter => {
-            let emitter = asset.emitters.get(item.index as usize)?;
+            let emitter = asset.emitters.get(index as usize)?;
             Some(emitter.name.clone())
         }
         Inspectable::Collider => {
-            let collider = asset.colliders.get(item.index as usize)?;
+            let collider = asset.colliders.get(index as usize)?;
             Some(collider.name.clone())
         }
     }
@@ -630,7 +665,7 @@ fn handle_item_double_click(
 
             *last_click = (None, 0.0);
 
-            let item_name = get_item_name(&editor_state, &assets, item);
+            let item_name = get_item_name(&editor_state, &assets, item.kind, item.index);
             let Some(item_name) = item_name else {
                 continue;
             };
@@ -5,7 +5,7 @@ use bevy::prelude::*;
 use bevy_sprinkles::asset::{ParticlesAuthors, ParticlesDimension};
 
 use crate::assets::example_thumbnail_path;
-use crate::io::examples_dir;
+use crate::io::{examples_dir, list_files};
 use crate::project::{OpenProjectEvent, load_project_from_path};
 use crate::ui::tokens::{
     BORDER_COLOR, CORNER_RADIUS_LG, FONT_PATH, TEXT_BODY_COLOR, TEXT_MUTED_COLOR, TEXT_SIZE,
@@ -56,18 +56,9 @@ struct ExamplesDialogState {
 
 // TODO: this does synchronous filesystem I/O which could block the main thread with many examples
 fn collect_example_entries() -> Vec<ExampleEntry> {
-    let examples_dir = examples_dir();
-    let Ok(read_dir) = std::fs::read_dir(&examples_dir) else {
-        return Vec::new();
-    };
-
-    let mut entries: Vec<ExampleEntry> = read_dir
-        .flatten()
-        .filter_map(|entry| {
-            let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) != Some("ron") {
-                return None;
-            }
+    let mut entries: Vec<ExampleEntry> = list_files(&examples_dir(), "ron")
+        .into_iter()
+        .filter_map(|path| {
             let result = load_project_from_path(&path).ok()?;
             let stem = path.file_stem()?.to_string_lossy().to_string();
             Some(ExampleEntry {
@@ -12,11 +12,48 @@ pub fn accelerations_section() -> (impl Bundle, InspectorSection) {
         (),
         InspectorSection::new(
             "Accelerations",
-            vec![vec![
-                InspectorFieldProps::new("accelerations.gravity")
-                    .vector(VectorSuffixes::XYZ)
+            vec![
+                vec![
+                    InspectorFieldProps::new("accelerations.gravity")
+                        .vector(VectorSuffixes::XYZ)
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("accelerations.gravity_scale")
+                        .vector(VectorSuffixes::Range)
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("accelerations.radial_acceleration.velocity")
+                        .vector(VectorSuffixes::Range)
+                        .with_label("Radial acceleration")
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new(
+                        "accelerations.radial_acceleration.velocity_over_lifetime",
+                    )
+                    .curve()
+                    .with_label("Radial acceleration over lifetime")
                     .into(),
-            ]],
+                ],
+                vec![
+                    InspectorFieldProps::new("accelerations.point_gravity.position")
+                        .vector(VectorSuffixes::XYZ)
+                        .with_label("Point gravity position")
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("accelerations.point_gravity.strength")
+                        .with_label("Point gravity strength")
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("accelerations.damping")
+                        .vector(VectorSuffixes::Range)
+                        .into(),
+                ],
+            ],
         ),
     )
 }
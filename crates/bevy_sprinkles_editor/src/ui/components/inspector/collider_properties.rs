@@ -2,15 +2,22 @@ use bevy::prelude::*;
 use bevy_sprinkles::prelude::*;
 
 use crate::state::{DirtyState, EditorState};
-use crate::ui::tokens::FONT_PATH;
+use crate::ui::components::data_panel::{DeleteItemEvent, DuplicateItemEvent};
+use crate::ui::tokens::{FONT_PATH, TEXT_MUTED_COLOR, TEXT_SIZE_SM};
+use crate::ui::widgets::button::{
+    ButtonClickEvent, ButtonProps, ButtonSize, ButtonVariant, button,
+};
+use crate::ui::widgets::color_picker::{ColorPickerProps, color_picker};
 use crate::ui::widgets::combobox::{ComboBoxChangeEvent, ComboBoxOptionData};
-use crate::ui::widgets::inspector_field::fields_row;
+use crate::ui::widgets::inspector_field::{InspectorFieldProps, fields_row, spawn_inspector_field};
 use crate::ui::widgets::text_edit::{TextEditCommitEvent, TextEditProps, text_edit};
 use crate::ui::widgets::vector_edit::{VectorEditProps, VectorSuffixes, vector_edit};
 
-use super::{DynamicSectionContent, InspectorSection, section_needs_setup, spawn_labeled_combobox};
+use super::{
+    DynamicSectionContent, FieldKind, InspectorSection, section_needs_setup, spawn_labeled_combobox,
+};
 use crate::ui::components::binding::{
-    find_ancestor, find_ancestor_entity, format_f32, get_inspecting_collider,
+    FieldBinding, find_ancestor, find_ancestor_entity, format_f32, get_inspecting_collider,
     get_inspecting_collider_mut,
 };
 
@@ -26,9 +33,16 @@ struct ColliderShapeComboBox;
 #[derive(Component)]
 struct ColliderShapeField(&'static str);
 
+#[derive(Component, Clone, Copy)]
+enum ColliderAction {
+    Duplicate,
+    Delete,
+}
+
 pub fn plugin(app: &mut App) {
     app.add_observer(handle_collider_shape_change)
         .add_observer(handle_collider_text_commit)
+        .add_observer(handle_collider_action_click)
         .add_systems(
             Update,
             setup_collider_content.after(super::update_inspected_collider_tracker),
@@ -127,12 +141,89 @@ fn setup_collider_content(
                     });
                 }
             }
+
+            parent.spawn(fields_row()).with_children(|row| {
+                spawn_inspector_field(
+                    row,
+                    InspectorFieldProps::new("invert")
+                        .bool()
+                        .with_label("Keep particles inside"),
+                    &asset_server,
+                );
+            });
+
+            parent.spawn(fields_row()).with_children(|row| {
+                row.spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(3.0),
+                    flex_grow: 1.0,
+                    ..default()
+                })
+                .with_children(|wrapper| {
+                    wrapper.spawn((
+                        Text::new("Gizmo color"),
+                        TextFont {
+                            font: font.clone().into(),
+                            font_size: TEXT_SIZE_SM.into(),
+                            weight: FontWeight::MEDIUM,
+                            ..default()
+                        },
+                        TextColor(TEXT_MUTED_COLOR.into()),
+                    ));
+                    let wrapper_target = wrapper.target_entity();
+                    wrapper
+                        .commands()
+                        .spawn_scene(color_picker(ColorPickerProps::new()))
+                        .insert(FieldBinding::emitter("gizmo_color", FieldKind::Color))
+                        .insert(ChildOf(wrapper_target));
+                });
+            });
+
+            parent.spawn(fields_row()).with_children(|row| {
+                let row_target = row.target_entity();
+                for (label, variant, action) in [
+                    (
+                        "Duplicate",
+                        ButtonVariant::Default,
+                        ColliderAction::Duplicate,
+                    ),
+                    ("Delete", ButtonVariant::Destructive, ColliderAction::Delete),
+                ] {
+                    row.commands()
+                        .spawn_scene(button(
+                            ButtonProps::new(label)
+                                .with_variant(variant)
+                                .with_size(ButtonSize::MD),
+                        ))
+                        .insert(action)
+                        .insert(ChildOf(row_target));
+                }
+            });
         })
         .id();
 
     commands.entity(entity).add_child(content);
 }
 
+fn handle_collider_action_click(
+    trigger: On<ButtonClickEvent>,
+    mut commands: Commands,
+    actions: Query<&ColliderAction>,
+    editor_state: Res<EditorState>,
+) {
+    let Ok(action) = actions.get(trigger.entity) else {
+        return;
+    };
+    let Some(inspecting) = editor_state.inspecting else {
+        return;
+    };
+
+    match action {
+        ColliderAction::Duplicate => commands.trigger(DuplicateItemEvent(inspecting)),
+        ColliderAction::Delete => commands.trigger(DeleteItemEvent(inspecting)),
+    }
+}
+
 fn handle_collider_shape_change(
     trigger: On<ComboBoxChangeEvent>,
     mut commands: Commands,
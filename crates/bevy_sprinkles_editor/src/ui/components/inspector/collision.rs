@@ -101,6 +101,31 @@ fn setup_collision_content(
                         InspectorFieldProps::new("collision.base_size"),
                         &asset_server,
                     );
+                    spawn_inspector_field(
+                        row,
+                        InspectorFieldProps::new("collision.max_substeps").u32(),
+                        &asset_server,
+                    );
+                    spawn_inspector_field(
+                        row,
+                        InspectorFieldProps::new("collision.ground_plane")
+                            .optional_f32()
+                            .with_placeholder("None"),
+                        &asset_server,
+                    );
+                });
+            }
+
+            if has_mode && !is_rigid {
+                parent.spawn(fields_row()).with_children(|row| {
+                    spawn_inspector_field(
+                        row,
+                        InspectorFieldProps::new("collision.accumulation_capacity")
+                            .optional_u32()
+                            .with_label("Accumulated hits")
+                            .with_placeholder("Disabled"),
+                        &asset_server,
+                    );
                 });
             }
 
@@ -9,7 +9,7 @@ use crate::ui::widgets::alert::{AlertSpan, AlertVariant, alert};
 use crate::ui::widgets::inspector_field::InspectorFieldProps;
 use crate::ui::widgets::variant_edit::{VariantDefinition, VariantEditProps};
 
-use super::utils::VariantConfig;
+use super::utils::{VariantConfig, combobox_options_from_reflect};
 use super::{InspectorItem, InspectorSection, section_needs_setup};
 
 #[derive(Component)]
@@ -58,9 +58,18 @@ pub fn colors_section() -> (impl Bundle, InspectorSection) {
                             .with_variants(color_variants())
                             .with_swatch_slot(true),
                     },
+                    InspectorFieldProps::new("colors.initial_color_sampling")
+                        .combobox(combobox_options_from_reflect::<InitialColorSampling>())
+                        .into(),
+                ],
+                vec![
                     InspectorFieldProps::new("colors.color_over_lifetime")
                         .gradient()
                         .into(),
+                    InspectorFieldProps::new("colors.color_over_lifetime_curves")
+                        .curve()
+                        .with_label("Color over lifetime curves")
+                        .into(),
                 ],
                 vec![
                     InspectorFieldProps::new("colors.alpha_over_lifetime")
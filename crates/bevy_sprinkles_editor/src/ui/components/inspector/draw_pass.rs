@@ -59,21 +59,58 @@ pub fn draw_pass_section() -> (impl Bundle, InspectorSection) {
                         .combobox(combobox_options_from_reflect::<DrawOrder>())
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("draw_pass.sort_bias").into(),
+                    InspectorFieldProps::new("draw_pass.sort_interval")
+                        .u32()
+                        .with_min(1.0)
+                        .into(),
+                ],
                 vec![
                     InspectorFieldProps::new("draw_pass.transform_align")
                         .optional_combobox(transform_align_options())
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("draw_pass.billboard_keep_world_up")
+                        .bool()
+                        .into(),
+                ],
                 vec![
                     InspectorFieldProps::new("draw_pass.shadow_caster")
                         .bool()
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("draw_pass.visibility")
+                        .combobox(combobox_options_from_reflect::<DrawPassVisibility>())
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("draw_pass.depth_prepass")
+                        .bool()
+                        .into(),
+                ],
                 vec![
                     InspectorFieldProps::new("draw_pass.use_local_coords")
                         .bool()
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("draw_pass.frustum_cull_margin")
+                        .optional_f32()
+                        .with_label("Particle culling margin")
+                        .with_placeholder("Disabled")
+                        .with_min(0.0)
+                        .into(),
+                ],
+                vec![InspectorFieldProps::new("draw_pass.metaball").bool().into()],
+                vec![
+                    InspectorFieldProps::new("draw_pass.post_process_mask")
+                        .optional_u32()
+                        .with_placeholder("None")
+                        .into(),
+                ],
             ],
         ),
     )
@@ -86,6 +123,9 @@ fn transform_align_options() -> Vec<ComboBoxOptionData> {
         ComboBoxOptionData::new("Billboard").with_value("Billboard"),
         ComboBoxOptionData::new("Billboard (Fixed Y)").with_value("BillboardFixedY"),
         ComboBoxOptionData::new("Billboard (Y to velocity)").with_value("BillboardYToVelocity"),
+        ComboBoxOptionData::new("Fixed axis").with_value("FixedAxis"),
+        ComboBoxOptionData::new("Velocity stretched billboard")
+            .with_value("VelocityStretchedBillboard"),
     ]
 }
 
@@ -166,6 +206,8 @@ fn material_variants() -> Vec<VariantDefinition> {
                 .fields_from::<StandardParticleMaterial>()
                 .override_combobox::<SerializableAlphaMode>("alpha_mode")
                 .override_optional_combobox::<SerializableFace>("cull_mode")
+                .override_optional_combobox::<ParticleBlendMode>("blend_mode")
+                .override_combobox::<AlphaFadeMode>("alpha_fade")
                 .override_field(
                     "perceptual_roughness",
                     VariantField::f32("perceptual_roughness")
@@ -174,6 +216,14 @@ fn material_variants() -> Vec<VariantDefinition> {
                 )
                 .override_field("metallic", VariantField::percent("metallic"))
                 .override_field("reflectance", VariantField::percent("reflectance"))
+                .override_field(
+                    "fog_density_multiplier",
+                    VariantField::f32("fog_density_multiplier").with_min(0.0),
+                )
+                .override_field(
+                    "near_fade_distance",
+                    VariantField::f32("near_fade_distance").with_min(0.0),
+                )
                 .override_field(
                     "attenuation_distance",
                     VariantField::new("attenuation_distance").with_kind(FieldKind::F32OrInfinity),
@@ -183,6 +233,8 @@ fn material_variants() -> Vec<VariantDefinition> {
                     vec!["emissive", "emissive_texture"],
                     vec!["emissive_exposure_weight"],
                     vec!["alpha_mode"],
+                    vec!["blend_mode"],
+                    vec!["alpha_fade"],
                     vec!["perceptual_roughness"],
                     vec!["metallic"],
                     vec!["reflectance"],
@@ -204,7 +256,12 @@ fn material_variants() -> Vec<VariantDefinition> {
                     vec!["cull_mode"],
                     vec!["unlit"],
                     vec!["fog_enabled"],
+                    vec!["fog_density_multiplier"],
+                    vec!["near_fade_distance"],
                     vec!["depth_bias"],
+                    vec!["distortion_texture"],
+                    vec!["distortion_strength", "distortion_speed"],
+                    vec!["texture_region_offset", "texture_region_size"],
                 ])
                 .default_value(DrawPassMaterial::Standard(
                     StandardParticleMaterial::default(),
@@ -5,10 +5,10 @@ use crate::ui::widgets::inspector_field::InspectorFieldProps;
 use crate::ui::widgets::variant_edit::{VariantDefinition, VariantEditProps};
 use crate::ui::widgets::vector_edit::VectorSuffixes;
 
-use super::utils::{VariantConfig, variants_from_reflect};
+use super::utils::{VariantConfig, combobox_options_from_reflect, variants_from_reflect};
 use super::{InspectorItem, InspectorSection};
 use crate::ui::icons::{
-    ICON_CUBE, ICON_EMPTY_AXIS, ICON_MESH_TORUS, ICON_MESH_UVSPHERE, ICON_SPHERE,
+    ICON_CUBE, ICON_EMPTY_AXIS, ICON_MESH_TORUS, ICON_MESH_UVSPHERE, ICON_SPHERE, ICON_TEXTURE,
 };
 
 pub fn plugin(_app: &mut App) {}
@@ -34,10 +34,50 @@ pub fn emission_section() -> (impl Bundle, InspectorSection) {
                     props: VariantEditProps::new("emission.shape")
                         .with_variants(emission_shape_variants()),
                 }],
+                vec![
+                    InspectorFieldProps::new("emission.orientation")
+                        .combobox(combobox_options_from_reflect::<EmissionOrientation>())
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("emission.position_randomness")
+                        .vector(VectorSuffixes::XYZ)
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("emission.shell_thickness")
+                        .with_min(0.0)
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("emission.edge_lifetime_scale")
+                        .with_min(0.0)
+                        .into(),
+                    InspectorFieldProps::new("emission.edge_speed_scale")
+                        .with_min(0.0)
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("emission.spatial_randomness")
+                        .percent()
+                        .into(),
+                    InspectorFieldProps::new("emission.spatial_randomness_scale")
+                        .with_min(0.0)
+                        .into(),
+                ],
                 vec![
                     InspectorFieldProps::new("emission.particles_amount")
                         .u32()
                         .into(),
+                    InspectorFieldProps::new("emission.max_particles")
+                        .optional_u32()
+                        .with_placeholder("Auto")
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("emission.rate_over_time")
+                        .curve()
+                        .into(),
                 ],
             ],
         ),
@@ -74,12 +114,27 @@ fn emission_shape_variants() -> Vec<VariantDefinition> {
             "Ring",
             VariantConfig::default()
                 .icon(ICON_MESH_TORUS)
+                .override_combobox::<RingEmissionOrder>("order")
                 .override_rows(vec![
                     vec!["axis"],
                     vec!["height"],
                     vec!["radius", "inner_radius"],
+                    vec!["arc", "order"],
                 ])
                 .default_value(EmissionShape::default_ring()),
         ),
+        (
+            "Image",
+            VariantConfig::default()
+                .icon(ICON_TEXTURE)
+                .override_combobox::<EmissionMaskChannel>("channel")
+                .override_rows(vec![
+                    vec!["texture"],
+                    vec!["size"],
+                    vec!["channel"],
+                    vec!["inherit_color"],
+                ])
+                .default_value(EmissionShape::default_image()),
+        ),
     ])
 }
@@ -10,6 +10,8 @@ mod project_properties;
 mod scale;
 mod settings_properties;
 mod sub_emitter;
+mod target;
+mod texture_sheet;
 mod time;
 mod trail;
 mod transform;
@@ -18,6 +20,7 @@ pub mod types;
 pub mod utils;
 mod velocities;
 mod visibility_aabb;
+mod wave;
 
 pub use types::{ComboBoxOption, FieldKind, VariantField};
 pub use utils::{name_to_label, path_to_label};
@@ -62,6 +65,7 @@ pub fn plugin(app: &mut App) {
         ))
         .add_plugins(project_properties::plugin)
         .add_plugins(visibility_aabb::plugin)
+        .add_plugins((wave::plugin, target::plugin))
         .add_systems(
             Update,
             (
@@ -204,6 +208,10 @@ fn setup_inspector_panel(
                                 spawn_section(emitter_content, emission::emission_section());
                                 spawn_section(emitter_content, scale::scale_section());
                                 spawn_section(emitter_content, colors::colors_section());
+                                spawn_section(
+                                    emitter_content,
+                                    texture_sheet::texture_sheet_section(),
+                                );
 
                                 let (extra, section) = velocities::velocities_section();
                                 let props =
@@ -216,6 +224,8 @@ fn setup_inspector_panel(
                                     accelerations::accelerations_section(),
                                 );
                                 spawn_section(emitter_content, turbulence::turbulence_section());
+                                spawn_section(emitter_content, wave::wave_section());
+                                spawn_section(emitter_content, target::target_section());
                                 spawn_section(emitter_content, trail::trail_section());
                                 spawn_section(emitter_content, collision::collision_section());
                                 spawn_section(emitter_content, sub_emitter::sub_emitter_section());
@@ -285,6 +295,10 @@ fn setup_inspector_panel(
                                     &mut settings_content.commands(),
                                     settings_target,
                                 );
+                                spawn_section(
+                                    settings_content,
+                                    settings_properties::preview_motion_section(),
+                                );
                             });
                     });
             });
@@ -11,7 +11,7 @@ use crate::ui::widgets::button::{
 use crate::ui::widgets::checkbox::{CheckboxProps, checkbox};
 use crate::ui::widgets::inspector_field::fields_row;
 use crate::ui::widgets::text_edit::{TextEditProps, text_edit};
-use crate::utils::{MAX_DISPLAY_PATH_LEN, truncate_path};
+use crate::utils::{MAX_DISPLAY_PATH_LEN, open_path, truncate_path};
 
 use super::{DynamicSectionContent, InspectorSection, section_needs_setup};
 use crate::ui::components::binding::FieldBinding;
@@ -113,6 +113,16 @@ fn setup_properties_content(
                     .insert(ChildOf(row_target));
             });
 
+            parent.spawn(fields_row()).with_children(|row| {
+                let row_target = row.target_entity();
+                row.commands()
+                    .spawn_scene(text_edit(
+                        TextEditProps::default().with_label("Description"),
+                    ))
+                    .insert(FieldBinding::asset("description", FieldKind::String))
+                    .insert(ChildOf(row_target));
+            });
+
             if let Some(ref path) = file_path {
                 spawn_file_path_field(parent, path, &font, &asset_server);
             }
@@ -127,7 +137,7 @@ fn handle_reveal_file_click(trigger: On<ButtonClickEvent>, buttons: Query<&Revea
         return;
     };
     if let Some(parent) = button.0.parent() {
-        let _ = open::that(parent);
+        open_path(parent);
     }
 }
 
@@ -1,16 +1,16 @@
 use bevy::prelude::*;
 
-use crate::io::{EditorBloom, EditorSmaaPreset, EditorTonemapping};
+use crate::io::{EditorBloom, EditorPreviewMotion, EditorSmaaPreset, EditorTonemapping};
 use crate::ui::tokens::BORDER_COLOR;
 use crate::ui::widgets::checkbox::{CheckboxProps, checkbox};
 use crate::ui::widgets::combobox::ComboBoxOptionData;
-use crate::ui::widgets::inspector_field::{combobox_field, fields_row};
+use crate::ui::widgets::inspector_field::{InspectorFieldProps, combobox_field, fields_row};
 
-use crate::ui::components::binding::FieldBinding;
+use crate::ui::components::binding::{BindingTarget, FieldBinding};
 use crate::ui::components::inspector::utils::{
     combobox_options_from_reflect, combobox_options_from_reflect_raw, combobox_options_to_combobox,
 };
-use crate::ui::components::inspector::{FieldKind, path_to_label};
+use crate::ui::components::inspector::{FieldKind, InspectorSection, path_to_label};
 
 fn optional_combobox_options(mut options: Vec<ComboBoxOptionData>) -> Vec<ComboBoxOptionData> {
     options.insert(
@@ -119,3 +119,35 @@ pub fn spawn_settings_properties_section(commands: &mut Commands, parent: Entity
         ))
         .insert(ChildOf(row));
 }
+
+pub fn preview_motion_section() -> (impl Bundle, InspectorSection) {
+    let field = |path| InspectorFieldProps::new(path).with_target(BindingTarget::EditorSettings);
+    (
+        (),
+        InspectorSection::new(
+            "Preview motion",
+            vec![
+                vec![
+                    field("preview_motion")
+                        .with_label("Path")
+                        .optional_combobox(optional_combobox_options(
+                            combobox_options_from_reflect::<EditorPreviewMotion>(),
+                        ))
+                        .into(),
+                ],
+                vec![
+                    field("preview_motion_speed")
+                        .with_label("Speed")
+                        .with_min(0.)
+                        .with_suffix("m/s")
+                        .into(),
+                    field("preview_motion_radius")
+                        .with_label("Size")
+                        .with_min(0.)
+                        .with_suffix("m")
+                        .into(),
+                ],
+            ],
+        ),
+    )
+}
@@ -5,14 +5,14 @@ use crate::state::{EditorState, Inspectable};
 use crate::ui::components::inspector::utils::name_to_label;
 use crate::ui::tokens::FONT_PATH;
 use crate::ui::widgets::alert::{AlertSpan, AlertVariant, alert};
-use crate::ui::widgets::checkbox::{CheckboxProps, checkbox};
 use crate::ui::widgets::combobox::{ComboBoxChangeEvent, ComboBoxOptionData};
-use crate::ui::widgets::inspector_field::fields_row;
+use crate::ui::widgets::inspector_field::{combobox_field, fields_row};
 use crate::ui::widgets::text_edit::{TextEditProps, text_edit};
+use crate::ui::widgets::vector_edit::{VectorEditProps, VectorSuffixes, vector_edit};
 
 use super::{DynamicSectionContent, InspectorSection, section_needs_setup, spawn_labeled_combobox};
 use crate::ui::components::binding::{EmitterWriter, FieldBinding};
-use crate::ui::components::inspector::FieldKind;
+use crate::ui::components::inspector::{ComboBoxOption, FieldKind};
 
 #[derive(Component)]
 struct SubEmitterSection;
@@ -50,10 +50,18 @@ fn mode_index(config: &Option<SubEmitterConfig>) -> usize {
             SubEmitterMode::AtEnd => 2,
             SubEmitterMode::AtCollision => 3,
             SubEmitterMode::AtStart => 4,
+            SubEmitterMode::Trail => 5,
         },
     }
 }
 
+fn amount_scale_options() -> Vec<ComboBoxOptionData> {
+    ["None", "Scale", "Lifetime"]
+        .into_iter()
+        .map(|variant| ComboBoxOptionData::new(variant).with_value(variant))
+        .collect()
+}
+
 fn mode_options() -> Vec<ComboBoxOptionData> {
     vec![
         ComboBoxOptionData::new(name_to_label("None")).with_value("None"),
@@ -61,6 +69,7 @@ fn mode_options() -> Vec<ComboBoxOptionData> {
         ComboBoxOptionData::new(name_to_label("AtEnd")).with_value("AtEnd"),
         ComboBoxOptionData::new(name_to_label("AtCollision")).with_value("AtCollision"),
         ComboBoxOptionData::new(name_to_label("AtStart")).with_value("AtStart"),
+        ComboBoxOptionData::new(name_to_label("Trail")).with_value("Trail"),
     ]
 }
 
@@ -161,7 +170,10 @@ fn spawn_fields(
     font: &Handle<Font>,
     _asset_server: &AssetServer,
 ) {
-    let is_constant = config.mode == SubEmitterMode::Constant;
+    let is_constant = matches!(
+        config.mode,
+        SubEmitterMode::Constant | SubEmitterMode::Trail
+    );
     let is_event = matches!(
         config.mode,
         SubEmitterMode::AtEnd | SubEmitterMode::AtCollision | SubEmitterMode::AtStart
@@ -221,16 +233,58 @@ fn spawn_fields(
         });
     }
 
+    if config.mode == SubEmitterMode::AtEnd {
+        parent.spawn(fields_row()).with_children(|row| {
+            let options = amount_scale_options();
+            row.spawn((
+                FieldBinding::emitter_variant_field(
+                    "sub_emitter",
+                    "amount_scale",
+                    FieldKind::ComboBox {
+                        options: options
+                            .iter()
+                            .map(|o| ComboBoxOption::new(&o.label, &o.label))
+                            .collect(),
+                        optional: false,
+                    },
+                ),
+                combobox_field("Scale amount by".into(), options),
+            ));
+        });
+    }
+
+    parent.spawn(fields_row()).with_children(|row| {
+        let row_target = row.target_entity();
+        row.commands()
+            .spawn_scene(text_edit(
+                TextEditProps::default()
+                    .with_label("Inherit velocity")
+                    .numeric_f32()
+                    .with_suffix("%")
+                    .with_min(0.0)
+                    .with_max(100.0),
+            ))
+            .insert(FieldBinding::emitter_variant_field(
+                "sub_emitter",
+                "keep_velocity.inherit_ratio",
+                FieldKind::F32Percent,
+            ))
+            .insert(ChildOf(row_target));
+    });
+
     parent.spawn(fields_row()).with_children(|row| {
         let row_target = row.target_entity();
         row.commands()
-            .spawn_scene(checkbox(
-                CheckboxProps::new("Keep velocity").checked(config.keep_velocity),
+            .spawn_scene(vector_edit(
+                VectorEditProps::default()
+                    .with_label("Speed multiplier")
+                    .with_size(VectorSuffixes::Range.vector_size())
+                    .with_suffixes(VectorSuffixes::Range),
             ))
             .insert(FieldBinding::emitter_variant_field(
                 "sub_emitter",
-                "keep_velocity",
-                FieldKind::Bool,
+                "keep_velocity.speed_multiplier",
+                FieldKind::Vector(VectorSuffixes::Range),
             ))
             .insert(ChildOf(row_target));
     });
@@ -238,7 +292,7 @@ fn spawn_fields(
     let target_amount = asset
         .emitters
         .get(config.target_emitter)
-        .map(|e| e.emission.particles_amount)
+        .map(|e| e.emission.capacity())
         .unwrap_or(0);
 
     let parent_target = parent.target_entity();
@@ -253,6 +307,8 @@ fn spawn_fields(
                     " particles can be spawned at once, limited by the sub-emitter's ".into(),
                 ),
                 AlertSpan::Bold("Particles amount".into()),
+                AlertSpan::Text(" or ".into()),
+                AlertSpan::Bold("Max particles".into()),
                 AlertSpan::Text(".".into()),
             ],
         ))
@@ -285,6 +341,7 @@ fn handle_sub_emitter_mode_change(
                     "AtEnd" => SubEmitterMode::AtEnd,
                     "AtCollision" => SubEmitterMode::AtCollision,
                     "AtStart" => SubEmitterMode::AtStart,
+                    "Trail" => SubEmitterMode::Trail,
                     _ => return false,
                 };
                 let prev = emitter.sub_emitter.clone().unwrap_or_default();
@@ -293,6 +350,7 @@ fn handle_sub_emitter_mode_change(
                     target_emitter: find_first_other_emitter_index(&editor_state, emitter),
                     frequency: prev.frequency,
                     amount: prev.amount,
+                    amount_scale: prev.amount_scale,
                     keep_velocity: prev.keep_velocity,
                 })
             }
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+use crate::ui::widgets::inspector_field::InspectorFieldProps;
+use crate::ui::widgets::variant_edit::{VariantDefinition, VariantEditProps};
+
+use super::utils::{VariantConfig, variants_from_reflect};
+use super::{InspectorItem, InspectorSection};
+use crate::ui::icons::{ICON_EMPTY_AXIS, ICON_TEXTURE};
+
+pub fn plugin(_app: &mut App) {}
+
+pub fn target_section() -> (impl Bundle, InspectorSection) {
+    (
+        (),
+        InspectorSection::new(
+            "Target",
+            vec![
+                vec![InspectorFieldProps::new("target.enabled").bool().into()],
+                vec![InspectorItem::Variant {
+                    path: "target.shape".into(),
+                    props: VariantEditProps::new("target.shape")
+                        .with_variants(target_shape_variants()),
+                }],
+                vec![
+                    InspectorFieldProps::new("target.blend_over_lifetime")
+                        .curve()
+                        .into(),
+                ],
+            ],
+        ),
+    )
+}
+
+fn target_shape_variants() -> Vec<VariantDefinition> {
+    variants_from_reflect::<TargetShape>(&[
+        (
+            "Points",
+            VariantConfig::default()
+                .icon(ICON_EMPTY_AXIS)
+                .default_value(TargetShape::default()),
+        ),
+        (
+            "Image",
+            VariantConfig::default()
+                .icon(ICON_TEXTURE)
+                .override_combobox::<EmissionMaskChannel>("channel")
+                .override_rows(vec![vec!["texture"], vec!["size"], vec!["channel"]])
+                .default_value(TargetShape::default_image()),
+        ),
+    ])
+}
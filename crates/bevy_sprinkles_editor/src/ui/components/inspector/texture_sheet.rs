@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+use crate::ui::widgets::inspector_field::InspectorFieldProps;
+
+use super::InspectorSection;
+use super::utils::combobox_options_from_reflect;
+
+pub fn texture_sheet_section() -> (impl Bundle, InspectorSection) {
+    (
+        (),
+        InspectorSection::new(
+            "Texture sheet",
+            vec![
+                vec![
+                    InspectorFieldProps::new("texture_sheet.enabled")
+                        .bool()
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("texture_sheet.columns")
+                        .u32()
+                        .into(),
+                    InspectorFieldProps::new("texture_sheet.rows").u32().into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("texture_sheet.random_mode")
+                        .combobox(combobox_options_from_reflect::<TextureSheetRandomMode>())
+                        .into(),
+                ],
+            ],
+        ),
+    )
+}
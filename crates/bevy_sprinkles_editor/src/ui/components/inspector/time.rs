@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
 
 use crate::ui::icons::{ICON_SEEDLING, ICON_TIME};
 use crate::ui::widgets::inspector_field::InspectorFieldProps;
 
 use super::InspectorSection;
+use super::utils::combobox_options_from_reflect;
 
 pub fn plugin(_app: &mut App) {}
 
@@ -38,6 +40,23 @@ pub fn time_section() -> (impl Bundle, InspectorSection) {
                         .percent()
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("cycle_randomness.direction")
+                        .with_min(0.0)
+                        .with_label("Cycle direction")
+                        .with_suffix("°")
+                        .into(),
+                    InspectorFieldProps::new("cycle_randomness.color")
+                        .percent()
+                        .with_label("Cycle color")
+                        .into(),
+                ],
+                vec![
+                    InspectorFieldProps::new("cycle_randomness.explosiveness")
+                        .percent()
+                        .with_label("Cycle explosiveness")
+                        .into(),
+                ],
                 vec![
                     InspectorFieldProps::new("time.fixed_fps")
                         .u32_or_empty()
@@ -49,7 +68,17 @@ pub fn time_section() -> (impl Bundle, InspectorSection) {
                         .with_placeholder("Random")
                         .into(),
                 ],
+                vec![
+                    InspectorFieldProps::new("time.frame_rate_independent")
+                        .bool()
+                        .into(),
+                ],
                 vec![InspectorFieldProps::new("time.one_shot").bool().into()],
+                vec![
+                    InspectorFieldProps::new("time.stop_action")
+                        .combobox(combobox_options_from_reflect::<EmitterStopAction>())
+                        .into(),
+                ],
             ],
         ),
     )
@@ -84,6 +84,11 @@ fn setup_turbulence_options(
                 (vec![
                     InspectorFieldProps::new("turbulence.influence_over_lifetime").curve(),
                 ],),
+                (vec![
+                    InspectorFieldProps::new("turbulence.use_volumes")
+                        .bool()
+                        .with_label("Only inside turbulence volumes"),
+                ],),
             ];
 
             for (fields,) in rows {
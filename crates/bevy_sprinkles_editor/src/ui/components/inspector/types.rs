@@ -21,6 +21,7 @@ pub enum FieldKind {
     F32,
     F32Percent,
     F32OrInfinity,
+    OptionalF32,
     U32,
     U32OrEmpty,
     OptionalU32,
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+use crate::state::EditorState;
+use crate::ui::widgets::inspector_field::{InspectorFieldProps, fields_row, spawn_inspector_field};
+use crate::ui::widgets::vector_edit::VectorSuffixes;
+
+use super::{InspectorSection, section_needs_setup};
+use crate::ui::components::binding::get_inspecting_emitter;
+
+#[derive(Component)]
+struct WaveSection;
+
+#[derive(Component)]
+struct WaveOptions;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Update, (setup_wave_options, toggle_wave_options));
+}
+
+pub fn wave_section() -> (impl Bundle, InspectorSection) {
+    (
+        WaveSection,
+        InspectorSection::new(
+            "Wave",
+            vec![vec![InspectorFieldProps::new("wave.enabled").bool().into()]],
+        ),
+    )
+}
+
+fn setup_wave_options(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    sections: Query<(Entity, &InspectorSection), With<WaveSection>>,
+    existing: Query<Entity, With<WaveOptions>>,
+) {
+    let Some(entity) = section_needs_setup(&sections, &existing) else {
+        return;
+    };
+
+    let enabled = get_inspecting_emitter(&editor_state, &assets)
+        .map(|(_, e)| e.wave.enabled)
+        .unwrap_or(false);
+
+    let display = if enabled {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    let options = commands
+        .spawn((
+            WaveOptions,
+            Node {
+                width: percent(100),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(12.0),
+                display,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            let rows: Vec<(Vec<InspectorFieldProps>,)> = vec![
+                (vec![
+                    InspectorFieldProps::new("wave.amplitude").with_min(0.0),
+                    InspectorFieldProps::new("wave.frequency")
+                        .with_min(0.0)
+                        .with_suffix("Hz"),
+                ],),
+                (vec![
+                    InspectorFieldProps::new("wave.phase_randomness").percent(),
+                ],),
+                (vec![
+                    InspectorFieldProps::new("wave.axis").vector(VectorSuffixes::XYZ),
+                ],),
+            ];
+
+            for (fields,) in rows {
+                parent.spawn(fields_row()).with_children(|row| {
+                    for props in fields {
+                        spawn_inspector_field(row, props, &asset_server);
+                    }
+                });
+            }
+        })
+        .id();
+
+    commands.entity(entity).add_child(options);
+}
+
+fn toggle_wave_options(
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    mut options: Query<&mut Node, With<WaveOptions>>,
+) {
+    let Ok(mut node) = options.single_mut() else {
+        return;
+    };
+
+    let enabled = get_inspecting_emitter(&editor_state, &assets)
+        .map(|(_, e)| e.wave.enabled)
+        .unwrap_or(false);
+
+    let display = if enabled {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    if node.display != display {
+        node.display = display;
+    }
+}
@@ -2,8 +2,9 @@ use bevy::color::palettes::tailwind;
 use bevy::prelude::*;
 use bevy_sprinkles::prelude::*;
 
+use crate::comparison::{BrowseComparisonEvent, ComparisonState, ToggleComparisonEvent};
 use crate::state::{PlaybackPlayEvent, PlaybackResetEvent};
-use crate::ui::icons::{ICON_PAUSE, ICON_PLAY, ICON_REPEAT, ICON_STOP};
+use crate::ui::icons::{ICON_ARROW_LEFT_RIGHT, ICON_PAUSE, ICON_PLAY, ICON_REPEAT, ICON_STOP};
 use crate::ui::tokens::{PRIMARY_COLOR, TEXT_BODY_COLOR};
 use crate::ui::widgets::button::{
     ButtonSize, ButtonVariant, IconButtonProps, icon_button, set_button_variant,
@@ -17,8 +18,10 @@ pub fn plugin(app: &mut App) {
             handle_play_pause_click,
             handle_stop_click,
             handle_loop_click,
+            handle_compare_click,
             update_play_pause_icon,
             update_loop_button_style,
+            update_compare_button_style,
         ),
     );
 }
@@ -35,6 +38,9 @@ pub struct StopButton;
 #[derive(Component, Default, Clone)]
 pub struct LoopButton;
 
+#[derive(Component, Default, Clone)]
+pub struct CompareButton;
+
 pub fn playback_controls() -> impl Scene {
     bsn! {
         EditorPlaybackControls
@@ -46,6 +52,7 @@ pub fn playback_controls() -> impl Scene {
             play_pause_button(),
             stop_button(),
             loop_button(),
+            compare_button(),
         ]
     }
 }
@@ -86,6 +93,18 @@ fn loop_button() -> impl Scene {
     }
 }
 
+fn compare_button() -> impl Scene {
+    bsn! {
+        CompareButton
+        icon_button(
+            IconButtonProps::new(ICON_ARROW_LEFT_RIGHT)
+                .color(TEXT_BODY_COLOR)
+                .variant(ButtonVariant::Ghost)
+                .with_size(ButtonSize::Icon),
+        )
+    }
+}
+
 fn handle_play_pause_click(
     mut commands: Commands,
     mut runtime_query: Query<&mut ParticleSystemRuntime, With<EditorParticlePreview>>,
@@ -127,6 +146,23 @@ fn handle_loop_click(
     }
 }
 
+fn handle_compare_click(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    button_query: Query<&Interaction, (Changed<Interaction>, With<CompareButton>)>,
+) {
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    for interaction in &button_query {
+        if *interaction == Interaction::Pressed {
+            if shift {
+                commands.trigger(BrowseComparisonEvent);
+            } else {
+                commands.trigger(ToggleComparisonEvent);
+            }
+        }
+    }
+}
+
 fn update_play_pause_icon(
     asset_server: Res<AssetServer>,
     runtime_query: Query<
@@ -194,3 +230,40 @@ fn update_loop_button_style(
         }
     }
 }
+
+fn update_compare_button_style(
+    comparison: Res<ComparisonState>,
+    mut button_query: Query<
+        (
+            &Children,
+            &mut ButtonVariant,
+            &mut BackgroundColor,
+            &mut BorderColor,
+        ),
+        With<CompareButton>,
+    >,
+    mut image_query: Query<&mut ImageNode>,
+) {
+    if !comparison.is_changed() {
+        return;
+    }
+
+    let variant = if comparison.enabled {
+        ButtonVariant::Active
+    } else {
+        ButtonVariant::Ghost
+    };
+
+    for (children, mut current_variant, mut bg, mut border) in &mut button_query {
+        if *current_variant != variant {
+            *current_variant = variant;
+            set_button_variant(variant, &mut bg, &mut border);
+        }
+
+        for child in children.iter() {
+            if let Ok(mut image) = image_query.get_mut(child) {
+                image.color = variant.text_color().into();
+            }
+        }
+    }
+}
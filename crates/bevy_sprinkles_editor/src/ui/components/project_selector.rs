@@ -15,11 +15,13 @@ use crate::project::{
     save_project_to_path,
 };
 use crate::state::{DirtyState, EditorState, Inspectable, Inspecting};
+use crate::thumbnails::ProjectThumbnails;
 use crate::ui::icons::{
-    ICON_ARROW_DOWN, ICON_CLOSE, ICON_FILE_ADD, ICON_FOLDER_IMAGE, ICON_FOLDER_OPEN,
+    ICON_ARROW_DOWN, ICON_CLOSE, ICON_FILE_ADD, ICON_FOLDER_IMAGE, ICON_FOLDER_OPEN, ICON_HEART,
 };
 use crate::ui::tokens::{
-    BORDER_COLOR, FONT_PATH, TEXT_BODY_COLOR, TEXT_MUTED_COLOR, TEXT_SIZE, TEXT_SIZE_SM,
+    BORDER_COLOR, CORNER_RADIUS, FONT_PATH, PRIMARY_COLOR, TEXT_BODY_COLOR, TEXT_MUTED_COLOR,
+    TEXT_SIZE, TEXT_SIZE_SM,
 };
 use crate::ui::widgets::button::{
     ButtonClickEvent, ButtonProps, ButtonSize, ButtonVariant, IconButtonProps, button, icon_button,
@@ -34,12 +36,16 @@ use crate::ui::widgets::text_edit::{
 use crate::ui::widgets::utils::is_descendant_of;
 use crate::utils::simplify_path;
 
+const THUMBNAIL_WIDTH: f32 = 48.0;
+const THUMBNAIL_HEIGHT: f32 = 32.0;
+
 pub fn plugin(app: &mut App) {
     app.add_observer(handle_trigger_click)
         .add_observer(handle_new_project_click)
         .add_observer(handle_open_project_click)
         .add_observer(handle_recent_project_click)
         .add_observer(handle_remove_recent_project_click)
+        .add_observer(handle_pin_project_click)
         .add_observer(handle_popover_option_click)
         .add_observer(handle_create_project)
         .add_observer(handle_browse_location_click)
@@ -89,6 +95,15 @@ struct RecentProjectRow;
 #[derive(Component)]
 struct RemoveRecentProjectButton(String);
 
+#[derive(Component)]
+struct PinProjectButton {
+    path: String,
+    pinned: bool,
+}
+
+#[derive(Component)]
+struct ProjectLists;
+
 #[derive(Component)]
 struct NewProjectNameInput;
 
@@ -180,6 +195,7 @@ fn handle_trigger_click(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut editor_data: ResMut<EditorData>,
+    mut thumbnails: ResMut<ProjectThumbnails>,
     triggers: Query<&ProjectSelectorTrigger>,
     mut states: Query<&mut ProjectSelectorState>,
     all_popovers: Query<Entity, With<EditorPopover>>,
@@ -201,8 +217,6 @@ fn handle_trigger_click(
         return;
     }
 
-    let font: Handle<Font> = asset_server.load(FONT_PATH);
-
     let popover_entity = commands
         .spawn_scene(popover(
             PopoverProps::new(trigger.entity)
@@ -211,7 +225,7 @@ fn handle_trigger_click(
                 .with_gap(6.0)
                 .with_z_index(200)
                 .with_node(Node {
-                    min_width: px(200.0),
+                    min_width: px(260.0),
                     ..default()
                 }),
         ))
@@ -265,8 +279,30 @@ fn handle_trigger_click(
         BackgroundColor(BORDER_COLOR.into()),
     ));
 
-    commands.entity(popover_entity).with_child((
-        Text::new("Recent projects"),
+    let prev_count =
+        editor_data.cache.recent_projects.len() + editor_data.cache.pinned_projects.len();
+    let exists = |p: &String| {
+        let exists = project_path(p).exists();
+        if !exists {
+            info!("Removing missing project: {p}");
+        }
+        exists
+    };
+    editor_data.cache.recent_projects.retain(exists);
+    editor_data.cache.pinned_projects.retain(exists);
+    if editor_data.cache.recent_projects.len() + editor_data.cache.pinned_projects.len()
+        != prev_count
+    {
+        save_editor_data(&editor_data);
+    }
+
+    let lists = spawn_project_lists(&mut commands, &editor_data, &mut thumbnails, &asset_server);
+    commands.entity(popover_entity).add_child(lists);
+}
+
+fn section_label(font: Handle<Font>, text: &str) -> impl Bundle {
+    (
+        Text::new(text),
         TextFont {
             font: font.into(),
             font_size: TEXT_SIZE_SM.into(),
@@ -275,57 +311,148 @@ fn handle_trigger_click(
         },
         TextColor(TEXT_MUTED_COLOR.into()),
         Node::default(),
-    ));
+    )
+}
 
-    let recent_wrapper_id = commands
-        .spawn(Node {
-            flex_direction: FlexDirection::Column,
-            ..default()
-        })
+fn spawn_project_lists(
+    commands: &mut Commands,
+    editor_data: &EditorData,
+    thumbnails: &mut ProjectThumbnails,
+    asset_server: &AssetServer,
+) -> Entity {
+    let font: Handle<Font> = asset_server.load(FONT_PATH);
+    let cache = &editor_data.cache;
+
+    let lists = commands
+        .spawn((
+            ProjectLists,
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: px(6.0),
+                ..default()
+            },
+        ))
         .id();
 
-    let prev_count = editor_data.cache.recent_projects.len();
-    editor_data.cache.recent_projects.retain(|p| {
-        let exists = project_path(p).exists();
-        if !exists {
-            info!("Removing missing recent project: {p}");
+    let recent: Vec<&String> = cache
+        .recent_projects
+        .iter()
+        .filter(|p| !cache.is_pinned(p))
+        .collect();
+
+    for (label, paths, pinned) in [
+        (
+            "Pinned",
+            cache.pinned_projects.iter().collect::<Vec<_>>(),
+            true,
+        ),
+        ("Recent projects", recent, false),
+    ] {
+        if pinned && paths.is_empty() {
+            continue;
         }
-        exists
-    });
-    if editor_data.cache.recent_projects.len() != prev_count {
-        save_editor_data(&editor_data);
+
+        let label = commands.spawn(section_label(font.clone(), label)).id();
+        let wrapper = commands
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                ..default()
+            })
+            .id();
+
+        for path_str in paths {
+            let thumbnail = thumbnails.get(path_str, asset_server);
+            let row = spawn_project_row(commands, path_str, pinned, thumbnail);
+            commands.entity(wrapper).add_child(row);
+        }
+
+        commands.entity(lists).add_children(&[label, wrapper]);
     }
 
-    for path_str in &editor_data.cache.recent_projects {
-        let full_path = project_path(path_str);
-        let name = load_project_from_path(&full_path)
-            .ok()
-            .map(|result| result.asset.name)
-            .unwrap_or_else(|| {
-                full_path
-                    .file_stem()
-                    .map(|s| s.to_string_lossy().to_string())
-                    .unwrap_or_else(|| path_str.clone())
-            });
+    lists
+}
 
-        let project_button = commands
-            .spawn_scene(button(
-                ButtonProps::new(name)
-                    .with_variant(ButtonVariant::Ghost)
-                    .align_left()
-                    .with_direction(FlexDirection::Column)
-                    .with_subtitle(path_str),
-            ))
-            .insert(RecentProjectButton(path_str.clone()))
-            .id();
+fn spawn_project_row(
+    commands: &mut Commands,
+    path_str: &str,
+    pinned: bool,
+    thumbnail: Option<Handle<Image>>,
+) -> Entity {
+    let full_path = project_path(path_str);
+    let name = load_project_from_path(&full_path)
+        .ok()
+        .map(|result| result.asset.name)
+        .unwrap_or_else(|| {
+            full_path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| path_str.to_string())
+        });
 
+    let thumbnail_node = commands
+        .spawn((
+            Node {
+                width: px(THUMBNAIL_WIDTH),
+                height: px(THUMBNAIL_HEIGHT),
+                flex_shrink: 0.0,
+                border_radius: BorderRadius::all(CORNER_RADIUS),
+                ..default()
+            },
+            BackgroundColor(BORDER_COLOR.into()),
+        ))
+        .id();
+    if let Some(thumbnail) = thumbnail {
         commands
-            .entity(project_button)
-            .entry::<Node>()
-            .and_modify(|mut node| {
-                node.flex_grow = 1.0;
-            });
+            .entity(thumbnail_node)
+            .insert(ImageNode::new(thumbnail));
+    }
+
+    let project_button = commands
+        .spawn_scene(button(
+            ButtonProps::new(name)
+                .with_variant(ButtonVariant::Ghost)
+                .align_left()
+                .with_direction(FlexDirection::Column)
+                .with_subtitle(path_str),
+        ))
+        .insert(RecentProjectButton(path_str.to_string()))
+        .id();
+
+    commands
+        .entity(project_button)
+        .entry::<Node>()
+        .and_modify(|mut node| {
+            node.flex_grow = 1.0;
+        });
 
+    let pin_color = if pinned {
+        PRIMARY_COLOR
+    } else {
+        TEXT_MUTED_COLOR
+    };
+    let pin_button = commands
+        .spawn_scene(icon_button(
+            IconButtonProps::new(ICON_HEART)
+                .color(pin_color)
+                .variant(ButtonVariant::Ghost)
+                .with_size(ButtonSize::IconSM),
+        ))
+        .insert((
+            PinProjectButton {
+                path: path_str.to_string(),
+                pinned,
+            },
+            if pinned {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+        ))
+        .id();
+
+    let mut row_children = vec![thumbnail_node, project_button, pin_button];
+
+    if !pinned {
         let remove_button = commands
             .spawn_scene(icon_button(
                 IconButtonProps::new(ICON_CLOSE)
@@ -333,35 +460,34 @@ fn handle_trigger_click(
                     .with_size(ButtonSize::IconSM),
             ))
             .insert((
-                RemoveRecentProjectButton(path_str.clone()),
+                RemoveRecentProjectButton(path_str.to_string()),
                 Visibility::Hidden,
             ))
             .id();
+        row_children.push(remove_button);
+    }
 
+    for &child in &row_children[2..] {
         commands
-            .entity(remove_button)
+            .entity(child)
             .entry::<Node>()
             .and_modify(|mut node| {
                 node.flex_shrink = 0.0;
             });
-
-        let row = commands
-            .spawn((
-                RecentProjectRow,
-                Hovered::default(),
-                Node {
-                    align_items: AlignItems::Center,
-                    column_gap: px(6.0),
-                    ..default()
-                },
-            ))
-            .add_children(&[project_button, remove_button])
-            .id();
-
-        commands.entity(recent_wrapper_id).add_child(row);
     }
 
-    commands.entity(popover_entity).add_child(recent_wrapper_id);
+    commands
+        .spawn((
+            RecentProjectRow,
+            Hovered::default(),
+            Node {
+                align_items: AlignItems::Center,
+                column_gap: px(6.0),
+                ..default()
+            },
+        ))
+        .add_children(&row_children)
+        .id()
 }
 
 fn handle_new_project_click(
@@ -426,14 +552,44 @@ fn handle_remove_recent_project_click(
     }
 }
 
+fn handle_pin_project_click(
+    trigger: On<ButtonClickEvent>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    buttons: Query<&PinProjectButton>,
+    mut editor_data: ResMut<EditorData>,
+    mut thumbnails: ResMut<ProjectThumbnails>,
+    lists: Query<(Entity, &ChildOf), With<ProjectLists>>,
+) {
+    let Ok(pin_button) = buttons.get(trigger.entity) else {
+        return;
+    };
+
+    editor_data.cache.toggle_pinned_project(&pin_button.path);
+    save_editor_data(&editor_data);
+
+    for (entity, child_of) in &lists {
+        commands.entity(entity).try_despawn();
+        let new_lists =
+            spawn_project_lists(&mut commands, &editor_data, &mut thumbnails, &asset_server);
+        commands.entity(child_of.parent()).add_child(new_lists);
+    }
+}
+
 fn update_remove_button_visibility(
     rows: Query<(&Children, &Hovered), (With<RecentProjectRow>, Changed<Hovered>)>,
-    mut remove_buttons: Query<&mut Visibility, With<RemoveRecentProjectButton>>,
+    mut remove_buttons: Query<
+        (&mut Visibility, Option<&PinProjectButton>),
+        Or<(With<RemoveRecentProjectButton>, With<PinProjectButton>)>,
+    >,
 ) {
     for (children, hovered) in &rows {
         let visible = hovered.get();
         for child in children.iter() {
-            if let Ok(mut visibility) = remove_buttons.get_mut(child) {
+            if let Ok((mut visibility, pin_button)) = remove_buttons.get_mut(child) {
+                if pin_button.is_some_and(|pin| pin.pinned) {
+                    continue;
+                }
                 *visibility = if visible {
                     Visibility::Inherited
                 } else {
@@ -448,7 +604,7 @@ fn handle_popover_option_click(
     trigger: On<ButtonClickEvent>,
     mut commands: Commands,
     triggers: Query<(), With<ProjectSelectorTrigger>>,
-    remove_buttons: Query<(), With<RemoveRecentProjectButton>>,
+    remove_buttons: Query<(), Or<(With<RemoveRecentProjectButton>, With<PinProjectButton>)>>,
     popovers: Query<Entity, With<ProjectSelectorPopover>>,
     parents: Query<&ChildOf>,
     mut states: Query<&mut ProjectSelectorState>,
@@ -842,6 +998,7 @@ fn handle_create_project(
     commands.remove_resource::<NewProjectDialogState>();
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn handle_browse_location_click(
     trigger: On<ButtonClickEvent>,
     buttons: Query<(), With<BrowseLocationButton>>,
@@ -873,6 +1030,20 @@ fn handle_browse_location_click(
     commands.insert_resource(BrowseLocationResult(path_result));
 }
 
+/// The web editor keeps projects in browser storage, so there are no folders to pick.
+#[cfg(target_arch = "wasm32")]
+fn handle_browse_location_click(
+    trigger: On<ButtonClickEvent>,
+    buttons: Query<(), With<BrowseLocationButton>>,
+    mut commands: Commands,
+) {
+    if buttons.get(trigger.entity).is_ok() {
+        commands.trigger(crate::ui::components::toasts::ToastEvent::error(
+            "Choosing a folder is not available in the web editor",
+        ));
+    }
+}
+
 fn poll_browse_location_result(
     result: Option<Res<BrowseLocationResult>>,
     state: Option<Res<NewProjectDialogState>>,
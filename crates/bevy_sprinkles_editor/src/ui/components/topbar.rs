@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 
+use crate::export::SaveOptimizedCopyEvent;
 use crate::project::SaveProjectEvent;
 use crate::ui::components::playback_controls::playback_controls;
 use crate::ui::components::project_selector::project_selector;
@@ -9,22 +10,41 @@ use crate::ui::widgets::button::{ButtonClickEvent, ButtonProps, ButtonVariant, b
 use crate::ui::widgets::separator::EditorSeparator;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(Update, setup_save_button_observer);
+    app.add_systems(
+        Update,
+        (setup_save_button_observer, setup_export_button_observer),
+    );
 }
 
 #[derive(Component)]
 pub struct SaveButton;
 
+#[derive(Component)]
+pub struct ExportButton;
+
 fn setup_save_button_observer(buttons: Query<Entity, Added<SaveButton>>, mut commands: Commands) {
     for entity in &buttons {
         commands.entity(entity).observe(on_save_button_click);
     }
 }
 
+fn setup_export_button_observer(
+    buttons: Query<Entity, Added<ExportButton>>,
+    mut commands: Commands,
+) {
+    for entity in &buttons {
+        commands.entity(entity).observe(on_export_button_click);
+    }
+}
+
 fn on_save_button_click(_event: On<ButtonClickEvent>, mut commands: Commands) {
     commands.trigger(SaveProjectEvent);
 }
 
+fn on_export_button_click(_event: On<ButtonClickEvent>, mut commands: Commands) {
+    commands.trigger(SaveOptimizedCopyEvent);
+}
+
 #[derive(Component, Default, Clone)]
 pub struct EditorTopbar;
 
@@ -70,6 +90,10 @@ pub fn spawn_topbar(commands: &mut Commands, parent: Entity) {
     commands
         .spawn_scene(EditorSeparator::vertical())
         .insert(ChildOf(right));
+    commands
+        .spawn_scene(button(ButtonProps::new("Export")))
+        .insert(ExportButton)
+        .insert(ChildOf(right));
     commands
         .spawn_scene(button(
             ButtonProps::new("Save").with_variant(ButtonVariant::Primary),
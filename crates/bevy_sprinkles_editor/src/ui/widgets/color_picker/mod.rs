@@ -1,5 +1,6 @@
 mod color_math;
 pub mod materials;
+mod swatches;
 
 use crate::ui::widgets::text_edit::set_text_input_value;
 use bevy::input_focus::InputFocus;
@@ -41,6 +42,7 @@ pub fn plugin(app: &mut App) {
         .add_plugins(UiMaterialPlugin::<HueSliderMaterial>::default())
         .add_plugins(UiMaterialPlugin::<AlphaSliderMaterial>::default())
         .add_plugins(UiMaterialPlugin::<CheckerboardMaterial>::default())
+        .add_plugins(swatches::plugin)
         .add_observer(handle_trigger_click)
         .add_observer(handle_input_mode_change)
         .add_systems(
@@ -164,7 +166,6 @@ pub struct ColorPickerChangeEvent {
 #[derive(EntityEvent)]
 pub struct ColorPickerCommitEvent {
     pub entity: Entity,
-    #[allow(dead_code)]
     pub color: [f32; 4],
 }
 
@@ -927,6 +928,8 @@ fn setup_color_picker_content(
                 .with_children(|row| {
                     spawn_input_fields(row, picker_entity, state.input_mode, state);
                 });
+
+            parent.spawn(swatches::swatch_row(picker_entity));
         });
     }
 }
@@ -0,0 +1,374 @@
+use bevy::picking::hover::Hovered;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::window::{PrimaryWindow, SystemCursorIcon};
+use bevy_sprinkles::prelude::*;
+
+use super::{ColorPickerChangeEvent, ColorPickerCommitEvent, ColorPickerState};
+use crate::io::{EditorData, save_editor_data};
+use crate::state::{DirtyState, EditorState};
+use crate::ui::icons::ICON_ADD;
+use crate::ui::tokens::{BORDER_COLOR, TEXT_MUTED_COLOR, TEXT_SIZE_SM};
+use crate::ui::widgets::button::{
+    ButtonClickEvent, ButtonProps, ButtonSize, ButtonVariant, IconButtonProps, button, icon_button,
+};
+use crate::ui::widgets::cursor::{ActiveCursor, HoverCursor};
+
+const SWATCH_SIZE: f32 = 18.0;
+const SWATCH_RADIUS: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Eyedropper>()
+        .add_observer(record_recent_color)
+        .add_observer(handle_eyedropper_click)
+        .add_observer(handle_add_to_palette_click)
+        .add_systems(
+            Update,
+            (
+                setup_swatch_rows,
+                sync_swatch_rows,
+                handle_swatch_click,
+                handle_eyedropper,
+            ),
+        );
+}
+
+/// Container for the eyedropper, recent colors and project palette of a picker.
+#[derive(Component)]
+pub(super) struct ColorSwatchRow(pub Entity);
+
+#[derive(Component)]
+struct SwatchList {
+    picker: Entity,
+    source: SwatchSource,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SwatchSource {
+    Recent,
+    Palette,
+}
+
+#[derive(Component)]
+struct Swatch {
+    picker: Entity,
+    color: [f32; 4],
+    source: SwatchSource,
+    index: usize,
+}
+
+#[derive(Component)]
+struct EyedropperButton(Entity);
+
+#[derive(Component)]
+struct AddToPaletteButton(Entity);
+
+/// Picker waiting for the user to click anywhere on screen to sample a color.
+#[derive(Resource, Default)]
+struct Eyedropper {
+    picker: Option<Entity>,
+}
+
+pub(super) fn swatch_row(picker: Entity) -> impl Bundle {
+    (
+        ColorSwatchRow(picker),
+        Node {
+            width: percent(100),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(6.0),
+            ..default()
+        },
+    )
+}
+
+fn setup_swatch_rows(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rows: Query<(Entity, &ColorSwatchRow), Added<ColorSwatchRow>>,
+) {
+    let font: Handle<Font> = asset_server.load(crate::ui::tokens::FONT_PATH);
+
+    for (row_entity, row) in &rows {
+        let picker = row.0;
+
+        let eyedropper = commands
+            .spawn_scene(button(
+                ButtonProps::new("Eyedropper")
+                    .with_variant(ButtonVariant::Default)
+                    .with_size(ButtonSize::MD),
+            ))
+            .insert(EyedropperButton(picker))
+            .id();
+        commands.entity(row_entity).add_child(eyedropper);
+
+        for (label, source) in [
+            ("Recent", SwatchSource::Recent),
+            ("Palette", SwatchSource::Palette),
+        ] {
+            let label_entity = commands
+                .spawn((
+                    Text::new(label),
+                    TextFont {
+                        font: font.clone().into(),
+                        font_size: TEXT_SIZE_SM.into(),
+                        weight: FontWeight::MEDIUM,
+                        ..default()
+                    },
+                    TextColor(TEXT_MUTED_COLOR.into()),
+                ))
+                .id();
+            let list_entity = commands
+                .spawn((
+                    SwatchList { picker, source },
+                    Node {
+                        flex_wrap: FlexWrap::Wrap,
+                        align_items: AlignItems::Center,
+                        column_gap: px(4.0),
+                        row_gap: px(4.0),
+                        min_height: px(SWATCH_SIZE),
+                        ..default()
+                    },
+                ))
+                .id();
+            commands
+                .entity(row_entity)
+                .add_children(&[label_entity, list_entity]);
+        }
+    }
+}
+
+fn sync_swatch_rows(
+    mut commands: Commands,
+    editor_data: Res<EditorData>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    new_lists: Query<(), Added<SwatchList>>,
+    lists: Query<(Entity, &SwatchList, Option<&Children>)>,
+) {
+    if !editor_data.is_changed() && !assets.is_changed() && new_lists.is_empty() {
+        return;
+    }
+
+    let palette = editor_state
+        .current_project
+        .as_ref()
+        .and_then(|handle| assets.get(handle))
+        .map(|asset| asset.sprinkles_editor.palette.as_slice())
+        .unwrap_or_default();
+
+    for (list_entity, list, children) in &lists {
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(child).try_despawn();
+            }
+        }
+
+        let colors = match list.source {
+            SwatchSource::Recent => editor_data.cache.recent_colors.as_slice(),
+            SwatchSource::Palette => palette,
+        };
+
+        commands.entity(list_entity).with_children(|parent| {
+            for (index, color) in colors.iter().enumerate() {
+                parent.spawn(swatch(list.picker, *color, list.source, index));
+            }
+        });
+
+        if list.source == SwatchSource::Palette {
+            let add_button = commands
+                .spawn_scene(icon_button(
+                    IconButtonProps::new(ICON_ADD)
+                        .variant(ButtonVariant::Ghost)
+                        .with_size(ButtonSize::IconSM),
+                ))
+                .insert(AddToPaletteButton(list.picker))
+                .id();
+            commands.entity(list_entity).add_child(add_button);
+        }
+    }
+}
+
+fn swatch(picker: Entity, color: [f32; 4], source: SwatchSource, index: usize) -> impl Bundle {
+    let clamped = color.map(|c| c.clamp(0.0, 1.0));
+    (
+        Swatch {
+            picker,
+            color,
+            source,
+            index,
+        },
+        Button,
+        Interaction::None,
+        Hovered::default(),
+        HoverCursor(SystemCursorIcon::Pointer),
+        Node {
+            width: px(SWATCH_SIZE),
+            height: px(SWATCH_SIZE),
+            border: UiRect::all(px(1.0)),
+            border_radius: BorderRadius::all(px(SWATCH_RADIUS)),
+            ..default()
+        },
+        BackgroundColor(Srgba::new(clamped[0], clamped[1], clamped[2], clamped[3]).into()),
+        BorderColor::all(BORDER_COLOR),
+    )
+}
+
+fn apply_color(
+    commands: &mut Commands,
+    picker: Entity,
+    state: &mut ColorPickerState,
+    color: [f32; 4],
+) {
+    state.set_from_rgba(color);
+    commands.trigger(ColorPickerChangeEvent {
+        entity: picker,
+        color,
+    });
+    commands.trigger(ColorPickerCommitEvent {
+        entity: picker,
+        color,
+    });
+}
+
+/// Clicking a swatch applies it; <kbd>Shift</kbd> + click removes a palette entry.
+fn handle_swatch_click(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    swatches: Query<(&Swatch, &Interaction), Changed<Interaction>>,
+    mut pickers: Query<&mut ColorPickerState>,
+    editor_state: Res<EditorState>,
+    mut assets: ResMut<Assets<ParticlesAsset>>,
+    mut dirty_state: ResMut<DirtyState>,
+) {
+    let shift = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
+    for (swatch, interaction) in &swatches {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if shift && swatch.source == SwatchSource::Palette {
+            let Some(asset) = editor_state
+                .current_project
+                .as_ref()
+                .and_then(|handle| assets.get_mut(handle))
+            else {
+                continue;
+            };
+            let palette = &mut asset.into_inner().sprinkles_editor.palette;
+            if swatch.index < palette.len() {
+                palette.remove(swatch.index);
+                dirty_state.has_unsaved_changes = true;
+            }
+            continue;
+        }
+
+        if let Ok(mut state) = pickers.get_mut(swatch.picker) {
+            apply_color(&mut commands, swatch.picker, &mut state, swatch.color);
+        }
+    }
+}
+
+fn handle_add_to_palette_click(
+    trigger: On<ButtonClickEvent>,
+    buttons: Query<&AddToPaletteButton>,
+    pickers: Query<&ColorPickerState>,
+    editor_state: Res<EditorState>,
+    mut assets: ResMut<Assets<ParticlesAsset>>,
+    mut dirty_state: ResMut<DirtyState>,
+) {
+    let Ok(button) = buttons.get(trigger.entity) else {
+        return;
+    };
+    let Ok(state) = pickers.get(button.0) else {
+        return;
+    };
+    let Some(asset) = editor_state
+        .current_project
+        .as_ref()
+        .and_then(|handle| assets.get_mut(handle))
+    else {
+        return;
+    };
+
+    let color = state.to_rgba();
+    let palette = &mut asset.into_inner().sprinkles_editor.palette;
+    if !palette.contains(&color) {
+        palette.push(color);
+        dirty_state.has_unsaved_changes = true;
+    }
+}
+
+fn record_recent_color(trigger: On<ColorPickerCommitEvent>, mut editor_data: ResMut<EditorData>) {
+    if editor_data.cache.recent_colors.first() == Some(&trigger.color) {
+        return;
+    }
+    editor_data.cache.add_recent_color(trigger.color);
+    save_editor_data(&editor_data);
+}
+
+fn handle_eyedropper_click(
+    trigger: On<ButtonClickEvent>,
+    mut commands: Commands,
+    buttons: Query<&EyedropperButton>,
+    mut eyedropper: ResMut<Eyedropper>,
+) {
+    let Ok(button) = buttons.get(trigger.entity) else {
+        return;
+    };
+    eyedropper.picker = Some(button.0);
+    commands
+        .entity(button.0)
+        .insert(ActiveCursor(SystemCursorIcon::Crosshair));
+}
+
+fn handle_eyedropper(
+    mut commands: Commands,
+    mut eyedropper: ResMut<Eyedropper>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+) {
+    let Some(picker) = eyedropper.picker else {
+        return;
+    };
+
+    // skip the frame the eyedropper was armed so the button click isn't sampled
+    if eyedropper.is_changed() {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) || mouse.just_pressed(MouseButton::Right) {
+        eyedropper.picker = None;
+        commands.entity(picker).try_remove::<ActiveCursor>();
+        return;
+    }
+
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    eyedropper.picker = None;
+    commands.entity(picker).try_remove::<ActiveCursor>();
+
+    let Some(position) = window.physical_cursor_position() else {
+        return;
+    };
+    let position = position.as_uvec2();
+
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |captured: On<ScreenshotCaptured>,
+              mut commands: Commands,
+              mut pickers: Query<&mut ColorPickerState>| {
+            let Ok(color) = captured.image.get_color_at(position.x, position.y) else {
+                return;
+            };
+            let Ok(mut state) = pickers.get_mut(picker) else {
+                return;
+            };
+            let srgba = color.to_srgba();
+            let color = [srgba.red, srgba.green, srgba.blue, state.alpha];
+            apply_color(&mut commands, picker, &mut state, color);
+        },
+    );
+}
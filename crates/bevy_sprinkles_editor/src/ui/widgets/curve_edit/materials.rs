@@ -56,6 +56,22 @@ pub struct CurveMaterial {
     easings_low: UVec4,
     #[uniform(0)]
     easings_high: UVec4,
+    #[uniform(0)]
+    in_tangents_x_low: Vec4,
+    #[uniform(0)]
+    in_tangents_x_high: Vec4,
+    #[uniform(0)]
+    in_tangents_y_low: Vec4,
+    #[uniform(0)]
+    in_tangents_y_high: Vec4,
+    #[uniform(0)]
+    out_tangents_x_low: Vec4,
+    #[uniform(0)]
+    out_tangents_x_high: Vec4,
+    #[uniform(0)]
+    out_tangents_y_low: Vec4,
+    #[uniform(0)]
+    out_tangents_y_high: Vec4,
 }
 
 impl CurveMaterial {
@@ -65,6 +81,10 @@ impl CurveMaterial {
         let mut modes = [0u32; MAX_POINTS];
         let mut tensions = [0.0f32; MAX_POINTS];
         let mut easings = [0u32; MAX_POINTS];
+        let mut in_tangents_x = [0.0f32; MAX_POINTS];
+        let mut in_tangents_y = [0.0f32; MAX_POINTS];
+        let mut out_tangents_x = [0.0f32; MAX_POINTS];
+        let mut out_tangents_y = [0.0f32; MAX_POINTS];
 
         for (i, point) in channel.points.iter().take(MAX_POINTS).enumerate() {
             positions[i] = point.position;
@@ -72,6 +92,10 @@ impl CurveMaterial {
             modes[i] = point.mode as u32;
             tensions[i] = point.tension as f32;
             easings[i] = point.easing as u32;
+            in_tangents_x[i] = point.in_tangent.x;
+            in_tangents_y[i] = point.in_tangent.y;
+            out_tangents_x[i] = point.out_tangent.x;
+            out_tangents_y[i] = point.out_tangent.y;
         }
 
         let [positions_low, positions_high] = pack_f32(&positions);
@@ -79,6 +103,10 @@ impl CurveMaterial {
         let [modes_low, modes_high] = pack_u32(&modes);
         let [tensions_low, tensions_high] = pack_f32(&tensions);
         let [easings_low, easings_high] = pack_u32(&easings);
+        let [in_tangents_x_low, in_tangents_x_high] = pack_f32(&in_tangents_x);
+        let [in_tangents_y_low, in_tangents_y_high] = pack_f32(&in_tangents_y);
+        let [out_tangents_x_low, out_tangents_x_high] = pack_f32(&out_tangents_x);
+        let [out_tangents_y_low, out_tangents_y_high] = pack_f32(&out_tangents_y);
 
         Self {
             border_radius: BORDER_RADIUS,
@@ -107,6 +135,14 @@ impl CurveMaterial {
             tensions_high,
             easings_low,
             easings_high,
+            in_tangents_x_low,
+            in_tangents_x_high,
+            in_tangents_y_low,
+            in_tangents_y_high,
+            out_tangents_x_low,
+            out_tangents_x_high,
+            out_tangents_y_low,
+            out_tangents_y_high,
         }
     }
 }
@@ -11,7 +11,7 @@ use bevy::prelude::*;
 use bevy::reflect::Typed;
 use bevy::ui::UiGlobalTransform;
 use bevy::window::SystemCursorIcon;
-use bevy_sprinkles::prelude::{Curve, CurveEasing, CurveMode, CurvePoint, CurveTexture};
+use bevy_sprinkles::prelude::{Curve, CurveEasing, CurveMode, CurvePoint, CurveTexture, CurveWrap};
 use inflector::Inflector;
 
 use materials::{CurveMaterial, MAX_POINTS};
@@ -37,10 +37,12 @@ use crate::ui::widgets::popover::{
 };
 use crate::ui::widgets::text_edit::EditorTextEdit;
 use crate::ui::widgets::text_edit::set_text_input_value;
+use crate::ui::widgets::text_edit::{TextEditCommitEvent, TextEditProps, text_edit};
 use crate::ui::widgets::utils::is_descendant_of;
 use crate::ui::widgets::vector_edit::{
     EditorVectorEdit, VectorEditProps, VectorSize, VectorSuffixes, vector_edit,
 };
+use crate::utils::evaluate_expression;
 use bevy::text::EditableText;
 
 #[derive(Clone, Copy, PartialEq, Default)]
@@ -85,6 +87,8 @@ const POINT_HANDLE_SIZE: f32 = 12.0;
 const TENSION_HANDLE_SIZE: f32 = 10.0;
 const HANDLE_BORDER: f32 = 1.0;
 const DRAG_SNAP_STEP: f64 = 0.01;
+const NUDGE_STEP_LARGE: f64 = 0.1;
+const POINT_SPACING: f32 = 0.001;
 const CURVE_ALPHA: f32 = 0.8;
 const FILL_ALPHA: f32 = 0.2;
 
@@ -93,6 +97,8 @@ pub fn plugin(app: &mut App) {
         .add_observer(handle_trigger_click)
         .add_observer(handle_preset_change)
         .add_observer(handle_axes_mode_change)
+        .add_observer(handle_wrap_change)
+        .add_observer(handle_repeat_commit)
         .add_observer(handle_axis_tab_click)
         .add_observer(handle_flip_click)
         .add_observer(handle_point_mode_change)
@@ -115,6 +121,16 @@ pub fn plugin(app: &mut App) {
                 sync_axis_tab_text_alignment,
                 sync_axes_combobox_to_state,
             ),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_box_select,
+                handle_selection_keys,
+                sync_selected_handle_colors,
+                sync_point_edit_to_state,
+                handle_point_edit_blur,
+            ),
         );
 }
 
@@ -125,6 +141,7 @@ pub struct EditorCurveEdit;
 pub struct CurveEditState {
     pub curve: CurveTexture,
     pub(crate) active_axis: CurveAxis,
+    pub(crate) selection: Vec<usize>,
 }
 
 impl CurveEditState {
@@ -132,6 +149,7 @@ impl CurveEditState {
         Self {
             curve,
             active_axis: CurveAxis::default(),
+            selection: Vec::new(),
         }
     }
 
@@ -185,6 +203,72 @@ impl CurveEditState {
             _ => &mut self.curve.x,
         }
     }
+
+    fn is_selected(&self, index: usize) -> bool {
+        self.selection.contains(&index)
+    }
+
+    fn clear_selection(&mut self) {
+        if !self.selection.is_empty() {
+            self.selection.clear();
+        }
+    }
+
+    /// Moves every selected point of the active curve by the same offset. The offset is
+    /// clamped so the points keep their order and stay inside the curve range.
+    fn move_selection(&mut self, delta_position: f32, delta_value: f64) -> bool {
+        let selection = self.selection.clone();
+        let curve = self.active_curve_mut();
+        let last = curve.points.len().saturating_sub(1);
+        let range_min = curve.range.min as f64;
+        let range_max = curve.range.max as f64;
+
+        let mut position_bounds = (f32::NEG_INFINITY, f32::INFINITY);
+        let mut value_bounds = (f64::NEG_INFINITY, f64::INFINITY);
+        let mut any_selected = false;
+
+        for &index in &selection {
+            let Some(point) = curve.points.get(index) else {
+                continue;
+            };
+            any_selected = true;
+
+            let lower = if index > 0 && !selection.contains(&(index - 1)) {
+                curve.points[index - 1].position + POINT_SPACING
+            } else {
+                0.0
+            };
+            let upper = if index < last && !selection.contains(&(index + 1)) {
+                curve.points[index + 1].position - POINT_SPACING
+            } else {
+                1.0
+            };
+
+            position_bounds.0 = position_bounds.0.max(lower - point.position);
+            position_bounds.1 = position_bounds.1.min(upper - point.position);
+            value_bounds.0 = value_bounds.0.max(range_min - point.value);
+            value_bounds.1 = value_bounds.1.min(range_max - point.value);
+        }
+
+        if !any_selected {
+            return false;
+        }
+
+        let delta_position = delta_position.max(position_bounds.0).min(position_bounds.1);
+        let delta_value = delta_value.max(value_bounds.0).min(value_bounds.1);
+        if delta_position == 0.0 && delta_value == 0.0 {
+            return false;
+        }
+
+        for &index in &selection {
+            if let Some(point) = curve.points.get_mut(index) {
+                point.position += delta_position;
+                point.value += delta_value;
+            }
+        }
+
+        true
+    }
 }
 
 #[derive(EntityEvent)]
@@ -259,6 +343,7 @@ struct CurveEditContent(Entity);
 struct CurveCanvas {
     curve_edit: Entity,
     point_count: usize,
+    modes: Vec<CurveMode>,
     active_axis: CurveAxis,
     is_per_axis: bool,
 }
@@ -287,6 +372,25 @@ struct AxisTabButton {
 #[derive(Component)]
 struct RangeEdit(Entity);
 
+#[derive(Component)]
+struct WrapComboBox(Entity);
+
+#[derive(Component)]
+struct RepeatEdit(Entity);
+
+#[derive(Component)]
+struct PointEditRow(Entity);
+
+#[derive(Component)]
+struct PointEdit(Entity);
+
+#[derive(Component)]
+struct SelectionBox {
+    curve_edit: Entity,
+    canvas: Entity,
+    start: Option<Vec2>,
+}
+
 #[derive(Component)]
 struct PointHandle {
     curve_edit: Entity,
@@ -294,11 +398,66 @@ struct PointHandle {
     index: usize,
 }
 
+// which Bezier handle of a segment a `TensionHandle` edits
+#[derive(Clone, Copy)]
+enum TangentSide {
+    Out,
+    In,
+}
+
 #[derive(Component)]
 struct TensionHandle {
     curve_edit: Entity,
     canvas: Entity,
     index: usize,
+    tangent: Option<TangentSide>,
+}
+
+impl TensionHandle {
+    fn move_tangent(&self, state: &mut CurveEditState, side: TangentSide, normalized: Vec2) {
+        let curve = state.active_curve_mut();
+        if self.index == 0 || self.index >= curve.points.len() {
+            return;
+        }
+
+        let left = curve.points[self.index - 1];
+        let right = curve.points[self.index];
+
+        let raw_pos = (normalized.x + 0.5).clamp(left.position, right.position) as f64;
+        let snapped_pos = (raw_pos / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
+
+        let range_min = curve.range.min as f64;
+        let range_max = curve.range.max as f64;
+        let range_span = curve.range.span() as f64;
+        let normalized_value = 0.5 - normalized.y;
+        let raw_value =
+            (range_min + normalized_value as f64 * range_span).clamp(range_min, range_max);
+        let snapped_value = (raw_value / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
+
+        match side {
+            TangentSide::Out => {
+                curve.points[self.index - 1].out_tangent = Vec2::new(
+                    snapped_pos as f32 - left.position,
+                    (snapped_value - left.value) as f32,
+                );
+            }
+            TangentSide::In => {
+                curve.points[self.index].in_tangent = Vec2::new(
+                    snapped_pos as f32 - right.position,
+                    (snapped_value - right.value) as f32,
+                );
+            }
+        }
+    }
+}
+
+// tangents that make a Bezier segment a straight line, used as a starting shape
+fn linear_tangents(left: &CurvePoint, right: &CurvePoint) -> (Vec2, Vec2) {
+    let third = Vec2::new(
+        right.position - left.position,
+        (right.value - left.value) as f32,
+    ) / 3.0;
+    (third, -third)
 }
 
 #[derive(Component)]
@@ -311,6 +470,7 @@ trait CurveControl: Component {
     fn curve_edit_entity(&self) -> Entity;
     fn canvas_entity(&self) -> Entity;
     fn active_cursor(&self) -> SystemCursorIcon;
+    fn select(&self, _state: &mut CurveEditState, _additive: bool) {}
     fn update_state(&self, state: &mut CurveEditState, normalized: Vec2, delta: Option<Vec2>);
 }
 
@@ -343,37 +503,48 @@ impl CurveControl for PointHandle {
         SystemCursorIcon::Grabbing
     }
 
+    fn select(&self, state: &mut CurveEditState, additive: bool) {
+        if additive {
+            if let Some(i) = state.selection.iter().position(|&s| s == self.index) {
+                state.selection.remove(i);
+            } else {
+                state.selection.push(self.index);
+            }
+        } else if !state.is_selected(self.index) {
+            state.selection = vec![self.index];
+        }
+    }
+
     fn update_state(&self, state: &mut CurveEditState, normalized: Vec2, _delta: Option<Vec2>) {
-        let curve = state.active_curve_mut();
-        if self.index >= curve.points.len() {
+        if !state.is_selected(self.index) {
             return;
         }
 
-        let new_pos = (normalized.x + 0.5).clamp(0.0, 1.0);
-        let snapped_pos = (new_pos as f64 / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
-        let prev_pos = if self.index > 0 {
-            curve.points[self.index - 1].position + 0.001
-        } else {
-            0.0
-        };
-        let next_pos = if self.index < curve.points.len() - 1 {
-            curve.points[self.index + 1].position - 0.001
-        } else {
-            1.0
-        };
-        let clamped_pos = (snapped_pos as f32).clamp(prev_pos, next_pos);
+        let (delta_position, delta_value) = {
+            let curve = state.active_curve();
+            let Some(point) = curve.points.get(self.index) else {
+                return;
+            };
 
-        let range_min = curve.range.min as f64;
-        let range_max = curve.range.max as f64;
-        let range_span = curve.range.span() as f64;
-        let normalized_value = 0.5 - normalized.y;
-        let raw_value =
-            (range_min + normalized_value as f64 * range_span).clamp(range_min, range_max);
-        let snapped_value = (raw_value / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
+            let new_pos = (normalized.x + 0.5).clamp(0.0, 1.0);
+            let snapped_pos = (new_pos as f64 / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
+
+            let range_min = curve.range.min as f64;
+            let range_max = curve.range.max as f64;
+            let range_span = curve.range.span() as f64;
+            let normalized_value = 0.5 - normalized.y;
+            let raw_value =
+                (range_min + normalized_value as f64 * range_span).clamp(range_min, range_max);
+            let snapped_value = (raw_value / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
 
-        curve.points[self.index].position = clamped_pos;
-        curve.points[self.index].value = snapped_value;
+            (
+                snapped_pos as f32 - point.position,
+                snapped_value - point.value,
+            )
+        };
 
+        // the dragged point leads and the rest of the selection follows by the same offset
+        state.move_selection(delta_position, delta_value);
         state.mark_custom();
     }
 }
@@ -388,10 +559,20 @@ impl CurveControl for TensionHandle {
     }
 
     fn active_cursor(&self) -> SystemCursorIcon {
-        SystemCursorIcon::ColResize
+        if self.tangent.is_some() {
+            SystemCursorIcon::Grabbing
+        } else {
+            SystemCursorIcon::ColResize
+        }
     }
 
-    fn update_state(&self, state: &mut CurveEditState, _normalized: Vec2, delta: Option<Vec2>) {
+    fn update_state(&self, state: &mut CurveEditState, normalized: Vec2, delta: Option<Vec2>) {
+        if let Some(side) = self.tangent {
+            self.move_tangent(state, side, normalized);
+            state.mark_custom();
+            return;
+        }
+
         let curve = state.active_curve_mut();
         if self.index == 0 || self.index >= curve.points.len() {
             return;
@@ -420,7 +601,7 @@ impl CurveControl for TensionHandle {
                 let snapped_tension = (raw_tension / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP;
                 curve.points[self.index].tension = snapped_tension;
             }
-            CurveMode::Hold => {}
+            CurveMode::Hold | CurveMode::Bezier => {}
         }
 
         state.mark_custom();
@@ -430,6 +611,7 @@ impl CurveControl for TensionHandle {
 fn on_control_press<C: CurveControl>(
     event: On<Pointer<Press>>,
     mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
     controls: Query<&C>,
     canvases: Query<(&ComputedNode, &UiGlobalTransform), With<CurveCanvas>>,
     mut states: Query<&mut CurveEditState>,
@@ -456,6 +638,8 @@ fn on_control_press<C: CurveControl>(
         return;
     };
 
+    let additive = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    control.select(&mut state, additive);
     control.update_state(&mut state, normalized, None);
 
     commands.trigger(CurveEditChangeEvent {
@@ -630,7 +814,7 @@ fn handle_trigger_click(
     mut commands: Commands,
     _asset_server: Res<AssetServer>,
     triggers: Query<&CurveEditTrigger>,
-    states: Query<&CurveEditState>,
+    mut states: Query<&mut CurveEditState>,
     mut trackers: Query<&mut PopoverTracker>,
     existing_popovers: Query<(Entity, &CurveEditPopover)>,
     all_popovers: Query<Entity, With<EditorPopover>>,
@@ -667,9 +851,10 @@ fn handle_trigger_click(
 
     activate_trigger(trigger.entity, &mut button_styles);
 
-    let Ok(state) = states.get(curve_edit_entity) else {
+    let Ok(mut state) = states.get_mut(curve_edit_entity) else {
         return;
     };
+    state.clear_selection();
 
     let presets: Vec<_> = CURVE_PRESETS
         .iter()
@@ -807,6 +992,7 @@ fn handle_trigger_click(
 
 fn setup_curve_edit_content(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     mut curve_materials: ResMut<Assets<CurveMaterial>>,
     states: Query<&CurveEditState>,
     contents: Query<(Entity, &CurveEditContent), Added<CurveEditContent>>,
@@ -825,6 +1011,7 @@ fn setup_curve_edit_content(
                     CurveCanvas {
                         curve_edit: curve_edit_entity,
                         point_count: channel.points.len(),
+                        modes: channel.points.iter().map(|p| p.mode).collect(),
                         active_axis: state.active_axis,
                         is_per_axis: state.is_per_axis(),
                     },
@@ -857,6 +1044,23 @@ fn setup_curve_edit_content(
                         },
                     ));
 
+                    canvas_parent.spawn((
+                        SelectionBox {
+                            curve_edit: curve_edit_entity,
+                            canvas: canvas_entity,
+                            start: None,
+                        },
+                        Pickable::IGNORE,
+                        Node {
+                            display: Display::None,
+                            position_type: PositionType::Absolute,
+                            border: UiRect::all(px(1.0)),
+                            ..default()
+                        },
+                        BackgroundColor(PRIMARY_COLOR.with_alpha(0.1).into()),
+                        BorderColor::all(PRIMARY_COLOR.with_alpha(0.6)),
+                    ));
+
                     let handle_color = state.accent_color();
                     spawn_point_handles(
                         canvas_parent,
@@ -888,6 +1092,88 @@ fn setup_curve_edit_content(
                 ))
                 .insert(RangeEdit(curve_edit_entity))
                 .insert(ChildOf(parent_target));
+
+            let font: Handle<Font> = asset_server.load(FONT_PATH);
+            parent
+                .spawn(Node {
+                    width: percent(100.0),
+                    column_gap: px(12.0),
+                    align_items: AlignItems::End,
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: px(3.0),
+                        flex_grow: 1.0,
+                        flex_basis: px(0.0),
+                        ..default()
+                    })
+                    .with_children(|wrapper| {
+                        wrapper.spawn((
+                            Text::new("Wrap"),
+                            TextFont {
+                                font: font.clone().into(),
+                                font_size: TEXT_SIZE_SM.into(),
+                                weight: FontWeight::MEDIUM,
+                                ..default()
+                            },
+                            TextColor(TEXT_MUTED_COLOR.into()),
+                        ));
+                        let wrapper_target = wrapper.target_entity();
+                        wrapper
+                            .commands()
+                            .spawn_scene(combobox_with_selected(
+                                wrap_options(),
+                                wrap_index(state.curve.wrap),
+                            ))
+                            .insert(WrapComboBox(curve_edit_entity))
+                            .insert(ChildOf(wrapper_target));
+                    });
+
+                    let row_target = row.target_entity();
+                    row.commands()
+                        .spawn_scene(text_edit(
+                            TextEditProps::default()
+                                .with_label("Repeat")
+                                .with_default_value(state.curve.repeat.to_string())
+                                .with_min(0.0)
+                                .numeric_f32(),
+                        ))
+                        .insert(RepeatEdit(curve_edit_entity))
+                        .insert(ChildOf(row_target))
+                        .entry::<Node>()
+                        .and_modify(|mut node| {
+                            node.flex_grow = 1.0;
+                            node.flex_basis = px(0.0);
+                        });
+                });
+
+            let point_row = parent
+                .spawn((
+                    PointEditRow(curve_edit_entity),
+                    Node {
+                        display: Display::None,
+                        width: percent(100.0),
+                        ..default()
+                    },
+                ))
+                .id();
+            let first_point = channel.points.first();
+            parent
+                .commands()
+                .spawn_scene(vector_edit(
+                    VectorEditProps::default()
+                        .with_label("Point")
+                        .with_size(VectorSize::Vec2)
+                        .with_suffixes(VectorSuffixes::XY)
+                        .with_default_values(vec![
+                            first_point.map(|p| p.position).unwrap_or_default(),
+                            first_point.map(|p| p.value as f32).unwrap_or_default(),
+                        ]),
+                ))
+                .insert(PointEdit(curve_edit_entity))
+                .insert(ChildOf(point_row));
         });
     }
 }
@@ -933,37 +1219,70 @@ fn spawn_tension_handles(
     channel_index: usize,
     handle_color: Srgba,
 ) {
-    let range_span = channel.range.span();
-
     for i in 1..channel.points.len() {
-        let p0 = &channel.points[i - 1];
-        let p1 = &channel.points[i];
+        let tangents: &[Option<TangentSide>] = match channel.points[i].mode {
+            CurveMode::Hold => &[],
+            CurveMode::Bezier => &[Some(TangentSide::Out), Some(TangentSide::In)],
+            _ => &[None],
+        };
 
-        if p1.mode == CurveMode::Hold {
-            continue;
+        for &tangent in tangents {
+            let pos = tension_handle_position(curve_texture, channel_index, channel, i, tangent);
+            let cursor = if tangent.is_some() {
+                SystemCursorIcon::Grab
+            } else {
+                SystemCursorIcon::ColResize
+            };
+
+            parent
+                .spawn((
+                    TensionHandle {
+                        curve_edit: curve_edit_entity,
+                        canvas: canvas_entity,
+                        index: i,
+                        tangent,
+                    },
+                    HoverCursor(cursor),
+                    handle_style(pos.x, pos.y, TENSION_HANDLE_SIZE, handle_color),
+                ))
+                .observe(on_control_press::<TensionHandle>)
+                .observe(on_control_release::<TensionHandle>)
+                .observe(on_control_drag_start::<TensionHandle>)
+                .observe(on_control_drag::<TensionHandle>)
+                .observe(on_control_drag_end::<TensionHandle>);
         }
+    }
+}
 
-        let mid_x = (p0.position + p1.position) / 2.0;
-        let curve_value_at_mid = curve_texture.sample_channel(channel_index, mid_x);
-        let normalized_curve_value = (curve_value_at_mid - channel.range.min) / range_span;
-        let y = 1.0 - normalized_curve_value;
+// canvas position of a segment's handle: the curve's midpoint for tension handles, or the
+// end of the Bezier handle, kept inside the segment the same way sampling clamps it
+fn tension_handle_position(
+    curve_texture: &CurveTexture,
+    channel_index: usize,
+    channel: &Curve,
+    index: usize,
+    tangent: Option<TangentSide>,
+) -> Vec2 {
+    let p0 = &channel.points[index - 1];
+    let p1 = &channel.points[index];
+
+    let (x, value) = match tangent {
+        Some(TangentSide::Out) => (
+            (p0.position + p0.out_tangent.x).clamp(p0.position, p1.position),
+            p0.value as f32 + p0.out_tangent.y,
+        ),
+        Some(TangentSide::In) => (
+            (p1.position + p1.in_tangent.x).clamp(p0.position, p1.position),
+            p1.value as f32 + p1.in_tangent.y,
+        ),
+        None => {
+            let mid_x = (p0.position + p1.position) / 2.0;
+            (mid_x, curve_texture.sample_channel(channel_index, mid_x))
+        }
+    };
 
-        parent
-            .spawn((
-                TensionHandle {
-                    curve_edit: curve_edit_entity,
-                    canvas: canvas_entity,
-                    index: i,
-                },
-                HoverCursor(SystemCursorIcon::ColResize),
-                handle_style(mid_x, y, TENSION_HANDLE_SIZE, handle_color),
-            ))
-            .observe(on_control_press::<TensionHandle>)
-            .observe(on_control_release::<TensionHandle>)
-            .observe(on_control_drag_start::<TensionHandle>)
-            .observe(on_control_drag::<TensionHandle>)
-            .observe(on_control_drag_end::<TensionHandle>);
-    }
+    let normalized_value = (value - channel.range.min) / channel.range.span();
+    Vec2::new(x, 1.0 - normalized_value)
 }
 
 fn handle_style(x: f32, y: f32, size: f32, color: Srgba) -> impl Bundle {
@@ -1041,30 +1360,28 @@ fn update_curve_visuals(
                 continue;
             }
 
-            let p0 = &channel.points[handle.index - 1];
-            let p1 = &channel.points[handle.index];
-
-            let mid_x = (p0.position + p1.position) / 2.0;
-            let curve_value_at_mid = state
-                .curve
-                .sample_channel(state.active_axis.channel_index(), mid_x);
-            let normalized_curve_value = (curve_value_at_mid - channel.range.min) / range_span;
-            let y = 1.0 - normalized_curve_value;
+            let pos = tension_handle_position(
+                &state.curve,
+                state.active_axis.channel_index(),
+                channel,
+                handle.index,
+                handle.tangent,
+            );
 
-            node.left = percent(mid_x * 100.0 - TENSION_HANDLE_SIZE / CANVAS_SIZE * 50.0);
-            node.top = percent(y * 100.0 - TENSION_HANDLE_SIZE / CANVAS_SIZE * 50.0);
+            node.left = percent(pos.x * 100.0 - TENSION_HANDLE_SIZE / CANVAS_SIZE * 50.0);
+            node.top = percent(pos.y * 100.0 - TENSION_HANDLE_SIZE / CANVAS_SIZE * 50.0);
         }
     }
 }
 
 fn respawn_handles_on_point_change(
     mut commands: Commands,
-    states: Query<(Entity, &CurveEditState), Changed<CurveEditState>>,
+    mut states: Query<(Entity, &mut CurveEditState), Changed<CurveEditState>>,
     mut canvases: Query<(Entity, &mut CurveCanvas)>,
     point_handles: Query<(Entity, &PointHandle)>,
     tension_handles: Query<(Entity, &TensionHandle)>,
 ) {
-    for (curve_edit_entity, state) in &states {
+    for (curve_edit_entity, mut state) in &mut states {
         for (canvas_entity, mut canvas) in &mut canvases {
             if canvas.curve_edit != curve_edit_entity {
                 continue;
@@ -1072,15 +1389,23 @@ fn respawn_handles_on_point_change(
 
             let channel = state.active_curve();
             let current_point_count = channel.points.len();
+            let current_modes: Vec<CurveMode> = channel.points.iter().map(|p| p.mode).collect();
             let axis_changed = canvas.active_axis != state.active_axis;
             let per_axis_changed = canvas.is_per_axis != state.is_per_axis();
-            if canvas.point_count == current_point_count && !axis_changed && !per_axis_changed {
+            let modes_changed = canvas.modes != current_modes;
+            if canvas.point_count == current_point_count
+                && !axis_changed
+                && !per_axis_changed
+                && !modes_changed
+            {
                 continue;
             }
 
             canvas.point_count = current_point_count;
+            canvas.modes = current_modes;
             canvas.active_axis = state.active_axis;
             canvas.is_per_axis = state.is_per_axis();
+            state.clear_selection();
 
             for (handle_entity, handle) in &point_handles {
                 if handle.curve_edit == canvas.curve_edit {
@@ -1141,29 +1466,62 @@ fn update_handle_colors(
             continue;
         }
         let accent = handle_accent(entity, &states, &point_handles, &tension_handles);
-        let hover_color = BACKGROUND_COLOR.mix(&accent, 0.8);
-        *bg = if is_dragging {
-            BackgroundColor(accent.into())
-        } else if hovered.get() {
-            BackgroundColor(hover_color.into())
-        } else {
-            BackgroundColor(BACKGROUND_COLOR.into())
-        };
+        let selected = is_point_selected(entity, &states, &point_handles);
+        *bg = handle_background(accent, hovered.get(), is_dragging || selected);
     }
 
     for entity in removed {
         if let Ok((hovered, mut bg)) = handles.p1().get_mut(entity) {
             let accent = handle_accent(entity, &states, &point_handles, &tension_handles);
-            let hover_color = BACKGROUND_COLOR.mix(&accent, 0.8);
-            *bg = if hovered.get() {
-                BackgroundColor(hover_color.into())
-            } else {
-                BackgroundColor(BACKGROUND_COLOR.into())
-            };
+            let selected = is_point_selected(entity, &states, &point_handles);
+            *bg = handle_background(accent, hovered.get(), selected);
         }
     }
 }
 
+fn sync_selected_handle_colors(
+    states: Query<(Entity, &CurveEditState), Changed<CurveEditState>>,
+    mut point_handles: Query<(&PointHandle, &Hovered, Has<Dragging>, &mut BackgroundColor)>,
+) {
+    for (curve_edit_entity, state) in &states {
+        let accent = state.accent_color();
+        for (handle, hovered, is_dragging, mut bg) in &mut point_handles {
+            if handle.curve_edit != curve_edit_entity {
+                continue;
+            }
+            let active = is_dragging || state.is_selected(handle.index);
+            bg.set_if_neq(handle_background(accent, hovered.get(), active));
+        }
+    }
+}
+
+fn handle_background(accent: Srgba, hovered: bool, active: bool) -> BackgroundColor {
+    if active {
+        BackgroundColor(accent.into())
+    } else if hovered {
+        BackgroundColor(BACKGROUND_COLOR.mix(&accent, 0.8).into())
+    } else {
+        BackgroundColor(BACKGROUND_COLOR.into())
+    }
+}
+
+fn is_point_selected(
+    entity: Entity,
+    states: &Query<&CurveEditState>,
+    point_handles: &Query<&PointHandle>,
+) -> bool {
+    point_handles
+        .get(entity)
+        .ok()
+        .and_then(|h| {
+            states
+                .get(h.curve_edit)
+                .ok()
+                .map(|s| s.is_selected(h.index))
+        })
+        .unwrap_or(false)
+}
+
 fn handle_accent(
     entity: Entity,
     states: &Query<&CurveEditState>,
@@ -1206,6 +1564,75 @@ fn handle_preset_change(
     trigger_curve_events(&mut commands, curve_edit_entity, &state.curve);
 }
 
+fn wrap_variants() -> Vec<&'static str> {
+    let bevy::reflect::TypeInfo::Enum(info) = CurveWrap::type_info() else {
+        return Vec::new();
+    };
+    info.iter().map(|variant| variant.name()).collect()
+}
+
+fn wrap_options() -> Vec<ComboBoxOptionData> {
+    wrap_variants()
+        .into_iter()
+        .map(|name| ComboBoxOptionData::new(name.to_sentence_case()))
+        .collect()
+}
+
+fn wrap_index(wrap: CurveWrap) -> usize {
+    wrap_variants()
+        .into_iter()
+        .position(|name| name.parse::<CurveWrap>() == Ok(wrap))
+        .unwrap_or_default()
+}
+
+fn handle_wrap_change(
+    trigger: On<ComboBoxChangeEvent>,
+    mut commands: Commands,
+    wrap_boxes: Query<&WrapComboBox>,
+    mut states: Query<&mut CurveEditState>,
+) {
+    let Ok(wrap_box) = wrap_boxes.get(trigger.entity) else {
+        return;
+    };
+    let Ok(mut state) = states.get_mut(wrap_box.0) else {
+        return;
+    };
+    let Some(wrap) = wrap_variants()
+        .get(trigger.selected)
+        .and_then(|name| name.parse::<CurveWrap>().ok())
+    else {
+        return;
+    };
+
+    state.curve.wrap = wrap;
+    trigger_curve_events(&mut commands, wrap_box.0, &state.curve);
+}
+
+fn handle_repeat_commit(
+    trigger: On<TextEditCommitEvent>,
+    mut commands: Commands,
+    repeat_edits: Query<(Entity, &RepeatEdit)>,
+    mut states: Query<&mut CurveEditState>,
+    parents: Query<&ChildOf>,
+) {
+    let Some(curve_edit_entity) = repeat_edits
+        .iter()
+        .find(|(entity, _)| is_descendant_of(trigger.entity, *entity, &parents))
+        .map(|(_, repeat_edit)| repeat_edit.0)
+    else {
+        return;
+    };
+    let Ok(mut state) = states.get_mut(curve_edit_entity) else {
+        return;
+    };
+    let Ok(repeat) = trigger.text.parse::<f32>() else {
+        return;
+    };
+
+    state.curve.repeat = repeat.max(0.0);
+    trigger_curve_events(&mut commands, curve_edit_entity, &state.curve);
+}
+
 fn handle_axes_mode_change(
     trigger: On<ComboBoxChangeEvent>,
     mut commands: Commands,
@@ -1389,6 +1816,11 @@ fn handle_flip_click(
         }
     }
 
+    let last = state.active_curve().points.len().saturating_sub(1);
+    for index in &mut state.selection {
+        *index = last.saturating_sub(*index);
+    }
+
     trigger_curve_events(&mut commands, curve_edit_entity, &state.curve);
 }
 
@@ -1640,6 +2072,299 @@ fn handle_canvas_right_click(
     }
 }
 
+fn handle_box_select(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    canvases: Query<(&ComputedNode, &UiGlobalTransform, &Hovered), With<CurveCanvas>>,
+    handles: Query<&Hovered, Or<(With<PointHandle>, With<TensionHandle>)>>,
+    mut boxes: Query<(&mut SelectionBox, &mut Node)>,
+    mut states: Query<&mut CurveEditState>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let Some(cursor_position) = window.cursor_position() else {
+        return;
+    };
+    let additive = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    for (mut selection_box, mut node) in &mut boxes {
+        let Ok((computed, ui_transform, hovered)) = canvases.get(selection_box.canvas) else {
+            continue;
+        };
+
+        let cursor_pos = cursor_position / computed.inverse_scale_factor;
+        let Some(normalized) = computed.normalize_point(*ui_transform, cursor_pos) else {
+            continue;
+        };
+        let current = (normalized + 0.5).clamp(Vec2::ZERO, Vec2::ONE);
+
+        if mouse.just_pressed(MouseButton::Left) {
+            if !hovered.get() || handles.iter().any(|h| h.get()) {
+                continue;
+            }
+            selection_box.start = Some(current);
+            if !additive && let Ok(mut state) = states.get_mut(selection_box.curve_edit) {
+                state.clear_selection();
+            }
+        }
+
+        let Some(start) = selection_box.start else {
+            continue;
+        };
+        let min = start.min(current);
+        let max = start.max(current);
+
+        if mouse.pressed(MouseButton::Left) {
+            node.display = Display::Flex;
+            node.left = percent(min.x * 100.0);
+            node.top = percent(min.y * 100.0);
+            node.width = percent((max.x - min.x) * 100.0);
+            node.height = percent((max.y - min.y) * 100.0);
+            continue;
+        }
+
+        selection_box.start = None;
+        node.display = Display::None;
+
+        let Ok(mut state) = states.get_mut(selection_box.curve_edit) else {
+            continue;
+        };
+
+        let channel = state.active_curve();
+        let range_span = channel.range.span();
+        let inside: Vec<usize> = channel
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, point)| {
+                let y = 1.0 - (point.value as f32 - channel.range.min) / range_span;
+                (min.x..=max.x).contains(&point.position) && (min.y..=max.y).contains(&y)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        if inside.is_empty() {
+            continue;
+        }
+        for index in inside {
+            if !state.is_selected(index) {
+                state.selection.push(index);
+            }
+        }
+    }
+}
+
+/// Arrow keys nudge the selected points, <kbd>Shift</kbd> for larger steps, and
+/// <kbd>Delete</kbd> removes them.
+fn handle_selection_keys(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    input_focus: Res<InputFocus>,
+    popovers: Query<&CurveEditPopover>,
+    mut states: Query<&mut CurveEditState>,
+) {
+    if input_focus.get().is_some() {
+        return;
+    }
+
+    let step = if keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        NUDGE_STEP_LARGE
+    } else {
+        DRAG_SNAP_STEP
+    };
+
+    let mut direction = Vec2::ZERO;
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        direction.y -= 1.0;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        direction.y += 1.0;
+    }
+    let delete = keyboard.just_pressed(KeyCode::Delete);
+
+    if direction == Vec2::ZERO && !delete {
+        return;
+    }
+
+    for popover in &popovers {
+        let Ok(mut state) = states.get_mut(popover.0) else {
+            continue;
+        };
+        if state.selection.is_empty() {
+            continue;
+        }
+
+        let changed = if delete {
+            let mut selection = std::mem::take(&mut state.selection);
+            selection.sort_unstable();
+            let channel = state.active_curve_mut();
+            let mut removed = false;
+            for index in selection.into_iter().rev() {
+                if channel.points.len() <= 2 {
+                    break;
+                }
+                if index < channel.points.len() {
+                    channel.points.remove(index);
+                    removed = true;
+                }
+            }
+            removed
+        } else {
+            let range_span = state.active_curve().range.span() as f64;
+            state.move_selection(
+                direction.x * step as f32,
+                direction.y as f64 * step * range_span,
+            )
+        };
+
+        if changed {
+            state.mark_custom();
+            trigger_curve_events(&mut commands, popover.0, &state.curve);
+        }
+    }
+}
+
+fn sync_point_edit_to_state(
+    input_focus: Res<InputFocus>,
+    states: Query<(Entity, &CurveEditState), Changed<CurveEditState>>,
+    mut rows: Query<(&PointEditRow, &mut Node)>,
+    point_edits: Query<(&PointEdit, &Children)>,
+    vector_edits: Query<&Children, With<EditorVectorEdit>>,
+    mut text_inputs: Query<(Entity, &mut EditableText), With<EditorTextEdit>>,
+    parents: Query<&ChildOf>,
+) {
+    for (curve_edit_entity, state) in &states {
+        let point = match state.selection.as_slice() {
+            [index] => state.active_curve().points.get(*index),
+            _ => None,
+        };
+
+        for (row, mut node) in &mut rows {
+            if row.0 == curve_edit_entity {
+                node.display = if point.is_some() {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+            }
+        }
+
+        let Some(point) = point else {
+            continue;
+        };
+        let values = [point.position as f64, point.value];
+
+        for (point_edit, point_children) in &point_edits {
+            if point_edit.0 != curve_edit_entity {
+                continue;
+            }
+
+            for point_child in point_children.iter() {
+                let Ok(vector_children) = vector_edits.get(point_child) else {
+                    continue;
+                };
+
+                for (i, vector_child) in vector_children.iter().enumerate() {
+                    let Some(&value) = values.get(i) else {
+                        continue;
+                    };
+                    let text =
+                        (((value / DRAG_SNAP_STEP).round() * DRAG_SNAP_STEP) as f32).to_string();
+
+                    for (text_input_entity, mut editable) in &mut text_inputs {
+                        if input_focus.get() == Some(text_input_entity) {
+                            continue;
+                        }
+
+                        if is_descendant_of(text_input_entity, vector_child, &parents) {
+                            set_text_input_value(&mut editable, text.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn handle_point_edit_blur(
+    input_focus: Res<InputFocus>,
+    mut last_focus: Local<Option<Entity>>,
+    mut commands: Commands,
+    mut states: Query<&mut CurveEditState>,
+    point_edits: Query<(&PointEdit, &Children)>,
+    vector_edits: Query<&Children, With<EditorVectorEdit>>,
+    text_inputs: Query<&EditableText, With<EditorTextEdit>>,
+    parents: Query<&ChildOf>,
+) {
+    let current_focus = input_focus.get();
+    let previous_focus = *last_focus;
+    *last_focus = current_focus;
+
+    let Some(blurred_entity) = previous_focus else {
+        return;
+    };
+    if current_focus == Some(blurred_entity) {
+        return;
+    }
+
+    let Ok(editable) = text_inputs.get(blurred_entity) else {
+        return;
+    };
+
+    for (point_edit, point_children) in &point_edits {
+        for point_child in point_children.iter() {
+            let Ok(vector_children) = vector_edits.get(point_child) else {
+                continue;
+            };
+
+            for (field_index, vector_child) in vector_children.iter().enumerate() {
+                if !is_descendant_of(blurred_entity, vector_child, &parents) {
+                    continue;
+                }
+
+                let Ok(value) = evaluate_expression(&editable.value().to_string()) else {
+                    return;
+                };
+                let Ok(mut state) = states.get_mut(point_edit.0) else {
+                    return;
+                };
+                let &[index] = state.selection.as_slice() else {
+                    return;
+                };
+
+                let (delta_position, delta_value) = {
+                    let Some(point) = state.active_curve().points.get(index) else {
+                        return;
+                    };
+                    if field_index == 0 {
+                        (value as f32 - point.position, 0.0)
+                    } else {
+                        (0.0, value - point.value)
+                    }
+                };
+
+                if state.move_selection(delta_position, delta_value) {
+                    state.mark_custom();
+                    trigger_curve_events(&mut commands, point_edit.0, &state.curve);
+                } else {
+                    // re-sync the field when the value was clamped away
+                    state.set_changed();
+                }
+
+                return;
+            }
+        }
+    }
+}
+
 fn menu_separator() -> impl Bundle {
     (
         Node {
@@ -1861,12 +2586,23 @@ fn handle_point_mode_change(
     if let Ok(mode_opt) = mode_options.get(trigger.entity) {
         if !mode_opt.disabled {
             if let Ok(mut state) = states.get_mut(mode_opt.curve_edit) {
-                if let Some(point) = state
-                    .active_curve_mut()
-                    .points
-                    .get_mut(mode_opt.point_index)
-                {
-                    point.mode = mode_opt.mode;
+                let points = &mut state.active_curve_mut().points;
+                if mode_opt.point_index < points.len() {
+                    let index = mode_opt.point_index;
+                    points[index].mode = mode_opt.mode;
+
+                    // start a new Bezier segment as a straight line instead of an S-curve
+                    if mode_opt.mode == CurveMode::Bezier
+                        && index > 0
+                        && points[index - 1].out_tangent == Vec2::ZERO
+                        && points[index].in_tangent == Vec2::ZERO
+                    {
+                        let (out_tangent, in_tangent) =
+                            linear_tangents(&points[index - 1], &points[index]);
+                        points[index - 1].out_tangent = out_tangent;
+                        points[index].in_tangent = in_tangent;
+                    }
+
                     state.mark_custom();
                     trigger_curve_events(&mut commands, mode_opt.curve_edit, &state.curve);
                     handled = true;
@@ -1930,12 +2666,15 @@ fn handle_tension_right_click(
             continue;
         };
 
-        if let Some(point) = state
-            .active_curve_mut()
-            .points
-            .get_mut(tension_handle.index)
-        {
-            point.tension = 0.0;
+        let points = &mut state.active_curve_mut().points;
+        let index = tension_handle.index;
+        if index > 0 && index < points.len() {
+            let (out_tangent, in_tangent) = linear_tangents(&points[index - 1], &points[index]);
+            match tension_handle.tangent {
+                Some(TangentSide::Out) => points[index - 1].out_tangent = out_tangent,
+                Some(TangentSide::In) => points[index].in_tangent = in_tangent,
+                None => points[index].tension = 0.0,
+            }
             state.mark_custom();
             trigger_curve_events(&mut commands, tension_handle.curve_edit, &state.curve);
         }
@@ -52,6 +52,11 @@ impl InspectorFieldProps {
         self
     }
 
+    pub fn optional_f32(mut self) -> Self {
+        self.kind = FieldKind::OptionalF32;
+        self
+    }
+
     pub fn u32(mut self) -> Self {
         self.kind = FieldKind::U32;
         self
@@ -174,7 +179,10 @@ impl InspectorFieldProps {
     }
 
     fn should_allow_empty(&self) -> bool {
-        matches!(self.kind, FieldKind::U32OrEmpty | FieldKind::OptionalU32)
+        matches!(
+            self.kind,
+            FieldKind::OptionalF32 | FieldKind::U32OrEmpty | FieldKind::OptionalU32
+        )
     }
 
     fn is_integer(&self) -> bool {
@@ -4,6 +4,7 @@ use bevy::text::TextLayoutInfo;
 use bevy::window::SystemCursorIcon;
 
 use crate::ui::widgets::cursor::HoverCursor;
+use crate::utils::open_url;
 
 const LINK_HIT_PADDING: f32 = 2.0;
 
@@ -96,7 +97,7 @@ fn position_link_hitboxes(
 fn handle_link_click(interactions: Query<(&Interaction, &LinkHitbox), Changed<Interaction>>) {
     for (interaction, hitbox) in &interactions {
         if *interaction == Interaction::Pressed {
-            let _ = open::that(&hitbox.url);
+            open_url(&hitbox.url);
         }
     }
 }
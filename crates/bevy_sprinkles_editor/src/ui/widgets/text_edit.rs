@@ -17,6 +17,7 @@ use crate::ui::tokens::{
     TEXT_SIZE_SM,
 };
 use crate::ui::widgets::cursor::{ActiveCursor, HoverCursor};
+use crate::utils::evaluate_expression;
 
 pub fn set_text_input_value(editable: &mut EditableText, text: String) {
     editable.queue_edit(TextEdit::SelectAll);
@@ -108,6 +109,10 @@ struct DragHitbox {
     start_value: f64,
 }
 
+/// Points a label drag hitbox at the wrapper of the input it scrubs.
+#[derive(Component)]
+struct LabelDragTarget(Entity);
+
 #[derive(Component, Clone, Copy)]
 struct NumericRange {
     min: f64,
@@ -286,51 +291,59 @@ fn setup_text_edit_input(
         }
         config.initialized = true;
 
+        let is_numeric = config.variant.is_numeric();
+        let wrapper_entity = commands.spawn_empty().id();
+
         if let Some(ref label) = config.label {
-            let label_entity = commands
-                .spawn((
-                    Text::new(label),
-                    TextFont {
-                        font: font.clone().into(),
-                        font_size: TEXT_SIZE_SM.into(),
-                        weight: FontWeight::MEDIUM,
-                        ..default()
-                    },
-                    TextColor(TEXT_MUTED_COLOR.into()),
-                ))
-                .id();
+            let mut label_entity = commands.spawn((
+                Text::new(label),
+                TextFont {
+                    font: font.clone().into(),
+                    font_size: TEXT_SIZE_SM.into(),
+                    weight: FontWeight::MEDIUM,
+                    ..default()
+                },
+                TextColor(TEXT_MUTED_COLOR.into()),
+            ));
+            if is_numeric {
+                label_entity.insert((
+                    DragHitbox::default(),
+                    LabelDragTarget(wrapper_entity),
+                    Interaction::None,
+                    Hovered::default(),
+                    HoverCursor(SystemCursorIcon::ColResize),
+                ));
+            }
+            let label_entity = label_entity.id();
             commands.entity(entity).add_child(label_entity);
         }
 
-        let is_numeric = config.variant.is_numeric();
         let filter = config.filter.as_ref().map(|f| match f {
-            FilterType::Decimal => {
-                EditableTextFilter::new(|c: char| c.is_ascii_digit() || c == '.' || c == '-')
-            }
+            FilterType::Decimal => EditableTextFilter::new(|c: char| {
+                c.is_ascii_digit() || c == '.' || is_expression_char(c)
+            }),
             FilterType::Integer => {
-                EditableTextFilter::new(|c: char| c.is_ascii_digit() || c == '-')
+                EditableTextFilter::new(|c: char| c.is_ascii_digit() || is_expression_char(c))
             }
         });
 
-        let wrapper_entity = commands
-            .spawn((
-                Node {
-                    width: percent(100),
-                    height: px(INPUT_HEIGHT),
-                    padding: UiRect::all(px(6)),
-                    border: UiRect::all(px(1)),
-                    border_radius: BorderRadius::all(px(2)),
-                    align_items: AlignItems::Center,
-                    column_gap: px(6),
-                    ..default()
-                },
-                BackgroundColor(Color::NONE),
-                BorderColor::all(BORDER_COLOR),
-                Interaction::None,
-                Hovered::default(),
-                HoverCursor(SystemCursorIcon::Text),
-            ))
-            .id();
+        commands.entity(wrapper_entity).insert((
+            Node {
+                width: percent(100),
+                height: px(INPUT_HEIGHT),
+                padding: UiRect::all(px(6)),
+                border: UiRect::all(px(1)),
+                border_radius: BorderRadius::all(px(2)),
+                align_items: AlignItems::Center,
+                column_gap: px(6),
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+            BorderColor::all(BORDER_COLOR),
+            Interaction::None,
+            Hovered::default(),
+            HoverCursor(SystemCursorIcon::Text),
+        ));
 
         commands.entity(entity).add_child(wrapper_entity);
 
@@ -718,21 +731,23 @@ fn handle_clamp_on_unfocus(
 
     let text = strip_suffix(&editable.value().to_string(), suffix);
 
-    commands.trigger(TextEditCommitEvent {
-        entity: was_focused,
-        text: text.clone(),
-    });
-
-    if !variant.is_numeric() {
+    if !variant.is_numeric() || (text.is_empty() && allow_empty.is_some()) {
+        commands.trigger(TextEditCommitEvent {
+            entity: was_focused,
+            text,
+        });
         return;
     }
 
-    if text.is_empty() && allow_empty.is_some() {
-        return;
-    }
+    // expressions like `2*0.35` are resolved here, so bindings only ever see a number
+    let value = clamp_value(evaluate_expression(&text).unwrap_or(0.0), range);
+    let text = format_numeric_value(value, *variant);
+    set_text_input_value(&mut editable, text.clone());
 
-    let value = text.parse().unwrap_or(0.0);
-    update_input_value(&mut editable, value, *variant, range);
+    commands.trigger(TextEditCommitEvent {
+        entity: was_focused,
+        text,
+    });
 }
 
 fn handle_numeric_increment(
@@ -782,7 +797,13 @@ fn handle_drag_value(
     mouse: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     keyboard: Res<ButtonInput<KeyCode>>,
-    mut drag_hitboxes: Query<(Entity, &mut DragHitbox, &Interaction, &ChildOf)>,
+    mut drag_hitboxes: Query<(
+        Entity,
+        &mut DragHitbox,
+        &Interaction,
+        &ChildOf,
+        Option<&LabelDragTarget>,
+    )>,
     wrappers: Query<&TextEditWrapper>,
     mut text_edits: Query<
         (
@@ -797,8 +818,9 @@ fn handle_drag_value(
     let Ok(window) = windows.single() else { return };
     let cursor_pos = window.cursor_position();
 
-    for (entity, mut hitbox, interaction, child_of) in &mut drag_hitboxes {
-        let Ok(wrapper) = wrappers.get(child_of.parent()) else {
+    for (entity, mut hitbox, interaction, child_of, label_target) in &mut drag_hitboxes {
+        let wrapper_entity = label_target.map_or(child_of.parent(), |target| target.0);
+        let Ok(wrapper) = wrappers.get(wrapper_entity) else {
             continue;
         };
         let input_entity = wrapper.0;
@@ -845,12 +867,16 @@ fn handle_drag_value(
                     || keyboard.pressed(KeyCode::SuperRight)
                     || keyboard.pressed(KeyCode::AltLeft)
                     || keyboard.pressed(KeyCode::AltRight);
-
-                let (amount, sensitivity) = match (*variant, alt_mode) {
-                    (TextEditVariant::NumericI32, false) => (1.0, 5.0),
-                    (TextEditVariant::NumericI32, true) => (10.0, 10.0),
-                    (_, false) => (0.1, 5.0),
-                    (_, true) => (1.0, 10.0),
+                let precise_mode =
+                    keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+
+                let (amount, sensitivity) = match (*variant, alt_mode, precise_mode) {
+                    (TextEditVariant::NumericI32, _, true) => (1.0, 20.0),
+                    (TextEditVariant::NumericI32, false, false) => (1.0, 5.0),
+                    (TextEditVariant::NumericI32, true, false) => (10.0, 10.0),
+                    (_, _, true) => (0.01, 5.0),
+                    (_, false, false) => (0.1, 5.0),
+                    (_, true, false) => (1.0, 10.0),
                 };
 
                 let steps = ((pos.x - hitbox.start_x) / sensitivity).floor() as f64;
@@ -889,7 +915,11 @@ fn strip_suffix(text: &str, suffix: Option<&TextEditSuffix>) -> String {
 }
 
 fn parse_numeric_value(text: &str, suffix: Option<&TextEditSuffix>) -> f64 {
-    strip_suffix(text, suffix).parse().unwrap_or(0.0)
+    evaluate_expression(&strip_suffix(text, suffix)).unwrap_or(0.0)
+}
+
+fn is_expression_char(c: char) -> bool {
+    matches!(c, '-' | '+' | '*' | '/' | '(' | ')' | ' ')
 }
 
 fn format_numeric_value(value: f64, variant: TextEditVariant) -> String {
@@ -674,6 +674,7 @@ fn handle_preset_click(
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn handle_select_file_click(
     trigger: On<ButtonClickEvent>,
     mut commands: Commands,
@@ -709,6 +710,20 @@ fn handle_select_file_click(
     });
 }
 
+/// Browsers don't expose file paths, so local textures can't be referenced on the web.
+#[cfg(target_arch = "wasm32")]
+fn handle_select_file_click(
+    trigger: On<ButtonClickEvent>,
+    mut commands: Commands,
+    select_buttons: Query<&SelectFileButton>,
+) {
+    if select_buttons.get(trigger.entity).is_ok() {
+        commands.trigger(crate::ui::components::toasts::ToastEvent::error(
+            "Local textures are not available in the web editor",
+        ));
+    }
+}
+
 fn poll_texture_file_pick(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -862,7 +877,7 @@ fn format_display_path(path: &str) -> String {
 }
 
 // TODO: `/data/assets/src-backup/` would be wrongly classified
-fn classify_texture_path(path: &str) -> (TextureRef, Option<String>) {
+pub(crate) fn classify_texture_path(path: &str) -> (TextureRef, Option<String>) {
     if let Some(assets_pos) = path.find("/assets/") {
         let before = &path[..assets_pos];
         if !before.contains("/src/") {
@@ -718,7 +718,10 @@ fn spawn_field_widget(
 ) -> Entity {
     let kind = &field.kind;
     match kind {
-        FieldKind::F32 | FieldKind::F32Percent | FieldKind::F32OrInfinity => {
+        FieldKind::F32
+        | FieldKind::F32Percent
+        | FieldKind::F32OrInfinity
+        | FieldKind::OptionalF32 => {
             let mut props = TextEditProps::default().with_label(label).numeric_f32();
             match kind {
                 FieldKind::F32Percent => {
@@ -727,6 +730,9 @@ fn spawn_field_widget(
                 FieldKind::F32OrInfinity => {
                     props = props.with_placeholder("∞").allow_empty();
                 }
+                FieldKind::OptionalF32 => {
+                    props = props.allow_empty();
+                }
                 _ => {}
             }
             if let Some(min) = field.min {
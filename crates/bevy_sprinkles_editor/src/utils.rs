@@ -50,3 +50,96 @@ pub fn simplify_path(path: &Path) -> String {
     }
     path.to_string_lossy().to_string()
 }
+
+/// Opens a URL in the user's browser.
+pub fn open_url(url: &str) {
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = open::that(url);
+    #[cfg(target_arch = "wasm32")]
+    crate::web::open_url(url);
+}
+
+/// Opens a file or folder with the system's default app. Does nothing on the web.
+pub fn open_path(path: &Path) {
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = open::that(path);
+    #[cfg(target_arch = "wasm32")]
+    let _ = path;
+}
+
+/// Evaluates a simple arithmetic expression such as `2*0.35` or `(1 + 2) / 4`.
+///
+/// Supports `+`, `-`, `*`, `/`, unary signs and parentheses. Returns `None` if the
+/// expression is malformed or does not evaluate to a finite number.
+pub fn evaluate_expression(text: &str) -> Option<f64> {
+    let mut parser = ExpressionParser {
+        chars: text.chars().filter(|c| !c.is_whitespace()).collect(),
+        pos: 0,
+    };
+    let value = parser.parse_sum()?;
+    if parser.pos != parser.chars.len() || !value.is_finite() {
+        return None;
+    }
+    Some(value)
+}
+
+struct ExpressionParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ExpressionParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn parse_sum(&mut self) -> Option<f64> {
+        let mut value = self.parse_product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.parse_product()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Some(value)
+    }
+
+    fn parse_product(&mut self) -> Option<f64> {
+        let mut value = self.parse_factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.parse_factor()?;
+            value = if op == '*' { value * rhs } else { value / rhs };
+        }
+        Some(value)
+    }
+
+    fn parse_factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            '-' => {
+                self.pos += 1;
+                self.parse_factor().map(|v| -v)
+            }
+            '+' => {
+                self.pos += 1;
+                self.parse_factor()
+            }
+            '(' => {
+                self.pos += 1;
+                let value = self.parse_sum()?;
+                if self.peek()? != ')' {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.pos += 1;
+                }
+                let number: String = self.chars[start..self.pos].iter().collect();
+                number.parse().ok()
+            }
+        }
+    }
+}
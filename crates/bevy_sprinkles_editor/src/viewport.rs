@@ -6,10 +6,12 @@ use bevy::asset::RenderAssetUsages;
 use bevy::camera::RenderTarget;
 use bevy::camera::primitives::Aabb;
 use bevy::camera::visibility::NoFrustumCulling;
+use bevy::camera::{OrthographicProjection, ScalingMode};
 use bevy::color::palettes::tailwind::{ZINC_200, ZINC_950};
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor};
 use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::input_focus::InputFocus;
 use bevy::math::Affine2;
 use bevy::picking::hover::Hovered;
 use bevy::post_process::bloom::Bloom;
@@ -20,7 +22,9 @@ use bevy::window::PresentMode;
 use bevy_sprinkles::prelude::*;
 use bevy_sprinkles::{ParticleBufferHandle, ParticleData};
 
-use crate::io::{EditorBloom, EditorData, EditorSmaaPreset, EditorTonemapping};
+use crate::io::{
+    EditorBloom, EditorData, EditorPreviewMotion, EditorSmaaPreset, EditorTonemapping,
+};
 
 use crate::state::{
     EditorState, GenerateAabbRequest, Inspectable, PlaybackPlayEvent, PlaybackResetEvent,
@@ -30,7 +34,6 @@ use crate::ui::components::binding::EmitterWriter;
 use crate::ui::components::seekbar::SeekbarDragState;
 use crate::ui::components::toasts::ToastEvent;
 use crate::ui::components::viewport::EditorViewport;
-use crate::ui::tokens::PRIMARY_COLOR;
 
 const MIN_ZOOM_DISTANCE: f32 = 0.1;
 const MAX_ZOOM_DISTANCE: f32 = 20.0;
@@ -38,6 +41,8 @@ const ZOOM_SPEED: f32 = 0.5;
 const INITIAL_ORBIT_DISTANCE: f32 = 8.0;
 const ORBIT_OFFSET: Vec3 = Vec3::new(1.0, 0.75, 1.0);
 const ORBIT_TARGET: Vec3 = Vec3::ZERO;
+const FRAME_PADDING: f32 = 2.5;
+const MAX_CAMERA_BOOKMARKS: usize = 9;
 
 const FLOOR_SIZE: f32 = 192.0;
 const FLOOR_TILE_SIZE: f32 = 4.0;
@@ -45,6 +50,9 @@ const FLOOR_TILE_SIZE: f32 = 4.0;
 #[derive(Component)]
 pub struct EditorCamera;
 
+#[derive(Component)]
+pub struct EditorFloor;
+
 #[derive(Default, Resource)]
 pub struct ViewportInputState {
     pub dragging: bool,
@@ -52,7 +60,9 @@ pub struct ViewportInputState {
 
 #[derive(Debug, Resource)]
 pub struct CameraSettings {
+    pub orbit_target: Vec3,
     pub orbit_distance: f32,
+    pub orthographic: bool,
     pub pitch_speed: f32,
     pub pitch_range: Range<f32>,
     pub yaw_speed: f32,
@@ -62,7 +72,9 @@ impl Default for CameraSettings {
     fn default() -> Self {
         let pitch_limit = FRAC_PI_2 - 0.01;
         Self {
+            orbit_target: ORBIT_TARGET,
             orbit_distance: INITIAL_ORBIT_DISTANCE,
+            orthographic: false,
             pitch_speed: 0.003,
             pitch_range: -pitch_limit..pitch_limit,
             yaw_speed: 0.004,
@@ -70,6 +82,39 @@ impl Default for CameraSettings {
     }
 }
 
+/// A stored camera angle that can be recalled with the number keys.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraBookmark {
+    rotation: Quat,
+    orbit_target: Vec3,
+    orbit_distance: f32,
+    orthographic: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct CameraBookmarks([Option<CameraBookmark>; MAX_CAMERA_BOOKMARKS]);
+
+/// Axis-aligned orthographic views of the orbit target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraViewPreset {
+    Front,
+    Side,
+    Top,
+}
+
+impl CameraViewPreset {
+    fn rotation(self, pitch_limit: f32) -> Quat {
+        match self {
+            Self::Front => Quat::IDENTITY,
+            Self::Side => Quat::from_rotation_y(FRAC_PI_2),
+            Self::Top => Quat::from_rotation_x(-pitch_limit),
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct FrameInspectedEvent;
+
 pub fn setup_camera(
     mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
@@ -100,7 +145,7 @@ pub fn setup_camera(
         Name::new("ViewportCamera"),
         Camera3d::default(),
         Camera {
-            order: -1,
+            order: -2,
             clear_color: ClearColorConfig::Custom(ZINC_950.into()),
             ..default()
         },
@@ -108,6 +153,7 @@ pub fn setup_camera(
         Transform::from_translation(initial_position).looking_at(ORBIT_TARGET, Vec3::Y),
         Msaa::Off,
         tonemapping,
+        MetaballSettings::default(),
         DistanceFog {
             color: ZINC_950.into(),
             falloff: FogFalloff::Linear {
@@ -163,6 +209,7 @@ pub fn setup_floor(
     });
 
     commands.spawn((
+        EditorFloor,
         Mesh3d(mesh),
         MeshMaterial3d(material),
         Name::new("Floor"),
@@ -172,10 +219,10 @@ pub fn setup_floor(
 }
 
 pub fn orbit_camera(
-    mut camera: Single<&mut Transform, With<EditorCamera>>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<EditorCamera>>,
     viewport: Single<&Hovered, With<EditorViewport>>,
     mut input_state: ResMut<ViewportInputState>,
-    camera_settings: Res<CameraSettings>,
+    mut camera_settings: ResMut<CameraSettings>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     mouse_motion: Res<AccumulatedMouseMotion>,
 ) {
@@ -199,6 +246,18 @@ pub fn orbit_camera(
     }
 
     let delta = -mouse_motion.delta;
+    if delta == Vec2::ZERO {
+        return;
+    }
+
+    let (camera, projection) = &mut *camera;
+
+    // orbiting out of an axis-aligned preset goes back to perspective
+    if camera_settings.orthographic {
+        camera_settings.orthographic = false;
+        apply_projection(projection, &camera_settings);
+    }
+
     let delta_pitch = delta.y * camera_settings.pitch_speed;
     let delta_yaw = delta.x * camera_settings.yaw_speed;
 
@@ -211,11 +270,11 @@ pub fn orbit_camera(
     let yaw = yaw + delta_yaw;
     camera.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
 
-    camera.translation = ORBIT_TARGET - camera.forward() * camera_settings.orbit_distance;
+    apply_orbit(camera, &camera_settings);
 }
 
 pub fn zoom_camera(
-    mut camera: Single<&mut Transform, With<EditorCamera>>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<EditorCamera>>,
     viewport: Single<&Hovered, With<EditorViewport>>,
     mut camera_settings: ResMut<CameraSettings>,
     mouse_scroll: Res<AccumulatedMouseScroll>,
@@ -233,7 +292,201 @@ pub fn zoom_camera(
     camera_settings.orbit_distance =
         (camera_settings.orbit_distance + zoom_delta).clamp(MIN_ZOOM_DISTANCE, MAX_ZOOM_DISTANCE);
 
-    camera.translation = ORBIT_TARGET - camera.forward() * camera_settings.orbit_distance;
+    let (transform, projection) = &mut *camera;
+    apply_orbit(transform, &camera_settings);
+    apply_projection(projection, &camera_settings);
+}
+
+fn apply_orbit(transform: &mut Transform, camera_settings: &CameraSettings) {
+    transform.translation =
+        camera_settings.orbit_target - transform.forward() * camera_settings.orbit_distance;
+}
+
+fn apply_projection(projection: &mut Projection, camera_settings: &CameraSettings) {
+    match (camera_settings.orthographic, &mut *projection) {
+        (true, Projection::Orthographic(ortho)) => {
+            ortho.scaling_mode = ScalingMode::FixedVertical {
+                viewport_height: camera_settings.orbit_distance,
+            };
+        }
+        (true, _) => {
+            *projection = Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical {
+                    viewport_height: camera_settings.orbit_distance,
+                },
+                ..OrthographicProjection::default_3d()
+            });
+        }
+        (false, Projection::Perspective(_)) => {}
+        (false, _) => {
+            *projection = Projection::Perspective(default());
+        }
+    }
+}
+
+/// Handles viewport camera shortcuts while no text input is focused.
+///
+/// - <kbd>F</kbd> frames the inspected emitter or collider.
+/// - <kbd>Numpad 1</kbd>, <kbd>Numpad 3</kbd> and <kbd>Numpad 7</kbd> switch to the
+///   orthographic front, side and top views.
+/// - <kbd>Numpad 5</kbd> toggles between perspective and orthographic.
+/// - <kbd>Ctrl</kbd> + <kbd>1</kbd>–<kbd>9</kbd> stores a bookmark, <kbd>1</kbd>–<kbd>9</kbd>
+///   recalls it.
+pub fn handle_camera_shortcuts(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    focus: Res<InputFocus>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<EditorCamera>>,
+    mut camera_settings: ResMut<CameraSettings>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+) {
+    if focus.get().is_some() {
+        return;
+    }
+
+    let (transform, projection) = &mut *camera;
+
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        commands.trigger(FrameInspectedEvent);
+    }
+
+    let preset = if keyboard.just_pressed(KeyCode::Numpad1) {
+        Some(CameraViewPreset::Front)
+    } else if keyboard.just_pressed(KeyCode::Numpad3) {
+        Some(CameraViewPreset::Side)
+    } else if keyboard.just_pressed(KeyCode::Numpad7) {
+        Some(CameraViewPreset::Top)
+    } else {
+        None
+    };
+
+    if let Some(preset) = preset {
+        transform.rotation = preset.rotation(camera_settings.pitch_range.end);
+        camera_settings.orthographic = true;
+        apply_orbit(transform, &camera_settings);
+        apply_projection(projection, &camera_settings);
+    }
+
+    if keyboard.just_pressed(KeyCode::Numpad5) {
+        camera_settings.orthographic = !camera_settings.orthographic;
+        apply_projection(projection, &camera_settings);
+    }
+
+    let ctrl_or_cmd = keyboard.pressed(KeyCode::SuperLeft)
+        || keyboard.pressed(KeyCode::SuperRight)
+        || keyboard.pressed(KeyCode::ControlLeft)
+        || keyboard.pressed(KeyCode::ControlRight);
+
+    let Some(slot) = BOOKMARK_KEYS
+        .iter()
+        .position(|key| keyboard.just_pressed(*key))
+    else {
+        return;
+    };
+
+    if ctrl_or_cmd {
+        bookmarks.0[slot] = Some(CameraBookmark {
+            rotation: transform.rotation,
+            orbit_target: camera_settings.orbit_target,
+            orbit_distance: camera_settings.orbit_distance,
+            orthographic: camera_settings.orthographic,
+        });
+        commands.trigger(ToastEvent::success(format!(
+            "Saved camera bookmark {}",
+            slot + 1
+        )));
+        return;
+    }
+
+    let Some(bookmark) = bookmarks.0[slot] else {
+        return;
+    };
+
+    transform.rotation = bookmark.rotation;
+    camera_settings.orbit_target = bookmark.orbit_target;
+    camera_settings.orbit_distance = bookmark.orbit_distance;
+    camera_settings.orthographic = bookmark.orthographic;
+    apply_orbit(transform, &camera_settings);
+    apply_projection(projection, &camera_settings);
+}
+
+const BOOKMARK_KEYS: [KeyCode; MAX_CAMERA_BOOKMARKS] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+pub fn handle_frame_inspected_event(
+    _trigger: On<FrameInspectedEvent>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    preview: Query<Entity, With<EditorParticlePreview>>,
+    emitters: Query<(&EmitterEntity, &EmitterRuntime, &GlobalTransform)>,
+    colliders: Query<(&ColliderEntity, &ParticlesCollider3D, &GlobalTransform)>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<EditorCamera>>,
+    mut camera_settings: ResMut<CameraSettings>,
+) {
+    let Some(inspecting) = editor_state.inspecting.as_ref() else {
+        return;
+    };
+    let index = inspecting.index as usize;
+
+    let bounds = match inspecting.kind {
+        Inspectable::Emitter => {
+            let Some(asset) = editor_state
+                .current_project
+                .as_ref()
+                .and_then(|handle| assets.get(handle))
+            else {
+                return;
+            };
+            let Some(emitter_data) = asset.emitters.get(index) else {
+                return;
+            };
+            let aabb = emitter_data.draw_pass.visibility_aabb;
+            emitters
+                .iter()
+                .find(|(emitter, runtime, _)| {
+                    runtime.emitter_index == index && preview.get(emitter.parent_system).is_ok()
+                })
+                .map(|(_, _, global_transform)| {
+                    let center = global_transform.transform_point(aabb.center.into());
+                    let half_extents = Vec3::from(aabb.half_extents) * global_transform.scale();
+                    (center, half_extents.length())
+                })
+        }
+        Inspectable::Collider => colliders
+            .iter()
+            .find(|(collider, _, _)| {
+                collider.collider_index == index && preview.get(collider.parent_system).is_ok()
+            })
+            .map(|(_, collider, global_transform)| {
+                let scale = global_transform.scale();
+                let radius = match &collider.shape {
+                    ParticlesColliderShape3D::Box { size } => (*size * scale * 0.5).length(),
+                    ParticlesColliderShape3D::Sphere { radius } => *radius * scale.max_element(),
+                };
+                (global_transform.translation(), radius)
+            }),
+    };
+
+    let Some((center, radius)) = bounds else {
+        return;
+    };
+
+    camera_settings.orbit_target = center;
+    camera_settings.orbit_distance =
+        (radius.max(0.1) * FRAME_PADDING).clamp(MIN_ZOOM_DISTANCE, MAX_ZOOM_DISTANCE);
+
+    let (transform, projection) = &mut *camera;
+    apply_orbit(transform, &camera_settings);
+    apply_projection(projection, &camera_settings);
 }
 
 #[derive(Component)]
@@ -270,6 +523,74 @@ pub fn spawn_preview_particle_system(
     ));
 }
 
+/// Moves the preview system along the path picked in the editor settings.
+pub fn animate_preview_motion(
+    time: Res<Time>,
+    editor_data: Res<EditorData>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    mut previews: Query<(&mut Transform, &ParticleSystemRuntime), With<EditorParticlePreview>>,
+    mut elapsed: Local<f32>,
+    mut was_moving: Local<bool>,
+) {
+    let Some(asset) = editor_state
+        .current_project
+        .as_ref()
+        .and_then(|handle| assets.get(handle))
+    else {
+        return;
+    };
+    let base = asset.initial_transform.to_transform();
+    let settings = &editor_data.settings;
+
+    let Some(motion) = &settings.preview_motion else {
+        if *was_moving {
+            for (mut transform, _) in &mut previews {
+                *transform = base;
+            }
+            *was_moving = false;
+            *elapsed = 0.0;
+        }
+        return;
+    };
+
+    let size = settings.preview_motion_radius.max(0.01);
+    let distance = *elapsed * settings.preview_motion_speed.max(0.0);
+    let offset = match motion {
+        EditorPreviewMotion::Circle => {
+            let angle = distance / size;
+            Vec3::new(angle.cos(), 0.0, angle.sin()) * size
+        }
+        EditorPreviewMotion::FigureEight => {
+            // lemniscate of Gerono, roughly arc-length parametrized by the circle radius
+            let angle = distance / size;
+            Vec3::new(angle.sin(), 0.0, angle.sin() * angle.cos()) * size
+        }
+        EditorPreviewMotion::BackAndForth => {
+            let travel = distance.rem_euclid(size * 4.0);
+            let x = if travel < size * 2.0 {
+                travel - size
+            } else {
+                size * 3.0 - travel
+            };
+            Vec3::new(x, 0.0, 0.0)
+        }
+    };
+
+    let mut paused = true;
+    for (mut transform, runtime) in &mut previews {
+        paused &= runtime.paused;
+        transform.translation = base.translation + base.rotation * offset;
+        transform.rotation = base.rotation;
+        transform.scale = base.scale;
+    }
+
+    if !paused {
+        *elapsed += time.delta_secs();
+    }
+    *was_moving = true;
+}
+
 pub fn despawn_preview_on_project_change(
     mut commands: Commands,
     editor_state: Res<EditorState>,
@@ -466,7 +787,12 @@ pub fn draw_collider_gizmos(
     mut gizmos: Gizmos,
     colliders: Query<(&ParticlesCollider3D, &ColliderEntity, &Transform)>,
     editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
 ) {
+    let asset = editor_state
+        .current_project
+        .as_ref()
+        .and_then(|handle| assets.get(handle));
     let inspected_index = editor_state
         .inspecting
         .as_ref()
@@ -478,10 +804,13 @@ pub fn draw_collider_gizmos(
             continue;
         }
 
-        let color = if collider.enabled {
-            PRIMARY_COLOR
+        let color: Color = if collider.enabled {
+            let [r, g, b, a] = asset
+                .and_then(|asset| asset.colliders.get(collider_entity.collider_index))
+                .map_or(ColliderData::DEFAULT_GIZMO_COLOR, |data| data.gizmo_color);
+            Srgba::new(r, g, b, a).into()
         } else {
-            ZINC_200
+            ZINC_200.into()
         };
 
         match &collider.shape {
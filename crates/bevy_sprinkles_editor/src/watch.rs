@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use bevy::prelude::*;
+use bevy_sprinkles::prelude::*;
+
+use crate::io::read_file;
+use crate::project::SaveResult;
+use crate::state::{DirtyState, EditorState, Inspectable, Inspecting};
+use crate::ui::components::toasts::ToastEvent;
+use crate::ui::widgets::dialog::{DialogActionEvent, EditorDialog, OpenConfirmationDialogEvent};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<WatchedProject>()
+        .add_observer(on_reload_project_event)
+        .add_observer(on_reload_confirmed)
+        .add_systems(
+            Update,
+            (
+                sync_watched_project,
+                poll_watched_project,
+                cleanup_pending_reload,
+            )
+                .chain(),
+        );
+}
+
+/// Tracks the modification time of the open project file.
+#[derive(Resource)]
+struct WatchedProject {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    timer: Timer,
+}
+
+impl Default for WatchedProject {
+    fn default() -> Self {
+        Self {
+            path: None,
+            modified: None,
+            timer: Timer::new(POLL_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// External changes waiting for the user to confirm discarding their unsaved edits.
+#[derive(Resource)]
+struct PendingReload {
+    path: PathBuf,
+    contents: String,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn sync_watched_project(editor_state: Res<EditorState>, mut watched: ResMut<WatchedProject>) {
+    if !editor_state.is_changed() || watched.path == editor_state.current_project_path {
+        return;
+    }
+
+    watched.path = editor_state.current_project_path.clone();
+    watched.modified = watched.path.as_deref().and_then(modified_time);
+}
+
+fn poll_watched_project(
+    time: Res<Time>,
+    mut watched: ResMut<WatchedProject>,
+    editor_state: Res<EditorState>,
+    assets: Res<Assets<ParticlesAsset>>,
+    dirty_state: Res<DirtyState>,
+    pending: Option<Res<PendingReload>>,
+    save_result: Option<Res<SaveResult>>,
+    mut commands: Commands,
+) {
+    if !watched.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    // our own save is still being written
+    if pending.is_some() || save_result.is_some() {
+        return;
+    }
+    let Some(path) = watched.path.clone() else {
+        return;
+    };
+    let Some(modified) = modified_time(&path) else {
+        return;
+    };
+    if watched.modified == Some(modified) {
+        return;
+    }
+    watched.modified = Some(modified);
+
+    let Ok(contents) = read_file(&path) else {
+        return;
+    };
+
+    let current = editor_state
+        .current_project
+        .as_ref()
+        .and_then(|handle| assets.get(handle))
+        .and_then(|asset| {
+            ron::ser::to_string_pretty(asset, ron::ser::PrettyConfig::default()).ok()
+        });
+    if current.as_deref() == Some(contents.as_str()) {
+        return;
+    }
+
+    if !dirty_state.has_unsaved_changes {
+        commands.trigger(ReloadProjectEvent { path, contents });
+        return;
+    }
+
+    let description = format!(
+        "\"{}\" was changed outside the editor. Reload it and discard your unsaved changes?",
+        file_name(&path)
+    );
+    commands.insert_resource(PendingReload { path, contents });
+    commands.trigger(
+        OpenConfirmationDialogEvent::new("Project changed on disk", "Reload")
+            .with_description(description),
+    );
+}
+
+/// Replaces the open project with `contents` read from `path`, keeping the path and selection.
+#[derive(Event)]
+struct ReloadProjectEvent {
+    path: PathBuf,
+    contents: String,
+}
+
+fn on_reload_project_event(
+    event: On<ReloadProjectEvent>,
+    mut editor_state: ResMut<EditorState>,
+    mut assets: ResMut<Assets<ParticlesAsset>>,
+    mut dirty_state: ResMut<DirtyState>,
+    mut commands: Commands,
+) {
+    let filename = file_name(&event.path);
+    let Ok(result) = bevy_sprinkles::asset::versions::migrate_str(&event.contents) else {
+        commands.trigger(ToastEvent::error(format!(
+            "\"{filename}\" changed on disk but could not be loaded"
+        )));
+        return;
+    };
+
+    let asset = result.asset;
+    let inspecting = editor_state.inspecting.filter(|inspecting| {
+        let len = match inspecting.kind {
+            Inspectable::Emitter => asset.emitters.len(),
+            Inspectable::Collider => asset.colliders.len(),
+        };
+        (inspecting.index as usize) < len
+    });
+    let has_emitters = !asset.emitters.is_empty();
+
+    let handle = assets.add(asset);
+    editor_state.open_project(handle, event.path.clone(), &mut dirty_state);
+    editor_state.inspecting = inspecting.or(has_emitters.then_some(Inspecting {
+        kind: Inspectable::Emitter,
+        index: 0,
+    }));
+    dirty_state.has_unsaved_changes |= result.was_migrated;
+
+    commands.trigger(ToastEvent::success(format!(
+        "Reloaded \"{filename}\" from disk"
+    )));
+}
+
+fn on_reload_confirmed(
+    _event: On<DialogActionEvent>,
+    pending: Option<Res<PendingReload>>,
+    mut commands: Commands,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    commands.trigger(ReloadProjectEvent {
+        path: pending.path.clone(),
+        contents: pending.contents.clone(),
+    });
+    commands.remove_resource::<PendingReload>();
+}
+
+fn cleanup_pending_reload(
+    pending: Option<Res<PendingReload>>,
+    dialogs: Query<(), With<EditorDialog>>,
+    mut commands: Commands,
+) {
+    if pending.is_some() && dialogs.is_empty() {
+        commands.remove_resource::<PendingReload>();
+    }
+}
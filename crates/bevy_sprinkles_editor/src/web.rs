@@ -0,0 +1,83 @@
+//! Browser backends for the editor's file access.
+//!
+//! The web build has no filesystem, so files are kept in `localStorage` keyed by their
+//! path, saved projects are offered as downloads, and links open in a new tab.
+
+use std::path::Path;
+
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Storage, Url};
+
+const STORAGE_PREFIX: &str = "sprinkles:";
+
+fn storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+fn storage_key(path: &Path) -> String {
+    format!("{STORAGE_PREFIX}{}", path.to_string_lossy())
+}
+
+pub fn read_file(path: &Path) -> Option<String> {
+    storage()?.get_item(&storage_key(path)).ok().flatten()
+}
+
+pub fn write_file(path: &Path, contents: &str) -> bool {
+    storage().is_some_and(|storage| storage.set_item(&storage_key(path), contents).is_ok())
+}
+
+pub fn remove_file(path: &Path) {
+    if let Some(storage) = storage() {
+        let _ = storage.remove_item(&storage_key(path));
+    }
+}
+
+/// Stored files whose path starts with `dir`.
+pub fn list_files(dir: &Path) -> Vec<String> {
+    let Some(storage) = storage() else {
+        return Vec::new();
+    };
+    let prefix = storage_key(dir);
+    let len = storage.length().unwrap_or(0);
+
+    (0..len)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter(|key| key.starts_with(&prefix))
+        .map(|key| key[STORAGE_PREFIX.len()..].to_string())
+        .collect()
+}
+
+/// Offers `contents` to the user as a file download named `filename`.
+pub fn download(filename: &str, contents: &str) -> bool {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return false;
+    };
+
+    let parts = js_sys::Array::of1(&contents.into());
+    let options = BlobPropertyBag::new();
+    options.set_type("application/ron");
+    let Ok(blob) = Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return false;
+    };
+    let Ok(url) = Url::create_object_url_with_blob(&blob) else {
+        return false;
+    };
+
+    let anchor = document
+        .create_element("a")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlAnchorElement>().ok());
+    if let Some(anchor) = &anchor {
+        anchor.set_href(&url);
+        anchor.set_download(filename);
+        anchor.click();
+    }
+    let _ = Url::revoke_object_url(&url);
+    anchor.is_some()
+}
+
+pub fn open_url(url: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.open_with_url_and_target(url, "_blank");
+    }
+}
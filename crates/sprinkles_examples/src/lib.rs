@@ -0,0 +1,147 @@
+#![deny(missing_docs)]
+//! Ready-made particle effects for [`bevy_sprinkles`].
+//!
+//! This crate bundles the example projects shipped with the Sprinkles editor so they can be
+//! spawned straight from code. They are known-good effects: a handy starting point for new
+//! projects, and a canonical reference when reporting bugs.
+//!
+//! # Usage
+//!
+//! Add [`SprinklesExamplesPlugin`] next to [`SprinklesPlugin`](bevy_sprinkles::SprinklesPlugin),
+//! then load any [`ParticlesExample`] through the asset server:
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_sprinkles::prelude::*;
+//! use sprinkles_examples::{ParticlesExample, SprinklesExamplesPlugin, load_example};
+//!
+//! fn main() {
+//!     App::new()
+//!         .add_plugins((DefaultPlugins, SprinklesPlugin, SprinklesExamplesPlugin))
+//!         .add_systems(Startup, setup)
+//!         .run();
+//! }
+//!
+//! fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn(Particles3d(load_example(
+//!         &asset_server,
+//!         ParticlesExample::Explosion,
+//!     )));
+//! }
+//! ```
+//!
+//! The raw RON source of every example is also available through [`ParticlesExample::source`],
+//! and [`ParticlesExample::parse`] turns it into a [`ParticlesAsset`] without going through the
+//! asset server.
+//!
+//! All the bundled effects are licensed under
+//! [CC0](https://creativecommons.org/publicdomain/zero/1.0/).
+
+use bevy::asset::embedded_asset;
+use bevy::prelude::*;
+use bevy_sprinkles::asset::ParticlesAsset;
+use bevy_sprinkles::asset::versions::{MigrationError, migrate_str};
+
+/// Registers every bundled example as an embedded asset.
+///
+/// Once added, examples can be loaded with [`load_example`] or from their
+/// [`asset_path`](ParticlesExample::asset_path).
+pub struct SprinklesExamplesPlugin;
+
+impl Plugin for SprinklesExamplesPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "assets/3d-explosion.ron");
+        embedded_asset!(app, "assets/acid-pool.ron");
+        embedded_asset!(app, "assets/loot.ron");
+        embedded_asset!(app, "assets/magic-puff.ron");
+        embedded_asset!(app, "assets/rain.ron");
+        embedded_asset!(app, "assets/tube-trails.ron");
+        embedded_asset!(app, "assets/windy-snow.ron");
+    }
+}
+
+/// A particle effect bundled with this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticlesExample {
+    /// A fiery explosion with debris, sparks, smoke and a shockwave.
+    Explosion,
+    /// A bubbling pool of acid.
+    AcidPool,
+    /// A loot drop with light beams, glow and trails.
+    Loot,
+    /// A magic puff with sparks, smoke and bouncing balls.
+    MagicPuff,
+    /// Rain that splashes into droplets through a sub-emitter when hitting a collider.
+    Rain,
+    /// Particles leaving tube trails behind them.
+    TubeTrails,
+    /// Snow blown by the wind, with dust and fog.
+    WindySnow,
+}
+
+impl ParticlesExample {
+    /// Every bundled example, sorted by file name.
+    pub const ALL: [Self; 7] = [
+        Self::Explosion,
+        Self::AcidPool,
+        Self::Loot,
+        Self::MagicPuff,
+        Self::Rain,
+        Self::TubeTrails,
+        Self::WindySnow,
+    ];
+
+    /// Returns the kebab-case slug of this example, matching its file name.
+    pub fn slug(self) -> &'static str {
+        match self {
+            Self::Explosion => "3d-explosion",
+            Self::AcidPool => "acid-pool",
+            Self::Loot => "loot",
+            Self::MagicPuff => "magic-puff",
+            Self::Rain => "rain",
+            Self::TubeTrails => "tube-trails",
+            Self::WindySnow => "windy-snow",
+        }
+    }
+
+    /// Returns the file name of this example, e.g. `"rain.ron"`.
+    pub fn file_name(self) -> String {
+        format!("{}.ron", self.slug())
+    }
+
+    /// Returns the embedded asset path of this example.
+    ///
+    /// Requires [`SprinklesExamplesPlugin`] to be added to the app.
+    pub fn asset_path(self) -> String {
+        format!("embedded://sprinkles_examples/assets/{}.ron", self.slug())
+    }
+
+    /// Returns the RON source of this example.
+    pub fn source(self) -> &'static str {
+        match self {
+            Self::Explosion => include_str!("assets/3d-explosion.ron"),
+            Self::AcidPool => include_str!("assets/acid-pool.ron"),
+            Self::Loot => include_str!("assets/loot.ron"),
+            Self::MagicPuff => include_str!("assets/magic-puff.ron"),
+            Self::Rain => include_str!("assets/rain.ron"),
+            Self::TubeTrails => include_str!("assets/tube-trails.ron"),
+            Self::WindySnow => include_str!("assets/windy-snow.ron"),
+        }
+    }
+
+    /// Parses this example into a [`ParticlesAsset`], migrating it to the current format
+    /// version if needed.
+    pub fn parse(self) -> Result<ParticlesAsset, MigrationError> {
+        migrate_str(self.source()).map(|result| result.asset)
+    }
+}
+
+/// Loads a bundled example through the asset server.
+///
+/// Requires [`SprinklesExamplesPlugin`] to be added to the app.
+pub fn load_example(
+    asset_server: &AssetServer,
+    example: ParticlesExample,
+) -> Handle<ParticlesAsset> {
+    asset_server.load(example.asset_path())
+}
@@ -0,0 +1,96 @@
+use bevy::asset::{AssetPlugin, AssetServer, LoadState};
+use bevy::prelude::*;
+use std::path::Path;
+
+use bevy_sprinkles::asset::{ParticlesAsset, ParticlesAssetLoader};
+use sprinkles_examples::{ParticlesExample, SprinklesExamplesPlugin, load_example};
+
+fn create_test_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        MinimalPlugins.set(bevy::app::ScheduleRunnerPlugin::run_loop(
+            std::time::Duration::from_millis(10),
+        )),
+    );
+    app.add_plugins(AssetPlugin::default());
+
+    app.init_asset::<ParticlesAsset>()
+        .init_asset_loader::<ParticlesAssetLoader>();
+
+    app.add_plugins(SprinklesExamplesPlugin);
+
+    app
+}
+
+fn run_until_loaded<T: Asset>(app: &mut App, handle: &Handle<T>, max_updates: u32) -> bool {
+    for _ in 0..max_updates {
+        app.update();
+
+        let asset_server = app.world().resource::<AssetServer>();
+        match asset_server.load_state(handle) {
+            LoadState::Loaded => return true,
+            LoadState::Failed(_) => return false,
+            _ => continue,
+        }
+    }
+    false
+}
+
+#[test]
+fn test_all_examples_parse() {
+    for example in ParticlesExample::ALL {
+        let asset = example
+            .parse()
+            .unwrap_or_else(|err| panic!("{} failed to parse: {err}", example.file_name()));
+        assert!(
+            !asset.emitters.is_empty(),
+            "{} has no emitters",
+            example.file_name()
+        );
+    }
+}
+
+#[test]
+fn test_all_examples_are_listed() {
+    let assets_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("assets");
+
+    let mut on_disk: Vec<String> = std::fs::read_dir(assets_dir)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.ends_with(".ron"))
+        .collect();
+    on_disk.sort();
+
+    let mut listed: Vec<String> = ParticlesExample::ALL
+        .iter()
+        .map(|example| example.file_name())
+        .collect();
+    listed.sort();
+
+    assert_eq!(on_disk, listed);
+}
+
+#[test]
+fn test_examples_load_through_asset_server() {
+    let mut app = create_test_app();
+
+    for example in ParticlesExample::ALL {
+        let handle = {
+            let asset_server = app.world().resource::<AssetServer>();
+            load_example(asset_server, example)
+        };
+
+        assert!(
+            run_until_loaded(&mut app, &handle, 100),
+            "{} failed to load",
+            example.file_name()
+        );
+
+        let assets = app.world().resource::<Assets<ParticlesAsset>>();
+        let asset = assets.get(&handle).unwrap();
+        assert_eq!(asset.name, example.parse().unwrap().name);
+    }
+}
@@ -0,0 +1,150 @@
+// Visual regression tests: every bundled example is rendered at a short sequence of frames
+// and compared against the reference images in `tests/visual/<slug>/`. They need a GPU, so
+// they only build with the `thumbnails` feature:
+//
+//     cargo test -p sprinkles_examples --features thumbnails --test visual
+//
+// Set `SPRINKLES_BLESS=1` to record the current renders as the new reference images. When a
+// frame doesn't match, a heatmap of the changed pixels is written next to its capture.
+
+use std::path::PathBuf;
+
+use bevy::image::Image;
+use bevy::math::UVec2;
+use bevy::render::render_resource::TextureFormat;
+use bevy_sprinkles::thumbnail::render_thumbnail_sequence;
+use image::{Rgba, RgbaImage};
+use sprinkles_examples::ParticlesExample;
+
+// a sequence instead of a single frame catches temporal bugs like flicker, popping and
+// sort jitter
+const FRAMES: [u32; 6] = [10, 20, 30, 40, 50, 60];
+const SIZE: UVec2 = UVec2::new(320, 180);
+// per-channel difference a pixel can have and still count as unchanged, so small
+// rounding differences between GPU drivers don't fail the comparison
+const CHANNEL_TOLERANCE: u8 = 8;
+// fraction of the pixels that can change before a frame counts as a regression
+const MAX_CHANGED_FRACTION: f32 = 0.002;
+const BLESS_VAR: &str = "SPRINKLES_BLESS";
+
+fn reference_path(example: ParticlesExample, frame: u32) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("visual")
+        .join(example.slug())
+        .join(format!("frame_{frame:03}.png"))
+}
+
+fn capture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("visual")
+}
+
+fn to_rgba(image: &Image) -> RgbaImage {
+    let mut data = image.data.clone().expect("captured image has no data");
+    match image.texture_descriptor.format {
+        TextureFormat::Bgra8UnormSrgb | TextureFormat::Bgra8Unorm => {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm => {}
+        format => panic!("unsupported capture format {format:?}"),
+    }
+    RgbaImage::from_raw(image.width(), image.height(), data).expect("capture size mismatch")
+}
+
+fn channel_difference(a: &Rgba<u8>, b: &Rgba<u8>) -> u8 {
+    a.0.iter()
+        .zip(b.0)
+        .map(|(a, b)| a.abs_diff(b))
+        .max()
+        .unwrap_or(0)
+}
+
+// unchanged pixels are a dim grayscale of the reference, changed ones go from yellow to red as
+// the difference grows
+fn diff_heatmap(capture: &RgbaImage, reference: &RgbaImage) -> RgbaImage {
+    RgbaImage::from_fn(capture.width(), capture.height(), |x, y| {
+        let reference = reference.get_pixel(x, y);
+        let difference = channel_difference(capture.get_pixel(x, y), reference);
+        if difference > CHANNEL_TOLERANCE {
+            Rgba([255, 255 - difference, 0, 255])
+        } else {
+            let [r, g, b, _] = reference.0.map(u16::from);
+            let gray = ((r + g + b) / 12) as u8;
+            Rgba([gray, gray, gray, 255])
+        }
+    })
+}
+
+// describes how `capture` differs from `reference`, or `None` if they match
+fn compare(capture: &RgbaImage, reference: &RgbaImage) -> Option<String> {
+    if capture.dimensions() != reference.dimensions() {
+        return Some(format!(
+            "size {:?} doesn't match the reference's {:?}",
+            capture.dimensions(),
+            reference.dimensions()
+        ));
+    }
+    let changed = capture
+        .pixels()
+        .zip(reference.pixels())
+        .filter(|(a, b)| channel_difference(a, b) > CHANNEL_TOLERANCE)
+        .count();
+    let total = capture.width() as usize * capture.height() as usize;
+    (changed as f32 > total as f32 * MAX_CHANGED_FRACTION)
+        .then(|| format!("{changed} of {total} pixels changed"))
+}
+
+#[test]
+fn test_examples_match_reference_frames() {
+    let bless = std::env::var_os(BLESS_VAR).is_some();
+    std::fs::create_dir_all(capture_dir()).unwrap();
+    let mut failures = Vec::new();
+
+    for example in ParticlesExample::ALL {
+        let asset = example
+            .parse()
+            .unwrap_or_else(|err| panic!("{} failed to parse: {err}", example.file_name()));
+        let images = render_thumbnail_sequence(asset, &FRAMES, SIZE)
+            .unwrap_or_else(|err| panic!("{} failed to render: {err}", example.file_name()));
+
+        for (frame, rendered) in FRAMES.into_iter().zip(&images) {
+            let name = format!("{}_frame_{frame:03}", example.slug());
+            let capture = to_rgba(rendered);
+            let capture_path = capture_dir().join(format!("{name}.png"));
+            capture.save(&capture_path).unwrap();
+
+            let reference_path = reference_path(example, frame);
+            if bless {
+                std::fs::create_dir_all(reference_path.parent().unwrap()).unwrap();
+                capture.save(&reference_path).unwrap();
+                continue;
+            }
+            let Ok(reference) = image::open(&reference_path) else {
+                failures.push(format!(
+                    "{name}: no reference at {}, run with {BLESS_VAR}=1 to record it",
+                    reference_path.display()
+                ));
+                continue;
+            };
+            let reference = reference.to_rgba8();
+            let Some(mismatch) = compare(&capture, &reference) else {
+                continue;
+            };
+            let mut failure = format!("{name}: {mismatch}, capture at {}", capture_path.display());
+            if capture.dimensions() == reference.dimensions() {
+                let diff_path = capture_dir().join(format!("{name}_diff.png"));
+                diff_heatmap(&capture, &reference).save(&diff_path).unwrap();
+                failure.push_str(&format!(", diff at {}", diff_path.display()));
+            }
+            failures.push(failure);
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "visual regressions:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,76 @@
+//! Sprinkles running in the browser on WebGPU.
+//!
+//! Build and serve with [Trunk](https://trunkrs.dev):
+//!
+//! ```sh
+//! rustup target add wasm32-unknown-unknown
+//! cd wasm-example && trunk serve
+//! ```
+//!
+//! It also runs natively with `cargo run -p bevy_sprinkles_wasm_example`.
+
+use bevy::{light::light_consts::lux, prelude::*};
+use bevy_sprinkles::prelude::*;
+use sprinkles_examples::{ParticlesExample, SprinklesExamplesPlugin, load_example};
+
+const REPLAY_SECS: f32 = 2.5;
+
+#[derive(Resource)]
+struct ReplayTimer(Timer);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Sprinkles on the web".into(),
+                #[cfg(target_arch = "wasm32")]
+                canvas: Some("#sprinkles".into()),
+                #[cfg(target_arch = "wasm32")]
+                fit_canvas_to_parent: true,
+                #[cfg(target_arch = "wasm32")]
+                prevent_default_event_handling: true,
+                ..default()
+            }),
+            ..default()
+        }))
+        // the effect is embedded in the binary, so there are no asset files to serve
+        .add_plugins((SprinklesPlugin, SprinklesExamplesPlugin))
+        .insert_resource(ReplayTimer(Timer::from_seconds(
+            REPLAY_SECS,
+            TimerMode::Repeating,
+        )))
+        .add_systems(Startup, setup_scene)
+        .add_systems(Update, replay_effect)
+        .run();
+}
+
+fn setup_scene(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            illuminance: lux::OVERCAST_DAY,
+            ..default()
+        },
+        Transform::from_rotation(Quat::from_euler(EulerRot::XYZ, -0.8, 0.4, 0.0)),
+    ));
+    commands.spawn(Particles3d(load_example(
+        &asset_server,
+        ParticlesExample::MagicPuff,
+    )));
+}
+
+// the effect is one-shot, so replay it on a loop
+fn replay_effect(
+    time: Res<Time>,
+    mut timer: ResMut<ReplayTimer>,
+    mut emitters: Query<&mut EmitterRuntime>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        for mut emitter in &mut emitters {
+            emitter.restart(None);
+        }
+    }
+}